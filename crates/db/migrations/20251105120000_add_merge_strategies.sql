@@ -0,0 +1,11 @@
+-- Per-project default merge strategy and commit message template
+ALTER TABLE projects ADD COLUMN merge_strategy TEXT NOT NULL DEFAULT 'squash'
+    CHECK (merge_strategy IN ('squash', 'rebase_fast_forward', 'merge_commit'));
+ALTER TABLE projects ADD COLUMN merge_commit_template TEXT;
+
+-- Strategy used for each direct merge (NULL for PR merges)
+ALTER TABLE merges ADD COLUMN merge_strategy TEXT
+    CHECK (merge_strategy IN ('squash', 'rebase_fast_forward', 'merge_commit'));
+
+-- Every direct merge before this migration was a squash merge
+UPDATE merges SET merge_strategy = 'squash' WHERE merge_type = 'direct';
@@ -0,0 +1,7 @@
+-- How agent commits are messaged, and which rules messages must satisfy before merging
+ALTER TABLE projects ADD COLUMN commit_message_strategy TEXT NOT NULL DEFAULT 'agent_summary'
+    CHECK (commit_message_strategy IN ('agent_summary', 'conventional_commit', 'template'));
+ALTER TABLE projects ADD COLUMN commit_message_template TEXT;
+ALTER TABLE projects ADD COLUMN commit_message_validation TEXT NOT NULL DEFAULT 'none'
+    CHECK (commit_message_validation IN ('none', 'conventional_commit', 'regex'));
+ALTER TABLE projects ADD COLUMN commit_message_pattern TEXT;
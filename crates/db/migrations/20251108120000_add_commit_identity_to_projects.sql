@@ -0,0 +1,7 @@
+-- Identity, co-author trailers and signing key used for every commit made on behalf of a project
+ALTER TABLE projects ADD COLUMN commit_author_name TEXT;
+ALTER TABLE projects ADD COLUMN commit_author_email TEXT;
+ALTER TABLE projects ADD COLUMN commit_co_authors TEXT;
+ALTER TABLE projects ADD COLUMN commit_signing_format TEXT NOT NULL DEFAULT 'none'
+    CHECK (commit_signing_format IN ('none', 'gpg', 'ssh'));
+ALTER TABLE projects ADD COLUMN commit_signing_key TEXT;
@@ -0,0 +1,4 @@
+-- OCI image and resource limits for running a project's executions inside containers
+ALTER TABLE projects ADD COLUMN container_image TEXT;
+ALTER TABLE projects ADD COLUMN container_cpus TEXT;
+ALTER TABLE projects ADD COLUMN container_memory TEXT;
@@ -0,0 +1,6 @@
+-- Per-project worktree retention policy, and pinned attempts that are never reaped
+ALTER TABLE projects ADD COLUMN worktree_retention_hours INTEGER NOT NULL DEFAULT 72;
+ALTER TABLE projects ADD COLUMN worktree_keep_recent INTEGER;
+ALTER TABLE projects ADD COLUMN worktree_keep_in_review BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE projects ADD COLUMN worktree_max_size_mb INTEGER;
+ALTER TABLE task_attempts ADD COLUMN worktree_pinned BOOLEAN NOT NULL DEFAULT FALSE;
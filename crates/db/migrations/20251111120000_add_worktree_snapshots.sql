@@ -0,0 +1,2 @@
+-- Archive a worktree's uncommitted and untracked files before cleanup removes it
+ALTER TABLE projects ADD COLUMN worktree_snapshot_enabled BOOLEAN NOT NULL DEFAULT FALSE;
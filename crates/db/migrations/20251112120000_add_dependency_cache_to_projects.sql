@@ -0,0 +1,2 @@
+-- Directories shared across worktrees through warm bases keyed by lockfile hashes
+ALTER TABLE projects ADD COLUMN dependency_cache TEXT;
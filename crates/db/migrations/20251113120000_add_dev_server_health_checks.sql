@@ -0,0 +1,7 @@
+-- Dev server health checks and restart policy, and the health of running dev servers
+ALTER TABLE projects ADD COLUMN dev_server_health_check TEXT;
+ALTER TABLE projects ADD COLUMN dev_server_restart_policy TEXT NOT NULL DEFAULT 'never'
+    CHECK (dev_server_restart_policy IN ('never', 'on_failure'));
+ALTER TABLE projects ADD COLUMN dev_server_max_restarts INTEGER NOT NULL DEFAULT 5;
+ALTER TABLE execution_processes ADD COLUMN health_status TEXT
+    CHECK (health_status IN ('starting', 'healthy', 'unhealthy'));
@@ -0,0 +1,34 @@
+PRAGMA foreign_keys = ON;
+
+-- Repositories a project owns besides its own git_repo_path. Attempts of such projects get
+-- a worktree of every repository, side by side in one workspace directory.
+CREATE TABLE project_repositories (
+    id            BLOB PRIMARY KEY,
+    project_id    BLOB NOT NULL,
+    name          TEXT NOT NULL,
+    git_repo_path TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE(project_id, name),
+    UNIQUE(project_id, git_repo_path)
+);
+
+-- The extra repositories checked out for an attempt, with how each was merged or proposed
+CREATE TABLE task_attempt_repositories (
+    id                    BLOB PRIMARY KEY,
+    task_attempt_id       BLOB NOT NULL,
+    project_repository_id BLOB NOT NULL,
+    target_branch         TEXT NOT NULL,
+    merge_commit          TEXT,
+    pr_number             INTEGER,
+    pr_url                TEXT,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_repository_id) REFERENCES project_repositories(id) ON DELETE CASCADE,
+    UNIQUE(task_attempt_id, project_repository_id)
+);
+
+CREATE INDEX idx_project_repositories_project_id ON project_repositories(project_id);
+CREATE INDEX idx_task_attempt_repositories_task_attempt_id ON task_attempt_repositories(task_attempt_id);
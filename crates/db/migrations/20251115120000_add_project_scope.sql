@@ -0,0 +1,3 @@
+-- Working directory and sparse checkout patterns for projects in monorepos
+ALTER TABLE projects ADD COLUMN working_dir TEXT;
+ALTER TABLE projects ADD COLUMN sparse_checkout TEXT;
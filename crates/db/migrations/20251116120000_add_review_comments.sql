@@ -0,0 +1,28 @@
+PRAGMA foreign_keys = ON;
+
+-- Line-anchored review comments on an attempt's diff. `code` holds the commented lines so
+-- comments can follow them as the diff changes; comments whose lines are gone are outdated.
+-- Sending a review records the execution process it went to, and the comments are resolved
+-- once that process completes.
+CREATE TABLE review_comments (
+    id                        BLOB PRIMARY KEY,
+    task_attempt_id           BLOB NOT NULL,
+    file_path                 TEXT NOT NULL,
+    side                      TEXT NOT NULL DEFAULT 'new'
+                                 CHECK (side IN ('old', 'new')),
+    line_start                INTEGER NOT NULL,
+    line_end                  INTEGER NOT NULL,
+    commit_sha                TEXT,
+    code                      TEXT,
+    body                      TEXT NOT NULL,
+    outdated                  BOOLEAN NOT NULL DEFAULT FALSE,
+    sent_execution_process_id BLOB,
+    resolved_at               TEXT,
+    created_at                TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (sent_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_review_comments_task_attempt_id ON review_comments(task_attempt_id);
+CREATE INDEX idx_review_comments_sent_execution_process_id ON review_comments(sent_execution_process_id);
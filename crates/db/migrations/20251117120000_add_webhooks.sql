@@ -0,0 +1,38 @@
+PRAGMA foreign_keys = ON;
+
+-- Outbound webhooks for task, attempt and process lifecycle events. `events` is a JSON array
+-- of subscribed event types, empty for all; webhooks without a project receive events from
+-- every project. Payloads are signed with `secret` when one is set.
+CREATE TABLE webhooks (
+    id         BLOB PRIMARY KEY,
+    name       TEXT NOT NULL,
+    url        TEXT NOT NULL,
+    secret     TEXT,
+    events     TEXT NOT NULL DEFAULT '[]',
+    project_id BLOB,
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- One row per event sent to a webhook. Failed requests are retried with backoff until
+-- the delivery succeeds or runs out of attempts.
+CREATE TABLE webhook_deliveries (
+    id              BLOB PRIMARY KEY,
+    webhook_id      BLOB NOT NULL,
+    event_type      TEXT NOT NULL,
+    payload         TEXT NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'pending'
+                       CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER,
+    error           TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhooks_project_id ON webhooks(project_id);
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id);
+CREATE INDEX idx_webhook_deliveries_status ON webhook_deliveries(status);
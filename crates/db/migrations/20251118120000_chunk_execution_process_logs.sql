@@ -0,0 +1,37 @@
+PRAGMA foreign_keys = ON;
+
+-- Logs are stored as append-only chunks of JSONL entries, compressed with zstd
+CREATE TABLE execution_process_log_chunks (
+    execution_id  BLOB NOT NULL,
+    chunk_index   INTEGER NOT NULL,
+    first_entry   INTEGER NOT NULL,   -- index of the chunk's first entry within the process
+    entry_count   INTEGER NOT NULL,
+    byte_size     INTEGER NOT NULL,   -- uncompressed size
+    compression   TEXT NOT NULL DEFAULT 'zstd'
+                     CHECK (compression IN ('none', 'zstd')),
+    data          BLOB NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (execution_id, chunk_index),
+    FOREIGN KEY (execution_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_execution_process_log_chunks_entries
+    ON execution_process_log_chunks(execution_id, first_entry);
+
+-- Existing logs become one uncompressed chunk each; the server compresses and
+-- splits them in the background after starting
+INSERT INTO execution_process_log_chunks
+    (execution_id, chunk_index, first_entry, entry_count, byte_size, compression, data, created_at)
+SELECT execution_id,
+       0,
+       0,
+       length(logs) - length(replace(logs, char(10), '')),
+       byte_size,
+       'none',
+       CAST(logs AS BLOB),
+       inserted_at
+FROM execution_process_logs
+WHERE logs != '';
+
+DROP INDEX idx_execution_process_logs_inserted_at;
+DROP TABLE execution_process_logs;
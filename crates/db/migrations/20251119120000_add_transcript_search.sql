@@ -0,0 +1,43 @@
+PRAGMA foreign_keys = ON;
+
+-- Searchable parts of each coding agent process's normalized conversation: assistant
+-- messages, commands the agent ran and paths of files it read or edited. `entry_index` is the
+-- entry's position in the process's normalized log. A process's rows are replaced as a whole
+-- whenever it is (re)indexed, and `transcript_indexed_processes` records that it was, so
+-- processes whose conversations have nothing searchable are not indexed over and over.
+CREATE TABLE transcript_entries (
+    execution_process_id BLOB NOT NULL,
+    entry_index          INTEGER NOT NULL,
+    kind                 TEXT NOT NULL
+                            CHECK (kind IN ('assistant_message', 'command', 'file_path')),
+    content              TEXT NOT NULL,
+    PRIMARY KEY (execution_process_id, entry_index, kind),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE TABLE transcript_indexed_processes (
+    execution_process_id BLOB PRIMARY KEY,
+    indexed_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE VIRTUAL TABLE transcript_entries_fts USING fts5(
+    content,
+    content = 'transcript_entries',
+    content_rowid = 'rowid'
+);
+
+CREATE TRIGGER transcript_entries_ai AFTER INSERT ON transcript_entries BEGIN
+    INSERT INTO transcript_entries_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
+
+CREATE TRIGGER transcript_entries_ad AFTER DELETE ON transcript_entries BEGIN
+    INSERT INTO transcript_entries_fts(transcript_entries_fts, rowid, content)
+    VALUES ('delete', old.rowid, old.content);
+END;
+
+CREATE TRIGGER transcript_entries_au AFTER UPDATE ON transcript_entries BEGIN
+    INSERT INTO transcript_entries_fts(transcript_entries_fts, rowid, content)
+    VALUES ('delete', old.rowid, old.content);
+    INSERT INTO transcript_entries_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
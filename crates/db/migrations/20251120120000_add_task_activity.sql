@@ -0,0 +1,95 @@
+PRAGMA foreign_keys = ON;
+
+-- Every status a task has been in, kept by triggers so no code path can change a status without
+-- it being recorded. `from_status` is NULL for the status a task was created with.
+CREATE TABLE task_status_history (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id     BLOB NOT NULL,
+    from_status TEXT CHECK (from_status IN ('todo','inprogress','done','cancelled','inreview')),
+    to_status   TEXT NOT NULL
+                   CHECK (to_status IN ('todo','inprogress','done','cancelled','inreview')),
+    changed_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_status_history_task_id ON task_status_history(task_id, changed_at);
+
+CREATE TRIGGER task_status_history_ai AFTER INSERT ON tasks BEGIN
+    INSERT INTO task_status_history (task_id, from_status, to_status, changed_at)
+    VALUES (new.id, NULL, new.status, new.created_at);
+END;
+
+CREATE TRIGGER task_status_history_au AFTER UPDATE OF status ON tasks
+WHEN old.status IS NOT new.status BEGIN
+    INSERT INTO task_status_history (task_id, from_status, to_status)
+    VALUES (new.id, old.status, new.status);
+END;
+
+-- Existing tasks only have their current status: assume they started as todo and moved to it
+-- when they were last updated
+INSERT INTO task_status_history (task_id, from_status, to_status, changed_at)
+SELECT id, NULL, 'todo', created_at FROM tasks;
+
+INSERT INTO task_status_history (task_id, from_status, to_status, changed_at)
+SELECT id, 'todo', status, updated_at FROM tasks WHERE status != 'todo';
+
+-- Relationships being added and removed, so removed ones still show up in task timelines
+CREATE TABLE task_relationship_history (
+    id                   INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_task_id       BLOB NOT NULL,
+    target_task_id       BLOB NOT NULL,
+    relationship_type_id BLOB NOT NULL,
+    change               TEXT NOT NULL CHECK (change IN ('added', 'removed')),
+    changed_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (source_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_relationship_history_source ON task_relationship_history(source_task_id);
+CREATE INDEX idx_task_relationship_history_target ON task_relationship_history(target_task_id);
+
+CREATE TRIGGER task_relationship_history_ai AFTER INSERT ON task_relationships BEGIN
+    INSERT INTO task_relationship_history
+        (source_task_id, target_task_id, relationship_type_id, change, changed_at)
+    VALUES
+        (new.source_task_id, new.target_task_id, new.relationship_type_id, 'added', new.created_at);
+END;
+
+-- Relationships removed because one of their tasks was deleted leave nothing to show
+CREATE TRIGGER task_relationship_history_ad AFTER DELETE ON task_relationships
+WHEN EXISTS (SELECT 1 FROM tasks WHERE id = old.source_task_id)
+ AND EXISTS (SELECT 1 FROM tasks WHERE id = old.target_task_id) BEGIN
+    INSERT INTO task_relationship_history
+        (source_task_id, target_task_id, relationship_type_id, change)
+    VALUES
+        (old.source_task_id, old.target_task_id, old.relationship_type_id, 'removed');
+END;
+
+INSERT INTO task_relationship_history
+    (source_task_id, target_task_id, relationship_type_id, change, changed_at)
+SELECT source_task_id, target_task_id, relationship_type_id, 'added', created_at
+FROM task_relationships;
+
+-- Tool approvals agents asked for and how they were resolved
+CREATE TABLE tool_approvals (
+    id                   TEXT PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    tool_name            TEXT NOT NULL,
+    tool_input_summary   TEXT NOT NULL DEFAULT '',
+    status               TEXT NOT NULL DEFAULT 'pending'
+                            CHECK (status IN ('pending', 'approved', 'denied', 'timed_out')),
+    denial_reason        TEXT,
+    requested_at         TEXT NOT NULL,
+    resolved_at          TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tool_approvals_execution_process_id ON tool_approvals(execution_process_id);
+
+-- When a pull request was seen closed without merging; merged ones have `pr_merged_at`
+ALTER TABLE merges ADD COLUMN pr_closed_at TEXT;
+
+CREATE TRIGGER merges_pr_closed_at AFTER UPDATE OF pr_status ON merges
+WHEN new.pr_status = 'closed' AND old.pr_status IS NOT 'closed' BEGIN
+    UPDATE merges SET pr_closed_at = datetime('now', 'subsec') WHERE id = new.id;
+END;
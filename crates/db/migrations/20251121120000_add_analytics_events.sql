@@ -0,0 +1,12 @@
+PRAGMA foreign_keys = ON;
+
+-- Analytics events kept on this machine when local analytics are enabled. Nothing references
+-- other tables, so events outlive the tasks and projects they mention.
+CREATE TABLE analytics_events (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_name  TEXT NOT NULL,
+    properties  TEXT NOT NULL DEFAULT '{}',
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_analytics_events_name_created_at ON analytics_events(event_name, created_at);
@@ -0,0 +1,44 @@
+PRAGMA foreign_keys = ON;
+
+-- `transcript_entries` had a composite primary key, so its full-text index was keyed on the
+-- implicit rowid, which VACUUM may renumber. Rebuild it around an explicit INTEGER PRIMARY
+-- KEY. The index only holds derived data: forgetting which processes were indexed makes the
+-- background indexer index them all again.
+DROP TRIGGER transcript_entries_ai;
+DROP TRIGGER transcript_entries_ad;
+DROP TRIGGER transcript_entries_au;
+DROP TABLE transcript_entries_fts;
+DROP TABLE transcript_entries;
+DELETE FROM transcript_indexed_processes;
+
+CREATE TABLE transcript_entries (
+    id                   INTEGER PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    entry_index          INTEGER NOT NULL,
+    kind                 TEXT NOT NULL
+                            CHECK (kind IN ('assistant_message', 'command', 'file_path')),
+    content              TEXT NOT NULL,
+    UNIQUE (execution_process_id, entry_index, kind),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE VIRTUAL TABLE transcript_entries_fts USING fts5(
+    content,
+    content = 'transcript_entries',
+    content_rowid = 'id'
+);
+
+CREATE TRIGGER transcript_entries_ai AFTER INSERT ON transcript_entries BEGIN
+    INSERT INTO transcript_entries_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER transcript_entries_ad AFTER DELETE ON transcript_entries BEGIN
+    INSERT INTO transcript_entries_fts(transcript_entries_fts, rowid, content)
+    VALUES ('delete', old.id, old.content);
+END;
+
+CREATE TRIGGER transcript_entries_au AFTER UPDATE ON transcript_entries BEGIN
+    INSERT INTO transcript_entries_fts(transcript_entries_fts, rowid, content)
+    VALUES ('delete', old.id, old.content);
+    INSERT INTO transcript_entries_fts(rowid, content) VALUES (new.id, new.content);
+END;
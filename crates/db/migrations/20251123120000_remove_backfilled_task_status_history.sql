@@ -0,0 +1,7 @@
+-- Tasks that existed before status history was recorded got rows guessed from their current
+-- status: created as todo, moved to their status when last updated. Neither is known to have
+-- happened, so drop them; those tasks' history starts from the first recorded change.
+DELETE FROM task_status_history
+WHERE datetime(changed_at) < (
+    SELECT datetime(installed_on) FROM _sqlx_migrations WHERE version = 20251120120000
+);
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+
+/// An analytics event recorded on this machine
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct AnalyticsEvent {
+    pub id: i64,
+    pub event_name: String,
+    pub properties: sqlx::types::Json<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Attempts started with an executor profile
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutorAttemptCount {
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    pub attempts: i64,
+}
+
+/// Finished coding agent runs of an executor profile
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutorRunCounts {
+    /// `None` for runs recorded before the executor was part of the event
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    pub runs: i64,
+    pub failed_runs: i64,
+    pub average_runtime_seconds: Option<f64>,
+    /// Approval responses for tool calls of these runs
+    pub approvals: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, TS)]
+pub struct WeeklyMerges {
+    /// Monday of the week, as `YYYY-MM-DD`
+    pub week_start: String,
+    /// Attempts merged directly into their target branch
+    #[ts(type = "number")]
+    pub merges: i64,
+    /// Pull requests of attempts merged on GitHub
+    #[ts(type = "number")]
+    pub pr_merges: i64,
+}
+
+impl AnalyticsEvent {
+    pub async fn create(
+        pool: &SqlitePool,
+        event_name: &str,
+        properties: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let properties = sqlx::types::Json(properties);
+        sqlx::query!(
+            "INSERT INTO analytics_events (event_name, properties) VALUES ($1, $2)",
+            event_name,
+            properties
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_all(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM analytics_events")
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn count(
+        pool: &SqlitePool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM analytics_events
+               WHERE datetime(created_at, 'subsec') >= datetime($1, 'subsec') AND datetime(created_at, 'subsec') < datetime($2, 'subsec')"#,
+            from,
+            to
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn attempts_by_executor(
+        pool: &SqlitePool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ExecutorAttemptCount>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorAttemptCount,
+            r#"SELECT json_extract(properties, '$.executor') as "executor?: String",
+                      json_extract(properties, '$.variant') as "variant?: String",
+                      COUNT(*) as "attempts!: i64"
+               FROM analytics_events
+               WHERE event_name = 'task_attempt_started'
+                 AND datetime(created_at, 'subsec') >= datetime($1, 'subsec') AND datetime(created_at, 'subsec') < datetime($2, 'subsec')
+               GROUP BY 1, 2
+               ORDER BY 1, 2"#,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn runs_by_executor(
+        pool: &SqlitePool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ExecutorRunCounts>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorRunCounts,
+            r#"SELECT json_extract(f.properties, '$.executor') as "executor?: String",
+                      json_extract(f.properties, '$.variant') as "variant?: String",
+                      COUNT(*) as "runs!: i64",
+                      SUM(CASE WHEN json_extract(f.properties, '$.execution_success')
+                               THEN 0 ELSE 1 END) as "failed_runs!: i64",
+                      AVG(json_extract(f.properties, '$.runtime_seconds'))
+                          as "average_runtime_seconds?: f64",
+                      SUM((SELECT COUNT(*) FROM analytics_events a
+                           WHERE a.event_name = 'approval_responded'
+                             AND json_extract(a.properties, '$.execution_process_id')
+                                 = json_extract(f.properties, '$.execution_process_id')))
+                          as "approvals!: i64"
+               FROM analytics_events f
+               WHERE f.event_name = 'task_attempt_finished'
+                 AND datetime(f.created_at, 'subsec') >= datetime($1, 'subsec')
+                 AND datetime(f.created_at, 'subsec') < datetime($2, 'subsec')
+               GROUP BY 1, 2
+               ORDER BY 1, 2"#,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Merges per week, oldest first; weeks without merges are left out
+    pub async fn merges_per_week(
+        pool: &SqlitePool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<WeeklyMerges>, sqlx::Error> {
+        sqlx::query_as!(
+            WeeklyMerges,
+            r#"SELECT date(created_at, 'weekday 0', '-6 days') as "week_start!: String",
+                      SUM(CASE WHEN event_name = 'pr_merged' THEN 0 ELSE 1 END) as "merges!: i64",
+                      SUM(CASE WHEN event_name = 'pr_merged' THEN 1 ELSE 0 END) as "pr_merges!: i64"
+               FROM analytics_events
+               WHERE event_name IN ('task_attempt_merged', 'task_attempt_repository_merged', 'pr_merged')
+                 AND datetime(created_at, 'subsec') >= datetime($1, 'subsec') AND datetime(created_at, 'subsec') < datetime($2, 'subsec')
+               GROUP BY 1
+               ORDER BY 1"#,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -333,6 +333,13 @@ impl Draft {
         Ok(())
     }
 
+    /// Follow-ups waiting for their attempt's running process to finish
+    pub async fn count_queued(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM drafts WHERE queued = 1"#)
+            .fetch_one(pool)
+            .await
+    }
+
     /// Set queued flag (and bump metadata) for a draft by attempt and type.
     pub async fn set_queued(
         pool: &SqlitePool,
@@ -1,119 +1,339 @@
+use std::collections::VecDeque;
+
 use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
-use ts_rs::TS;
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use thiserror::Error;
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
-pub struct ExecutionProcessLogs {
-    pub execution_id: Uuid,
-    pub logs: String, // JSONL format
-    pub byte_size: i64,
-    pub inserted_at: DateTime<Utc>,
+/// Entries per chunk before it is written
+pub const LOG_CHUNK_MAX_ENTRIES: usize = 512;
+/// Uncompressed JSONL bytes per chunk before it is written
+pub const LOG_CHUNK_MAX_BYTES: usize = 256 * 1024;
+
+/// Fast levels already shrink JSONL logs several times over
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Error)]
+pub enum LogStorageError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
 }
 
-#[derive(Debug, Deserialize, TS)]
-pub struct CreateExecutionProcessLogs {
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq)]
+#[sqlx(type_name = "log_chunk_compression", rename_all = "lowercase")]
+pub enum LogChunkCompression {
+    /// Carried over from the single-row log table, compressed in the background
+    None,
+    Zstd,
+}
+
+/// A run of consecutive log entries of an execution process
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionProcessLogChunk {
     pub execution_id: Uuid,
-    pub logs: String,
+    pub chunk_index: i64,
+    /// Index of the chunk's first entry among all of the process's entries
+    pub first_entry: i64,
+    pub entry_count: i64,
+    /// Size of the uncompressed JSONL
     pub byte_size: i64,
+    pub compression: LogChunkCompression,
+    pub data: Vec<u8>,
+    pub created_at: DateTime<Utc>,
 }
 
+impl ExecutionProcessLogChunk {
+    /// The chunk's entries as JSONL
+    pub fn jsonl(&self) -> Result<String, LogStorageError> {
+        let bytes = match self.compression {
+            LogChunkCompression::None => self.data.clone(),
+            LogChunkCompression::Zstd => zstd::decode_all(self.data.as_slice())?,
+        };
+        String::from_utf8(bytes).map_err(|e| {
+            LogStorageError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })
+    }
+
+    pub fn parse_entries(&self) -> Result<Vec<LogMsg>, LogStorageError> {
+        let jsonl = self.jsonl()?;
+        let mut messages = Vec::new();
+        for line in jsonl.lines() {
+            if !line.trim().is_empty() {
+                messages.push(serde_json::from_str(line)?);
+            }
+        }
+        Ok(messages)
+    }
+}
+
+/// Execution process logs, stored as append-only compressed chunks of JSONL entries
+pub struct ExecutionProcessLogs;
+
 impl ExecutionProcessLogs {
-    /// Find logs by execution process ID
-    pub async fn find_by_execution_id(
+    /// Append serialized entries, one JSON document each, as a new chunk
+    pub async fn append_chunk(
         pool: &SqlitePool,
         execution_id: Uuid,
-    ) -> Result<Option<Self>, sqlx::Error> {
-        sqlx::query_as!(
-            ExecutionProcessLogs,
-            r#"SELECT 
-                execution_id as "execution_id!: Uuid",
-                logs,
-                byte_size,
-                inserted_at as "inserted_at!: DateTime<Utc>"
-               FROM execution_process_logs 
+        lines: &[String],
+    ) -> Result<(), LogStorageError> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let jsonl = Self::join_lines(lines);
+        let data = zstd::encode_all(jsonl.as_bytes(), ZSTD_LEVEL)?;
+        let entry_count = lines.len() as i64;
+        let byte_size = jsonl.len() as i64;
+        let compression = LogChunkCompression::Zstd;
+
+        sqlx::query!(
+            r#"INSERT INTO execution_process_log_chunks
+                   (execution_id, chunk_index, first_entry, entry_count, byte_size, compression, data)
+               SELECT $1, COALESCE(MAX(chunk_index) + 1, 0), COALESCE(MAX(first_entry + entry_count), 0), $2, $3, $4, $5
+               FROM execution_process_log_chunks
                WHERE execution_id = $1"#,
-            execution_id
+            execution_id,
+            entry_count,
+            byte_size,
+            compression,
+            data
         )
-        .fetch_optional(pool)
-        .await
+        .execute(pool)
+        .await?;
+        Ok(())
     }
 
-    /// Create or update execution process logs
-    pub async fn upsert(
+    /// Append a single entry as its own chunk
+    pub async fn append_entry(
         pool: &SqlitePool,
-        data: &CreateExecutionProcessLogs,
-    ) -> Result<Self, sqlx::Error> {
-        let now = Utc::now();
+        execution_id: Uuid,
+        msg: &LogMsg,
+    ) -> Result<(), LogStorageError> {
+        let line = serde_json::to_string(msg)?;
+        Self::append_chunk(pool, execution_id, &[line]).await
+    }
 
-        sqlx::query_as!(
-            ExecutionProcessLogs,
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, $4)
-               ON CONFLICT (execution_id) DO UPDATE
-               SET logs = EXCLUDED.logs, 
-                   byte_size = EXCLUDED.byte_size,
-                   inserted_at = EXCLUDED.inserted_at
-               RETURNING 
-                execution_id as "execution_id!: Uuid",
-                logs,
-                byte_size,
-                inserted_at as "inserted_at!: DateTime<Utc>""#,
-            data.execution_id,
-            data.logs,
-            data.byte_size,
-            now
+    pub async fn exists(pool: &SqlitePool, execution_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM execution_process_log_chunks WHERE execution_id = $1) as "exists!: bool""#,
+            execution_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(result.exists)
+    }
+
+    pub async fn entry_count(pool: &SqlitePool, execution_id: Uuid) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT COALESCE(SUM(entry_count), 0) as "count!: i64"
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1"#,
+            execution_id
         )
         .fetch_one(pool)
+        .await?;
+        Ok(result.count)
+    }
+
+    /// The chunk holding entry `entry`, or the first one after it
+    async fn find_chunk_from(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        entry: i64,
+    ) -> Result<Option<ExecutionProcessLogChunk>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessLogChunk,
+            r#"SELECT execution_id as "execution_id!: Uuid", chunk_index, first_entry, entry_count, byte_size, compression as "compression!: LogChunkCompression", data, created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1 AND first_entry + entry_count > $2
+               ORDER BY chunk_index ASC
+               LIMIT 1"#,
+            execution_id,
+            entry
+        )
+        .fetch_optional(pool)
         .await
     }
 
-    /// Parse JSONL logs back into Vec<LogMsg>
-    pub fn parse_logs(&self) -> Result<Vec<LogMsg>, serde_json::Error> {
-        let mut messages = Vec::new();
-        for line in self.logs.lines() {
-            if !line.trim().is_empty() {
-                let msg: LogMsg = serde_json::from_str(line)?;
-                messages.push(msg);
-            }
+    /// Stream entries from index `from_entry` on, decompressing one chunk at a time
+    pub fn stream_entries(
+        pool: SqlitePool,
+        execution_id: Uuid,
+        from_entry: usize,
+    ) -> BoxStream<'static, Result<LogMsg, LogStorageError>> {
+        struct State {
+            pool: SqlitePool,
+            next_entry: i64,
+            buffered: VecDeque<LogMsg>,
+            failed: bool,
         }
-        Ok(messages)
+
+        let state = State {
+            pool,
+            next_entry: from_entry as i64,
+            buffered: VecDeque::new(),
+            failed: false,
+        };
+
+        stream::unfold(state, move |mut state| async move {
+            loop {
+                if let Some(msg) = state.buffered.pop_front() {
+                    return Some((Ok(msg), state));
+                }
+                if state.failed {
+                    return None;
+                }
+
+                let chunk = match Self::find_chunk_from(&state.pool, execution_id, state.next_entry)
+                    .await
+                {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => return None,
+                    Err(e) => {
+                        state.failed = true;
+                        return Some((Err(e.into()), state));
+                    }
+                };
+
+                let skip = (state.next_entry - chunk.first_entry).max(0) as usize;
+                state.next_entry = chunk.first_entry + chunk.entry_count;
+                match chunk.parse_entries() {
+                    Ok(entries) => state.buffered.extend(entries.into_iter().skip(skip)),
+                    Err(e) => {
+                        state.failed = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
+        .boxed()
     }
 
-    /// Convert Vec<LogMsg> to JSONL format
-    pub fn serialize_logs(messages: &[LogMsg]) -> Result<String, serde_json::Error> {
-        let mut jsonl = String::new();
-        for msg in messages {
-            let line = serde_json::to_string(msg)?;
-            jsonl.push_str(&line);
-            jsonl.push('\n');
+    /// A single entry, decompressing only the chunk that holds it
+    pub async fn find_entry(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        index: usize,
+    ) -> Result<Option<LogMsg>, LogStorageError> {
+        Self::stream_entries(pool.clone(), execution_id, index)
+            .next()
+            .await
+            .transpose()
+    }
+
+    /// Compress and split the chunks carried over from the single-row log table
+    pub async fn compress_legacy_chunks(pool: &SqlitePool) -> Result<usize, LogStorageError> {
+        let mut converted = 0;
+        // One process at a time, so large histories are never all held in memory
+        while let Some(chunk) = sqlx::query_as!(
+            ExecutionProcessLogChunk,
+            r#"SELECT execution_id as "execution_id!: Uuid", chunk_index, first_entry, entry_count, byte_size, compression as "compression!: LogChunkCompression", data, created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_log_chunks
+               WHERE compression = 'none'
+               LIMIT 1"#
+        )
+        .fetch_optional(pool)
+        .await?
+        {
+            let jsonl = chunk.jsonl()?;
+            let lines: Vec<String> = jsonl
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect();
+
+            let mut tx = pool.begin().await?;
+            sqlx::query!(
+                "DELETE FROM execution_process_log_chunks WHERE execution_id = $1",
+                chunk.execution_id
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            let mut first_entry = 0;
+            for (chunk_index, group) in Self::split_lines(&lines).into_iter().enumerate() {
+                Self::insert_chunk(
+                    &mut *tx,
+                    chunk.execution_id,
+                    chunk_index as i64,
+                    first_entry,
+                    group,
+                )
+                .await?;
+                first_entry += group.len() as i64;
+            }
+            tx.commit().await?;
+            converted += 1;
         }
-        Ok(jsonl)
+        Ok(converted)
     }
 
-    /// Append a JSONL line to the logs for an execution process
-    pub async fn append_log_line(
-        pool: &SqlitePool,
+    async fn insert_chunk<'e, E>(
+        executor: E,
         execution_id: Uuid,
-        jsonl_line: &str,
-    ) -> Result<(), sqlx::Error> {
-        let byte_size = jsonl_line.len() as i64;
+        chunk_index: i64,
+        first_entry: i64,
+        lines: &[String],
+    ) -> Result<(), LogStorageError>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let jsonl = Self::join_lines(lines);
+        let data = zstd::encode_all(jsonl.as_bytes(), ZSTD_LEVEL)?;
+        let entry_count = lines.len() as i64;
+        let byte_size = jsonl.len() as i64;
+        let compression = LogChunkCompression::Zstd;
+
         sqlx::query!(
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, datetime('now', 'subsec'))
-               ON CONFLICT (execution_id) DO UPDATE
-               SET logs = logs || $2,
-                   byte_size = byte_size + $3,
-                   inserted_at = datetime('now', 'subsec')"#,
+            r#"INSERT INTO execution_process_log_chunks
+                   (execution_id, chunk_index, first_entry, entry_count, byte_size, compression, data)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
             execution_id,
-            jsonl_line,
-            byte_size
+            chunk_index,
+            first_entry,
+            entry_count,
+            byte_size,
+            compression,
+            data
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
-
         Ok(())
     }
+
+    /// Group lines into runs no larger than a chunk
+    pub fn split_lines(lines: &[String]) -> Vec<&[String]> {
+        let mut groups = Vec::new();
+        let mut start = 0;
+        let mut bytes = 0;
+        for (i, line) in lines.iter().enumerate() {
+            if i > start
+                && (i - start >= LOG_CHUNK_MAX_ENTRIES || bytes + line.len() > LOG_CHUNK_MAX_BYTES)
+            {
+                groups.push(&lines[start..i]);
+                start = i;
+                bytes = 0;
+            }
+            bytes += line.len() + 1;
+        }
+        if start < lines.len() {
+            groups.push(&lines[start..]);
+        }
+        groups
+    }
+
+    fn join_lines(lines: &[String]) -> String {
+        let mut jsonl = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+        for line in lines {
+            jsonl.push_str(line);
+            jsonl.push('\n');
+        }
+        jsonl
+    }
 }
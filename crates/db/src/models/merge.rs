@@ -14,6 +14,20 @@ pub enum MergeStatus {
     Unknown,
 }
 
+/// How a task branch is integrated into its target branch on a direct merge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Collapse all task commits into a single commit on the target branch
+    #[default]
+    Squash,
+    /// Replay task commits onto the target branch and fast-forward it
+    RebaseFastForward,
+    /// Create a merge commit even when a fast-forward is possible
+    MergeCommit,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Merge {
@@ -27,6 +41,7 @@ pub struct DirectMerge {
     pub task_attempt_id: Uuid,
     pub merge_commit: String,
     pub target_branch_name: String,
+    pub merge_strategy: MergeStrategy,
     pub created_at: DateTime<Utc>,
 }
 
@@ -62,6 +77,7 @@ struct MergeRow {
     task_attempt_id: Uuid,
     merge_type: MergeType,
     merge_commit: Option<String>,
+    merge_strategy: Option<MergeStrategy>,
     target_branch_name: String,
     pr_number: Option<i64>,
     pr_url: Option<String>,
@@ -85,6 +101,7 @@ impl Merge {
         task_attempt_id: Uuid,
         target_branch_name: &str,
         merge_commit: &str,
+        merge_strategy: MergeStrategy,
     ) -> Result<DirectMerge, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -92,13 +109,14 @@ impl Merge {
         sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
-                id, task_attempt_id, merge_type, merge_commit, created_at, target_branch_name
-            ) VALUES ($1, $2, 'direct', $3, $4, $5)
+                id, task_attempt_id, merge_type, merge_commit, merge_strategy, created_at, target_branch_name
+            ) VALUES ($1, $2, 'direct', $3, $4, $5, $6)
             RETURNING 
                 id as "id!: Uuid",
                 task_attempt_id as "task_attempt_id!: Uuid",
                 merge_type as "merge_type!: MergeType",
                 merge_commit,
+                merge_strategy as "merge_strategy?: MergeStrategy",
                 pr_number,
                 pr_url,
                 pr_status as "pr_status?: MergeStatus",
@@ -110,6 +128,7 @@ impl Merge {
             id,
             task_attempt_id,
             merge_commit,
+            merge_strategy,
             now,
             target_branch_name
         )
@@ -138,6 +157,7 @@ impl Merge {
                 task_attempt_id as "task_attempt_id!: Uuid",
                 merge_type as "merge_type!: MergeType",
                 merge_commit,
+                merge_strategy as "merge_strategy?: MergeStrategy",
                 pr_number,
                 pr_url,
                 pr_status as "pr_status?: MergeStatus",
@@ -167,6 +187,7 @@ impl Merge {
                 task_attempt_id as "task_attempt_id!: Uuid",
                 merge_type as "merge_type!: MergeType",
                 merge_commit,
+                merge_strategy as "merge_strategy?: MergeStrategy",
                 pr_number,
                 pr_url,
                 pr_status as "pr_status?: MergeStatus",
@@ -226,6 +247,7 @@ impl Merge {
                 task_attempt_id as "task_attempt_id!: Uuid",
                 merge_type as "merge_type!: MergeType",
                 merge_commit,
+                merge_strategy as "merge_strategy?: MergeStrategy",
                 pr_number,
                 pr_url,
                 pr_status as "pr_status?: MergeStatus",
@@ -245,6 +267,46 @@ impl Merge {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    /// Find a merge by its SQLite rowid
+    pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            MergeRow,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_attempt_id as "task_attempt_id!: Uuid",
+                merge_type as "merge_type!: MergeType",
+                merge_commit,
+                merge_strategy as "merge_strategy?: MergeStrategy",
+                pr_number,
+                pr_url,
+                pr_status as "pr_status?: MergeStatus",
+                pr_merged_at as "pr_merged_at?: DateTime<Utc>",
+                pr_merge_commit_sha,
+                target_branch_name as "target_branch_name!: String",
+                created_at as "created_at!: DateTime<Utc>"
+            FROM merges
+            WHERE rowid = $1"#,
+            rowid
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    /// When a PR was seen closed without being merged
+    pub async fn pr_closed_at(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT pr_closed_at as "pr_closed_at?: DateTime<Utc>" FROM merges WHERE id = $1"#,
+            merge_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.and_then(|row| row.pr_closed_at))
+    }
+
     /// Find the most recent merge for a task attempt
     pub async fn find_latest_by_task_attempt_id(
         pool: &SqlitePool,
@@ -266,6 +328,7 @@ impl From<MergeRow> for DirectMerge {
                 .merge_commit
                 .expect("direct merge must have merge_commit"),
             target_branch_name: row.target_branch_name,
+            merge_strategy: row.merge_strategy.unwrap_or_default(),
             created_at: row.created_at,
         }
     }
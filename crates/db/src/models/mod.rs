@@ -1,3 +1,4 @@
+pub mod analytics_event;
 pub mod draft;
 pub mod execution_process;
 pub mod execution_process_logs;
@@ -5,10 +6,18 @@ pub mod executor_session;
 pub mod image;
 pub mod merge;
 pub mod project;
+pub mod project_repository;
+pub mod review_comment;
 pub mod tag;
 pub mod task;
 pub mod task_attempt;
+pub mod task_attempt_repository;
 pub mod task_relationship;
+pub mod task_relationship_history;
 pub mod task_relationship_type;
+pub mod task_status_history;
 pub mod task_template;
 pub mod task_template_group;
+pub mod tool_approval;
+pub mod transcript_entry;
+pub mod webhook;
@@ -2,11 +2,19 @@ use std::path::PathBuf;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::merge::MergeStrategy;
+
+/// Worktree retention window for projects that don't set one
+pub const DEFAULT_WORKTREE_RETENTION_HOURS: i64 = 72;
+
+/// Automatic dev server restarts for projects that don't set a limit
+pub const DEFAULT_DEV_SERVER_MAX_RESTARTS: i64 = 5;
+
 #[derive(Debug, Error)]
 pub enum ProjectError {
     #[error(transparent)]
@@ -21,6 +29,54 @@ pub enum ProjectError {
     CreateFailed(String),
 }
 
+/// How the message for a coding agent's commit is produced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommitMessageStrategy {
+    /// Use the agent's final message
+    #[default]
+    AgentSummary,
+    /// Ask the agent to end its run with a conventional-commit message
+    ConventionalCommit,
+    /// Render the project's commit message template
+    Template,
+}
+
+/// Rules commit messages must satisfy before an attempt can be merged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommitMessageValidation {
+    #[default]
+    None,
+    ConventionalCommit,
+    /// Match the project's commit message pattern
+    Regex,
+}
+
+/// How commits made for a project are signed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommitSigningFormat {
+    #[default]
+    None,
+    Gpg,
+    Ssh,
+}
+
+/// What happens when a dev server exits with an error or fails its health check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DevServerRestartPolicy {
+    #[default]
+    Never,
+    /// Restart with exponential backoff, up to the project's restart limit
+    OnFailure,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Project {
     pub id: Uuid,
@@ -30,6 +86,58 @@ pub struct Project {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    /// Default strategy for direct merges of this project's attempts
+    pub merge_strategy: MergeStrategy,
+    /// Commit message template for direct merges; `None` uses the built-in format
+    pub merge_commit_template: Option<String>,
+    pub commit_message_strategy: CommitMessageStrategy,
+    /// Template used by the `template` commit message strategy
+    pub commit_message_template: Option<String>,
+    pub commit_message_validation: CommitMessageValidation,
+    /// Regex used by the `regex` commit message validation
+    pub commit_message_pattern: Option<String>,
+    /// Author and committer name for commits; falls back to the git config when unset
+    pub commit_author_name: Option<String>,
+    /// Author and committer email for commits; falls back to the git config when unset
+    pub commit_author_email: Option<String>,
+    /// `Name <email>` entries, one per line, added as `Co-authored-by` trailers
+    pub commit_co_authors: Option<String>,
+    pub commit_signing_format: CommitSigningFormat,
+    /// GPG key id, or path to the SSH key, used to sign commits
+    pub commit_signing_key: Option<String>,
+    /// OCI image executions run in; `None` runs them directly on the host
+    pub container_image: Option<String>,
+    /// CPU limit for execution containers, e.g. `2` or `1.5`
+    pub container_cpus: Option<String>,
+    /// Memory limit for execution containers, e.g. `4g` or `512m`
+    pub container_memory: Option<String>,
+    /// Hours without activity before an attempt's worktree is removed
+    #[ts(type = "number")]
+    pub worktree_retention_hours: i64,
+    /// Always keep the worktrees of this many most recently active attempts
+    #[ts(type = "number | null")]
+    pub worktree_keep_recent: Option<i64>,
+    /// Keep worktrees while their task is in review
+    pub worktree_keep_in_review: bool,
+    /// Remove the oldest worktrees once the project's worktrees exceed this size
+    #[ts(type = "number | null")]
+    pub worktree_max_size_mb: Option<i64>,
+    /// Archive uncommitted and untracked files before cleanup and restore them when the
+    /// worktree is recreated
+    pub worktree_snapshot_enabled: bool,
+    /// Directories shared across worktrees, one per line as `dir[: lockfile, ...]`
+    pub dependency_cache: Option<String>,
+    /// Dev server health check: `http[:/path]`, an `http(s)://` URL, `tcp[:port]` or
+    /// `command: <script>`
+    pub dev_server_health_check: Option<String>,
+    pub dev_server_restart_policy: DevServerRestartPolicy,
+    /// Automatic restarts in a row before the dev server is left stopped
+    #[ts(type = "number")]
+    pub dev_server_max_restarts: i64,
+    /// Directory coding agents run in, relative to the repository root
+    pub working_dir: Option<String>,
+    /// Sparse checkout patterns for worktrees, one per line in gitignore syntax
+    pub sparse_checkout: Option<String>,
 
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -46,6 +154,35 @@ pub struct CreateProject {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    pub merge_strategy: Option<MergeStrategy>,
+    pub merge_commit_template: Option<String>,
+    pub commit_message_strategy: Option<CommitMessageStrategy>,
+    pub commit_message_template: Option<String>,
+    pub commit_message_validation: Option<CommitMessageValidation>,
+    pub commit_message_pattern: Option<String>,
+    pub commit_author_name: Option<String>,
+    pub commit_author_email: Option<String>,
+    pub commit_co_authors: Option<String>,
+    pub commit_signing_format: Option<CommitSigningFormat>,
+    pub commit_signing_key: Option<String>,
+    pub container_image: Option<String>,
+    pub container_cpus: Option<String>,
+    pub container_memory: Option<String>,
+    #[ts(type = "number | null")]
+    pub worktree_retention_hours: Option<i64>,
+    #[ts(type = "number | null")]
+    pub worktree_keep_recent: Option<i64>,
+    pub worktree_keep_in_review: Option<bool>,
+    #[ts(type = "number | null")]
+    pub worktree_max_size_mb: Option<i64>,
+    pub worktree_snapshot_enabled: Option<bool>,
+    pub dependency_cache: Option<String>,
+    pub dev_server_health_check: Option<String>,
+    pub dev_server_restart_policy: Option<DevServerRestartPolicy>,
+    #[ts(type = "number | null")]
+    pub dev_server_max_restarts: Option<i64>,
+    pub working_dir: Option<String>,
+    pub sparse_checkout: Option<String>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -56,6 +193,87 @@ pub struct UpdateProject {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    pub merge_strategy: Option<MergeStrategy>,
+    pub merge_commit_template: Option<String>,
+    pub commit_message_strategy: Option<CommitMessageStrategy>,
+    pub commit_message_template: Option<String>,
+    pub commit_message_validation: Option<CommitMessageValidation>,
+    pub commit_message_pattern: Option<String>,
+    pub commit_author_name: Option<String>,
+    pub commit_author_email: Option<String>,
+    pub commit_co_authors: Option<String>,
+    pub commit_signing_format: Option<CommitSigningFormat>,
+    pub commit_signing_key: Option<String>,
+    pub container_image: Option<String>,
+    pub container_cpus: Option<String>,
+    pub container_memory: Option<String>,
+    #[ts(type = "number | null")]
+    pub worktree_retention_hours: Option<i64>,
+    #[ts(type = "number | null")]
+    pub worktree_keep_recent: Option<i64>,
+    pub worktree_keep_in_review: Option<bool>,
+    #[ts(type = "number | null")]
+    pub worktree_max_size_mb: Option<i64>,
+    pub worktree_snapshot_enabled: Option<bool>,
+    pub dependency_cache: Option<String>,
+    pub dev_server_health_check: Option<String>,
+    pub dev_server_restart_policy: Option<DevServerRestartPolicy>,
+    #[ts(type = "number | null")]
+    pub dev_server_max_restarts: Option<i64>,
+    pub working_dir: Option<String>,
+    pub sparse_checkout: Option<String>,
+}
+
+/// Commit message settings written by `Project::update`
+#[derive(Debug, Clone, Default)]
+pub struct CommitMessageSettings {
+    pub strategy: CommitMessageStrategy,
+    pub template: Option<String>,
+    pub validation: CommitMessageValidation,
+    pub pattern: Option<String>,
+}
+
+/// Commit identity settings written by `Project::update`
+#[derive(Debug, Clone, Default)]
+pub struct CommitIdentitySettings {
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub co_authors: Option<String>,
+    pub signing_format: CommitSigningFormat,
+    pub signing_key: Option<String>,
+}
+
+/// Worktree retention settings written by `Project::update`
+#[derive(Debug, Clone)]
+pub struct WorktreeRetentionSettings {
+    pub retention_hours: i64,
+    pub keep_recent: Option<i64>,
+    pub keep_in_review: bool,
+    pub max_size_mb: Option<i64>,
+    pub snapshot: bool,
+}
+
+/// Dev server health and restart settings written by `Project::update`
+#[derive(Debug, Clone)]
+pub struct DevServerSettings {
+    pub health_check: Option<String>,
+    pub restart_policy: DevServerRestartPolicy,
+    pub max_restarts: i64,
+}
+
+/// Working directory and sparse checkout settings written by `Project::update`
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScopeSettings {
+    pub working_dir: Option<String>,
+    pub sparse_checkout: Option<String>,
+}
+
+/// Execution container settings written by `Project::update`
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionContainerSettings {
+    pub image: Option<String>,
+    pub cpus: Option<String>,
+    pub memory: Option<String>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -82,7 +300,7 @@ impl Project {
     pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy as "merge_strategy!: MergeStrategy", merge_commit_template, commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", commit_message_template, commit_message_validation as "commit_message_validation!: CommitMessageValidation", commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format as "commit_signing_format!: CommitSigningFormat", commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review as "worktree_keep_in_review!: bool", worktree_max_size_mb, worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", dependency_cache, dev_server_health_check, dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", dev_server_max_restarts, working_dir, sparse_checkout, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
         )
         .fetch_all(pool)
         .await
@@ -93,7 +311,7 @@ impl Project {
         sqlx::query_as!(
             Project,
             r#"
-            SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, 
+            SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, p.merge_strategy as "merge_strategy!: MergeStrategy", p.merge_commit_template, p.commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", p.commit_message_template, p.commit_message_validation as "commit_message_validation!: CommitMessageValidation", p.commit_message_pattern, p.commit_author_name, p.commit_author_email, p.commit_co_authors, p.commit_signing_format as "commit_signing_format!: CommitSigningFormat", p.commit_signing_key, p.container_image, p.container_cpus, p.container_memory, p.worktree_retention_hours, p.worktree_keep_recent, p.worktree_keep_in_review as "worktree_keep_in_review!: bool", p.worktree_max_size_mb, p.worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", p.dependency_cache, p.dev_server_health_check, p.dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", p.dev_server_max_restarts, p.working_dir, p.sparse_checkout,
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
             WHERE p.id IN (
@@ -113,7 +331,7 @@ impl Project {
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy as "merge_strategy!: MergeStrategy", merge_commit_template, commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", commit_message_template, commit_message_validation as "commit_message_validation!: CommitMessageValidation", commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format as "commit_signing_format!: CommitSigningFormat", commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review as "worktree_keep_in_review!: bool", worktree_max_size_mb, worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", dependency_cache, dev_server_health_check, dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", dev_server_max_restarts, working_dir, sparse_checkout, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
             id
         )
         .fetch_optional(pool)
@@ -126,7 +344,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy as "merge_strategy!: MergeStrategy", merge_commit_template, commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", commit_message_template, commit_message_validation as "commit_message_validation!: CommitMessageValidation", commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format as "commit_signing_format!: CommitSigningFormat", commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review as "worktree_keep_in_review!: bool", worktree_max_size_mb, worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", dependency_cache, dev_server_health_check, dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", dev_server_max_restarts, working_dir, sparse_checkout, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
             git_repo_path
         )
         .fetch_optional(pool)
@@ -140,7 +358,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy as "merge_strategy!: MergeStrategy", merge_commit_template, commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", commit_message_template, commit_message_validation as "commit_message_validation!: CommitMessageValidation", commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format as "commit_signing_format!: CommitSigningFormat", commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review as "worktree_keep_in_review!: bool", worktree_max_size_mb, worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", dependency_cache, dev_server_health_check, dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", dev_server_max_restarts, working_dir, sparse_checkout, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
             git_repo_path,
             exclude_id
         )
@@ -153,16 +371,54 @@ impl Project {
         data: &CreateProject,
         project_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
+        let merge_strategy = data.merge_strategy.unwrap_or_default();
+        let commit_message_strategy = data.commit_message_strategy.unwrap_or_default();
+        let commit_message_validation = data.commit_message_validation.unwrap_or_default();
+        let commit_signing_format = data.commit_signing_format.unwrap_or_default();
+        let worktree_retention_hours = data
+            .worktree_retention_hours
+            .unwrap_or(DEFAULT_WORKTREE_RETENTION_HOURS);
+        let worktree_keep_in_review = data.worktree_keep_in_review.unwrap_or(false);
+        let worktree_snapshot_enabled = data.worktree_snapshot_enabled.unwrap_or(false);
+        let dev_server_restart_policy = data.dev_server_restart_policy.unwrap_or_default();
+        let dev_server_max_restarts = data
+            .dev_server_max_restarts
+            .unwrap_or(DEFAULT_DEV_SERVER_MAX_RESTARTS);
         sqlx::query_as!(
             Project,
-            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy, merge_commit_template, commit_message_strategy, commit_message_template, commit_message_validation, commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format, commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review, worktree_max_size_mb, worktree_snapshot_enabled, dependency_cache, dev_server_health_check, dev_server_restart_policy, dev_server_max_restarts, working_dir, sparse_checkout) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy as "merge_strategy!: MergeStrategy", merge_commit_template, commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", commit_message_template, commit_message_validation as "commit_message_validation!: CommitMessageValidation", commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format as "commit_signing_format!: CommitSigningFormat", commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review as "worktree_keep_in_review!: bool", worktree_max_size_mb, worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", dependency_cache, dev_server_health_check, dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", dev_server_max_restarts, working_dir, sparse_checkout, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
             data.name,
             data.git_repo_path,
             data.setup_script,
             data.dev_script,
             data.cleanup_script,
-            data.copy_files
+            data.copy_files,
+            merge_strategy,
+            data.merge_commit_template,
+            commit_message_strategy,
+            data.commit_message_template,
+            commit_message_validation,
+            data.commit_message_pattern,
+            data.commit_author_name,
+            data.commit_author_email,
+            data.commit_co_authors,
+            commit_signing_format,
+            data.commit_signing_key,
+            data.container_image,
+            data.container_cpus,
+            data.container_memory,
+            worktree_retention_hours,
+            data.worktree_keep_recent,
+            worktree_keep_in_review,
+            data.worktree_max_size_mb,
+            worktree_snapshot_enabled,
+            data.dependency_cache,
+            data.dev_server_health_check,
+            dev_server_restart_policy,
+            dev_server_max_restarts,
+            data.working_dir,
+            data.sparse_checkout
         )
         .fetch_one(pool)
         .await
@@ -178,17 +434,51 @@ impl Project {
         dev_script: Option<String>,
         cleanup_script: Option<String>,
         copy_files: Option<String>,
+        dependency_cache: Option<String>,
+        merge_strategy: MergeStrategy,
+        merge_commit_template: Option<String>,
+        commit_messages: CommitMessageSettings,
+        commit_identity: CommitIdentitySettings,
+        execution_container: ExecutionContainerSettings,
+        worktree_retention: WorktreeRetentionSettings,
+        dev_server: DevServerSettings,
+        scope: ProjectScopeSettings,
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7, merge_strategy = $8, merge_commit_template = $9, commit_message_strategy = $10, commit_message_template = $11, commit_message_validation = $12, commit_message_pattern = $13, commit_author_name = $14, commit_author_email = $15, commit_co_authors = $16, commit_signing_format = $17, commit_signing_key = $18, container_image = $19, container_cpus = $20, container_memory = $21, worktree_retention_hours = $22, worktree_keep_recent = $23, worktree_keep_in_review = $24, worktree_max_size_mb = $25, worktree_snapshot_enabled = $26, dependency_cache = $27, dev_server_health_check = $28, dev_server_restart_policy = $29, dev_server_max_restarts = $30, working_dir = $31, sparse_checkout = $32 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, merge_strategy as "merge_strategy!: MergeStrategy", merge_commit_template, commit_message_strategy as "commit_message_strategy!: CommitMessageStrategy", commit_message_template, commit_message_validation as "commit_message_validation!: CommitMessageValidation", commit_message_pattern, commit_author_name, commit_author_email, commit_co_authors, commit_signing_format as "commit_signing_format!: CommitSigningFormat", commit_signing_key, container_image, container_cpus, container_memory, worktree_retention_hours, worktree_keep_recent, worktree_keep_in_review as "worktree_keep_in_review!: bool", worktree_max_size_mb, worktree_snapshot_enabled as "worktree_snapshot_enabled!: bool", dependency_cache, dev_server_health_check, dev_server_restart_policy as "dev_server_restart_policy!: DevServerRestartPolicy", dev_server_max_restarts, working_dir, sparse_checkout, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             name,
             git_repo_path,
             setup_script,
             dev_script,
             cleanup_script,
-            copy_files
+            copy_files,
+            merge_strategy,
+            merge_commit_template,
+            commit_messages.strategy,
+            commit_messages.template,
+            commit_messages.validation,
+            commit_messages.pattern,
+            commit_identity.author_name,
+            commit_identity.author_email,
+            commit_identity.co_authors,
+            commit_identity.signing_format,
+            commit_identity.signing_key,
+            execution_container.image,
+            execution_container.cpus,
+            execution_container.memory,
+            worktree_retention.retention_hours,
+            worktree_retention.keep_recent,
+            worktree_retention.keep_in_review,
+            worktree_retention.max_size_mb,
+            worktree_retention.snapshot,
+            dependency_cache,
+            dev_server.health_check,
+            dev_server.restart_policy,
+            dev_server.max_restarts,
+            scope.working_dir,
+            scope.sparse_checkout
         )
         .fetch_one(pool)
         .await
@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Side of the diff a review comment is anchored to
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "review_comment_side", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewCommentSide {
+    /// The attempt's base, for comments on removed lines
+    Old,
+    /// The attempt's worktree
+    New,
+}
+
+/// A review comment on lines of an attempt's diff
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ReviewComment {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub file_path: String,
+    pub side: ReviewCommentSide,
+    /// First commented line, 1-based
+    #[ts(type = "number")]
+    pub line_start: i64,
+    /// Last commented line, inclusive
+    #[ts(type = "number")]
+    pub line_end: i64,
+    /// Worktree HEAD when the comment was written
+    pub commit_sha: Option<String>,
+    /// Content of the commented lines, used to re-anchor the comment as the diff changes
+    pub code: Option<String>,
+    pub body: String,
+    /// The commented lines no longer exist in the file
+    pub outdated: bool,
+    /// Execution process the comment was sent to for the agent to address
+    pub sent_execution_process_id: Option<Uuid>,
+    #[ts(type = "Date | null")]
+    pub resolved_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateReviewComment {
+    pub file_path: String,
+    pub side: ReviewCommentSide,
+    #[ts(type = "number")]
+    pub line_start: i64,
+    #[ts(type = "number | null")]
+    pub line_end: Option<i64>,
+    pub code: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateReviewComment {
+    pub body: Option<String>,
+    /// Resolve or reopen the comment
+    pub resolved: Option<bool>,
+}
+
+impl ReviewComment {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", file_path, side as "side!: ReviewCommentSide", line_start, line_end, commit_sha, code, body, outdated as "outdated!: bool", sent_execution_process_id as "sent_execution_process_id: Uuid", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", file_path, side as "side!: ReviewCommentSide", line_start, line_end, commit_sha, code, body, outdated as "outdated!: bool", sent_execution_process_id as "sent_execution_process_id: Uuid", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE task_attempt_id = $1
+               ORDER BY file_path ASC, line_start ASC, created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Unresolved comments that haven't been sent to an agent yet
+    pub async fn find_open_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", file_path, side as "side!: ReviewCommentSide", line_start, line_end, commit_sha, code, body, outdated as "outdated!: bool", sent_execution_process_id as "sent_execution_process_id: Uuid", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE task_attempt_id = $1 AND resolved_at IS NULL AND sent_execution_process_id IS NULL
+               ORDER BY file_path ASC, line_start ASC, created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        data: &CreateReviewComment,
+        commit_sha: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let line_end = data
+            .line_end
+            .unwrap_or(data.line_start)
+            .max(data.line_start);
+        sqlx::query_as!(
+            ReviewComment,
+            r#"INSERT INTO review_comments (id, task_attempt_id, file_path, side, line_start, line_end, commit_sha, code, body)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid", file_path, side as "side!: ReviewCommentSide", line_start, line_end, commit_sha, code, body, outdated as "outdated!: bool", sent_execution_process_id as "sent_execution_process_id: Uuid", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_attempt_id,
+            data.file_path,
+            data.side,
+            data.line_start,
+            line_end,
+            commit_sha,
+            data.code,
+            data.body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update_body(pool: &SqlitePool, id: Uuid, body: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE review_comments SET body = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            body
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Resolve a comment, or reopen it so it is sent with the next review
+    pub async fn set_resolved(
+        pool: &SqlitePool,
+        id: Uuid,
+        resolved: bool,
+    ) -> Result<(), sqlx::Error> {
+        if resolved {
+            sqlx::query!(
+                "UPDATE review_comments SET resolved_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1 AND resolved_at IS NULL",
+                id
+            )
+            .execute(pool)
+            .await?;
+        } else {
+            sqlx::query!(
+                "UPDATE review_comments SET resolved_at = NULL, sent_execution_process_id = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1",
+                id
+            )
+            .execute(pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Move a comment to the lines now holding its code, or mark it outdated
+    pub async fn update_anchor(
+        pool: &SqlitePool,
+        id: Uuid,
+        line_start: i64,
+        line_end: i64,
+        outdated: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE review_comments SET line_start = $2, line_end = $3, outdated = $4, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            line_start,
+            line_end,
+            outdated
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record that comments were sent to an execution process
+    pub async fn mark_sent(
+        pool: &SqlitePool,
+        ids: &[Uuid],
+        execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for id in ids {
+            sqlx::query!(
+                "UPDATE review_comments SET sent_execution_process_id = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+                id,
+                execution_process_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Resolve the attempt's sent comments whose execution process completed, and reopen
+    /// those whose process failed or was stopped
+    pub async fn settle_sent(pool: &SqlitePool, task_attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE review_comments
+               SET resolved_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE task_attempt_id = $1
+                 AND resolved_at IS NULL
+                 AND sent_execution_process_id IN (
+                     SELECT id FROM execution_processes WHERE status = 'completed'
+                 )"#,
+            task_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        sqlx::query!(
+            r#"UPDATE review_comments
+               SET sent_execution_process_id = NULL, updated_at = datetime('now', 'subsec')
+               WHERE task_attempt_id = $1
+                 AND resolved_at IS NULL
+                 AND sent_execution_process_id IN (
+                     SELECT id FROM execution_processes WHERE status IN ('failed', 'killed')
+                 )"#,
+            task_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM review_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -6,7 +6,10 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{project::Project, task::Task};
+use super::{
+    project::Project,
+    task::{Task, TaskStatus},
+};
 
 #[derive(Debug, Error)]
 pub enum TaskAttemptError {
@@ -44,11 +47,29 @@ pub struct TaskAttempt {
     pub executor: String, // Name of the base coding agent to use ("AMP", "CLAUDE_CODE",
     // "GEMINI", etc.)
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
+    pub worktree_pinned: bool,  // Pinned worktrees are never removed by retention cleanup
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
+    pub parent_attempt_id: Option<Uuid>, // Attempt whose branch this attempt is stacked on
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// A live worktree considered by retention cleanup
+#[derive(Debug, Clone)]
+pub struct WorktreeCleanupCandidate {
+    pub attempt_id: Uuid,
+    pub project_id: Uuid,
+    pub container_ref: String,
+    pub git_repo_path: String,
+    pub task_status: TaskStatus,
+    pub pinned: bool,
+    /// An execution process is still running in the worktree
+    pub running: bool,
+    pub last_activity_at: DateTime<Utc>,
+    /// The project snapshots worktrees before removing them
+    pub snapshot: bool,
+}
+
 /// GitHub PR creation parameters
 pub struct CreatePrParams<'a> {
     pub attempt_id: Uuid,
@@ -84,6 +105,7 @@ pub struct CreateTaskAttempt {
     pub executor: BaseCodingAgent,
     pub base_branch: String,
     pub branch: String,
+    pub parent_attempt_id: Option<Uuid>,
 }
 
 impl TaskAttempt {
@@ -106,7 +128,9 @@ impl TaskAttempt {
                               target_branch,
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
+                              worktree_pinned AS "worktree_pinned!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              parent_attempt_id AS "parent_attempt_id: Uuid",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -126,7 +150,9 @@ impl TaskAttempt {
                               target_branch,
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
+                              worktree_pinned AS "worktree_pinned!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              parent_attempt_id AS "parent_attempt_id: Uuid",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -157,7 +183,9 @@ impl TaskAttempt {
                        ta.target_branch,
                        ta.executor AS "executor!",
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
+                       ta.worktree_pinned   AS "worktree_pinned!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       ta.parent_attempt_id AS "parent_attempt_id: Uuid",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -230,7 +258,9 @@ impl TaskAttempt {
                        target_branch,
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
+                       worktree_pinned   AS "worktree_pinned!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       parent_attempt_id AS "parent_attempt_id: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -251,7 +281,9 @@ impl TaskAttempt {
                        target_branch,
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
+                       worktree_pinned   AS "worktree_pinned!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       parent_attempt_id AS "parent_attempt_id: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -262,6 +294,34 @@ impl TaskAttempt {
         .await
     }
 
+    /// Find attempts stacked directly on the given attempt. Oldest first.
+    pub async fn find_children(
+        pool: &SqlitePool,
+        parent_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttempt,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       target_branch,
+                       executor AS "executor!",
+                       worktree_deleted  AS "worktree_deleted!: bool",
+                       worktree_pinned   AS "worktree_pinned!: bool",
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       parent_attempt_id AS "parent_attempt_id: Uuid",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>"
+               FROM    task_attempts
+               WHERE   parent_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            parent_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find task attempts by task_id with project git repo path for cleanup operations
     pub async fn find_by_task_id_with_project(
         pool: &SqlitePool,
@@ -299,12 +359,18 @@ impl TaskAttempt {
             .collect())
     }
 
+    /// Whether `container_ref` is an attempt's worktree, or the workspace directory holding
+    /// the worktrees of an attempt with several repositories
     pub async fn container_ref_exists(
         pool: &SqlitePool,
         container_ref: &str,
     ) -> Result<bool, sqlx::Error> {
         let result = sqlx::query!(
-            r#"SELECT EXISTS(SELECT 1 FROM task_attempts WHERE container_ref = ?) as "exists!: bool""#,
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM task_attempts
+                   WHERE container_ref = $1
+                      OR substr(container_ref, 1, length($1) + 1) IN ($1 || '/', $1 || '\')
+               ) as "exists!: bool""#,
             container_ref
         )
         .fetch_one(pool)
@@ -313,53 +379,59 @@ impl TaskAttempt {
         Ok(result.exists)
     }
 
-    /// Find task attempts that are expired (72+ hours since last activity) and eligible for worktree cleanup
-    /// Activity includes: execution completion, task attempt updates (including worktree recreation),
-    /// and any attempts that are currently in progress
-    pub async fn find_expired_for_cleanup(
+    /// Attempts that still have a worktree on disk, with what retention cleanup needs to
+    /// decide whether to keep them. Last activity is the latest execution completion, or the
+    /// attempt's last update (which includes worktree recreation) when nothing has completed.
+    pub async fn find_worktree_cleanup_candidates(
         pool: &SqlitePool,
-    ) -> Result<Vec<(Uuid, String, String)>, sqlx::Error> {
-        let records = sqlx::query!(
+    ) -> Result<Vec<WorktreeCleanupCandidate>, sqlx::Error> {
+        sqlx::query_as!(
+            WorktreeCleanupCandidate,
             r#"
-            SELECT ta.id as "attempt_id!: Uuid", ta.container_ref, p.git_repo_path as "git_repo_path!"
+            SELECT ta.id as "attempt_id!: Uuid",
+                   t.project_id as "project_id!: Uuid",
+                   ta.container_ref as "container_ref!",
+                   p.git_repo_path as "git_repo_path!",
+                   t.status as "task_status!: TaskStatus",
+                   ta.worktree_pinned as "pinned!: bool",
+                   EXISTS(
+                       SELECT 1 FROM execution_processes ep
+                       WHERE ep.task_attempt_id = ta.id AND ep.completed_at IS NULL
+                   ) as "running!: bool",
+                   COALESCE(
+                       (SELECT MAX(ep.completed_at) FROM execution_processes ep
+                        WHERE ep.task_attempt_id = ta.id),
+                       ta.updated_at
+                   ) as "last_activity_at!: DateTime<Utc>",
+                   p.worktree_snapshot_enabled as "snapshot!: bool"
             FROM task_attempts ta
-            LEFT JOIN execution_processes ep ON ta.id = ep.task_attempt_id AND ep.completed_at IS NOT NULL
             JOIN tasks t ON ta.task_id = t.id
             JOIN projects p ON t.project_id = p.id
-            WHERE ta.worktree_deleted = FALSE
-                -- Exclude attempts with any running processes (in progress)
-                AND ta.id NOT IN (
-                    SELECT DISTINCT ep2.task_attempt_id
-                    FROM execution_processes ep2
-                    WHERE ep2.completed_at IS NULL
-                )
-            GROUP BY ta.id, ta.container_ref, p.git_repo_path, ta.updated_at
-            HAVING datetime('now', '-72 hours') > datetime(
-                MAX(
-                    CASE
-                        WHEN ep.completed_at IS NOT NULL THEN ep.completed_at
-                        ELSE ta.updated_at
-                    END
-                )
-            )
-            ORDER BY MAX(
-                CASE
-                    WHEN ep.completed_at IS NOT NULL THEN ep.completed_at
-                    ELSE ta.updated_at
-                END
-            ) ASC
+            WHERE ta.worktree_deleted = FALSE AND ta.container_ref IS NOT NULL
+            ORDER BY COALESCE(
+                         (SELECT MAX(ep.completed_at) FROM execution_processes ep
+                          WHERE ep.task_attempt_id = ta.id),
+                         ta.updated_at
+                     ) ASC
             "#
         )
         .fetch_all(pool)
-        .await?;
+        .await
+    }
 
-        Ok(records
-            .into_iter()
-            .filter_map(|r| {
-                r.container_ref
-                    .map(|path| (r.attempt_id, path, r.git_repo_path))
-            })
-            .collect())
+    pub async fn set_worktree_pinned(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        pinned: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET worktree_pinned = $1 WHERE id = $2",
+            pinned,
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
     }
 
     pub async fn create(
@@ -372,9 +444,9 @@ impl TaskAttempt {
         // Insert the record into the database
         Ok(sqlx::query_as!(
             TaskAttempt,
-            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at, parent_attempt_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", worktree_pinned as "worktree_pinned!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", parent_attempt_id as "parent_attempt_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None, // Container isn't known yet
@@ -382,7 +454,8 @@ impl TaskAttempt {
             data.base_branch, // Target branch is same as base branch during creation
             data.executor,
             false, // worktree_deleted is false during creation
-            Option::<DateTime<Utc>>::None // setup_completed_at is None during creation
+            Option::<DateTime<Utc>>::None, // setup_completed_at is None during creation
+            data.parent_attempt_id
         )
         .fetch_one(pool)
         .await?)
@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An extra repository checked out for an attempt, joined with the project repository
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskAttemptRepository {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub project_repository_id: Uuid,
+    pub name: String,
+    pub git_repo_path: PathBuf,
+    /// Branch the attempt's branch was created from in this repository
+    pub target_branch: String,
+    /// Commit on the target branch once the repository's changes were merged
+    pub merge_commit: Option<String>,
+    #[ts(type = "number | null")]
+    pub pr_number: Option<i64>,
+    pub pr_url: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TaskAttemptRepository {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttemptRepository,
+            r#"SELECT tar.id as "id!: Uuid", tar.task_attempt_id as "task_attempt_id!: Uuid", tar.project_repository_id as "project_repository_id!: Uuid", pr.name, pr.git_repo_path, tar.target_branch, tar.merge_commit, tar.pr_number, tar.pr_url, tar.created_at as "created_at!: DateTime<Utc>", tar.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attempt_repositories tar
+               JOIN project_repositories pr ON pr.id = tar.project_repository_id
+               WHERE tar.task_attempt_id = $1
+               ORDER BY pr.created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_project_repository_id(
+        pool: &SqlitePool,
+        project_repository_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttemptRepository,
+            r#"SELECT tar.id as "id!: Uuid", tar.task_attempt_id as "task_attempt_id!: Uuid", tar.project_repository_id as "project_repository_id!: Uuid", pr.name, pr.git_repo_path, tar.target_branch, tar.merge_commit, tar.pr_number, tar.pr_url, tar.created_at as "created_at!: DateTime<Utc>", tar.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attempt_repositories tar
+               JOIN project_repositories pr ON pr.id = tar.project_repository_id
+               WHERE tar.project_repository_id = $1"#,
+            project_repository_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_attempt_and_repository(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        project_repository_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttemptRepository,
+            r#"SELECT tar.id as "id!: Uuid", tar.task_attempt_id as "task_attempt_id!: Uuid", tar.project_repository_id as "project_repository_id!: Uuid", pr.name, pr.git_repo_path, tar.target_branch, tar.merge_commit, tar.pr_number, tar.pr_url, tar.created_at as "created_at!: DateTime<Utc>", tar.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attempt_repositories tar
+               JOIN project_repositories pr ON pr.id = tar.project_repository_id
+               WHERE tar.task_attempt_id = $1 AND tar.project_repository_id = $2"#,
+            task_attempt_id,
+            project_repository_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        project_repository_id: Uuid,
+        target_branch: &str,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_attempt_repositories (id, task_attempt_id, project_repository_id, target_branch)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(task_attempt_id, project_repository_id) DO NOTHING"#,
+            id,
+            task_attempt_id,
+            project_repository_id,
+            target_branch
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_merge_commit(
+        pool: &SqlitePool,
+        id: Uuid,
+        merge_commit: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempt_repositories SET merge_commit = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            merge_commit
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_pr(
+        pool: &SqlitePool,
+        id: Uuid,
+        pr_number: i64,
+        pr_url: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempt_repositories SET pr_number = $2, pr_url = $3, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            pr_number,
+            pr_url
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
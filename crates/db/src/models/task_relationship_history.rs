@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipChange {
+    Added,
+    Removed,
+}
+
+/// A relationship of a task being added or removed, seen from that task
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TaskRelationshipChange {
+    #[ts(type = "number")]
+    pub id: i64,
+    pub change: RelationshipChange,
+    /// Whether the task is the relationship's source
+    pub outgoing: bool,
+    pub other_task_id: Uuid,
+    pub other_task_title: String,
+    /// Label of the relationship from the task's side, e.g. "blocks" or "blocked by"; `None`
+    /// if its type has since been deleted
+    pub label: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TaskRelationshipChange {
+    /// Relationships of the task being added and removed, oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRelationshipChange,
+            r#"SELECT h.id as "id!: i64",
+                      h.change as "change!: RelationshipChange",
+                      (h.source_task_id = $1) as "outgoing!: bool",
+                      other.id as "other_task_id!: Uuid",
+                      other.title as "other_task_title!: String",
+                      CASE
+                          WHEN h.source_task_id = $1 THEN COALESCE(rt.forward_label, rt.display_name)
+                          ELSE COALESCE(rt.reverse_label, rt.display_name)
+                      END as "label?: String",
+                      h.changed_at as "changed_at!: DateTime<Utc>"
+               FROM task_relationship_history h
+               JOIN tasks other
+                 ON other.id = CASE WHEN h.source_task_id = $1 THEN h.target_task_id
+                                    ELSE h.source_task_id END
+               LEFT JOIN task_relationship_types rt ON rt.id = h.relationship_type_id
+               WHERE h.source_task_id = $1 OR h.target_task_id = $1
+               ORDER BY h.changed_at ASC, h.id ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The change seen from each of the two tasks of the relationship, source first
+    pub async fn find_by_id(pool: &SqlitePool, id: i64) -> Result<Vec<(Uuid, Self)>, sqlx::Error> {
+        let Some(tasks) = sqlx::query!(
+            r#"SELECT source_task_id as "source_task_id!: Uuid",
+                      target_task_id as "target_task_id!: Uuid"
+               FROM task_relationship_history
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut changes = Vec::new();
+        for task_id in [tasks.source_task_id, tasks.target_task_id] {
+            let change = sqlx::query_as!(
+                TaskRelationshipChange,
+                r#"SELECT h.id as "id!: i64",
+                          h.change as "change!: RelationshipChange",
+                          (h.source_task_id = $1) as "outgoing!: bool",
+                          other.id as "other_task_id!: Uuid",
+                          other.title as "other_task_title!: String",
+                          CASE
+                              WHEN h.source_task_id = $1 THEN COALESCE(rt.forward_label, rt.display_name)
+                              ELSE COALESCE(rt.reverse_label, rt.display_name)
+                          END as "label?: String",
+                          h.changed_at as "changed_at!: DateTime<Utc>"
+                   FROM task_relationship_history h
+                   JOIN tasks other
+                     ON other.id = CASE WHEN h.source_task_id = $1 THEN h.target_task_id
+                                        ELSE h.source_task_id END
+                   LEFT JOIN task_relationship_types rt ON rt.id = h.relationship_type_id
+                   WHERE h.id = $2"#,
+                task_id,
+                id
+            )
+            .fetch_optional(pool)
+            .await?;
+            changes.extend(change.map(|change| (task_id, change)));
+        }
+        Ok(changes)
+    }
+}
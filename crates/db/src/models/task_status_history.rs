@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// A task moving between statuses, recorded by database triggers
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TaskStatusChange {
+    #[ts(type = "number")]
+    pub id: i64,
+    pub task_id: Uuid,
+    /// `None` for the status the task was created with
+    pub from_status: Option<TaskStatus>,
+    pub to_status: TaskStatus,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TaskStatusChange {
+    pub async fn find_by_id(pool: &SqlitePool, id: i64) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskStatusChange,
+            r#"SELECT id as "id!: i64",
+                      task_id as "task_id!: Uuid",
+                      from_status as "from_status?: TaskStatus",
+                      to_status as "to_status!: TaskStatus",
+                      changed_at as "changed_at!: DateTime<Utc>"
+               FROM task_status_history
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The task's status changes, oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskStatusChange,
+            r#"SELECT id as "id!: i64",
+                      task_id as "task_id!: Uuid",
+                      from_status as "from_status?: TaskStatus",
+                      to_status as "to_status!: TaskStatus",
+                      changed_at as "changed_at!: DateTime<Utc>"
+               FROM task_status_history
+               WHERE task_id = $1
+               ORDER BY changed_at ASC, id ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Status changes of all the project's tasks, ordered by task and then oldest first
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskStatusChange,
+            r#"SELECT h.id as "id!: i64",
+                      h.task_id as "task_id!: Uuid",
+                      h.from_status as "from_status?: TaskStatus",
+                      h.to_status as "to_status!: TaskStatus",
+                      h.changed_at as "changed_at!: DateTime<Utc>"
+               FROM task_status_history h
+               JOIN tasks t ON t.id = h.task_id
+               WHERE t.project_id = $1
+               ORDER BY h.task_id, h.changed_at ASC, h.id ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
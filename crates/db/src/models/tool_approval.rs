@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ToolApprovalStatus {
+    Pending,
+    Approved,
+    Denied,
+    TimedOut,
+}
+
+/// A tool call an agent asked approval for
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ToolApproval {
+    pub id: String,
+    pub execution_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub tool_name: String,
+    /// One line describing what the tool would do
+    pub tool_input_summary: String,
+    pub status: ToolApprovalStatus,
+    pub denial_reason: Option<String>,
+    pub requested_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Approval waits across all processes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApprovalWaitStats {
+    /// Approvals still waiting on an answer for a running process
+    pub pending: i64,
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+    pub resolved: i64,
+    /// Time resolved approvals spent waiting, added up
+    pub total_wait_seconds: f64,
+}
+
+impl ToolApproval {
+    pub async fn create(
+        pool: &SqlitePool,
+        id: &str,
+        execution_process_id: Uuid,
+        tool_name: &str,
+        tool_input_summary: &str,
+        requested_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO tool_approvals
+                   (id, execution_process_id, tool_name, tool_input_summary, requested_at)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (id) DO NOTHING"#,
+            id,
+            execution_process_id,
+            tool_name,
+            tool_input_summary,
+            requested_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record how a pending approval was resolved; later resolutions are ignored
+    pub async fn resolve(
+        pool: &SqlitePool,
+        id: &str,
+        status: ToolApprovalStatus,
+        denial_reason: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let resolved_at = Utc::now();
+        sqlx::query!(
+            r#"UPDATE tool_approvals
+               SET status = $2, denial_reason = $3, resolved_at = $4
+               WHERE id = $1 AND status = 'pending'"#,
+            id,
+            status,
+            denial_reason,
+            resolved_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ToolApproval,
+            r#"SELECT a.id as "id!: String",
+                      a.execution_process_id as "execution_process_id!: Uuid",
+                      ep.task_attempt_id as "task_attempt_id!: Uuid",
+                      a.tool_name as "tool_name!: String",
+                      a.tool_input_summary as "tool_input_summary!: String",
+                      a.status as "status!: ToolApprovalStatus",
+                      a.denial_reason,
+                      a.requested_at as "requested_at!: DateTime<Utc>",
+                      a.resolved_at as "resolved_at?: DateTime<Utc>"
+               FROM tool_approvals a
+               JOIN execution_processes ep ON ep.id = a.execution_process_id
+               WHERE a.rowid = $1"#,
+            rowid
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Approvals asked for by the task's processes, oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ToolApproval,
+            r#"SELECT a.id as "id!: String",
+                      a.execution_process_id as "execution_process_id!: Uuid",
+                      ep.task_attempt_id as "task_attempt_id!: Uuid",
+                      a.tool_name as "tool_name!: String",
+                      a.tool_input_summary as "tool_input_summary!: String",
+                      a.status as "status!: ToolApprovalStatus",
+                      a.denial_reason,
+                      a.requested_at as "requested_at!: DateTime<Utc>",
+                      a.resolved_at as "resolved_at?: DateTime<Utc>"
+               FROM tool_approvals a
+               JOIN execution_processes ep ON ep.id = a.execution_process_id
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               WHERE ta.task_id = $1
+               ORDER BY a.requested_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn wait_stats(pool: &SqlitePool) -> Result<ApprovalWaitStats, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalWaitStats,
+            r#"SELECT COALESCE(SUM(CASE WHEN a.status = 'pending' AND ep.status = 'running'
+                                        THEN 1 ELSE 0 END), 0) as "pending!: i64",
+                      MIN(CASE WHEN a.status = 'pending' AND ep.status = 'running'
+                               THEN a.requested_at END) as "oldest_pending_at?: DateTime<Utc>",
+                      COALESCE(SUM(CASE WHEN a.resolved_at IS NOT NULL THEN 1 ELSE 0 END), 0)
+                          as "resolved!: i64",
+                      COALESCE(SUM(unixepoch(a.resolved_at, 'subsec')
+                                   - unixepoch(a.requested_at, 'subsec')), 0.0)
+                          as "total_wait_seconds!: f64"
+               FROM tool_approvals a
+               JOIN execution_processes ep ON ep.id = a.execution_process_id"#
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,195 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Maximum number of search results returned at once
+pub const TRANSCRIPT_SEARCH_MAX_LIMIT: i64 = 200;
+const TRANSCRIPT_SEARCH_DEFAULT_LIMIT: i64 = 50;
+
+/// What part of a conversation an indexed entry came from
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "transcript_entry_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptEntryKind {
+    AssistantMessage,
+    /// Command the agent ran
+    Command,
+    /// Path of a file the agent read or edited
+    FilePath,
+}
+
+/// Searchable text of one normalized log entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    pub entry_index: i64,
+    pub kind: TranscriptEntryKind,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct TranscriptSearchQuery {
+    /// Words to find; each matches as a prefix and all of them must match
+    pub q: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    /// Base coding agent of the attempt, e.g. `CLAUDE_CODE`
+    pub executor: Option<String>,
+    pub kind: Option<TranscriptEntryKind>,
+    /// Only processes started at or after this time
+    #[ts(type = "Date | null")]
+    pub from: Option<DateTime<Utc>>,
+    /// Only processes started before this time
+    #[ts(type = "Date | null")]
+    pub to: Option<DateTime<Utc>>,
+    #[ts(type = "number | null")]
+    pub limit: Option<i64>,
+}
+
+/// An indexed entry matching a search, with enough context to link to it
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TranscriptSearchResult {
+    pub execution_process_id: Uuid,
+    /// Index of the entry in the process's normalized log
+    #[ts(type = "number")]
+    pub entry_index: i64,
+    pub kind: TranscriptEntryKind,
+    /// Excerpt of the entry with matched terms wrapped in `\u{2}` and `\u{3}`
+    pub snippet: String,
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_id: Uuid,
+    pub executor: String,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+}
+
+/// Turn free text into an FTS5 query of quoted prefix terms, so user input can't be parsed as
+/// query syntax
+pub fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+impl TranscriptEntry {
+    /// Replace the process's indexed entries and record that it has been indexed
+    pub async fn replace_for_process(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        entries: &[TranscriptEntry],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM transcript_entries WHERE execution_process_id = $1",
+            execution_process_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for entry in entries {
+            sqlx::query!(
+                r#"INSERT INTO transcript_entries (execution_process_id, entry_index, kind, content)
+                   VALUES ($1, $2, $3, $4)
+                   ON CONFLICT (execution_process_id, entry_index, kind) DO UPDATE
+                   SET content = excluded.content"#,
+                execution_process_id,
+                entry.entry_index,
+                entry.kind,
+                entry.content
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"INSERT INTO transcript_indexed_processes (execution_process_id)
+               VALUES ($1)
+               ON CONFLICT (execution_process_id) DO UPDATE
+               SET indexed_at = datetime('now', 'subsec')"#,
+            execution_process_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Finished coding agent processes that have never been indexed, oldest first
+    pub async fn find_unindexed_process_ids(
+        pool: &SqlitePool,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT ep.id as "id!: Uuid"
+               FROM execution_processes ep
+               WHERE ep.run_reason = 'codingagent'
+                 AND ep.status != 'running'
+                 AND NOT EXISTS (
+                     SELECT 1 FROM transcript_indexed_processes tip
+                     WHERE tip.execution_process_id = ep.id
+                 )
+               ORDER BY ep.created_at ASC
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|row| row.id).collect())
+    }
+
+    /// Entries matching the query, best matches first
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &TranscriptSearchQuery,
+    ) -> Result<Vec<TranscriptSearchResult>, sqlx::Error> {
+        let Some(terms) = fts_query(&query.q) else {
+            return Ok(Vec::new());
+        };
+        let limit = query
+            .limit
+            .unwrap_or(TRANSCRIPT_SEARCH_DEFAULT_LIMIT)
+            .clamp(1, TRANSCRIPT_SEARCH_MAX_LIMIT);
+
+        sqlx::query_as!(
+            TranscriptSearchResult,
+            r#"SELECT te.execution_process_id as "execution_process_id!: Uuid",
+                      te.entry_index as "entry_index!: i64",
+                      te.kind as "kind!: TranscriptEntryKind",
+                      snippet(transcript_entries_fts, 0, char(2), char(3), '…', 16) as "snippet!: String",
+                      ep.task_attempt_id as "task_attempt_id!: Uuid",
+                      t.id as "task_id!: Uuid",
+                      t.title as "task_title!: String",
+                      t.project_id as "project_id!: Uuid",
+                      ta.executor as "executor!: String",
+                      ep.started_at as "started_at!: DateTime<Utc>"
+               FROM transcript_entries_fts
+               JOIN transcript_entries te ON te.id = transcript_entries_fts.rowid
+               JOIN execution_processes ep ON ep.id = te.execution_process_id
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE transcript_entries_fts MATCH $1
+                 AND ($2 IS NULL OR t.project_id = $2)
+                 AND ($3 IS NULL OR t.id = $3)
+                 AND ($4 IS NULL OR ta.executor = $4)
+                 AND ($5 IS NULL OR te.kind = $5)
+                 AND ($6 IS NULL OR datetime(ep.started_at, 'subsec') >= datetime($6, 'subsec'))
+                 AND ($7 IS NULL OR datetime(ep.started_at, 'subsec') < datetime($7, 'subsec'))
+               ORDER BY transcript_entries_fts.rank
+               LIMIT $8"#,
+            terms,
+            query.project_id,
+            query.task_id,
+            query.executor,
+            query.kind,
+            query.from,
+            query.to,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,331 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Lifecycle events a webhook can subscribe to
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "webhook_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookEventType {
+    TaskCreated,
+    TaskStatusChanged,
+    AttemptStarted,
+    ProcessCompleted,
+    ProcessFailed,
+    ApprovalRequested,
+    PrMerged,
+    /// Sent when testing a webhook, regardless of its subscriptions
+    Ping,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDeliveryStatus {
+    /// Not delivered yet, or waiting to be retried
+    Pending,
+    Succeeded,
+    /// Every attempt failed
+    Failed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    /// Key for the `X-Vibe-Kanban-Signature-256` HMAC of each payload. Never sent to clients
+    /// after [`CreatedWebhook`].
+    #[serde(skip_serializing)]
+    #[ts(skip)]
+    pub secret: Option<String>,
+    /// Whether payloads are signed
+    pub has_secret: bool,
+    /// Subscribed events, empty for all
+    #[ts(type = "WebhookEventType[]")]
+    pub events: sqlx::types::Json<Vec<WebhookEventType>>,
+    /// Only send events from this project, or from every project when unset
+    pub project_id: Option<Uuid>,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateWebhook {
+    pub name: String,
+    pub url: String,
+    /// Generated when not given; empty to send unsigned payloads
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    pub project_id: Option<Uuid>,
+}
+
+/// A webhook as returned when it is created, the only time its secret is shown
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub webhook: Webhook,
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateWebhook {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    /// Empty to stop signing payloads
+    pub secret: Option<String>,
+    pub events: Option<Vec<WebhookEventType>>,
+    pub enabled: Option<bool>,
+}
+
+/// An event sent, or being sent, to a webhook
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_type: WebhookEventType,
+    /// Request body, exactly as signed
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    #[ts(type = "number")]
+    pub attempts: i64,
+    /// HTTP status of the last attempt
+    #[ts(type = "number | null")]
+    pub response_status: Option<i64>,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// Whether the webhook should receive an event from a project
+    pub fn accepts(&self, project_id: Uuid, event_type: WebhookEventType) -> bool {
+        self.enabled
+            && self.project_id.is_none_or(|id| id == project_id)
+            && (self.events.is_empty() || self.events.contains(&event_type))
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", name, url, secret, secret IS NOT NULL as "has_secret!: bool", events as "events!: sqlx::types::Json<Vec<WebhookEventType>>", project_id as "project_id: Uuid", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", name, url, secret, secret IS NOT NULL as "has_secret!: bool", events as "events!: sqlx::types::Json<Vec<WebhookEventType>>", project_id as "project_id: Uuid", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled webhooks for a project, including those not scoped to any project
+    pub async fn find_enabled_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", name, url, secret, secret IS NOT NULL as "has_secret!: bool", events as "events!: sqlx::types::Json<Vec<WebhookEventType>>", project_id as "project_id: Uuid", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE enabled = TRUE AND (project_id IS NULL OR project_id = $1)"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateWebhook) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let events = sqlx::types::Json(&data.events);
+        sqlx::query_as!(
+            Webhook,
+            r#"INSERT INTO webhooks (id, name, url, secret, events, project_id)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid", name, url, secret, secret IS NOT NULL as "has_secret!: bool", events as "events!: sqlx::types::Json<Vec<WebhookEventType>>", project_id as "project_id: Uuid", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.url,
+            data.secret,
+            events,
+            data.project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateWebhook,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let url = data.url.as_ref().unwrap_or(&existing.url);
+        let secret = match &data.secret {
+            Some(secret) if secret.is_empty() => None,
+            Some(secret) => Some(secret.clone()),
+            None => existing.secret,
+        };
+        let events = sqlx::types::Json(data.events.as_ref().unwrap_or(&existing.events.0));
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+
+        sqlx::query_as!(
+            Webhook,
+            r#"UPDATE webhooks
+               SET name = $2, url = $3, secret = $4, events = $5, enabled = $6, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", name, url, secret, secret IS NOT NULL as "has_secret!: bool", events as "events!: sqlx::types::Json<Vec<WebhookEventType>>", project_id as "project_id: Uuid", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            url,
+            secret,
+            events,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM webhooks WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl WebhookDelivery {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"SELECT id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event_type as "event_type!: WebhookEventType", payload, status as "status!: WebhookDeliveryStatus", attempts, response_status, error, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhook_deliveries
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recent deliveries to a webhook
+    pub async fn find_by_webhook_id(
+        pool: &SqlitePool,
+        webhook_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"SELECT id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event_type as "event_type!: WebhookEventType", payload, status as "status!: WebhookDeliveryStatus", attempts, response_status, error, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhook_deliveries
+               WHERE webhook_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            webhook_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Deliveries still waiting to be sent or retried
+    pub async fn find_pending(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"SELECT id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event_type as "event_type!: WebhookEventType", payload, status as "status!: WebhookDeliveryStatus", attempts, response_status, error, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhook_deliveries
+               WHERE status = 'pending'
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        webhook_id: Uuid,
+        event_type: WebhookEventType,
+        payload: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"INSERT INTO webhook_deliveries (id, webhook_id, event_type, payload)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event_type as "event_type!: WebhookEventType", payload, status as "status!: WebhookDeliveryStatus", attempts, response_status, error, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            webhook_id,
+            event_type,
+            payload
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Give up on every delivery to a webhook that is still pending. Returns how many there were.
+    pub async fn fail_pending(
+        pool: &SqlitePool,
+        webhook_id: Uuid,
+        error: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE webhook_deliveries
+               SET status = 'failed', error = $2, updated_at = datetime('now', 'subsec')
+               WHERE webhook_id = $1 AND status = 'pending'"#,
+            webhook_id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Record the outcome of one attempt to send the delivery
+    pub async fn record_attempt(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: WebhookDeliveryStatus,
+        response_status: Option<i64>,
+        error: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"UPDATE webhook_deliveries
+               SET status = $2, attempts = attempts + 1, response_status = $3, error = $4, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event_type as "event_type!: WebhookEventType", payload, status as "status!: WebhookDeliveryStatus", attempts, response_status, error, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            status,
+            response_status,
+            error
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
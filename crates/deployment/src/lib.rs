@@ -29,7 +29,9 @@ use services::services::{
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
     image::{ImageError, ImageService},
+    local_analytics::LocalAnalyticsSink,
     pr_monitor::PrMonitorService,
+    webhooks::WebhookService,
     worktree_manager::WorktreeError,
 };
 use sqlx::{Error as SqlxError, types::Uuid};
@@ -85,7 +87,9 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn analytics(&self) -> &Option<AnalyticsService>;
 
-    fn container(&self) -> &impl ContainerService;
+    fn local_analytics(&self) -> &LocalAnalyticsSink;
+
+    fn container(&self) -> &(impl ContainerService + Clone + Send + Sync + 'static);
 
     fn auth(&self) -> &AuthService;
 
@@ -105,6 +109,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn drafts(&self) -> &DraftsService;
 
+    fn webhooks(&self) -> &WebhookService;
+
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
         let user_id = self.user_id();
         let config = self.config().read().await;
@@ -125,7 +131,15 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 user_id: self.user_id().to_string(),
                 analytics_service: analytics_service.clone(),
             });
-        PrMonitorService::spawn(db, config, analytics).await
+        PrMonitorService::spawn(
+            db,
+            config,
+            self.container().clone(),
+            analytics,
+            self.local_analytics().clone(),
+            self.webhooks().clone(),
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -137,6 +151,9 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         {
             analytics.track_event(self.user_id(), event_name, Some(properties.clone()));
         }
+        self.local_analytics()
+            .track_event(event_name, &properties)
+            .await;
     }
 
     /// Cleanup executions marked as running in the db, call at startup
@@ -279,6 +296,31 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                         dev_script: None,
                         cleanup_script: None,
                         copy_files: None,
+                        merge_strategy: None,
+                        merge_commit_template: None,
+                        commit_message_strategy: None,
+                        commit_message_template: None,
+                        commit_message_validation: None,
+                        commit_message_pattern: None,
+                        commit_author_name: None,
+                        commit_author_email: None,
+                        commit_co_authors: None,
+                        commit_signing_format: None,
+                        commit_signing_key: None,
+                        container_image: None,
+                        container_cpus: None,
+                        container_memory: None,
+                        worktree_retention_hours: None,
+                        worktree_keep_recent: None,
+                        worktree_keep_in_review: None,
+                        worktree_max_size_mb: None,
+                        worktree_snapshot_enabled: None,
+                        dependency_cache: None,
+                        dev_server_health_check: None,
+                        dev_server_restart_policy: None,
+                        dev_server_max_restarts: None,
+                        working_dir: None,
+                        sparse_checkout: None,
                     };
                     // Ensure existing repo has a main branch if it's empty
                     if let Err(e) = self.git().ensure_main_branch_exists(&repo.path) {
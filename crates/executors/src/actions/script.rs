@@ -1,11 +1,11 @@
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use ts_rs::TS;
-use workspace_utils::shell::get_shell_command;
+use workspace_utils::shell::get_execution_shell_command;
 
 use crate::{
     actions::Executable,
@@ -30,6 +30,10 @@ pub struct ScriptRequest {
     pub script: String,
     pub language: ScriptRequestLanguage,
     pub context: ScriptContext,
+    /// Extra environment for the script, set when it is spawned and never persisted
+    #[serde(skip)]
+    #[ts(skip)]
+    pub env: HashMap<String, String>,
 }
 
 #[async_trait]
@@ -39,7 +43,7 @@ impl Executable for ScriptRequest {
         current_dir: &Path,
         _approvals: Arc<dyn ExecutorApprovalService>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let mut command = Command::new(shell_cmd);
         command
             .kill_on_drop(true)
@@ -48,6 +52,7 @@ impl Executable for ScriptRequest {
             .stderr(std::process::Stdio::piped())
             .arg(shell_arg)
             .arg(&self.script)
+            .envs(&self.env)
             .current_dir(current_dir);
 
         let child = command.group_spawn()?;
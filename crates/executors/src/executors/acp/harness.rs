@@ -14,7 +14,7 @@ use tokio_util::{
     io::ReaderStream,
 };
 use tracing::error;
-use workspace_utils::{shell::get_shell_command, stream_lines::LinesStreamExt};
+use workspace_utils::{shell::get_execution_shell_command, stream_lines::LinesStreamExt};
 
 use super::{AcpClient, SessionManager};
 use crate::executors::{ExecutorError, SpawnedChild, acp::AcpEvent};
@@ -52,7 +52,7 @@ impl AcpAgentHarness {
         prompt: String,
         full_command: String,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let mut command = Command::new(shell_cmd);
         command
             .kill_on_drop(true)
@@ -90,7 +90,7 @@ impl AcpAgentHarness {
         session_id: &str,
         full_command: String,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let mut command = Command::new(shell_cmd);
         command
             .kill_on_drop(true)
@@ -6,7 +6,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::{io::AsyncWriteExt, process::Command};
 use ts_rs::TS;
-use workspace_utils::{msg_store::MsgStore, shell::get_shell_command};
+use workspace_utils::{msg_store::MsgStore, shell::get_execution_shell_command};
 
 use crate::{
     command::{CmdOverrides, CommandBuilder, apply_overrides},
@@ -45,7 +45,7 @@ impl Amp {
 #[async_trait]
 impl StandardCodingAgentExecutor for Amp {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let amp_command = self.build_command_builder().build_initial();
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
@@ -78,7 +78,7 @@ impl StandardCodingAgentExecutor for Amp {
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
         // Use shell command for cross-platform compatibility
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
 
         // 1) Fork the thread synchronously to obtain new thread id
         let fork_cmd = self.build_command_builder().build_follow_up(&[
@@ -86,7 +86,7 @@ impl StandardCodingAgentExecutor for Amp {
             "fork".to_string(),
             session_id.to_string(),
         ]);
-        let fork_output = Command::new(shell_cmd)
+        let fork_output = Command::new(&shell_cmd)
             .kill_on_drop(true)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
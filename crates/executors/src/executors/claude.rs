@@ -18,7 +18,7 @@ use workspace_utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
     path::make_path_relative,
-    shell::get_shell_command,
+    shell::get_execution_shell_command,
 };
 
 use self::{client::ClaudeAgentClient, protocol::ProtocolPeer, types::PermissionMode};
@@ -203,7 +203,7 @@ impl ClaudeCode {
         prompt: &str,
         base_command: String,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
         let mut command = Command::new(shell_cmd);
@@ -21,7 +21,7 @@ use serde_json::Value;
 use strum_macros::AsRefStr;
 use tokio::process::Command;
 use ts_rs::TS;
-use workspace_utils::{msg_store::MsgStore, shell::get_shell_command};
+use workspace_utils::{msg_store::MsgStore, shell::get_execution_shell_command};
 
 use self::{
     client::{AppServerClient, LogWriter},
@@ -251,7 +251,7 @@ impl Codex {
         resume_session: Option<&str>,
     ) -> Result<SpawnedChild, ExecutorError> {
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
 
         let mut process = Command::new(shell_cmd);
         process
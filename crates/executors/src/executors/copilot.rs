@@ -19,7 +19,7 @@ use tokio::{
 use ts_rs::TS;
 use uuid::Uuid;
 use workspace_utils::{
-    msg_store::MsgStore, path::get_vibe_kanban_temp_dir, shell::get_shell_command,
+    msg_store::MsgStore, path::get_vibe_kanban_temp_dir, shell::get_execution_shell_command,
 };
 
 use crate::{
@@ -97,7 +97,7 @@ impl Copilot {
 #[async_trait]
 impl StandardCodingAgentExecutor for Copilot {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let log_dir = Self::create_temp_log_dir(current_dir).await?;
         let copilot_command = self
             .build_command_builder(&log_dir.to_string_lossy())
@@ -136,7 +136,7 @@ impl StandardCodingAgentExecutor for Copilot {
         prompt: &str,
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let log_dir = Self::create_temp_log_dir(current_dir).await?;
         let copilot_command = self
             .build_command_builder(&log_dir.to_string_lossy())
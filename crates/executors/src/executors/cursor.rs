@@ -15,7 +15,7 @@ use workspace_utils::{
     },
     msg_store::MsgStore,
     path::make_path_relative,
-    shell::{get_shell_command, resolve_executable_path},
+    shell::{get_execution_shell_command, resolve_executable_path},
 };
 
 use crate::{
@@ -67,7 +67,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         mcp::ensure_mcp_server_trust(self, current_dir).await;
 
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let agent_cmd = self.build_command_builder().build_initial();
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
@@ -100,7 +100,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
     ) -> Result<SpawnedChild, ExecutorError> {
         mcp::ensure_mcp_server_trust(self, current_dir).await;
 
-        let (shell_cmd, shell_arg) = get_shell_command();
+        let (shell_cmd, shell_arg) = get_execution_shell_command();
         let agent_cmd = self
             .build_command_builder()
             .build_follow_up(&["--resume".to_string(), session_id.to_string()]);
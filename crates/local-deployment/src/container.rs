@@ -3,30 +3,38 @@ use std::{
     io,
     path::{Path, PathBuf},
     sync::{Arc, atomic::AtomicUsize},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::Utc;
 use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
         draft::{Draft, DraftType},
         execution_process::{
-            ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+            DevServerHealth, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
+            ExecutionProcessStatus,
         },
         executor_session::ExecutorSession,
         image::TaskImage,
         merge::Merge,
-        project::Project,
+        project::{CommitMessageStrategy, DevServerRestartPolicy, Project},
+        project_repository::ProjectRepository,
+        review_comment::ReviewComment,
         task::{Task, TaskStatus},
-        task_attempt::TaskAttempt,
+        task_attempt::{TaskAttempt, WorktreeCleanupCandidate},
+        task_attempt_repository::TaskAttemptRepository,
     },
 };
 use deployment::DeploymentError;
 use executors::{
-    actions::{Executable, ExecutorAction},
+    actions::{
+        Executable, ExecutorAction, ExecutorActionType,
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     executors::BaseCodingAgent,
     logs::{
@@ -42,19 +50,41 @@ use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
+    commit_identity::CommitIdentity,
+    commit_message::{
+        CommitMessageContext, extract_commit_message, render_commit_message,
+        with_conventional_commit_request,
+    },
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
-    diff_stream::{self, DiffStreamHandle},
-    git::{Commit, DiffTarget, GitService},
+    conversation_export,
+    copy_files::{self, CopyFilesReport, CopyTemplateValues, parse_copy_files},
+    dependency_cache::{DependencyCache, DependencyCacheEntry, parse_dependency_caches},
+    dev_server::{DevServers, dev_server_env},
+    dev_server_health::{
+        CHECK_INTERVAL, HealthTracker, ProbeTarget, parse_health_check, restart_backoff,
+    },
+    diff_stream::{self, DiffPaths, DiffStreamHandle},
+    git::{Commit, DiffTarget, GitService, GitServiceError},
     image::ImageService,
+    local_analytics::LocalAnalyticsSink,
     notification::NotificationService,
+    oci_runtime::{ExecutionContainer, OciRuntime},
+    project_scope::ProjectScope,
+    transcript_search,
+    webhooks::{WebhookEvent, WebhookService},
+    workspace::{AttemptWorkspace, primary_repo_dir_name},
     worktree_manager::WorktreeManager,
+    worktree_retention::{WorktreeRetention, select_worktrees_to_remove},
+    worktree_snapshot::{self, SnapshotLocation},
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
+    path::get_vibe_kanban_temp_dir,
+    shell::with_execution_shell,
     text::{git_branch_id, short_uuid},
 };
 use uuid::Uuid;
@@ -70,10 +100,20 @@ pub struct LocalContainerService {
     git: GitService,
     image_service: ImageService,
     analytics: Option<AnalyticsContext>,
+    local_analytics: LocalAnalyticsSink,
     approvals: Approvals,
+    oci_runtime: Option<OciRuntime>,
+    /// Wrapper scripts of executions currently running inside a container
+    container_executions: Arc<RwLock<HashMap<Uuid, PathBuf>>>,
+    /// Lines from setting up an attempt's worktree, shown at the top of the log of its first
+    /// execution, keyed by attempt
+    setup_notices: Arc<RwLock<HashMap<Uuid, Vec<String>>>>,
+    dev_servers: DevServers,
+    webhooks: WebhookService,
 }
 
 impl LocalContainerService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: DBService,
         msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
@@ -81,7 +121,9 @@ impl LocalContainerService {
         git: GitService,
         image_service: ImageService,
         analytics: Option<AnalyticsContext>,
+        local_analytics: LocalAnalyticsSink,
         approvals: Approvals,
+        webhooks: WebhookService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
 
@@ -93,7 +135,13 @@ impl LocalContainerService {
             git,
             image_service,
             analytics,
+            local_analytics,
             approvals,
+            oci_runtime: OciRuntime::detect(),
+            container_executions: Arc::new(RwLock::new(HashMap::new())),
+            setup_notices: Arc::new(RwLock::new(HashMap::new())),
+            dev_servers: DevServers::default(),
+            webhooks,
         }
     }
 
@@ -112,6 +160,123 @@ impl LocalContainerService {
         map.remove(id);
     }
 
+    /// Remove the container and wrapper script of an execution that ran inside a container.
+    /// Killing the wrapper's process group only stops the runtime client.
+    async fn release_execution_container(&self, exec_id: Uuid) {
+        let Some(shell) = self.container_executions.write().await.remove(&exec_id) else {
+            return;
+        };
+        if let Some(runtime) = &self.oci_runtime {
+            runtime.remove_execution_container(exec_id).await;
+        }
+        if let Err(e) = tokio::fs::remove_file(&shell).await {
+            tracing::debug!(
+                "Failed to remove container wrapper {}: {}",
+                shell.display(),
+                e
+            );
+        }
+    }
+
+    /// Keep lines for the top of the log of the attempt's first execution, whatever it runs
+    async fn add_setup_notices(&self, attempt_id: Uuid, notices: Vec<String>) {
+        for notice in &notices {
+            tracing::info!("Attempt {}: {}", attempt_id, notice);
+        }
+        self.setup_notices
+            .write()
+            .await
+            .entry(attempt_id)
+            .or_default()
+            .extend(notices);
+    }
+
+    fn dependency_cache_entries(project: &Project) -> Vec<DependencyCacheEntry> {
+        let Some(spec) = project.dependency_cache.as_deref() else {
+            return Vec::new();
+        };
+        parse_dependency_caches(spec).unwrap_or_else(|e| {
+            tracing::warn!(
+                "Ignoring invalid dependency cache for project {}: {}",
+                project.id,
+                e
+            );
+            Vec::new()
+        })
+    }
+
+    /// Pre-populate a new worktree's cached dependency directories from their warm bases
+    async fn populate_dependency_caches(project: &Project, worktree_path: &Path) {
+        let entries = Self::dependency_cache_entries(project);
+        if entries.is_empty() {
+            return;
+        }
+        let project_id = project.id;
+        let worktree_path = worktree_path.to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            let cache = DependencyCache::default();
+            for entry in entries {
+                match cache.populate(project_id, &worktree_path, &entry) {
+                    Ok(Some(mode)) => tracing::info!(
+                        "Populated {} in {} from the dependency cache ({:?})",
+                        entry.dir,
+                        worktree_path.display(),
+                        mode
+                    ),
+                    Ok(None) => tracing::debug!("No warm dependency cache for {}", entry.dir),
+                    Err(e) => tracing::warn!(
+                        "Failed to populate {} from the dependency cache: {}",
+                        entry.dir,
+                        e
+                    ),
+                }
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Dependency cache task failed: {}", e);
+        }
+    }
+
+    /// Store the dependency directories produced by a successful setup script as warm bases
+    async fn warm_dependency_caches(&self, task: &Task, task_attempt: &TaskAttempt) {
+        let project = match task.parent_project(&self.db.pool).await {
+            Ok(Some(project)) => project,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Failed to load project for dependency cache: {}", e);
+                return;
+            }
+        };
+        let entries = Self::dependency_cache_entries(&project);
+        if entries.is_empty() {
+            return;
+        }
+        let worktree_path = self.task_attempt_to_current_dir(task_attempt);
+        let result = tokio::task::spawn_blocking(move || {
+            let cache = DependencyCache::default();
+            for entry in entries {
+                match cache.warm(project.id, &worktree_path, &entry) {
+                    Ok(true) => tracing::info!(
+                        "Stored {} from {} in the dependency cache",
+                        entry.dir,
+                        worktree_path.display()
+                    ),
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!(
+                        "Failed to store {} in the dependency cache: {}",
+                        entry.dir,
+                        e
+                    ),
+                }
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Dependency cache task failed: {}", e);
+        }
+    }
+
     /// A context is finalized when
     /// - The next action is None (no follow-up actions)
     /// - The run reason is not DevServer
@@ -232,32 +397,115 @@ impl LocalContainerService {
     pub async fn cleanup_expired_attempt(
         db: &DBService,
         attempt_id: Uuid,
+        project_id: Uuid,
         worktree_path: PathBuf,
         git_repo_path: PathBuf,
+        snapshot: bool,
     ) -> Result<(), DeploymentError> {
-        WorktreeManager::cleanup_worktree(&worktree_path, Some(&git_repo_path)).await?;
+        // Attempts of projects with several repositories have a worktree of each in a workspace
+        let extra_repos =
+            TaskAttemptRepository::find_by_task_attempt_id(&db.pool, attempt_id).await?;
+        let workspace_root = worktree_path
+            .parent()
+            .filter(|_| !extra_repos.is_empty())
+            .map(Path::to_path_buf);
+        let mut worktrees = vec![(worktree_path, git_repo_path, project_id)];
+        if let Some(root) = &workspace_root {
+            worktrees.extend(extra_repos.into_iter().map(|repo| {
+                (
+                    root.join(&repo.name),
+                    repo.git_repo_path,
+                    repo.project_repository_id,
+                )
+            }));
+        }
+
+        for (worktree_path, _, repo_id) in &worktrees {
+            if !snapshot || !worktree_path.exists() {
+                continue;
+            }
+            // Keep the worktree when its state can't be archived; the next run retries
+            let path = worktree_path.clone();
+            let location = SnapshotLocation::in_default_dir(attempt_id, *repo_id);
+            tokio::task::spawn_blocking(move || {
+                worktree_snapshot::create_snapshot(&location, &path)
+            })
+            .await
+            .map_err(|e| DeploymentError::Other(anyhow!("Snapshot task failed: {e}")))?
+            .map_err(|e| {
+                DeploymentError::Other(anyhow!(
+                    "Failed to snapshot worktree {}: {e}",
+                    worktree_path.display()
+                ))
+            })?;
+        }
+        for (worktree_path, git_repo_path, _) in &worktrees {
+            WorktreeManager::cleanup_worktree(worktree_path, Some(git_repo_path)).await?;
+        }
+        if let Some(root) = &workspace_root
+            && let Err(e) = tokio::fs::remove_dir_all(root).await
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove workspace {}: {}", root.display(), e);
+        }
         // Mark worktree as deleted in database after successful cleanup
         TaskAttempt::mark_worktree_deleted(&db.pool, attempt_id).await?;
         tracing::info!("Successfully marked worktree as deleted for attempt {attempt_id}",);
         Ok(())
     }
 
+    /// Remove worktrees according to each project's retention policy
     pub async fn cleanup_expired_attempts(db: &DBService) -> Result<(), DeploymentError> {
-        let expired_attempts = TaskAttempt::find_expired_for_cleanup(&db.pool).await?;
-        if expired_attempts.is_empty() {
+        let candidates = TaskAttempt::find_worktree_cleanup_candidates(&db.pool).await?;
+        if candidates.is_empty() {
+            tracing::debug!("No worktrees to consider for cleanup");
+            return Ok(());
+        }
+
+        let policies: HashMap<Uuid, WorktreeRetention> = Project::find_all(&db.pool)
+            .await?
+            .iter()
+            .map(|project| (project.id, WorktreeRetention::from_project(project)))
+            .collect();
+
+        // Sizes are only needed for projects that cap their total worktree size
+        let sized: Vec<(Uuid, PathBuf)> = candidates
+            .iter()
+            .filter(|c| {
+                policies
+                    .get(&c.project_id)
+                    .is_some_and(|policy| policy.max_size_bytes.is_some())
+            })
+            .map(|c| (c.attempt_id, PathBuf::from(&c.container_ref)))
+            .collect();
+        let sizes: HashMap<Uuid, u64> = tokio::task::spawn_blocking(move || {
+            sized
+                .into_iter()
+                .map(|(id, path)| (id, WorktreeManager::directory_size(&path)))
+                .collect()
+        })
+        .await
+        .map_err(|e| DeploymentError::Other(anyhow!("Failed to measure worktrees: {e}")))?;
+
+        let to_remove = select_worktrees_to_remove(&candidates, &policies, &sizes, Utc::now());
+        if to_remove.is_empty() {
             tracing::debug!("No expired worktrees found");
             return Ok(());
         }
-        tracing::info!(
-            "Found {} expired worktrees to clean up",
-            expired_attempts.len()
-        );
-        for (attempt_id, worktree_path, git_repo_path) in expired_attempts {
+        tracing::info!("Found {} expired worktrees to clean up", to_remove.len());
+        let candidates: HashMap<Uuid, &WorktreeCleanupCandidate> =
+            candidates.iter().map(|c| (c.attempt_id, c)).collect();
+        for attempt_id in to_remove {
+            let Some(candidate) = candidates.get(&attempt_id) else {
+                continue;
+            };
             Self::cleanup_expired_attempt(
                 db,
                 attempt_id,
-                PathBuf::from(worktree_path),
-                PathBuf::from(git_repo_path),
+                candidate.project_id,
+                PathBuf::from(&candidate.container_ref),
+                PathBuf::from(&candidate.git_repo_path),
+                candidate.snapshot,
             )
             .await
             .unwrap_or_else(|e| {
@@ -303,6 +551,8 @@ impl LocalContainerService {
         let config = self.config.clone();
         let container = self.clone();
         let analytics = self.analytics.clone();
+        let local_analytics = self.local_analytics.clone();
+        let webhooks = self.webhooks.clone();
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
 
@@ -334,6 +584,7 @@ impl LocalContainerService {
                 }
             }
 
+            let mut index_transcript = false;
             let (exit_code, status) = match status_result {
                 Ok(exit_status) => {
                     let code = exit_status.code().unwrap_or(-1) as i64;
@@ -347,6 +598,8 @@ impl LocalContainerService {
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
 
+            container.release_execution_container(exec_id).await;
+
             if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
                 && let Err(e) =
                     ExecutionProcess::update_completion(&db.pool, exec_id, status, exit_code).await
@@ -355,16 +608,56 @@ impl LocalContainerService {
             }
 
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                if ctx.execution_process.run_reason == ExecutionProcessRunReason::DevServer
+                    && ctx.execution_process.status == ExecutionProcessStatus::Failed
+                {
+                    container.schedule_dev_server_restart(&ctx).await;
+                }
+
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
 
+                // Resolve the review comments this run addressed, or reopen them if it failed
+                if ctx.execution_process.run_reason == ExecutionProcessRunReason::CodingAgent
+                    && let Err(e) = ReviewComment::settle_sent(&db.pool, ctx.task_attempt.id).await
+                {
+                    tracing::warn!("Failed to settle review comments: {}", e);
+                }
+
+                // Index the conversation for search once its log is complete, below
+                index_transcript =
+                    ctx.execution_process.run_reason == ExecutionProcessRunReason::CodingAgent;
+
+                if let Some(event) = WebhookEvent::for_finished_process(
+                    ctx.task.clone(),
+                    ctx.task_attempt.clone(),
+                    &ctx.execution_process,
+                ) {
+                    webhooks.dispatch(ctx.task.project_id, event);
+                }
+
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
                 ) && exit_code == Some(0);
 
+                // Capture installed dependencies in the background; copying large directories
+                // must not hold up the next action
+                if success
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::SetupScript
+                    )
+                {
+                    let container = container.clone();
+                    let (task, task_attempt) = (ctx.task.clone(), ctx.task_attempt.clone());
+                    tokio::spawn(async move {
+                        container.warm_dependency_caches(&task, &task_attempt).await;
+                    });
+                }
+
                 let cleanup_done = matches!(
                     ctx.execution_process.run_reason,
                     ExecutionProcessRunReason::CleanupScript
@@ -422,20 +715,55 @@ impl LocalContainerService {
                 }
 
                 // Fire analytics event when CodingAgent execution has finished
-                if config.read().await.analytics_enabled == Some(true)
-                    && matches!(
-                        &ctx.execution_process.run_reason,
-                        ExecutionProcessRunReason::CodingAgent
-                    )
-                    && let Some(analytics) = &analytics
-                {
-                    analytics.analytics_service.track_event(&analytics.user_id, "task_attempt_finished", Some(json!({
+                if matches!(
+                    &ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) {
+                    let process = &ctx.execution_process;
+                    let profile =
+                        process
+                            .executor_action()
+                            .ok()
+                            .and_then(|action| match action.typ() {
+                                ExecutorActionType::CodingAgentInitialRequest(request) => {
+                                    Some(&request.executor_profile_id)
+                                }
+                                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                                    Some(&request.executor_profile_id)
+                                }
+                                ExecutorActionType::ScriptRequest(_) => None,
+                            });
+                    let properties = json!({
                         "task_id": ctx.task.id.to_string(),
                         "project_id": ctx.task.project_id.to_string(),
                         "attempt_id": ctx.task_attempt.id.to_string(),
-                        "execution_success": matches!(ctx.execution_process.status, ExecutionProcessStatus::Completed),
-                        "exit_code": ctx.execution_process.exit_code,
-                    })));
+                        "execution_success": matches!(process.status, ExecutionProcessStatus::Completed),
+                        "exit_code": process.exit_code,
+                    });
+                    if config.read().await.analytics_enabled == Some(true)
+                        && let Some(analytics) = &analytics
+                    {
+                        analytics.analytics_service.track_event(
+                            &analytics.user_id,
+                            "task_attempt_finished",
+                            Some(properties.clone()),
+                        );
+                    }
+
+                    // The local sink also gets what usage reports need; none of it is sent
+                    // remotely
+                    let mut local_properties = properties;
+                    local_properties["execution_process_id"] = json!(process.id.to_string());
+                    local_properties["executor"] = json!(profile.map(|profile| profile.executor));
+                    local_properties["variant"] =
+                        json!(profile.and_then(|profile| profile.variant.as_ref()));
+                    local_properties["runtime_seconds"] =
+                        json!(process.completed_at.map(|completed_at| {
+                            (completed_at - process.started_at).num_milliseconds() as f64 / 1000.0
+                        }));
+                    local_analytics
+                        .track_event("task_attempt_finished", &local_properties)
+                        .await;
                 }
             }
 
@@ -456,13 +784,30 @@ impl LocalContainerService {
             if let Some(msg_arc) = msg_stores.write().await.remove(&exec_id) {
                 msg_arc.push_finished();
                 tokio::time::sleep(Duration::from_millis(50)).await; // Wait for the finish message to propogate
-                match Arc::try_unwrap(msg_arc) {
-                    Ok(inner) => drop(inner),
-                    Err(arc) => tracing::error!(
-                        "There are still {} strong Arcs to MsgStore for {}",
-                        Arc::strong_count(&arc),
-                        exec_id
-                    ),
+                if index_transcript {
+                    // The normalizer may still be processing the tail of the log
+                    let pool = db.pool.clone();
+                    tokio::spawn(async move {
+                        let Some(patches) =
+                            conversation_export::drained_patches(exec_id, msg_arc).await
+                        else {
+                            return;
+                        };
+                        if let Err(e) =
+                            transcript_search::index_patches(&pool, exec_id, &patches).await
+                        {
+                            tracing::warn!("Failed to index transcript of {}: {}", exec_id, e);
+                        }
+                    });
+                } else {
+                    match Arc::try_unwrap(msg_arc) {
+                        Ok(inner) => drop(inner),
+                        Err(arc) => tracing::error!(
+                            "There are still {} strong Arcs to MsgStore for {}",
+                            Arc::strong_count(&arc),
+                            exec_id
+                        ),
+                    }
                 }
             }
 
@@ -513,8 +858,16 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(attempt_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        notices: Vec<String>,
+    ) {
         let store = Arc::new(MsgStore::new());
+        for notice in notices {
+            store.push_stderr(format!("{notice}\n"));
+        }
 
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
@@ -578,6 +931,7 @@ impl LocalContainerService {
         project_repo_path: &Path,
         merge_commit_id: &str,
         stats_only: bool,
+        scope: &ProjectScope,
     ) -> Result<DiffStreamHandle, ContainerError> {
         let diffs = self.git().get_diffs(
             DiffTarget::Commit {
@@ -590,6 +944,7 @@ impl LocalContainerService {
         let cum = Arc::new(AtomicUsize::new(0));
         let diffs: Vec<_> = diffs
             .into_iter()
+            .filter(|d| scope.contains_diff(d))
             .map(|mut d| {
                 diff_stream::apply_stream_omit_policy(&mut d, &cum, stats_only);
                 d
@@ -617,12 +972,14 @@ impl LocalContainerService {
         worktree_path: &Path,
         base_commit: &Commit,
         stats_only: bool,
+        paths: DiffPaths,
     ) -> Result<DiffStreamHandle, ContainerError> {
         diff_stream::create(
             self.git().clone(),
             worktree_path.to_path_buf(),
             base_commit.clone(),
             stats_only,
+            paths,
         )
         .await
         .map_err(|e| ContainerError::Other(anyhow!("{e}")))
@@ -656,6 +1013,10 @@ impl ContainerService for LocalContainerService {
         &self.git
     }
 
+    fn dev_servers(&self) -> &DevServers {
+        &self.dev_servers
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
@@ -672,33 +1033,79 @@ impl ContainerService for LocalContainerService {
 
         let worktree_dir_name =
             LocalContainerService::dir_name_from_task_attempt(&task_attempt.id, &task.title);
-        let worktree_path = WorktreeManager::get_worktree_base_dir().join(&worktree_dir_name);
+        let workspace_dir = WorktreeManager::get_worktree_base_dir().join(&worktree_dir_name);
 
         let project = task
             .parent_project(&self.db.pool)
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
 
+        // With several repositories, each gets a worktree inside the workspace directory
+        let repositories = ProjectRepository::find_by_project_id(&self.db.pool, project.id).await?;
+        let worktree_path = if repositories.is_empty() {
+            workspace_dir.clone()
+        } else {
+            workspace_dir.join(primary_repo_dir_name(&project.git_repo_path))
+        };
+
         WorktreeManager::create_worktree(
             &project.git_repo_path,
             &task_attempt.branch,
             &worktree_path,
             &task_attempt.target_branch,
             true, // create new branch
+            &ProjectScope::from_project(&project).sparse_checkout_patterns(),
         )
         .await?;
 
+        for repository in &repositories {
+            // Branch from whatever the repository has checked out; guessing a branch name
+            // could base the attempt on the wrong history
+            let target_branch = self
+                .git()
+                .get_current_branch(&repository.git_repo_path)
+                .map_err(|e| {
+                    ContainerError::Other(anyhow!(
+                        "Failed to read the current branch of {}: {e}",
+                        repository.git_repo_path.display()
+                    ))
+                })?;
+            WorktreeManager::create_worktree(
+                &repository.git_repo_path,
+                &task_attempt.branch,
+                &workspace_dir.join(&repository.name),
+                &target_branch,
+                true,
+                &[],
+            )
+            .await?;
+            TaskAttemptRepository::create(
+                &self.db.pool,
+                task_attempt.id,
+                repository.id,
+                &target_branch,
+            )
+            .await?;
+        }
+
         // Copy files specified in the project's copy_files field
-        if let Some(copy_files) = &project.copy_files
-            && !copy_files.trim().is_empty()
+        if project
+            .copy_files
+            .as_deref()
+            .is_some_and(|copy_files| !copy_files.trim().is_empty())
         {
-            self.copy_project_files(&project.git_repo_path, &worktree_path, copy_files)
+            let notices = match self
+                .copy_project_files(&project, task_attempt, &worktree_path)
                 .await
-                .unwrap_or_else(|e| {
-                    tracing::warn!("Failed to copy project files: {}", e);
-                });
+            {
+                Ok(report) => report.log_lines(),
+                Err(e) => vec![format!("copy_files: {e}")],
+            };
+            self.add_setup_notices(task_attempt.id, notices).await;
         }
 
+        Self::populate_dependency_caches(&project, &worktree_path).await;
+
         // Copy task images from cache to worktree
         if let Err(e) = self
             .image_service
@@ -725,26 +1132,65 @@ impl ContainerService for LocalContainerService {
             .parent_task(&self.db.pool)
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
-        let git_repo_path = match Project::find_by_id(&self.db.pool, task.project_id).await {
-            Ok(Some(project)) => Some(project.git_repo_path.clone()),
-            Ok(None) => None,
+        let project = match Project::find_by_id(&self.db.pool, task.project_id).await {
+            Ok(project) => project,
             Err(e) => {
                 tracing::error!("Failed to fetch project {}: {}", task.project_id, e);
                 None
             }
         };
-        WorktreeManager::cleanup_worktree(
-            &PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default()),
-            git_repo_path.as_deref(),
-        )
-        .await
-        .unwrap_or_else(|e| {
+        let git_repo_path = project
+            .as_ref()
+            .map(|project| project.git_repo_path.clone());
+        let worktree_path = PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default());
+        WorktreeManager::cleanup_worktree(&worktree_path, git_repo_path.as_deref())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Failed to clean up worktree for task attempt {}: {}",
+                    task_attempt.id,
+                    e
+                );
+            });
+        if let Some(project) = &project
+            && let Some(workspace) =
+                AttemptWorkspace::load(&self.db.pool, project, task_attempt).await?
+            && workspace.is_multi_repo()
+        {
+            for repo in workspace.extra_repos() {
+                WorktreeManager::cleanup_worktree(&repo.worktree_path, Some(&repo.repo_path))
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            "Failed to clean up the {} worktree of task attempt {}: {}",
+                            repo.name,
+                            task_attempt.id,
+                            e
+                        );
+                    });
+            }
+            if let Err(e) = tokio::fs::remove_dir_all(&workspace.root).await
+                && e.kind() != io::ErrorKind::NotFound
+            {
+                tracing::warn!(
+                    "Failed to remove workspace {}: {}",
+                    workspace.root.display(),
+                    e
+                );
+            }
+        }
+        if let Err(e) = worktree_snapshot::discard_attempt_snapshots(
+            &worktree_snapshot::snapshot_dir(),
+            task_attempt.id,
+        ) {
             tracing::warn!(
-                "Failed to clean up worktree for task attempt {}: {}",
+                "Failed to discard snapshots of task attempt {}: {}",
                 task_attempt.id,
                 e
             );
-        });
+        }
+        self.setup_notices.write().await.remove(&task_attempt.id);
+        self.dev_servers.release(task_attempt.id);
         Ok(())
     }
 
@@ -772,9 +1218,32 @@ impl ContainerService for LocalContainerService {
             &project.git_repo_path,
             &task_attempt.branch,
             &worktree_path,
+            &ProjectScope::from_project(&project).sparse_checkout_patterns(),
+            Some(&SnapshotLocation::in_default_dir(
+                task_attempt.id,
+                project.id,
+            )),
         )
         .await?;
 
+        if let Some(workspace) =
+            AttemptWorkspace::load(&self.db.pool, &project, task_attempt).await?
+        {
+            for repo in workspace.extra_repos() {
+                WorktreeManager::ensure_worktree_exists(
+                    &repo.repo_path,
+                    &task_attempt.branch,
+                    &repo.worktree_path,
+                    &[],
+                    Some(&SnapshotLocation::in_default_dir(
+                        task_attempt.id,
+                        repo.repo_id,
+                    )),
+                )
+                .await?;
+            }
+        }
+
         Ok(container_ref.to_string())
     }
 
@@ -782,13 +1251,26 @@ impl ContainerService for LocalContainerService {
         if let Some(container_ref) = &task_attempt.container_ref {
             // If container_ref is set, check if the worktree exists
             let path = PathBuf::from(container_ref);
-            if path.exists() {
-                self.git().is_worktree_clean(&path).map_err(|e| e.into())
-            } else {
+            if !path.exists() {
                 return Ok(true); // No worktree means it's clean
             }
+            if !self.git().is_worktree_clean(&path)? {
+                return Ok(false);
+            }
+            // Every other repository of the workspace has to be clean too
+            let extra_repos =
+                TaskAttemptRepository::find_by_task_attempt_id(&self.db.pool, task_attempt.id)
+                    .await?;
+            let root = path.parent().unwrap_or(&path);
+            for repo in extra_repos {
+                let worktree_path = root.join(&repo.name);
+                if worktree_path.exists() && !self.git().is_worktree_clean(&worktree_path)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
         } else {
-            return Ok(true); // No container_ref means no worktree, so it's clean
+            Ok(true) // No container_ref means no worktree, so it's clean
         }
     }
 
@@ -798,14 +1280,79 @@ impl ContainerService for LocalContainerService {
         execution_process: &ExecutionProcess,
         executor_action: &ExecutorAction,
     ) -> Result<(), ContainerError> {
-        // Get the worktree path
-        let container_ref = task_attempt
-            .container_ref
-            .as_ref()
+        let project = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent task not found")))?
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent project not found")))?;
+
+        // Executions run in the worktree, or in the workspace holding all of the attempt's
+        // worktrees when the project owns several repositories. Coding agents of monorepo
+        // projects run in the project's working directory.
+        let workspace = AttemptWorkspace::load(&self.db.pool, &project, task_attempt)
+            .await?
             .ok_or(ContainerError::Other(anyhow!(
                 "Container ref not found for task attempt"
             )))?;
-        let current_dir = PathBuf::from(container_ref);
+        let current_dir = match &executor_action.typ {
+            ExecutorActionType::ScriptRequest(_) => workspace.root.clone(),
+            _ => {
+                let agent_dir = workspace.agent_dir(&ProjectScope::from_project(&project));
+                if agent_dir.is_dir() {
+                    agent_dir
+                } else {
+                    tracing::warn!(
+                        "Working directory {} doesn't exist; running the agent in {}",
+                        agent_dir.display(),
+                        workspace.root.display()
+                    );
+                    workspace.root.clone()
+                }
+            }
+        };
+
+        // Ask the agent to hand back a conventional-commit message; the stored action keeps
+        // the prompt the user wrote. Runs resolving a paused rebase or merge continue that
+        // operation instead of committing, so they aren't asked.
+        let mut executor_action = executor_action.clone();
+        let resolving_conflicts =
+            task_attempt
+                .container_ref
+                .as_deref()
+                .is_some_and(|container_ref| {
+                    matches!(
+                        self.git().detect_conflict_op(Path::new(container_ref)),
+                        Ok(Some(_))
+                    )
+                });
+        if project.commit_message_strategy == CommitMessageStrategy::ConventionalCommit
+            && !resolving_conflicts
+        {
+            match &mut executor_action.typ {
+                ExecutorActionType::CodingAgentInitialRequest(request) => {
+                    request.prompt = with_conventional_commit_request(&request.prompt);
+                }
+                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                    request.prompt = with_conventional_commit_request(&request.prompt);
+                }
+                ExecutorActionType::ScriptRequest(_) => {}
+            }
+        }
+
+        // Dev servers listen on the attempt's own port so attempts can run side by side
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer
+            && let ExecutorActionType::ScriptRequest(request) = &mut executor_action.typ
+        {
+            match self.dev_servers.port_for(task_attempt.id) {
+                Some(port) => request.env.extend(dev_server_env(port)),
+                None => tracing::warn!(
+                    "No free dev server port for attempt {}; starting without one",
+                    task_attempt.id
+                ),
+            }
+        }
 
         let approvals_service: Arc<dyn ExecutorApprovalService> =
             match executor_action.base_executor() {
@@ -819,16 +1366,87 @@ impl ContainerService for LocalContainerService {
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
-        // Create the child and stream, add to execution tracker
-        let mut spawned = executor_action
-            .spawn(&current_dir, approvals_service)
-            .await?;
+        // Create the child and stream, add to execution tracker. Projects with a container
+        // image run every command through a wrapper that starts it inside the container.
+        let mut spawned = match ExecutionContainer::from_project(&project) {
+            None => {
+                executor_action
+                    .spawn(&current_dir, approvals_service)
+                    .await?
+            }
+            Some(container) => {
+                let runtime = self.oci_runtime.as_ref().ok_or(ContainerError::Other(anyhow!(
+                    "Project runs executions in containers, but no container runtime was found; install docker or podman, or set VK_CONTAINER_RUNTIME"
+                )))?;
+                let git_dirs: Vec<PathBuf> = workspace
+                    .repos
+                    .iter()
+                    .map(|repo| repo.repo_path.join(".git"))
+                    .collect();
+                let mounts: Vec<&Path> = std::iter::once(workspace.root.as_path())
+                    .chain(git_dirs.iter().map(PathBuf::as_path))
+                    .collect();
+                // Dev servers are reached through their port published on the host
+                let ports: Vec<u16> = (execution_process.run_reason
+                    == ExecutionProcessRunReason::DevServer)
+                    .then(|| self.dev_servers.port_for(task_attempt.id))
+                    .flatten()
+                    .into_iter()
+                    .collect();
+                let shell = runtime.write_execution_shell(
+                    &container,
+                    execution_process.id,
+                    &mounts,
+                    &ports,
+                    &get_vibe_kanban_temp_dir().join("containers"),
+                )?;
+                self.container_executions
+                    .write()
+                    .await
+                    .insert(execution_process.id, shell.clone());
+                match with_execution_shell(
+                    shell,
+                    executor_action.spawn(&current_dir, approvals_service),
+                )
+                .await
+                {
+                    Ok(spawned) => spawned,
+                    Err(e) => {
+                        self.release_execution_container(execution_process.id).await;
+                        return Err(e.into());
+                    }
+                }
+            }
+        };
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+        let notices = self
+            .setup_notices
+            .write()
+            .await
+            .remove(&task_attempt.id)
+            .unwrap_or_default();
+        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child, notices)
             .await;
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer
+            && let Some(store) = self.msg_stores.read().await.get(&execution_process.id)
+        {
+            tokio::spawn(self.dev_servers.clone().track_url(
+                task_attempt.id,
+                execution_process.id,
+                store.clone(),
+            ));
+        }
 
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer {
+            self.spawn_dev_server_health_monitor(
+                &project,
+                task_attempt.id,
+                execution_process.id,
+                current_dir.clone(),
+            );
+        }
 
         // Spawn unified exit monitor: watches OS exit and optional executor signal
         let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
@@ -869,6 +1487,7 @@ impl ContainerService for LocalContainerService {
             }
         }
         self.remove_child_from_store(&execution_process.id).await;
+        self.release_execution_container(execution_process.id).await;
 
         // Mark the process finished in the MsgStore
         if let Some(msg) = self.msg_stores.write().await.remove(&execution_process.id) {
@@ -928,28 +1547,63 @@ impl ContainerService for LocalContainerService {
             false
         };
 
-        if let Some(merge) = &latest_merge
+        let project = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent task not found")))?
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent project not found")))?;
+        let extra_repos =
+            TaskAttemptRepository::find_by_task_attempt_id(&self.db.pool, task_attempt.id).await?;
+
+        if extra_repos.is_empty()
+            && let Some(merge) = &latest_merge
             && let Some(commit) = merge.merge_commit()
             && self.is_container_clean(task_attempt).await?
             && !is_ahead
         {
-            let wrapper =
-                self.create_merged_diff_stream(&project_repo_path, &commit, stats_only)?;
+            let wrapper = self.create_merged_diff_stream(
+                &project_repo_path,
+                &commit,
+                stats_only,
+                &ProjectScope::from_project(&project),
+            )?;
             return Ok(Box::pin(wrapper));
         }
 
         let container_ref = self.ensure_container_exists(task_attempt).await?;
-        let worktree_path = PathBuf::from(container_ref);
-        let base_commit = self.git().get_base_commit(
-            &project_repo_path,
-            &task_attempt.branch,
-            &task_attempt.target_branch,
-        )?;
+        let workspace = AttemptWorkspace::new(
+            &project,
+            task_attempt,
+            Path::new(&container_ref),
+            extra_repos,
+        );
 
-        let wrapper = self
-            .create_live_diff_stream(&worktree_path, &base_commit, stats_only)
-            .await?;
-        Ok(Box::pin(wrapper))
+        // Each repository of a workspace streams its own diff, with paths under its directory.
+        // The project's scope applies to its own repository.
+        let scope = ProjectScope::from_project(&project);
+        let mut streams = Vec::with_capacity(workspace.repos.len());
+        for repo in &workspace.repos {
+            let base_commit = self.git().get_base_commit(
+                &repo.repo_path,
+                &task_attempt.branch,
+                &repo.target_branch,
+            )?;
+            let paths = DiffPaths {
+                prefix: workspace.is_multi_repo().then(|| repo.name.clone()),
+                scope: if repo.attempt_repository.is_none() {
+                    scope.clone()
+                } else {
+                    ProjectScope::default()
+                },
+            };
+            streams.push(
+                self.create_live_diff_stream(&repo.worktree_path, &base_commit, stats_only, paths)
+                    .await?,
+            );
+        }
+        Ok(Box::pin(futures::stream::select_all(streams)))
     }
 
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
@@ -960,39 +1614,35 @@ impl ContainerService for LocalContainerService {
             return Ok(false);
         }
 
+        let container_ref = ctx.task_attempt.container_ref.as_ref().ok_or_else(|| {
+            ContainerError::Other(anyhow::anyhow!("Container reference not found"))
+        })?;
+        let worktree_path = Path::new(container_ref);
+
+        let project = ctx
+            .task
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent project not found")))?;
+        let git = self
+            .git()
+            .with_commit_identity(CommitIdentity::from_project(&project));
+
+        // A coding agent run that ends while a rebase/merge is paused was resolving
+        // its conflicts: continue the operation instead of committing on top of it.
+        if matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+        ) && git.detect_conflict_op(worktree_path)?.is_some()
+        {
+            return self
+                .continue_after_conflict_resolution(ctx, &git, worktree_path)
+                .await;
+        }
+
         let message = match ctx.execution_process.run_reason {
             ExecutionProcessRunReason::CodingAgent => {
-                // Try to retrieve the task summary from the executor session
-                // otherwise fallback to default message
-                match ExecutorSession::find_by_execution_process_id(
-                    &self.db().pool,
-                    ctx.execution_process.id,
-                )
-                .await
-                {
-                    Ok(Some(session)) if session.summary.is_some() => session.summary.unwrap(),
-                    Ok(_) => {
-                        tracing::debug!(
-                            "No summary found for execution process {}, using default message",
-                            ctx.execution_process.id
-                        );
-                        format!(
-                            "Commit changes from coding agent for task attempt {}",
-                            ctx.task_attempt.id
-                        )
-                    }
-                    Err(e) => {
-                        tracing::debug!(
-                            "Failed to retrieve summary for execution process {}: {}",
-                            ctx.execution_process.id,
-                            e
-                        );
-                        format!(
-                            "Commit changes from coding agent for task attempt {}",
-                            ctx.task_attempt.id
-                        )
-                    }
-                }
+                self.coding_agent_commit_message(ctx, &project).await?
             }
             ExecutionProcessRunReason::CleanupScript => {
                 format!(
@@ -1005,10 +1655,6 @@ impl ContainerService for LocalContainerService {
             )))?,
         };
 
-        let container_ref = ctx.task_attempt.container_ref.as_ref().ok_or_else(|| {
-            ContainerError::Other(anyhow::anyhow!("Container reference not found"))
-        })?;
-
         tracing::debug!(
             "Committing changes for task attempt {} at path {:?}: '{}'",
             ctx.task_attempt.id,
@@ -1016,55 +1662,244 @@ impl ContainerService for LocalContainerService {
             message
         );
 
-        let changes_committed = self.git().commit(Path::new(container_ref), &message)?;
+        let scope = ProjectScope::from_project(&project);
+        let mut changes_committed = git.commit_in_scope(worktree_path, &message, &scope)?;
+        if let Some(workspace) =
+            AttemptWorkspace::load(&self.db.pool, &project, &ctx.task_attempt).await?
+        {
+            for repo in workspace.extra_repos() {
+                changes_committed |= git.commit(&repo.worktree_path, &message)?;
+            }
+        }
         Ok(changes_committed)
     }
 
-    /// Copy files from the original project directory to the worktree
     async fn copy_project_files(
         &self,
-        source_dir: &Path,
-        target_dir: &Path,
-        copy_files: &str,
-    ) -> Result<(), ContainerError> {
-        let files: Vec<&str> = copy_files
-            .split(',')
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .collect();
+        project: &Project,
+        task_attempt: &TaskAttempt,
+        worktree_path: &Path,
+    ) -> Result<CopyFilesReport, ContainerError> {
+        let spec = parse_copy_files(project.copy_files.as_deref().unwrap_or_default())
+            .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+        let values = CopyTemplateValues {
+            branch: task_attempt.branch.clone(),
+            base_branch: task_attempt.target_branch.clone(),
+            attempt_id: task_attempt.id.to_string(),
+            task_id: task_attempt.task_id.to_string(),
+            worktree: worktree_path.to_string_lossy().to_string(),
+            port: spec
+                .has_templates()
+                .then(|| self.dev_servers.port_for(task_attempt.id))
+                .flatten(),
+        };
+        let source_dir = project.git_repo_path.clone();
+        let target_dir = worktree_path.to_path_buf();
+        let report = tokio::task::spawn_blocking(move || {
+            copy_files::copy_project_files(&source_dir, &target_dir, &spec, &values)
+        })
+        .await
+        .map_err(|e| ContainerError::Other(anyhow!("copy_files task failed: {e}")))?;
+        tracing::info!(
+            "Copied project files into {}: {} copied, {} linked, {} unmatched patterns, {} errors",
+            worktree_path.display(),
+            report.copied,
+            report.linked,
+            report.unmatched.len(),
+            report.errors.len()
+        );
+        Ok(report)
+    }
+}
 
-        for file_path in files {
-            let source_file = source_dir.join(file_path);
-            let target_file = target_dir.join(file_path);
+impl LocalContainerService {
+    /// Check a dev server's health every [`CHECK_INTERVAL`] until it exits. Dev servers that
+    /// turn unhealthy are stopped as failed when the project restarts them, which hands them
+    /// to [`Self::schedule_dev_server_restart`].
+    fn spawn_dev_server_health_monitor(
+        &self,
+        project: &Project,
+        attempt_id: Uuid,
+        exec_id: Uuid,
+        worktree: PathBuf,
+    ) {
+        let check = match project
+            .dev_server_health_check
+            .as_deref()
+            .map(parse_health_check)
+            .transpose()
+        {
+            Ok(Some(Some(check))) => check,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring dev server health check of project {}: {}",
+                    project.id,
+                    e
+                );
+                return;
+            }
+        };
+        let restart_on_failure =
+            project.dev_server_restart_policy == DevServerRestartPolicy::OnFailure;
+        let in_container = ExecutionContainer::from_project(project).is_some();
+        let container = self.clone();
 
-            // Create parent directories if needed
-            if let Some(parent) = target_file.parent()
-                && !parent.exists()
+        tokio::spawn(async move {
+            let pool = &container.db.pool;
+            if let Err(e) =
+                ExecutionProcess::update_health_status(pool, exec_id, DevServerHealth::Starting)
+                    .await
             {
-                std::fs::create_dir_all(parent).map_err(|e| {
-                    ContainerError::Other(anyhow!("Failed to create directory {parent:?}: {e}"))
-                })?;
+                tracing::warn!("Failed to record health of dev server {}: {}", exec_id, e);
             }
+            let target = match container.oci_runtime.as_ref() {
+                Some(runtime) if in_container => ProbeTarget::Container {
+                    runtime,
+                    execution_id: exec_id,
+                },
+                _ => ProbeTarget::Host,
+            };
+            let started = Instant::now();
+            let mut tracker = HealthTracker::default();
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+                if container.get_child_from_store(&exec_id).await.is_none() {
+                    break;
+                }
+                let Some(dev_server) = container.dev_servers.get(attempt_id) else {
+                    break;
+                };
+                let result = check.probe(&dev_server, &worktree, target).await;
+                let Some(health) = tracker.record(result.is_ok(), started.elapsed()) else {
+                    continue;
+                };
 
-            // Copy the file
-            if source_file.exists() {
-                std::fs::copy(&source_file, &target_file).map_err(|e| {
-                    ContainerError::Other(anyhow!(
-                        "Failed to copy file {source_file:?} to {target_file:?}: {e}"
-                    ))
-                })?;
-                tracing::info!("Copied file {:?} to worktree", file_path);
-            } else {
-                return Err(ContainerError::Other(anyhow!(
-                    "File {source_file:?} does not exist in the project directory"
-                )));
+                if let Err(e) = ExecutionProcess::update_health_status(pool, exec_id, health).await
+                {
+                    tracing::warn!("Failed to record health of dev server {}: {}", exec_id, e);
+                }
+                let notice = match (health, &result) {
+                    (DevServerHealth::Unhealthy, Err(reason)) => {
+                        format!("Dev server is unhealthy: {reason}")
+                    }
+                    (DevServerHealth::Unhealthy, Ok(())) => "Dev server is unhealthy".to_string(),
+                    _ => "Dev server is healthy".to_string(),
+                };
+                if let Some(store) = container.msg_stores.read().await.get(&exec_id) {
+                    store.push_stderr(format!("{notice}\n"));
+                }
+
+                match health {
+                    DevServerHealth::Healthy => container.dev_servers.reset_restarts(attempt_id),
+                    DevServerHealth::Unhealthy if restart_on_failure => {
+                        if let Ok(Some(process)) = ExecutionProcess::find_by_id(pool, exec_id).await
+                            && let Err(e) = container
+                                .stop_execution(&process, ExecutionProcessStatus::Failed)
+                                .await
+                        {
+                            tracing::error!(
+                                "Failed to stop unhealthy dev server {}: {}",
+                                exec_id,
+                                e
+                            );
+                        }
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    /// Start a failed dev server again after a backoff, unless the project doesn't restart
+    /// dev servers or this one has already been restarted too often in a row
+    async fn schedule_dev_server_restart(&self, ctx: &ExecutionContext) {
+        let attempt_id = ctx.task_attempt.id;
+        let project = match Project::find_by_id(&self.db.pool, ctx.task.project_id).await {
+            Ok(Some(project)) => project,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to load project to restart dev server: {}", e);
+                return;
             }
+        };
+        if project.dev_server_restart_policy != DevServerRestartPolicy::OnFailure {
+            return;
         }
-        Ok(())
+        let Some(script) = project.dev_script.filter(|s| !s.trim().is_empty()) else {
+            return;
+        };
+        let uptime = (Utc::now() - ctx.execution_process.started_at)
+            .to_std()
+            .unwrap_or_default();
+        let restart = self.dev_servers.record_restart(attempt_id, uptime);
+        if i64::from(restart) > project.dev_server_max_restarts {
+            tracing::warn!(
+                "Dev server for attempt {} failed {} times in a row; not restarting it again",
+                attempt_id,
+                restart
+            );
+            return;
+        }
+
+        let container = self.clone();
+        let task_attempt = ctx.task_attempt.clone();
+        let failed_id = ctx.execution_process.id;
+        let project_id = project.id;
+        let max_restarts = project.dev_server_max_restarts;
+        tokio::spawn(async move {
+            tokio::time::sleep(restart_backoff(restart)).await;
+            // The user may have started a new dev server in the meantime
+            match ExecutionProcess::find_running_dev_servers_by_project(
+                &container.db.pool,
+                project_id,
+            )
+            .await
+            {
+                Ok(running) if running.iter().any(|p| p.task_attempt_id == attempt_id) => return,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("Failed to check running dev servers: {}", e);
+                    return;
+                }
+            }
+
+            let executor_action = ExecutorAction::new(
+                ExecutorActionType::ScriptRequest(ScriptRequest {
+                    script,
+                    language: ScriptRequestLanguage::Bash,
+                    context: ScriptContext::DevServer,
+                    env: HashMap::new(),
+                }),
+                None,
+            );
+            match container
+                .start_execution(
+                    &task_attempt,
+                    &executor_action,
+                    &ExecutionProcessRunReason::DevServer,
+                )
+                .await
+            {
+                Ok(process) => {
+                    if let Some(store) = container.msg_stores.read().await.get(&process.id) {
+                        store.push_stderr(format!(
+                            "Restarted dev server after process {failed_id} failed (restart {restart} of {max_restarts})\n"
+                        ));
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to restart dev server for attempt {}: {}",
+                        attempt_id,
+                        e
+                    )
+                }
+            }
+        });
     }
-}
 
-impl LocalContainerService {
     /// Extract the last assistant message from the MsgStore history
     fn extract_last_assistant_message(&self, exec_id: &Uuid) -> Option<String> {
         // Get the MsgStore for this execution
@@ -1096,6 +1931,72 @@ impl LocalContainerService {
         None
     }
 
+    /// Commit message for a coding agent run, following the project's commit message strategy
+    async fn coding_agent_commit_message(
+        &self,
+        ctx: &ExecutionContext,
+        project: &Project,
+    ) -> Result<String, ContainerError> {
+        // Try to retrieve the task summary from the executor session
+        let summary = match ExecutorSession::find_by_execution_process_id(
+            &self.db().pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(session) => session.and_then(|session| session.summary),
+            Err(e) => {
+                tracing::debug!(
+                    "Failed to retrieve summary for execution process {}: {}",
+                    ctx.execution_process.id,
+                    e
+                );
+                None
+            }
+        };
+
+        let message = match project.commit_message_strategy {
+            CommitMessageStrategy::AgentSummary => None,
+            CommitMessageStrategy::ConventionalCommit => {
+                let message = summary.as_deref().and_then(extract_commit_message);
+                if message.is_none() {
+                    tracing::debug!(
+                        "No commit message found in summary of execution process {}, using summary",
+                        ctx.execution_process.id
+                    );
+                }
+                message
+            }
+            CommitMessageStrategy::Template => project
+                .commit_message_template
+                .as_deref()
+                .filter(|template| !template.trim().is_empty())
+                .map(|template| {
+                    render_commit_message(
+                        template,
+                        &CommitMessageContext {
+                            task: &ctx.task,
+                            task_attempt: &ctx.task_attempt,
+                            summary: summary.as_deref(),
+                        },
+                    )
+                })
+                .filter(|message| !message.is_empty()),
+        };
+
+        // Otherwise fall back to the summary, then to a default message
+        Ok(message.or(summary).unwrap_or_else(|| {
+            tracing::debug!(
+                "No summary found for execution process {}, using default message",
+                ctx.execution_process.id
+            );
+            format!(
+                "Commit changes from coding agent for task attempt {}",
+                ctx.task_attempt.id
+            )
+        }))
+    }
+
     /// Update the executor session summary with the final assistant message
     async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
         // Check if there's an executor session for this execution process
@@ -1116,6 +2017,39 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Verify an agent's conflict resolution and continue the paused rebase/merge.
+    /// Leftover markers or new conflicts are reported in the process log and
+    /// leave the operation paused so the user can retry or abort.
+    async fn continue_after_conflict_resolution(
+        &self,
+        ctx: &ExecutionContext,
+        git: &GitService,
+        worktree_path: &Path,
+    ) -> Result<bool, ContainerError> {
+        match git.continue_conflicts(worktree_path) {
+            Ok(op) => {
+                tracing::info!(
+                    "Continued {:?} for task attempt {} after agent conflict resolution",
+                    op,
+                    ctx.task_attempt.id
+                );
+                Ok(true)
+            }
+            Err(GitServiceError::MergeConflicts(msg)) => {
+                tracing::warn!(
+                    "Conflicts not resolved for task attempt {}: {}",
+                    ctx.task_attempt.id,
+                    msg
+                );
+                if let Some(store) = self.get_msg_store_by_id(&ctx.execution_process.id).await {
+                    store.push(LogMsg::Stderr(format!("{msg}\n")));
+                }
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// If a queued follow-up draft exists for this attempt and nothing is running,
     /// start it immediately and clear the draft.
     async fn try_consume_queued_followup(
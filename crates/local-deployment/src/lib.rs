@@ -16,6 +16,8 @@ use services::services::{
     filesystem::FilesystemService,
     git::GitService,
     image::ImageService,
+    local_analytics::LocalAnalyticsSink,
+    webhooks::WebhookService,
 };
 use tokio::sync::RwLock;
 use utils::{assets::config_path, msg_store::MsgStore};
@@ -31,6 +33,7 @@ pub struct LocalDeployment {
     user_id: String,
     db: DBService,
     analytics: Option<AnalyticsService>,
+    local_analytics: LocalAnalyticsSink,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
     container: LocalContainerService,
     git: GitService,
@@ -41,6 +44,7 @@ pub struct LocalDeployment {
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     drafts: DraftsService,
+    webhooks: WebhookService,
 }
 
 #[async_trait]
@@ -82,12 +86,17 @@ impl Deployment for LocalDeployment {
         let events_msg_store = Arc::new(MsgStore::new());
         let events_entry_count = Arc::new(RwLock::new(0));
 
+        // Webhooks share the hook's DB service, since the hook dispatches to them
+        let hook_db = DBService::new().await?; // Temporary DB service for the hook
+        let webhooks = WebhookService::new(hook_db.clone());
+
         // Create DB with event hooks
         let db = {
             let hook = EventService::create_hook(
                 events_msg_store.clone(),
                 events_entry_count.clone(),
-                DBService::new().await?, // Temporary DB service for the hook
+                hook_db,
+                webhooks.clone(),
             );
             DBService::new_with_after_connect(hook).await?
         };
@@ -103,7 +112,12 @@ impl Deployment for LocalDeployment {
             });
         }
 
-        let approvals = Approvals::new(msg_stores.clone());
+        let approvals = Approvals::new(
+            msg_stores.clone(),
+            db.clone(),
+            config.clone(),
+            webhooks.clone(),
+        );
 
         // We need to make analytics accessible to the ContainerService
         // TODO: Handle this more gracefully
@@ -111,6 +125,7 @@ impl Deployment for LocalDeployment {
             user_id: user_id.clone(),
             analytics_service: s.clone(),
         });
+        let local_analytics = LocalAnalyticsSink::new(db.clone(), config.clone());
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
@@ -118,7 +133,9 @@ impl Deployment for LocalDeployment {
             git.clone(),
             image.clone(),
             analytics_ctx,
+            local_analytics.clone(),
             approvals.clone(),
+            webhooks.clone(),
         );
         container.spawn_worktree_cleanup().await;
 
@@ -131,6 +148,7 @@ impl Deployment for LocalDeployment {
             user_id,
             db,
             analytics,
+            local_analytics,
             msg_stores,
             container,
             git,
@@ -141,6 +159,7 @@ impl Deployment for LocalDeployment {
             file_search_cache,
             approvals,
             drafts,
+            webhooks,
         })
     }
 
@@ -164,7 +183,11 @@ impl Deployment for LocalDeployment {
         &self.analytics
     }
 
-    fn container(&self) -> &impl ContainerService {
+    fn local_analytics(&self) -> &LocalAnalyticsSink {
+        &self.local_analytics
+    }
+
+    fn container(&self) -> &(impl ContainerService + Clone + Send + Sync + 'static) {
         &self.container
     }
     fn auth(&self) -> &AuthService {
@@ -202,6 +225,10 @@ impl Deployment for LocalDeployment {
     fn drafts(&self) -> &DraftsService {
         &self.drafts
     }
+
+    fn webhooks(&self) -> &WebhookService {
+        &self.webhooks
+    }
 }
 
 impl LocalDeployment {
@@ -227,13 +254,16 @@ impl LocalDeployment {
         let events_msg_store = Arc::new(MsgStore::new());
         let events_entry_count = Arc::new(RwLock::new(0));
         
+        let temp_db = DBService::new_with_path(db_path).await?;
+        let webhooks = WebhookService::new(temp_db.clone());
+
         // Create DB with custom path
         let db = {
-            let temp_db = DBService::new_with_path(db_path).await?;
             let hook = EventService::create_hook(
                 events_msg_store.clone(),
                 events_entry_count.clone(),
                 temp_db.clone(),
+                webhooks.clone(),
             );
             // Create a new DB service with the hook
             let database_url = format!("sqlite://{}", db_path.to_string_lossy());
@@ -266,8 +296,14 @@ impl LocalDeployment {
         let image = ImageService::new(db.clone().pool)
             .map_err(|e| DeploymentError::Other(anyhow::anyhow!("Failed to create ImageService: {}", e)))?;
         
-        let approvals = Approvals::new(msg_stores.clone());
+        let approvals = Approvals::new(
+            msg_stores.clone(),
+            db.clone(),
+            config.clone(),
+            webhooks.clone(),
+        );
         
+        let local_analytics = LocalAnalyticsSink::new(db.clone(), config.clone());
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
@@ -275,7 +311,9 @@ impl LocalDeployment {
             git.clone(),
             image.clone(),
             None, // analytics_ctx
+            local_analytics.clone(),
             approvals.clone(),
+            webhooks.clone(),
         );
         // Don't spawn worktree cleanup in tests
         
@@ -288,6 +326,7 @@ impl LocalDeployment {
             user_id,
             db,
             analytics,
+            local_analytics,
             msg_stores,
             container,
             git,
@@ -298,6 +337,7 @@ impl LocalDeployment {
             file_search_cache,
             approvals,
             drafts,
+            webhooks,
         })
     }
 }
@@ -17,6 +17,26 @@ fn generate_types_content() -> String {
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        db::models::project::CommitMessageStrategy::decl(),
+        db::models::project::CommitMessageValidation::decl(),
+        db::models::project::CommitSigningFormat::decl(),
+        db::models::project::DevServerRestartPolicy::decl(),
+        db::models::project_repository::ProjectRepository::decl(),
+        db::models::project_repository::CreateProjectRepository::decl(),
+        db::models::review_comment::ReviewComment::decl(),
+        db::models::review_comment::ReviewCommentSide::decl(),
+        db::models::review_comment::CreateReviewComment::decl(),
+        db::models::review_comment::UpdateReviewComment::decl(),
+        db::models::transcript_entry::TranscriptEntryKind::decl(),
+        db::models::transcript_entry::TranscriptSearchQuery::decl(),
+        db::models::transcript_entry::TranscriptSearchResult::decl(),
+        db::models::webhook::Webhook::decl(),
+        db::models::webhook::WebhookEventType::decl(),
+        db::models::webhook::WebhookDeliveryStatus::decl(),
+        db::models::webhook::CreateWebhook::decl(),
+        db::models::webhook::CreatedWebhook::decl(),
+        db::models::webhook::UpdateWebhook::decl(),
+        db::models::webhook::WebhookDelivery::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
@@ -35,6 +55,20 @@ fn generate_types_content() -> String {
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_relationship_history::RelationshipChange::decl(),
+        db::models::tool_approval::ToolApprovalStatus::decl(),
+        services::services::task_activity::TaskActivityEvent::decl(),
+        services::services::task_activity::TaskActivity::decl(),
+        services::services::task_metrics::TaskMetricsQuery::decl(),
+        services::services::task_metrics::DurationStats::decl(),
+        services::services::task_metrics::AgentRunStats::decl(),
+        services::services::task_metrics::FlowMetrics::decl(),
+        services::services::task_metrics::ExecutorProfileMetrics::decl(),
+        services::services::task_metrics::TaskMetrics::decl(),
+        db::models::analytics_event::WeeklyMerges::decl(),
+        services::services::local_analytics::AnalyticsReportQuery::decl(),
+        services::services::local_analytics::ExecutorUsage::decl(),
+        services::services::local_analytics::AnalyticsReport::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -49,12 +83,23 @@ fn generate_types_content() -> String {
         services::services::drafts::UpdateRetryFollowUpDraftRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
+        server::routes::task_attempts::PinWorktreeRequest::decl(),
+        db::models::task_attempt_repository::TaskAttemptRepository::decl(),
+        server::routes::task_attempts::repositories::TaskAttemptRepositoryStatus::decl(),
+        services::services::dev_server::DevServerInfo::decl(),
+        services::services::conversation_export::ExportFormat::decl(),
+        server::routes::containers::WorktreeDiskUsage::decl(),
+        server::routes::containers::WorktreeDiskUsageReport::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
         server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         services::services::github_service::GitHubServiceError::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
+        services::services::config::NotificationChannelConfig::decl(),
+        services::services::config::NotificationChannelKind::decl(),
+        services::services::config::NotificationEvent::decl(),
+        services::services::config::SmtpSecurity::decl(),
         services::services::config::ThemeMode::decl(),
         services::services::config::EditorConfig::decl(),
         services::services::config::EditorType::decl(),
@@ -92,6 +137,8 @@ fn generate_types_content() -> String {
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
+        server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
+        server::routes::task_attempts::ResolveConflictsRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::ReplaceProcessRequest::decl(),
         server::routes::task_attempts::CommitInfo::decl(),
@@ -101,10 +148,12 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process::DevServerHealth::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
         db::models::merge::MergeStatus::decl(),
+        db::models::merge::MergeStrategy::decl(),
         db::models::merge::PullRequestInfo::decl(),
         db::models::draft::Draft::decl(),
         db::models::draft::DraftType::decl(),
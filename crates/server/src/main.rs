@@ -1,17 +1,26 @@
+use std::{future::IntoFuture, time::Duration};
+
 use anyhow::{self, Error as AnyhowError};
+use db::models::execution_process_logs::ExecutionProcessLogs;
 use deployment::{Deployment, DeploymentError};
 use server::{DeploymentImpl, routes};
+use services::services::transcript_search::backfill_transcript_index;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 use tracing_subscriber::{EnvFilter, prelude::*};
 use utils::{
     assets::asset_dir,
     browser::open_browser,
+    otel,
     port_file::write_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
 };
 
+/// How long open connections get to finish once shutdown starts
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Error)]
 pub enum VibeKanbanError {
     #[error(transparent)]
@@ -33,10 +42,18 @@ async fn main() -> Result<(), VibeKanbanError> {
         "warn,server={level},services={level},db={level},executors={level},deployment={level},local_deployment={level},utils={level}",
         level = log_level
     );
-    let env_filter = EnvFilter::try_new(filter_string).expect("Failed to create tracing filter");
+    let env_filter =
+        || EnvFilter::try_new(&filter_string).expect("Failed to create tracing filter");
+    // Spans are only exported when an OTLP collector is configured in the environment
+    let tracer_provider = otel::tracer_provider_from_env();
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
+        .with(tracing_subscriber::fmt::layer().with_filter(env_filter()))
         .with(sentry_layer())
+        .with(
+            tracer_provider
+                .as_ref()
+                .map(|provider| otel::otlp_layer(provider).with_filter(env_filter())),
+        )
         .init();
 
     // Create asset directory if it doesn't exist
@@ -49,10 +66,29 @@ async fn main() -> Result<(), VibeKanbanError> {
     deployment.cleanup_orphan_executions().await?;
     deployment.backfill_before_head_commits().await?;
     deployment.spawn_pr_monitor_service().await;
+    if let Err(e) = deployment.webhooks().resume_pending().await {
+        tracing::warn!("Failed to resume pending webhook deliveries: {}", e);
+    }
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
 
+    // Compress logs carried over from before chunked log storage, then index the transcripts
+    // of processes that finished before transcript search
+    let deployment_for_logs = deployment.clone();
+    tokio::spawn(async move {
+        match ExecutionProcessLogs::compress_legacy_chunks(&deployment_for_logs.db().pool).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Compressed logs of {} execution processes", count),
+            Err(e) => tracing::warn!("Failed to compress legacy execution logs: {}", e),
+        }
+        match backfill_transcript_index(deployment_for_logs.container()).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Indexed transcripts of {} execution processes", count),
+            Err(e) => tracing::warn!("Failed to index transcripts: {}", e),
+        }
+    });
+
     // Pre-warm file search cache for most active projects
     let deployment_for_cache = deployment.clone();
     tokio::spawn(async move {
@@ -105,6 +141,59 @@ async fn main() -> Result<(), VibeKanbanError> {
         });
     }
 
-    axum::serve(listener, app_router).await?;
+    // Stop on Ctrl-C or SIGTERM, so that exported spans are flushed below
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown_signal().await;
+            shutdown.cancel();
+        }
+    });
+    let serve = axum::serve(listener, app_router)
+        .with_graceful_shutdown(shutdown.clone().cancelled_owned())
+        .into_future();
+    let served = tokio::select! {
+        served = serve => served,
+        // Event streams keep their connections open, so don't wait on them for long
+        _ = async {
+            shutdown.cancelled().await;
+            tokio::time::sleep(SHUTDOWN_GRACE_PERIOD).await;
+        } => Ok(()),
+    };
+    if let Some(provider) = tracer_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush exported spans: {}", e);
+    }
+    served?;
     Ok(())
 }
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
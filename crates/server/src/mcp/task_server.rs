@@ -687,6 +687,7 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             base_branch,
+            parent_attempt_id: None,
         };
 
         let url = self.url("/api/task-attempts");
@@ -7,6 +7,7 @@ use axum::{
 use db::models::{
     execution_process::ExecutionProcess, project::Project, tag::Tag, task::Task,
     task_attempt::TaskAttempt, task_template::TaskTemplate, task_template_group::TaskTemplateGroup,
+    webhook::Webhook,
 };
 use deployment::Deployment;
 use uuid::Uuid;
@@ -251,3 +252,27 @@ pub async fn load_template_group_middleware(
 
     Ok(next.run(request).await)
 }
+
+pub async fn load_webhook_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(webhook_id): Path<Uuid>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let webhook = match Webhook::find_by_id(&deployment.db().pool, webhook_id).await {
+        Ok(Some(webhook)) => webhook,
+        Ok(None) => {
+            tracing::warn!("Webhook {} not found", webhook_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch webhook {}: {}", webhook_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let mut request = request;
+    request.extensions_mut().insert(webhook);
+
+    Ok(next.run(request).await)
+}
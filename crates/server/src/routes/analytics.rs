@@ -0,0 +1,42 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::analytics_event::AnalyticsEvent;
+use deployment::Deployment;
+use services::services::local_analytics::{
+    AnalyticsReport, AnalyticsReportQuery, LocalAnalyticsError, analytics_report,
+};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Usage aggregated from the analytics events stored on this machine
+pub async fn get_analytics_report(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AnalyticsReportQuery>,
+) -> Result<ResponseJson<ApiResponse<AnalyticsReport>>, ApiError> {
+    let report = analytics_report(&deployment.db().pool, &query)
+        .await
+        .map_err(|e| match e {
+            LocalAnalyticsError::Database(e) => ApiError::Database(e),
+            e @ LocalAnalyticsError::InvalidWindow => ApiError::BadRequest(e.to_string()),
+        })?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// Forget every stored analytics event, returning how many there were
+pub async fn delete_analytics_events(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<u64>>, ApiError> {
+    let deleted = AnalyticsEvent::delete_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(deleted)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/analytics/report", get(get_analytics_report))
+        .route("/analytics/events", delete(delete_analytics_events))
+}
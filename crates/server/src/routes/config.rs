@@ -6,7 +6,7 @@ use axum::{
     extract::{Path, Query, State},
     http,
     response::{Json as ResponseJson, Response},
-    routing::{get, put},
+    routing::{get, post, put},
 };
 use deployment::{Deployment, DeploymentError};
 use executors::{
@@ -16,7 +16,14 @@ use executors::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{Config, ConfigError, SoundFile, save_config_to_file};
+use services::services::{
+    config::{
+        Config, ConfigError, NotificationChannelConfig, NotificationEvent, SoundFile,
+        save_config_to_file,
+    },
+    notification::NotificationService,
+    notification_channels::{Notification, build_channel},
+};
 use tokio::fs;
 use ts_rs::TS;
 use utils::{assets::config_path, response::ApiResponse};
@@ -28,6 +35,7 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/info", get(get_user_system_info))
         .route("/config", put(update_config))
         .route("/sounds/{sound}", get(get_sound))
+        .route("/notifications/test", post(test_notification_channel))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
 }
@@ -202,6 +210,31 @@ async fn get_sound(Path(sound): Path<SoundFile>) -> Result<Response, ApiError> {
     Ok(response)
 }
 
+/// Send a sample notification through a channel, which need not be saved yet
+async fn test_notification_channel(
+    State(deployment): State<DeploymentImpl>,
+    Json(channel_config): Json<NotificationChannelConfig>,
+) -> ResponseJson<ApiResponse<()>> {
+    let notify_cfg = deployment.config().read().await.notifications.clone();
+    let notification = Notification {
+        event: NotificationEvent::AttemptCompleted,
+        title: "Vibe Kanban test notification".to_string(),
+        message: format!(
+            "✅ '{}' is set up to receive notifications",
+            channel_config.name
+        ),
+        url: NotificationService::link(&notify_cfg, "/settings/general").await,
+    };
+    let channel = build_channel(&channel_config.channel, &reqwest::Client::new());
+
+    match channel.send(&notification).await {
+        Ok(()) => ResponseJson(ApiResponse::success(())),
+        Err(e) => ResponseJson(ApiResponse::error(&format!(
+            "Failed to send test notification: {e}"
+        ))),
+    }
+}
+
 #[derive(TS, Debug, Deserialize)]
 pub struct McpServerQuery {
     executor: BaseCodingAgent,
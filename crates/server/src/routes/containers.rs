@@ -7,6 +7,7 @@ use axum::{
 use db::models::task_attempt::TaskAttempt;
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::worktree_manager::WorktreeManager;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -49,6 +50,67 @@ pub async fn get_container_info(
     Ok(ResponseJson(ApiResponse::success(container_info)))
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreeDiskUsage {
+    pub path: String,
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+    /// Attempt owning the worktree; `None` for directories no attempt refers to
+    pub attempt_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub pinned: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreeDiskUsageReport {
+    pub base_dir: String,
+    #[ts(type = "number")]
+    pub total_bytes: u64,
+    /// Free space on the filesystem holding the worktrees, when known
+    #[ts(type = "number | null")]
+    pub available_bytes: Option<u64>,
+    /// Largest first
+    pub worktrees: Vec<WorktreeDiskUsage>,
+}
+
+pub async fn get_worktree_disk_usage(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<WorktreeDiskUsageReport>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let mut worktrees = Vec::new();
+    for (path, size_bytes) in WorktreeManager::disk_usage().await? {
+        let path = path.to_string_lossy().to_string();
+        let attempt = match TaskAttempt::resolve_container_ref(pool, &path).await {
+            Ok((attempt_id, _, project_id)) => TaskAttempt::find_by_id(pool, attempt_id)
+                .await?
+                .map(|attempt| (attempt, project_id)),
+            Err(sqlx::Error::RowNotFound) => None,
+            Err(e) => return Err(ApiError::Database(e)),
+        };
+        worktrees.push(WorktreeDiskUsage {
+            path,
+            size_bytes,
+            attempt_id: attempt.as_ref().map(|(attempt, _)| attempt.id),
+            project_id: attempt.as_ref().map(|(_, project_id)| *project_id),
+            pinned: attempt.is_some_and(|(attempt, _)| attempt.worktree_pinned),
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        WorktreeDiskUsageReport {
+            base_dir: WorktreeManager::get_worktree_base_dir()
+                .to_string_lossy()
+                .to_string(),
+            total_bytes: worktrees.iter().map(|w| w.size_bytes).sum(),
+            available_bytes: WorktreeManager::available_space(),
+            worktrees,
+        },
+    )))
+}
+
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
-    Router::new().route("/containers/info", get(get_container_info))
+    Router::new()
+        .route("/containers/info", get(get_container_info))
+        .route("/containers/disk-usage", get(get_worktree_disk_usage))
 }
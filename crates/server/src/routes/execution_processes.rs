@@ -5,17 +5,22 @@ use axum::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
+    http::header,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
 };
-use db::models::execution_process::{
-    ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus,
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    task_attempt::TaskAttemptError,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
-use services::services::container::ContainerService;
+use services::services::{
+    container::ContainerService,
+    conversation_export::{ConversationExport, ExportFormat, export_conversation},
+};
 use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
@@ -180,6 +185,56 @@ async fn handle_normalized_logs_ws(
     Ok(())
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Serve a rendered export as a file download
+pub(crate) fn export_response(
+    export: &ConversationExport,
+    format: ExportFormat,
+) -> Result<Response, ApiError> {
+    let body = export
+        .render(format)
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))?;
+    let disposition = format!("attachment; filename=\"{}\"", export.file_name(format));
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+pub async fn export_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let task_attempt = execution_process
+        .parent_task_attempt(pool)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+
+    let export = export_conversation(
+        deployment.container(),
+        &task,
+        &task_attempt,
+        std::slice::from_ref(&execution_process),
+    )
+    .await;
+    export_response(&export, query.format)
+}
+
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -253,6 +308,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/stop", post(stop_execution_process))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
+        .route("/export", get(export_execution_process))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
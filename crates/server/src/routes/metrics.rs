@@ -0,0 +1,28 @@
+use axum::{
+    Router,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use deployment::Deployment;
+use services::services::prometheus::MetricsSnapshot;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// GET /metrics, in the Prometheus text exposition format
+pub async fn get_metrics(State(deployment): State<DeploymentImpl>) -> Result<Response, ApiError> {
+    let snapshot = MetricsSnapshot::collect(&deployment.db().pool).await?;
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        snapshot.encode(),
+    )
+        .into_response())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/metrics", get(get_metrics))
+}
@@ -1,11 +1,12 @@
 use axum::{
     Router,
-    middleware::from_fn_with_state,
+    middleware::{from_fn, from_fn_with_state},
     routing::{IntoMakeService, get},
 };
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::request_span_middleware};
 
+pub mod analytics;
 pub mod approvals;
 pub mod auth;
 pub mod config;
@@ -18,6 +19,8 @@ pub mod execution_processes;
 pub mod frontend;
 pub mod health;
 pub mod images;
+pub mod metrics;
+pub mod preview;
 pub mod projects;
 pub mod tags;
 pub mod task_attempts;
@@ -26,6 +29,8 @@ pub mod task_relationships;
 pub mod task_template_groups;
 pub mod task_templates;
 pub mod tasks;
+pub mod transcripts;
+pub mod webhooks;
 
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
     // Create routers with different middleware layers
@@ -43,20 +48,26 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(task_relationships::router(&deployment))
         .merge(task_templates::router(&deployment))
         .merge(task_template_groups::router(&deployment))
+        .merge(transcripts::router())
+        .merge(webhooks::router(&deployment))
         .merge(auth::router(&deployment))
         .merge(filesystem::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(analytics::router())
         .nest("/images", images::routes())
         .layer(from_fn_with_state(
             deployment.clone(),
             auth::sentry_user_context_middleware,
         ))
+        .layer(from_fn(request_span_middleware))
         .with_state(deployment.clone());
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
+        .merge(preview::router().with_state(deployment.clone()))
+        .merge(metrics::router().with_state(deployment.clone()))
         .nest("/api", base_routes)
         .into_make_service()
 }
@@ -78,19 +89,25 @@ pub fn router_for_testing(deployment: DeploymentImpl) -> Router {
         .merge(task_relationships::router(&deployment))
         .merge(task_templates::router(&deployment))
         .merge(task_template_groups::router(&deployment))
+        .merge(transcripts::router())
+        .merge(webhooks::router(&deployment))
         .merge(auth::router(&deployment))
         .merge(filesystem::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(analytics::router())
         .nest("/images", images::routes())
         .layer(from_fn_with_state(
             deployment.clone(),
             auth::sentry_user_context_middleware,
         ))
+        .layer(from_fn(request_span_middleware))
         .with_state(deployment.clone());
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
+        .merge(preview::router().with_state(deployment.clone()))
+        .merge(metrics::router().with_state(deployment.clone()))
         .nest("/api", base_routes)
 }
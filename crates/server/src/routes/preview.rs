@@ -0,0 +1,172 @@
+//! Reverse proxy to attempts' dev servers at `/preview/{attempt_id}/`.
+//!
+//! Requests are forwarded with the prefix stripped, so apps that load assets from relative
+//! paths work unchanged. Bodies are buffered, and WebSocket upgrades (e.g. hot reload) are
+//! not proxied. The browser's cookies and credentials for this server are never passed on to
+//! the dev server.
+
+use std::{sync::LazyLock, time::Duration};
+
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::{Path, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+    routing::{any, get},
+};
+use deployment::Deployment;
+use services::services::{container::ContainerService, dev_server::preview_path};
+use uuid::Uuid;
+
+use crate::DeploymentImpl;
+
+const MAX_REQUEST_BODY: usize = 32 * 1024 * 1024;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .expect("valid preview proxy client")
+});
+
+/// Headers that describe a single connection rather than the message
+fn is_hop_by_hop(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "connection"
+            | "keep-alive"
+            | "proxy-authenticate"
+            | "proxy-authorization"
+            | "te"
+            | "trailer"
+            | "transfer-encoding"
+            | "upgrade"
+    )
+}
+
+fn forwarded_headers(headers: &HeaderMap) -> HeaderMap {
+    headers
+        .iter()
+        .filter(|(name, _)| {
+            !is_hop_by_hop(name) && *name != header::HOST && *name != header::CONTENT_LENGTH
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// Request headers minus the credentials the browser sends to this server, which the dev
+/// server has no business seeing
+fn forwarded_request_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = forwarded_headers(headers);
+    headers.remove(header::COOKIE);
+    headers.remove(header::AUTHORIZATION);
+    headers
+}
+
+/// Keep redirects from the dev server inside the preview prefix
+fn rewrite_location(location: &str, upstream: &str, prefix: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if let Some(rest) = location.strip_prefix(upstream) {
+        format!("{prefix}{}", if rest.is_empty() { "/" } else { rest })
+    } else if location.starts_with('/') && !location.starts_with("//") {
+        format!("{prefix}{location}")
+    } else {
+        location.to_string()
+    }
+}
+
+async fn forward(deployment: DeploymentImpl, attempt_id: Uuid, req: Request) -> Response {
+    let Some(dev_server) = deployment.container().dev_servers().get(attempt_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            "No dev server has been started for this attempt",
+        )
+            .into_response();
+    };
+    let upstream = dev_server.upstream();
+    // Forward the path as the browser sent it rather than the decoded route param
+    let path = req.uri().path().splitn(4, '/').nth(3).unwrap_or_default();
+    let mut url = format!("{upstream}/{path}");
+    if let Some(query) = req.uri().query() {
+        url.push('?');
+        url.push_str(query);
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = match to_bytes(body, MAX_REQUEST_BODY).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()).into_response(),
+    };
+    let response = match CLIENT
+        .request(parts.method, &url)
+        .headers(forwarded_request_headers(&parts.headers))
+        .body(body)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::debug!("Preview proxy request to {} failed: {}", url, e);
+            return (
+                StatusCode::BAD_GATEWAY,
+                format!("Dev server at {upstream} is not responding"),
+            )
+                .into_response();
+        }
+    };
+
+    let status = response.status();
+    let mut headers = forwarded_headers(response.headers());
+    if let Some(location) = headers
+        .get(header::LOCATION)
+        .and_then(|value| value.to_str().ok())
+        .map(|location| rewrite_location(location, &upstream, &dev_server.preview_path))
+        && let Ok(value) = HeaderValue::from_str(&location)
+    {
+        headers.insert(header::LOCATION, value);
+    }
+    let body = match response.bytes().await {
+        Ok(body) => body,
+        Err(e) => {
+            return (
+                StatusCode::BAD_GATEWAY,
+                format!("Failed to read dev server response: {e}"),
+            )
+                .into_response();
+        }
+    };
+
+    let mut proxied = Response::new(Body::from(body));
+    *proxied.status_mut() = status;
+    *proxied.headers_mut() = headers;
+    proxied
+}
+
+async fn redirect_to_root(Path(attempt_id): Path<Uuid>) -> Redirect {
+    Redirect::permanent(&preview_path(attempt_id))
+}
+
+async fn proxy_root(
+    State(deployment): State<DeploymentImpl>,
+    Path(attempt_id): Path<Uuid>,
+    req: Request,
+) -> Response {
+    forward(deployment, attempt_id, req).await
+}
+
+async fn proxy_path(
+    State(deployment): State<DeploymentImpl>,
+    Path((attempt_id, _)): Path<(Uuid, String)>,
+    req: Request,
+) -> Response {
+    forward(deployment, attempt_id, req).await
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/preview/{attempt_id}", get(redirect_to_root))
+        .route("/preview/{attempt_id}/", any(proxy_root))
+        .route("/preview/{attempt_id}/{*path}", any(proxy_path))
+}
@@ -1,22 +1,39 @@
-use std::path::Path;
-
 use axum::{
     Extension, Json, Router,
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
-use db::models::project::{
-    CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+use db::models::{
+    project::{
+        CommitIdentitySettings, CommitMessageSettings, CreateProject, DevServerSettings,
+        ExecutionContainerSettings, Project, ProjectError, ProjectScopeSettings, SearchMatchType,
+        SearchResult, UpdateProject, WorktreeRetentionSettings,
+    },
+    project_repository::{CreateProjectRepository, ProjectRepository},
+    task_attempt::TaskAttempt,
+    task_attempt_repository::TaskAttemptRepository,
 };
 use deployment::Deployment;
 use ignore::WalkBuilder;
 use services::services::{
+    commit_identity::validate_commit_identity,
+    commit_message::validate_commit_message_settings,
+    copy_files::validate_copy_files,
+    dependency_cache::{DependencyCache, validate_dependency_caches},
+    dev_server_health::validate_dev_server_health,
     file_ranker::FileRanker,
-    file_search_cache::{CacheError, SearchMode, SearchQuery},
+    file_search_cache::{CacheError, SearchMode, SearchQuery, scope_result_paths},
     git::GitBranch,
+    oci_runtime::validate_execution_container,
+    project_scope::{ProjectScope, normalize_working_dir, validate_project_scope},
+    task_metrics::{TaskMetrics, TaskMetricsError, TaskMetricsQuery, project_task_metrics},
+    workspace::validate_repository_name,
+    worktree_manager::WorktreeManager,
+    worktree_retention::validate_worktree_retention,
+    worktree_snapshot::{self, SnapshotLocation},
 };
 use utils::{path::expand_tilde, response::ApiResponse};
 use uuid::Uuid;
@@ -44,6 +61,21 @@ pub async fn get_project_branches(
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+/// Lead time, cycle time, throughput and agent success rate of the project's tasks
+pub async fn get_project_metrics(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskMetricsQuery>,
+) -> Result<ResponseJson<ApiResponse<TaskMetrics>>, ApiError> {
+    let metrics = project_task_metrics(&deployment.db().pool, project.id, &query)
+        .await
+        .map_err(|e| match e {
+            TaskMetricsError::Database(e) => ApiError::Database(e),
+            e @ TaskMetricsError::InvalidWindow => ApiError::BadRequest(e.to_string()),
+        })?;
+    Ok(ResponseJson(ApiResponse::success(metrics)))
+}
+
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
@@ -57,9 +89,78 @@ pub async fn create_project(
         cleanup_script,
         copy_files,
         use_existing_repo,
+        merge_strategy,
+        merge_commit_template,
+        commit_message_strategy,
+        commit_message_template,
+        commit_message_validation,
+        commit_message_pattern,
+        commit_author_name,
+        commit_author_email,
+        commit_co_authors,
+        commit_signing_format,
+        commit_signing_key,
+        container_image,
+        container_cpus,
+        container_memory,
+        worktree_retention_hours,
+        worktree_keep_recent,
+        worktree_keep_in_review,
+        worktree_max_size_mb,
+        worktree_snapshot_enabled,
+        dependency_cache,
+        dev_server_health_check,
+        dev_server_restart_policy,
+        dev_server_max_restarts,
+        working_dir,
+        sparse_checkout,
     } = payload;
     tracing::debug!("Creating project '{}'", name);
 
+    if let Err(e) = validate_commit_message_settings(
+        commit_message_validation.unwrap_or_default(),
+        commit_message_pattern.as_deref(),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_commit_identity(
+        commit_author_email.as_deref(),
+        commit_co_authors.as_deref(),
+        commit_signing_format.unwrap_or_default(),
+        commit_signing_key.as_deref(),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_execution_container(
+        container_image.as_deref(),
+        container_cpus.as_deref(),
+        container_memory.as_deref(),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_worktree_retention(
+        worktree_retention_hours,
+        worktree_keep_recent,
+        worktree_max_size_mb,
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_dependency_caches(dependency_cache.as_deref()) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_copy_files(copy_files.as_deref()) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) =
+        validate_dev_server_health(dev_server_health_check.as_deref(), dev_server_max_restarts)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_project_scope(working_dir.as_deref(), sparse_checkout.as_deref()) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    let working_dir = working_dir.as_deref().and_then(normalize_working_dir);
+
     // Validate and setup git repository
     let path = std::path::absolute(expand_tilde(&git_repo_path))?;
     // Check if git repo path is already used by another project
@@ -143,6 +244,31 @@ pub async fn create_project(
             dev_script,
             cleanup_script,
             copy_files,
+            merge_strategy,
+            merge_commit_template,
+            commit_message_strategy,
+            commit_message_template,
+            commit_message_validation,
+            commit_message_pattern,
+            commit_author_name,
+            commit_author_email,
+            commit_co_authors,
+            commit_signing_format,
+            commit_signing_key,
+            container_image,
+            container_cpus,
+            container_memory,
+            worktree_retention_hours,
+            worktree_keep_recent,
+            worktree_keep_in_review,
+            worktree_max_size_mb,
+            worktree_snapshot_enabled,
+            dependency_cache,
+            dev_server_health_check,
+            dev_server_restart_policy,
+            dev_server_max_restarts,
+            working_dir,
+            sparse_checkout,
         },
         id,
     )
@@ -184,7 +310,75 @@ pub async fn update_project(
         dev_script,
         cleanup_script,
         copy_files,
+        merge_strategy,
+        merge_commit_template,
+        commit_message_strategy,
+        commit_message_template,
+        commit_message_validation,
+        commit_message_pattern,
+        commit_author_name,
+        commit_author_email,
+        commit_co_authors,
+        commit_signing_format,
+        commit_signing_key,
+        container_image,
+        container_cpus,
+        container_memory,
+        worktree_retention_hours,
+        worktree_keep_recent,
+        worktree_keep_in_review,
+        worktree_max_size_mb,
+        worktree_snapshot_enabled,
+        dependency_cache,
+        dev_server_health_check,
+        dev_server_restart_policy,
+        dev_server_max_restarts,
+        working_dir,
+        sparse_checkout,
     } = payload;
+    if let Err(e) = validate_commit_message_settings(
+        commit_message_validation.unwrap_or(existing_project.commit_message_validation),
+        commit_message_pattern.as_deref(),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_commit_identity(
+        commit_author_email.as_deref(),
+        commit_co_authors.as_deref(),
+        commit_signing_format.unwrap_or(existing_project.commit_signing_format),
+        commit_signing_key.as_deref(),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_execution_container(
+        container_image.as_deref(),
+        container_cpus.as_deref(),
+        container_memory.as_deref(),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_worktree_retention(
+        worktree_retention_hours,
+        worktree_keep_recent,
+        worktree_max_size_mb,
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_dependency_caches(dependency_cache.as_deref()) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_copy_files(copy_files.as_deref()) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) =
+        validate_dev_server_health(dev_server_health_check.as_deref(), dev_server_max_restarts)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    if let Err(e) = validate_project_scope(working_dir.as_deref(), sparse_checkout.as_deref()) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+    let working_dir = working_dir.as_deref().and_then(normalize_working_dir);
     // If git_repo_path is being changed, check if the new path is already used by another project
     let git_repo_path = if let Some(new_git_repo_path) = git_repo_path.map(|s| expand_tilde(&s))
         && new_git_repo_path != existing_project.git_repo_path
@@ -220,6 +414,49 @@ pub async fn update_project(
         dev_script,
         cleanup_script,
         copy_files,
+        dependency_cache,
+        merge_strategy.unwrap_or(existing_project.merge_strategy),
+        merge_commit_template,
+        CommitMessageSettings {
+            strategy: commit_message_strategy.unwrap_or(existing_project.commit_message_strategy),
+            template: commit_message_template,
+            validation: commit_message_validation
+                .unwrap_or(existing_project.commit_message_validation),
+            pattern: commit_message_pattern,
+        },
+        CommitIdentitySettings {
+            author_name: commit_author_name,
+            author_email: commit_author_email,
+            co_authors: commit_co_authors,
+            signing_format: commit_signing_format.unwrap_or(existing_project.commit_signing_format),
+            signing_key: commit_signing_key,
+        },
+        ExecutionContainerSettings {
+            image: container_image,
+            cpus: container_cpus,
+            memory: container_memory,
+        },
+        WorktreeRetentionSettings {
+            retention_hours: worktree_retention_hours
+                .unwrap_or(existing_project.worktree_retention_hours),
+            keep_recent: worktree_keep_recent,
+            keep_in_review: worktree_keep_in_review
+                .unwrap_or(existing_project.worktree_keep_in_review),
+            max_size_mb: worktree_max_size_mb,
+            snapshot: worktree_snapshot_enabled
+                .unwrap_or(existing_project.worktree_snapshot_enabled),
+        },
+        DevServerSettings {
+            health_check: dev_server_health_check,
+            restart_policy: dev_server_restart_policy
+                .unwrap_or(existing_project.dev_server_restart_policy),
+            max_restarts: dev_server_max_restarts
+                .unwrap_or(existing_project.dev_server_max_restarts),
+        },
+        ProjectScopeSettings {
+            working_dir,
+            sparse_checkout,
+        },
     )
     .await
     {
@@ -249,6 +486,15 @@ pub async fn delete_project(
                     )
                     .await;
 
+                let project_id = project.id;
+                if let Ok(Err(e)) = tokio::task::spawn_blocking(move || {
+                    DependencyCache::default().clear_project(project_id)
+                })
+                .await
+                {
+                    tracing::warn!("Failed to clear dependency cache: {}", e);
+                }
+
                 Ok(ResponseJson(ApiResponse::success(())))
             }
         }
@@ -259,6 +505,109 @@ pub async fn delete_project(
     }
 }
 
+pub async fn get_project_repositories(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectRepository>>>, ApiError> {
+    let repositories =
+        ProjectRepository::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(repositories)))
+}
+
+pub async fn create_project_repository(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectRepository>,
+) -> Result<ResponseJson<ApiResponse<ProjectRepository>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let name = payload.name.trim();
+    let existing = ProjectRepository::find_by_project_id(pool, project.id).await?;
+    let taken: Vec<&str> = existing.iter().map(|repo| repo.name.as_str()).collect();
+    if let Err(e) = validate_repository_name(name, &project.git_repo_path, &taken) {
+        return Ok(ResponseJson(ApiResponse::error(&e)));
+    }
+
+    let path = std::path::absolute(expand_tilde(payload.git_repo_path.trim()))?;
+    if !path.is_dir() || !path.join(".git").exists() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The specified directory is not a git repository",
+        )));
+    }
+    if path == project.git_repo_path || existing.iter().any(|repo| repo.git_repo_path == path) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The project already uses this git repository",
+        )));
+    }
+
+    let repository =
+        ProjectRepository::create(pool, project.id, name, &path.to_string_lossy()).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "project_repository_added",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "repository_count": existing.len() + 1,
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(repository)))
+}
+
+/// Remove a repository from the project, along with the worktrees attempts have of it
+pub async fn delete_project_repository(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, repository_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repository = match ProjectRepository::find_by_id(pool, repository_id).await? {
+        Some(repository) if repository.project_id == project.id => repository,
+        _ => return Ok(ResponseJson(ApiResponse::error("Repository not found"))),
+    };
+
+    for attempt_repository in
+        TaskAttemptRepository::find_by_project_repository_id(pool, repository.id).await?
+    {
+        // Snapshots of the repository's worktree could never be restored again
+        if let Err(e) = worktree_snapshot::discard_snapshot(&SnapshotLocation::in_default_dir(
+            attempt_repository.task_attempt_id,
+            repository.id,
+        )) {
+            tracing::warn!(
+                "Failed to discard snapshots of {} for attempt {}: {}",
+                repository.name,
+                attempt_repository.task_attempt_id,
+                e
+            );
+        }
+        let Some(workspace_root) =
+            TaskAttempt::find_by_id(pool, attempt_repository.task_attempt_id)
+                .await?
+                .and_then(|attempt| attempt.container_ref)
+                .and_then(|container_ref| {
+                    std::path::Path::new(&container_ref)
+                        .parent()
+                        .map(std::path::Path::to_path_buf)
+                })
+        else {
+            continue;
+        };
+        let worktree_path = workspace_root.join(&repository.name);
+        if let Err(e) =
+            WorktreeManager::cleanup_worktree(&worktree_path, Some(&repository.git_repo_path)).await
+        {
+            tracing::warn!(
+                "Failed to clean up worktree {}: {}",
+                worktree_path.display(),
+                e
+            );
+        }
+    }
+
+    ProjectRepository::delete(pool, repository.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 #[derive(serde::Deserialize)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
@@ -316,10 +665,11 @@ pub async fn search_project_files(
 
     let repo_path = &project.git_repo_path;
     let file_search_cache = deployment.file_search_cache();
+    let scope = ProjectScope::from_project(&project);
 
     // Try cache first
     match file_search_cache
-        .search(repo_path, query, mode.clone())
+        .search(repo_path, query, mode.clone(), &scope)
         .await
     {
         Ok(results) => {
@@ -339,7 +689,13 @@ pub async fn search_project_files(
                 query,
                 mode
             );
-            match search_files_in_repo(&project.git_repo_path.to_string_lossy(), query, mode).await
+            match search_files_in_repo(
+                &project.git_repo_path.to_string_lossy(),
+                query,
+                mode,
+                &scope,
+            )
+            .await
             {
                 Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
                 Err(e) => {
@@ -351,7 +707,13 @@ pub async fn search_project_files(
         Err(CacheError::BuildError(e)) => {
             tracing::error!("Cache build error for repo {:?}: {}", repo_path, e);
             // Fall back to filesystem search
-            match search_files_in_repo(&project.git_repo_path.to_string_lossy(), query, mode).await
+            match search_files_in_repo(
+                &project.git_repo_path.to_string_lossy(),
+                query,
+                mode,
+                &scope,
+            )
+            .await
             {
                 Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
                 Err(e) => {
@@ -367,8 +729,9 @@ async fn search_files_in_repo(
     repo_path: &str,
     query: &str,
     mode: SearchMode,
+    scope: &ProjectScope,
 ) -> Result<Vec<SearchResult>, Box<dyn std::error::Error + Send + Sync>> {
-    let repo_path = Path::new(repo_path);
+    let repo_path = std::path::Path::new(repo_path);
 
     if !repo_path.exists() {
         return Err("Repository path does not exist".into());
@@ -422,6 +785,9 @@ async fn search_files_in_repo(
         }
 
         let relative_path = path.strip_prefix(repo_path)?;
+        if !scope.contains(&relative_path.to_string_lossy()) {
+            continue;
+        }
         let relative_path_str = relative_path.to_string_lossy().to_lowercase();
 
         let file_name = path
@@ -487,6 +853,7 @@ async fn search_files_in_repo(
 
     // Limit to top 10 results
     results.truncate(10);
+    scope_result_paths(&mut results, mode, scope);
 
     Ok(results)
 }
@@ -499,7 +866,16 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/branches", get(get_project_branches))
         .route("/search", get(search_project_files))
+        .route("/metrics", get(get_project_metrics))
         .route("/open-editor", post(open_project_in_editor))
+        .route(
+            "/repositories",
+            get(get_project_repositories).post(create_project_repository),
+        )
+        .route(
+            "/repositories/{repository_id}",
+            delete(delete_project_repository),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -1,4 +1,6 @@
 pub mod drafts;
+pub mod repositories;
+pub mod review_comments;
 pub mod util;
 
 use axum::{
@@ -9,16 +11,18 @@ use axum::{
     },
     http::StatusCode,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{get, post, put},
 };
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
-    merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
-    project::{Project, ProjectError},
+    merge::{Merge, MergeStatus, MergeStrategy, PrMerge, PullRequestInfo},
+    project::{CommitMessageValidation, Project, ProjectError},
+    review_comment::ReviewComment,
     task::{Task, TaskRelationships, TaskStatus},
     task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+    task_attempt_repository::TaskAttemptRepository,
 };
 use deployment::Deployment;
 use executors::{
@@ -32,9 +36,19 @@ use executors::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    commit_identity::CommitIdentity,
+    commit_message::{
+        CommitMessageContext, merge_commit_message, pull_request_commit_message,
+        validate_commit_message,
+    },
+    conflict_resolution::build_conflict_resolution_prompt,
     container::ContainerService,
+    conversation_export::export_conversation,
+    dev_server::DevServerInfo,
     git::{ConflictOp, WorktreeResetOptions},
     github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+    review_comments::build_review_prompt,
+    stack::{NewBase, restack_children},
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -45,7 +59,10 @@ use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_task_attempt_middleware,
-    routes::task_attempts::util::{ensure_worktree_path, handle_images_for_prompt},
+    routes::{
+        execution_processes::{ExportQuery, export_response},
+        task_attempts::util::{ensure_workspace, ensure_worktree_path, handle_images_for_prompt},
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -126,12 +143,46 @@ pub async fn get_task_attempt(
     Ok(ResponseJson(ApiResponse::success(task_attempt)))
 }
 
+/// The attempt's coding agent conversations, in the order they ran
+pub async fn export_task_attempt_conversation(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let processes: Vec<ExecutionProcess> =
+        ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false)
+            .await?
+            .into_iter()
+            .filter(|process| process.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .collect();
+
+    let export =
+        export_conversation(deployment.container(), &task, &task_attempt, &processes).await;
+    export_response(&export, query.format)
+}
+
+/// Attempts stacked directly on this attempt's branch
+pub async fn get_stacked_attempts(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttempt>>>, ApiError> {
+    let children = TaskAttempt::find_children(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(children)))
+}
+
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
 pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     /// Executor profile specification
     pub executor_profile_id: ExecutorProfileId,
     pub base_branch: String,
+    /// Stack the new attempt on this attempt's branch instead of `base_branch`
+    pub parent_attempt_id: Option<Uuid>,
 }
 
 impl CreateTaskAttemptBody {
@@ -151,6 +202,28 @@ pub async fn create_task_attempt(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    // A stacked attempt branches from (and merges back into) its parent attempt's branch
+    let base_branch = match payload.parent_attempt_id {
+        Some(parent_attempt_id) => {
+            let parent = TaskAttempt::find_by_id(&deployment.db().pool, parent_attempt_id)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!("Parent attempt {parent_attempt_id} not found"))
+                })?;
+            let parent_task = parent
+                .parent_task(&deployment.db().pool)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+            if parent_task.project_id != task.project_id {
+                return Err(ApiError::BadRequest(
+                    "Attempts can only be stacked on attempts from the same project".to_string(),
+                ));
+            }
+            parent.branch
+        }
+        None => payload.base_branch.clone(),
+    };
+
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
@@ -161,8 +234,9 @@ pub async fn create_task_attempt(
         &deployment.db().pool,
         &CreateTaskAttempt {
             executor: executor_profile_id.executor,
-            base_branch: payload.base_branch.clone(),
+            base_branch,
             branch: git_branch_name.clone(),
+            parent_attempt_id: payload.parent_attempt_id,
         },
         attempt_id,
         payload.task_id,
@@ -182,6 +256,7 @@ pub async fn create_task_attempt(
                 "variant": &executor_profile_id.variant,
                 "executor": &executor_profile_id.executor,
                 "attempt_id": task_attempt.id.to_string(),
+                "stacked": task_attempt.parent_attempt_id.is_some(),
             }),
         )
         .await;
@@ -199,6 +274,8 @@ pub struct CreateFollowUpAttempt {
     pub retry_process_id: Option<Uuid>,
     pub force_when_dirty: Option<bool>,
     pub perform_git_reset: Option<bool>,
+    /// Bundle the attempt's open review comments into the prompt
+    pub send_review: Option<bool>,
 }
 
 pub async fn follow_up(
@@ -300,7 +377,20 @@ pub async fn follow_up(
     )
     .await?;
 
-    let mut prompt = payload.prompt;
+    let review_comments = if payload.send_review.unwrap_or(false) {
+        ReviewComment::find_open_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?
+    } else {
+        Vec::new()
+    };
+    let mut prompt = if review_comments.is_empty() {
+        payload.prompt
+    } else {
+        [build_review_prompt(&review_comments), payload.prompt]
+            .into_iter()
+            .filter(|part| !part.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
     if let Some(image_ids) = &payload.image_ids {
         prompt = handle_images_for_prompt(&deployment, &task_attempt, task.id, image_ids, &prompt)
             .await?;
@@ -336,6 +426,12 @@ pub async fn follow_up(
         )
         .await?;
 
+    // The comments are resolved once this execution completes
+    if !review_comments.is_empty() {
+        let ids: Vec<Uuid> = review_comments.iter().map(|comment| comment.id).collect();
+        ReviewComment::mark_sent(&deployment.db().pool, &ids, execution_process.id).await?;
+    }
+
     // Clear drafts post-send:
     // - If this was a retry send, the retry draft has already been cleared above.
     // - Otherwise, clear the follow-up draft to avoid.
@@ -601,12 +697,22 @@ pub async fn compare_commit_to_head(
     })))
 }
 
+#[derive(Debug, Default, Deserialize, Serialize, TS)]
+pub struct MergeTaskAttemptRequest {
+    /// Strategy for this merge; defaults to the project's merge strategy
+    pub strategy: Option<MergeStrategy>,
+    /// Commit message for this merge; defaults to the project's commit message template
+    pub commit_message: Option<String>,
+}
+
 #[axum::debug_handler]
 pub async fn merge_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
+    payload: Option<Json<MergeTaskAttemptRequest>>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
 
     let task = task_attempt
         .parent_task(pool)
@@ -617,36 +723,94 @@ pub async fn merge_task_attempt(
     let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
     let worktree_path = worktree_path_buf.as_path();
 
-    let task_uuid_str = task.id.to_string();
-    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
-
-    // Create commit message with task title and description
-    let mut commit_message = format!("{} (vibe-kanban {})", ctx.task.title, first_uuid_section);
-
-    // Add description on next line if it exists
-    if let Some(description) = &ctx.task.description
-        && !description.trim().is_empty()
+    let strategy = payload.strategy.unwrap_or(ctx.project.merge_strategy);
+    let commit_message = match payload
+        .commit_message
+        .filter(|message| !message.trim().is_empty())
     {
-        commit_message.push_str("\n\n");
-        commit_message.push_str(description);
+        Some(message) => message,
+        None => merge_commit_message(
+            ctx.project.merge_commit_template.as_deref(),
+            &CommitMessageContext {
+                task: &ctx.task,
+                task_attempt: &ctx.task_attempt,
+                summary: None,
+            },
+        ),
+    };
+
+    // Messages that will land on the target branch must satisfy the project's rules
+    if ctx.project.commit_message_validation != CommitMessageValidation::None {
+        let mut messages = Vec::new();
+        if strategy != MergeStrategy::RebaseFastForward {
+            messages.push(commit_message.clone());
+        }
+        if strategy != MergeStrategy::Squash {
+            messages.extend(deployment.git().get_commit_messages(
+                &ctx.project.git_repo_path,
+                &ctx.task_attempt.target_branch,
+                &ctx.task_attempt.branch,
+            )?);
+        }
+        for message in &messages {
+            if let Err(reason) = validate_commit_message(
+                ctx.project.commit_message_validation,
+                ctx.project.commit_message_pattern.as_deref(),
+                message,
+            ) {
+                return Ok(ResponseJson(ApiResponse::error(&reason)));
+            }
+        }
     }
 
-    let merge_commit_id = deployment.git().merge_changes(
-        &ctx.project.git_repo_path,
-        worktree_path,
-        &ctx.task_attempt.branch,
-        &ctx.task_attempt.target_branch,
-        &commit_message,
-    )?;
+    let old_head = deployment
+        .git()
+        .get_branch_oid(&ctx.project.git_repo_path, &ctx.task_attempt.branch)?;
+
+    let merge_commit_id = deployment
+        .git()
+        .with_commit_identity(CommitIdentity::from_project(&ctx.project))
+        .merge_changes(
+            &ctx.project.git_repo_path,
+            worktree_path,
+            &ctx.task_attempt.branch,
+            &ctx.task_attempt.target_branch,
+            &commit_message,
+            strategy,
+        )?;
 
     Merge::create_direct(
         pool,
         task_attempt.id,
         &ctx.task_attempt.target_branch,
         &merge_commit_id,
+        strategy,
     )
     .await?;
-    Task::update_status(pool, ctx.task.id, TaskStatus::Done).await?;
+    // With several repositories the task is done once every one of them is merged
+    let extra_repos = TaskAttemptRepository::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    if extra_repos.iter().all(|repo| repo.merge_commit.is_some()) {
+        Task::update_status(pool, ctx.task.id, TaskStatus::Done).await?;
+    }
+
+    // Attempts stacked on this one now build on the branch it was merged into
+    let github_token = deployment.config().read().await.github.token();
+    if let Err(e) = restack_children(
+        deployment.container(),
+        &ctx.project,
+        &ctx.task_attempt,
+        &old_head,
+        NewBase::branch(&ctx.task_attempt.target_branch),
+        github_token,
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to restack attempts stacked on {}: {}",
+            task_attempt.id,
+            e
+        );
+    }
 
     deployment
         .track_if_analytics_allowed(
@@ -655,6 +819,7 @@ pub async fn merge_task_attempt(
                 "task_id": ctx.task.id.to_string(),
                 "project_id": ctx.project.id.to_string(),
                 "attempt_id": task_attempt.id.to_string(),
+                "merge_strategy": strategy,
             }),
         )
         .await;
@@ -718,8 +883,41 @@ pub async fn create_github_pr(
         .await?
         .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
 
+    // The PR's title and body become the squash commit on the target branch
+    if project.commit_message_validation != CommitMessageValidation::None
+        && let Err(reason) = validate_commit_message(
+            project.commit_message_validation,
+            project.commit_message_pattern.as_deref(),
+            &pull_request_commit_message(&request.title, request.body.as_deref()),
+        )
+    {
+        return Ok(ResponseJson(ApiResponse::error(&reason)));
+    }
+
     let workspace_path = ensure_worktree_path(&deployment, &task_attempt).await?;
 
+    // A stacked attempt's PR targets its parent's branch, which must exist on the remote
+    if let Some(parent_attempt_id) = task_attempt.parent_attempt_id
+        && let Some(parent) = TaskAttempt::find_by_id(pool, parent_attempt_id).await?
+        && parent.branch == target_branch
+    {
+        let parent_path = ensure_worktree_path(&deployment, &parent).await?;
+        if let Err(e) = deployment
+            .git()
+            .push_to_github(&parent_path, &parent.branch, &github_token)
+        {
+            tracing::error!("Failed to push parent branch to GitHub: {}", e);
+            return Ok(ResponseJson(ApiResponse::error(
+                format!(
+                    "Failed to push parent attempt branch '{}' to GitHub: {}",
+                    parent.branch,
+                    GitHubServiceError::from(e)
+                )
+                .as_str(),
+            )));
+        }
+    }
+
     // Push the branch to GitHub first
     if let Err(e) =
         deployment
@@ -828,8 +1026,8 @@ pub async fn open_task_attempt_in_editor(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<Option<OpenEditorRequest>>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    // Get the task attempt to access the worktree path
-    let base_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    // Open the whole workspace when the attempt has worktrees of several repositories
+    let base_path_buf = ensure_workspace(&deployment, &task_attempt).await?.root;
     let base_path = base_path_buf.as_path();
 
     // If a specific file path is provided, use it; otherwise use the base path
@@ -1136,14 +1334,21 @@ pub async fn rebase_task_attempt(
     let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
     let worktree_path = worktree_path_buf.as_path();
 
-    let result = deployment.git().rebase_branch(
-        &ctx.project.git_repo_path,
-        worktree_path,
-        &new_base_branch,
-        &old_base_branch,
-        &task_attempt.branch.clone(),
-        github_config.token(),
-    );
+    let old_head = deployment
+        .git()
+        .get_branch_oid(&ctx.project.git_repo_path, &task_attempt.branch)?;
+
+    let result = deployment
+        .git()
+        .with_commit_identity(CommitIdentity::from_project(&ctx.project))
+        .rebase_branch(
+            &ctx.project.git_repo_path,
+            worktree_path,
+            &new_base_branch,
+            &old_base_branch,
+            &task_attempt.branch.clone(),
+            github_config.token(),
+        );
     if let Err(e) = result {
         use services::services::git::GitServiceError;
         return match e {
@@ -1166,6 +1371,23 @@ pub async fn rebase_task_attempt(
         };
     }
 
+    if let Err(e) = restack_children(
+        deployment.container(),
+        &ctx.project,
+        &task_attempt,
+        &old_head,
+        NewBase::branch(&task_attempt.branch),
+        github_config.token(),
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to restack attempts stacked on {}: {}",
+            task_attempt.id,
+            e
+        );
+    }
+
     deployment
         .track_if_analytics_allowed(
             "task_attempt_rebased",
@@ -1194,6 +1416,94 @@ pub async fn abort_conflicts_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ResolveConflictsRequest {
+    /// Optional variant override for the resolving agent
+    pub variant: Option<String>,
+}
+
+#[axum::debug_handler]
+pub async fn resolve_conflicts_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictsRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_path_buf.as_path();
+
+    let Some(op) = deployment.git().detect_conflict_op(worktree_path)? else {
+        return Err(ApiError::BadRequest(
+            "No rebase, merge, cherry-pick or revert is in progress for this attempt".to_string(),
+        ));
+    };
+    let conflicted_files = deployment.git().get_conflicted_files(worktree_path)?;
+    if conflicted_files.is_empty() {
+        return Err(ApiError::BadRequest(
+            "There are no conflicted files to resolve; continue or abort the operation instead"
+                .to_string(),
+        ));
+    }
+    let hunks = deployment.git().get_conflict_hunks(worktree_path)?;
+    let prompt = build_conflict_resolution_prompt(
+        &op,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+        &conflicted_files,
+        &hunks,
+    );
+
+    let initial_executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, task_attempt.id).await?;
+    let executor_profile_id = ExecutorProfileId {
+        executor: initial_executor_profile_id.executor,
+        variant: payload.variant.or(initial_executor_profile_id.variant),
+    };
+
+    // Resume the agent's session when possible so it keeps the attempt's context.
+    // No cleanup action: the cleanup script must not run while the operation is paused.
+    let action_type =
+        match ExecutionProcess::find_latest_session_id_by_task_attempt(pool, task_attempt.id)
+            .await?
+        {
+            Some(session_id) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    prompt,
+                    session_id,
+                    executor_profile_id,
+                })
+            }
+            None => ExecutorActionType::CodingAgentInitialRequest(
+                executors::actions::coding_agent_initial::CodingAgentInitialRequest {
+                    prompt,
+                    executor_profile_id,
+                },
+            ),
+        };
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &ExecutorAction::new(action_type, None),
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_conflicts_agent_resolution_started",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "conflict_op": op,
+                "conflicted_file_count": conflicted_files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
 #[derive(serde::Deserialize)]
 pub struct DeleteFileQuery {
     file_path: String,
@@ -1205,16 +1515,24 @@ pub async fn delete_task_attempt_file(
     Query(query): Query<DeleteFileQuery>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let container_ref = deployment
-        .container()
-        .ensure_container_exists(&task_attempt)
-        .await?;
-    let worktree_path = std::path::Path::new(&container_ref);
+    // Diff paths of multi-repository workspaces start with the repository's directory
+    let workspace = ensure_workspace(&deployment, &task_attempt).await?;
+    let (repo, file_path) = workspace.resolve_path(&query.file_path);
+
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let project = Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
 
     // Use GitService to delete file and commit
     let _commit_id = deployment
         .git()
-        .delete_file_and_commit(worktree_path, &query.file_path)
+        .with_commit_identity(CommitIdentity::from_project(&project))
+        .delete_file_and_commit(&repo.worktree_path, file_path)
         .map_err(|e| {
             tracing::error!(
                 "Failed to delete file '{}' from task attempt {}: {}",
@@ -1247,7 +1565,7 @@ pub async fn start_dev_server(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
-    // Stop any existing dev servers for this project
+    // Stop this attempt's running dev server; other attempts keep theirs on their own ports
     let existing_dev_servers =
         match ExecutionProcess::find_running_dev_servers_by_project(pool, project.id).await {
             Ok(servers) => servers,
@@ -1263,11 +1581,14 @@ pub async fn start_dev_server(
             }
         };
 
-    for dev_server in existing_dev_servers {
+    for dev_server in existing_dev_servers
+        .into_iter()
+        .filter(|p| p.task_attempt_id == task_attempt.id)
+    {
         tracing::info!(
-            "Stopping existing dev server {} for project {}",
+            "Stopping existing dev server {} for task attempt {}",
             dev_server.id,
-            project.id
+            task_attempt.id
         );
 
         if let Err(e) = deployment
@@ -1280,12 +1601,19 @@ pub async fn start_dev_server(
     }
 
     if let Some(dev_server) = project.dev_script {
+        // A manual start gets a fresh budget of automatic restarts
+        deployment
+            .container()
+            .dev_servers()
+            .reset_restarts(task_attempt.id);
+
         // TODO: Derive script language from system config
         let executor_action = ExecutorAction::new(
             ExecutorActionType::ScriptRequest(ScriptRequest {
                 script: dev_server,
                 language: ScriptRequestLanguage::Bash,
                 context: ScriptContext::DevServer,
+                env: Default::default(),
             }),
             None,
         );
@@ -1318,6 +1646,16 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Port, detected URL and preview path of the attempt's dev server
+pub async fn get_dev_server(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<DevServerInfo>>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(
+        deployment.container().dev_servers().get(task_attempt.id),
+    )))
+}
+
 pub async fn get_task_attempt_children(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -1460,9 +1798,32 @@ pub async fn attach_existing_pr(
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct PinWorktreeRequest {
+    pub pinned: bool,
+}
+
+/// Pin or unpin an attempt's worktree. Pinned worktrees are never removed by retention
+/// cleanup.
+#[axum::debug_handler]
+pub async fn pin_task_attempt_worktree(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PinWorktreeRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskAttempt::set_worktree_pinned(pool, task_attempt.id, payload.pinned).await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
+        .route("/stacked-attempts", get(get_stacked_attempts))
+        .route("/export", get(export_task_attempt_conversation))
         .route("/follow-up", post(follow_up))
         .route(
             "/draft",
@@ -1475,12 +1836,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/commit-info", get(get_commit_info))
         .route("/commit-compare", get(compare_commit_to_head))
         .route("/start-dev-server", post(start_dev_server))
+        .route("/dev-server", get(get_dev_server))
         .route("/branch-status", get(get_task_attempt_branch_status))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/conflicts/resolve", post(resolve_conflicts_task_attempt))
         .route("/pr", post(create_github_pr))
         .route("/pr/attach", post(attach_existing_pr))
         .route("/open-editor", post(open_task_attempt_in_editor))
@@ -1488,6 +1851,40 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
+        .route("/pin-worktree", post(pin_task_attempt_worktree))
+        .route(
+            "/review-comments",
+            get(review_comments::get_review_comments).post(review_comments::create_review_comment),
+        )
+        .route(
+            "/review-comments/reanchor",
+            post(review_comments::reanchor_review_comments),
+        )
+        .route(
+            "/review-comments/prompt",
+            get(review_comments::get_review_prompt),
+        )
+        .route(
+            "/review-comments/{comment_id}",
+            put(review_comments::update_review_comment)
+                .delete(review_comments::delete_review_comment),
+        )
+        .route(
+            "/repositories",
+            get(repositories::get_task_attempt_repositories),
+        )
+        .route(
+            "/repositories/{repository_id}/merge",
+            post(repositories::merge_task_attempt_repository),
+        )
+        .route(
+            "/repositories/{repository_id}/push",
+            post(repositories::push_task_attempt_repository),
+        )
+        .route(
+            "/repositories/{repository_id}/pr",
+            post(repositories::create_task_attempt_repository_pr),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_task_attempt_middleware,
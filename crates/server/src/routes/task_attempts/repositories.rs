@@ -0,0 +1,332 @@
+//! Git operations on the extra repositories of attempts whose project owns several. The
+//! project's own repository goes through the attempt's merge, push and PR routes.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    merge::Merge,
+    project::{CommitMessageValidation, Project, ProjectError},
+    task::{Task, TaskStatus},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+    task_attempt_repository::TaskAttemptRepository,
+};
+use deployment::Deployment;
+use git2::BranchType;
+use serde::Serialize;
+use services::services::{
+    commit_identity::CommitIdentity,
+    commit_message::{
+        CommitMessageContext, merge_commit_message, pull_request_commit_message,
+        validate_commit_message,
+    },
+    github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+    workspace::WorkspaceRepo,
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{
+        CreateGitHubPrRequest, MergeTaskAttemptRequest, util::ensure_workspace,
+    },
+};
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskAttemptRepositoryStatus {
+    pub repository: TaskAttemptRepository,
+    /// Path of the repository's worktree
+    pub worktree_path: String,
+    pub commits_ahead: Option<usize>,
+    pub commits_behind: Option<usize>,
+    pub has_uncommitted_changes: Option<bool>,
+}
+
+async fn load_project(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<(Task, Project), ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let project = Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    Ok((task, project))
+}
+
+/// The attempt's worktree of a project repository, once its worktrees exist
+async fn find_repository(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    repository_id: Uuid,
+) -> Result<Option<(WorkspaceRepo, TaskAttemptRepository)>, ApiError> {
+    let workspace = ensure_workspace(deployment, task_attempt).await?;
+    Ok(workspace.extra_repos().iter().find_map(|repo| {
+        repo.attempt_repository
+            .as_ref()
+            .filter(|row| row.project_repository_id == repository_id)
+            .map(|row| (repo.clone(), row.clone()))
+    }))
+}
+
+pub async fn get_task_attempt_repositories(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttemptRepositoryStatus>>>, ApiError> {
+    if task_attempt.container_ref.is_none() {
+        return Ok(ResponseJson(ApiResponse::success(Vec::new())));
+    }
+    let workspace = ensure_workspace(&deployment, &task_attempt).await?;
+    let statuses = workspace
+        .extra_repos()
+        .iter()
+        .filter_map(|repo| {
+            let repository = repo.attempt_repository.clone()?;
+            let (commits_ahead, commits_behind) = match deployment.git().get_branch_status(
+                &repo.repo_path,
+                &task_attempt.branch,
+                &repo.target_branch,
+            ) {
+                Ok((ahead, behind)) => (Some(ahead), Some(behind)),
+                Err(_) => (None, None),
+            };
+            Some(TaskAttemptRepositoryStatus {
+                repository,
+                worktree_path: repo.worktree_path.to_string_lossy().to_string(),
+                commits_ahead,
+                commits_behind,
+                has_uncommitted_changes: deployment
+                    .git()
+                    .is_worktree_clean(&repo.worktree_path)
+                    .ok()
+                    .map(|is_clean| !is_clean),
+            })
+        })
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(statuses)))
+}
+
+pub async fn merge_task_attempt_repository(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, repository_id)): Path<(Uuid, Uuid)>,
+    payload: Option<Json<MergeTaskAttemptRequest>>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+    let (task, project) = load_project(&deployment, &task_attempt).await?;
+    let Some((repo, row)) = find_repository(&deployment, &task_attempt, repository_id).await?
+    else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The attempt has no worktree of this repository",
+        )));
+    };
+
+    let strategy = payload.strategy.unwrap_or(project.merge_strategy);
+    let commit_message = match payload
+        .commit_message
+        .filter(|message| !message.trim().is_empty())
+    {
+        Some(message) => message,
+        None => merge_commit_message(
+            project.merge_commit_template.as_deref(),
+            &CommitMessageContext {
+                task: &task,
+                task_attempt: &task_attempt,
+                summary: None,
+            },
+        ),
+    };
+    if project.commit_message_validation != CommitMessageValidation::None
+        && let Err(reason) = validate_commit_message(
+            project.commit_message_validation,
+            project.commit_message_pattern.as_deref(),
+            &commit_message,
+        )
+    {
+        return Ok(ResponseJson(ApiResponse::error(&reason)));
+    }
+
+    let merge_commit_id = deployment
+        .git()
+        .with_commit_identity(CommitIdentity::from_project(&project))
+        .merge_changes(
+            &repo.repo_path,
+            &repo.worktree_path,
+            &task_attempt.branch,
+            &repo.target_branch,
+            &commit_message,
+            strategy,
+        )?;
+    TaskAttemptRepository::update_merge_commit(pool, row.id, &merge_commit_id).await?;
+
+    // The task is done once the project's own repository and every other one are merged
+    let primary_merged = Merge::find_latest_by_task_attempt_id(pool, task_attempt.id)
+        .await?
+        .and_then(|merge| merge.merge_commit())
+        .is_some();
+    let all_merged = TaskAttemptRepository::find_by_task_attempt_id(pool, task_attempt.id)
+        .await?
+        .iter()
+        .all(|repo| repo.merge_commit.is_some());
+    if primary_merged && all_merged {
+        Task::update_status(pool, task.id, TaskStatus::Done).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_repository_merged",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "merge_strategy": strategy,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn push_task_attempt_repository(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, repository_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let github_config = deployment.config().read().await.github.clone();
+    let Some(github_token) = github_config.token() else {
+        return Err(GitHubServiceError::TokenInvalid.into());
+    };
+    let github_service = GitHubService::new(&github_token)?;
+    github_service.check_token().await?;
+
+    let Some((repo, _)) = find_repository(&deployment, &task_attempt, repository_id).await? else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The attempt has no worktree of this repository",
+        )));
+    };
+    deployment
+        .git()
+        .push_to_github(&repo.worktree_path, &task_attempt.branch, &github_token)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn create_task_attempt_repository_pr(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, repository_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<CreateGitHubPrRequest>,
+) -> Result<ResponseJson<ApiResponse<String, GitHubServiceError>>, ApiError> {
+    let github_config = deployment.config().read().await.github.clone();
+    let Some(github_token) = github_config.token() else {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitHubServiceError::TokenInvalid,
+        )));
+    };
+    let github_service = GitHubService::new(&github_token)?;
+
+    let Some((repo, row)) = find_repository(&deployment, &task_attempt, repository_id).await?
+    else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The attempt has no worktree of this repository",
+        )));
+    };
+    let target_branch = request
+        .target_branch
+        .filter(|branch| !branch.trim().is_empty())
+        .unwrap_or_else(|| repo.target_branch.clone());
+
+    let (_, project) = load_project(&deployment, &task_attempt).await?;
+    if project.commit_message_validation != CommitMessageValidation::None
+        && let Err(reason) = validate_commit_message(
+            project.commit_message_validation,
+            project.commit_message_pattern.as_deref(),
+            &pull_request_commit_message(&request.title, request.body.as_deref()),
+        )
+    {
+        return Ok(ResponseJson(ApiResponse::error(&reason)));
+    }
+
+    if let Err(e) =
+        deployment
+            .git()
+            .push_to_github(&repo.worktree_path, &task_attempt.branch, &github_token)
+    {
+        tracing::error!("Failed to push {} branch to GitHub: {}", repo.name, e);
+        let gh_e = GitHubServiceError::from(e);
+        if gh_e.is_api_data() {
+            return Ok(ResponseJson(ApiResponse::error_with_data(gh_e)));
+        } else {
+            return Ok(ResponseJson(ApiResponse::error(
+                format!("Failed to push branch to GitHub: {}", gh_e).as_str(),
+            )));
+        }
+    }
+
+    // Remote branches are formatted as {remote}/{branch} locally; PR APIs want the branch name
+    let base_branch = if matches!(
+        deployment
+            .git()
+            .find_branch_type(&repo.repo_path, &target_branch)?,
+        BranchType::Remote
+    ) {
+        let remote = deployment
+            .git()
+            .get_remote_name_from_branch_name(&repo.worktree_path, &target_branch)?;
+        target_branch
+            .strip_prefix(&format!("{remote}/"))
+            .unwrap_or(&target_branch)
+            .to_string()
+    } else {
+        target_branch
+    };
+    let pr_request = CreatePrRequest {
+        title: request.title.clone(),
+        body: request.body.clone(),
+        head_branch: task_attempt.branch.clone(),
+        base_branch,
+    };
+    let repo_info = deployment.git().get_github_repo_info(&repo.repo_path)?;
+
+    match github_service.create_pr(&repo_info, &pr_request).await {
+        Ok(pr_info) => {
+            if let Err(e) = TaskAttemptRepository::update_pr(
+                &deployment.db().pool,
+                row.id,
+                pr_info.number,
+                &pr_info.url,
+            )
+            .await
+            {
+                tracing::error!("Failed to record PR of {}: {}", repo.name, e);
+            }
+            if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
+                tracing::warn!("Failed to open PR in browser: {}", e);
+            }
+            Ok(ResponseJson(ApiResponse::success(pr_info.url)))
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to create GitHub PR for {} of attempt {}: {}",
+                repo.name,
+                task_attempt.id,
+                e
+            );
+            if e.is_api_data() {
+                Ok(ResponseJson(ApiResponse::error_with_data(e)))
+            } else {
+                Ok(ResponseJson(ApiResponse::error(
+                    format!("Failed to create PR: {}", e).as_str(),
+                )))
+            }
+        }
+    }
+}
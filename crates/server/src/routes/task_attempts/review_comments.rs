@@ -0,0 +1,173 @@
+//! Review comments on an attempt's diff. Listing only reads them; `reanchor` moves
+//! unresolved comments to where their lines are in the current worktree. Open comments are
+//! sent to the agent with a follow-up's `send_review`, as rendered by `prompt`.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::ProjectError,
+    review_comment::{CreateReviewComment, ReviewComment, UpdateReviewComment},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use services::services::{
+    review_comments::{build_review_prompt, reanchor_comments},
+    workspace::AttemptWorkspace,
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The attempt's workspace, without creating worktrees that don't exist
+async fn load_workspace(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<Option<AttemptWorkspace>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?
+        .parent_project(pool)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    Ok(AttemptWorkspace::load(pool, &project, task_attempt)
+        .await?
+        .filter(|workspace| workspace.root.exists()))
+}
+
+async fn find_comment(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    comment_id: Uuid,
+) -> Result<ReviewComment, ApiError> {
+    ReviewComment::find_by_id(&deployment.db().pool, comment_id)
+        .await?
+        .filter(|comment| comment.task_attempt_id == task_attempt.id)
+        .ok_or(ApiError::BadRequest("Review comment not found".to_string()))
+}
+
+pub async fn get_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewComment>>>, ApiError> {
+    let comments =
+        ReviewComment::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+/// The review prompt the next follow-up with `send_review` would send; empty without open
+/// comments
+pub async fn get_review_prompt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let comments =
+        ReviewComment::find_open_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    let prompt = if comments.is_empty() {
+        String::new()
+    } else {
+        build_review_prompt(&comments)
+    };
+    Ok(ResponseJson(ApiResponse::success(prompt)))
+}
+
+/// Settle comments sent to finished runs and re-anchor the unresolved ones to the current
+/// worktree, returning every comment
+pub async fn reanchor_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewComment>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ReviewComment::settle_sent(pool, task_attempt.id).await?;
+    let mut comments = ReviewComment::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    if let Some(workspace) = load_workspace(&deployment, &task_attempt).await? {
+        reanchor_comments(
+            pool,
+            deployment.git(),
+            &workspace,
+            &task_attempt.branch,
+            &mut comments,
+        )
+        .await?;
+    }
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+pub async fn create_review_comment(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    if payload.body.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Review comments can't be empty",
+        )));
+    }
+    if payload.file_path.is_empty() || payload.line_start < 1 {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Review comments need a file and a line",
+        )));
+    }
+    if payload
+        .line_end
+        .is_some_and(|line_end| line_end < payload.line_start)
+    {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Review comments can't end before they start",
+        )));
+    }
+
+    let commit_sha = load_workspace(&deployment, &task_attempt)
+        .await?
+        .and_then(|workspace| {
+            let (repo, _) = workspace.resolve_path(&payload.file_path);
+            deployment.git().get_head_info(&repo.worktree_path).ok()
+        })
+        .map(|head| head.oid);
+    let comment = ReviewComment::create(
+        &deployment.db().pool,
+        task_attempt.id,
+        &payload,
+        commit_sha.as_deref(),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn update_review_comment(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let comment = find_comment(&deployment, &task_attempt, comment_id).await?;
+    if let Some(body) = &payload.body {
+        if body.trim().is_empty() {
+            return Ok(ResponseJson(ApiResponse::error(
+                "Review comments can't be empty",
+            )));
+        }
+        ReviewComment::update_body(pool, comment.id, body).await?;
+    }
+    if let Some(resolved) = payload.resolved {
+        ReviewComment::set_resolved(pool, comment.id, resolved).await?;
+    }
+    let comment = find_comment(&deployment, &task_attempt, comment_id).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn delete_review_comment(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let comment = find_comment(&deployment, &task_attempt, comment_id).await?;
+    ReviewComment::delete(&deployment.db().pool, comment.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
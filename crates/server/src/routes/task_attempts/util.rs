@@ -1,6 +1,11 @@
-use db::models::image::TaskImage;
+use db::models::{
+    image::TaskImage, project::ProjectError, task_attempt::TaskAttemptError,
+    task_attempt_repository::TaskAttemptRepository,
+};
 use deployment::Deployment;
-use services::services::{container::ContainerService, image::ImageService};
+use services::services::{
+    container::ContainerService, image::ImageService, workspace::AttemptWorkspace,
+};
 use uuid::Uuid;
 
 use crate::error::ApiError;
@@ -17,6 +22,29 @@ pub async fn ensure_worktree_path(
     Ok(std::path::PathBuf::from(container_ref))
 }
 
+/// Ensure every worktree of the attempt exists and describe its workspace.
+pub async fn ensure_workspace(
+    deployment: &crate::DeploymentImpl,
+    attempt: &db::models::task_attempt::TaskAttempt,
+) -> Result<AttemptWorkspace, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?
+        .parent_project(pool)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    let worktree_path = ensure_worktree_path(deployment, attempt).await?;
+    let extra_repos = TaskAttemptRepository::find_by_task_attempt_id(pool, attempt.id).await?;
+    Ok(AttemptWorkspace::new(
+        &project,
+        attempt,
+        &worktree_path,
+        extra_repos,
+    ))
+}
+
 /// Associate images to the task, copy into worktree, and canonicalize paths in the prompt.
 /// Returns the transformed prompt.
 pub async fn handle_images_for_prompt(
@@ -16,6 +16,7 @@ use db::models::{
     image::TaskImage,
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{CreateTaskAttempt, TaskAttempt},
+    task_attempt_repository::TaskAttemptRepository,
     task_template::TaskTemplate,
 };
 use db::models::executor_session::ExecutorSession;
@@ -24,8 +25,9 @@ use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use services::services::container::{
-    ContainerService, WorktreeCleanupData, cleanup_worktrees_direct,
+use services::services::{
+    container::{ContainerService, WorktreeCleanupData, cleanup_worktrees_direct},
+    task_activity::{TaskActivity, task_timeline},
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -196,6 +198,58 @@ pub async fn get_task_attempts_with_notes(
     Ok(ResponseJson(ApiResponse::success(out)))
 }
 
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskActivity>>>, ApiError> {
+    let activity = task_timeline(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(activity)))
+}
+
+pub async fn stream_task_activity_ws(
+    ws: WebSocketUpgrade,
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_task_activity_ws(socket, deployment, task.id).await {
+            tracing::warn!("task activity WS closed: {}", e);
+        }
+    })
+}
+
+async fn handle_task_activity_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    task_id: Uuid,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_task_activity_raw(task_id)
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break; // client disconnected
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTask>,
@@ -270,6 +324,7 @@ pub async fn create_task_and_start(
             executor: payload.executor_profile_id.executor,
             base_branch: payload.base_branch,
             branch: git_branch_name,
+            parent_attempt_id: None,
         },
         attempt_id,
         task.id,
@@ -381,19 +436,34 @@ pub async fn delete_task(
         .await?
         .ok_or_else(|| ApiError::Database(SqlxError::RowNotFound))?;
 
-    let cleanup_data: Vec<WorktreeCleanupData> = attempts
-        .iter()
-        .filter_map(|attempt| {
-            attempt
-                .container_ref
-                .as_ref()
-                .map(|worktree_path| WorktreeCleanupData {
-                    attempt_id: attempt.id,
-                    worktree_path: PathBuf::from(worktree_path),
-                    git_repo_path: Some(project.git_repo_path.clone()),
-                })
-        })
-        .collect();
+    let mut cleanup_data: Vec<WorktreeCleanupData> = Vec::new();
+    for attempt in &attempts {
+        let Some(worktree_path) = attempt.container_ref.as_ref().map(PathBuf::from) else {
+            continue;
+        };
+        // Attempts of multi-repository projects have a worktree of each repository
+        let extra_repos =
+            TaskAttemptRepository::find_by_task_attempt_id(&deployment.db().pool, attempt.id)
+                .await?;
+        let workspace_root = worktree_path
+            .parent()
+            .filter(|_| !extra_repos.is_empty())
+            .map(PathBuf::from);
+        if let Some(root) = &workspace_root {
+            cleanup_data.extend(extra_repos.into_iter().map(|repo| WorktreeCleanupData {
+                attempt_id: attempt.id,
+                worktree_path: root.join(&repo.name),
+                git_repo_path: Some(repo.git_repo_path),
+                workspace_root: Some(root.clone()),
+            }));
+        }
+        cleanup_data.push(WorktreeCleanupData {
+            attempt_id: attempt.id,
+            worktree_path,
+            git_repo_path: Some(project.git_repo_path.clone()),
+            workspace_root,
+        });
+    }
 
     // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
     let mut tx = deployment.db().pool.begin().await?;
@@ -469,6 +539,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_id_router = Router::new()
         .route("/", get(get_task).put(update_task).delete(delete_task))
         .route("/attempts-with-notes", get(get_task_attempts_with_notes))
+        .route("/activity", get(get_task_activity))
+        .route("/activity/stream/ws", get(stream_task_activity_ws))
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
     let inner = Router::new()
@@ -0,0 +1,26 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::transcript_entry::{
+    TranscriptEntry, TranscriptSearchQuery, TranscriptSearchResult,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Search indexed agent transcripts across all projects
+pub async fn search_transcripts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TranscriptSearchQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TranscriptSearchResult>>>, ApiError> {
+    let results = TranscriptEntry::search(&deployment.db().pool, &query).await?;
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/transcripts/search", get(search_transcripts))
+}
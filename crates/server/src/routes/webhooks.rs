@@ -0,0 +1,151 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::webhook::{CreateWebhook, CreatedWebhook, UpdateWebhook, Webhook, WebhookDelivery};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_webhook_middleware};
+
+/// Deliveries listed in a webhook's log
+const DELIVERY_LOG_LIMIT: i64 = 50;
+
+fn validate_url(url: &str) -> Result<(), String> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        Ok(_) => Err("Webhook URLs must use http or https".to_string()),
+        Err(e) => Err(format!("Invalid webhook URL: {e}")),
+    }
+}
+
+pub async fn get_webhooks(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Webhook>>>, ApiError> {
+    let webhooks = Webhook::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(webhooks)))
+}
+
+pub async fn get_webhook(
+    Extension(webhook): Extension<Webhook>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+pub async fn create_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateWebhook>,
+) -> Result<ResponseJson<ApiResponse<CreatedWebhook>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::error("Webhooks need a name")));
+    }
+    if let Err(message) = validate_url(&payload.url) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
+    payload.secret = match payload.secret {
+        Some(secret) if secret.is_empty() => None,
+        Some(secret) => Some(secret),
+        None => Some(format!(
+            "{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        )),
+    };
+    let webhook = Webhook::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "webhook_created",
+            serde_json::json!({
+                "webhook_id": webhook.id.to_string(),
+                "event_count": webhook.events.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedWebhook {
+        secret: webhook.secret.clone(),
+        webhook,
+    })))
+}
+
+pub async fn update_webhook(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateWebhook>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    if payload
+        .name
+        .as_ref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Ok(ResponseJson(ApiResponse::error("Webhooks need a name")));
+    }
+    if let Some(url) = &payload.url
+        && let Err(message) = validate_url(url)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
+    let updated = Webhook::update(&deployment.db().pool, webhook.id, &payload).await?;
+    if webhook.enabled && !updated.enabled {
+        deployment.webhooks().fail_pending(updated.id).await?;
+    }
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_webhook(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Webhook::delete(&deployment.db().pool, webhook.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn get_webhook_deliveries(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WebhookDelivery>>>, ApiError> {
+    let deliveries =
+        WebhookDelivery::find_by_webhook_id(&deployment.db().pool, webhook.id, DELIVERY_LOG_LIMIT)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(deliveries)))
+}
+
+/// Send a ping event to the webhook and return the logged delivery
+pub async fn test_webhook(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<WebhookDelivery>>, ApiError> {
+    let delivery = deployment.webhooks().ping(&webhook).await?;
+    Ok(ResponseJson(ApiResponse::success(delivery)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let webhook_router = Router::new()
+        .route(
+            "/",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route("/deliveries", get(get_webhook_deliveries))
+        .route("/test", post(test_webhook))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_webhook_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_webhooks).post(create_webhook))
+        .nest("/{webhook_id}", webhook_router);
+
+    Router::new().nest("/webhooks", inner)
+}
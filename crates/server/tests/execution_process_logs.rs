@@ -0,0 +1,173 @@
+use std::str::FromStr;
+
+use db::models::execution_process_logs::{
+    ExecutionProcessLogs, LOG_CHUNK_MAX_BYTES, LOG_CHUNK_MAX_ENTRIES,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use sqlx::{SqlitePool, sqlite::SqliteConnectOptions};
+use tempfile::TempDir;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+/// Migrated database without foreign keys, so logs can be stored without a process
+async fn create_log_db() -> (SqlitePool, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let database_url = format!("sqlite://{}", temp_dir.path().join("logs.db").display());
+    let options = SqliteConnectOptions::from_str(&database_url)
+        .unwrap()
+        .create_if_missing(true)
+        .foreign_keys(false);
+    let pool = SqlitePool::connect_with(options).await.unwrap();
+    sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+    (pool, temp_dir)
+}
+
+fn line(i: usize) -> String {
+    serde_json::to_string(&LogMsg::Stdout(format!("line {i}"))).unwrap()
+}
+
+fn stdout(msg: &LogMsg) -> &str {
+    match msg {
+        LogMsg::Stdout(content) => content,
+        other => panic!("expected stdout, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn chunks_stream_in_order_from_any_entry() {
+    let (pool, _temp_dir) = create_log_db().await;
+    let execution_id = Uuid::new_v4();
+
+    let lines: Vec<String> = (0..10).map(line).collect();
+    for chunk in lines.chunks(4) {
+        ExecutionProcessLogs::append_chunk(&pool, execution_id, chunk)
+            .await
+            .unwrap();
+    }
+    ExecutionProcessLogs::append_entry(&pool, execution_id, &LogMsg::Stderr("done".into()))
+        .await
+        .unwrap();
+
+    assert!(
+        ExecutionProcessLogs::exists(&pool, execution_id)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !ExecutionProcessLogs::exists(&pool, Uuid::new_v4())
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        ExecutionProcessLogs::entry_count(&pool, execution_id)
+            .await
+            .unwrap(),
+        11
+    );
+
+    let all: Vec<LogMsg> = ExecutionProcessLogs::stream_entries(pool.clone(), execution_id, 0)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 11);
+    for (i, msg) in all.iter().take(10).enumerate() {
+        assert_eq!(stdout(msg), format!("line {i}"));
+    }
+    assert!(matches!(&all[10], LogMsg::Stderr(content) if content == "done"));
+
+    // Starting mid-chunk continues across chunk boundaries
+    let tail: Vec<LogMsg> = ExecutionProcessLogs::stream_entries(pool.clone(), execution_id, 6)
+        .take(3)
+        .try_collect()
+        .await
+        .unwrap();
+    let tail: Vec<&str> = tail.iter().map(stdout).collect();
+    assert_eq!(tail, ["line 6", "line 7", "line 8"]);
+
+    let entry = ExecutionProcessLogs::find_entry(&pool, execution_id, 5)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stdout(&entry), "line 5");
+    assert!(
+        ExecutionProcessLogs::find_entry(&pool, execution_id, 11)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn legacy_logs_are_compressed_and_split() {
+    let (pool, _temp_dir) = create_log_db().await;
+    let execution_id = Uuid::new_v4();
+
+    // A row as the migration carries it over from the single-row table
+    let count = LOG_CHUNK_MAX_ENTRIES * 2 + 10;
+    let jsonl: String = (0..count).map(|i| format!("{}\n", line(i))).collect();
+    sqlx::query(
+        "INSERT INTO execution_process_log_chunks
+             (execution_id, chunk_index, first_entry, entry_count, byte_size, compression, data)
+         VALUES ($1, 0, 0, $2, $3, 'none', $4)",
+    )
+    .bind(execution_id)
+    .bind(count as i64)
+    .bind(jsonl.len() as i64)
+    .bind(jsonl.as_bytes())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(
+        ExecutionProcessLogs::compress_legacy_chunks(&pool)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        ExecutionProcessLogs::compress_legacy_chunks(&pool)
+            .await
+            .unwrap(),
+        0
+    );
+
+    let (chunks, uncompressed): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), SUM(compression = 'none') FROM execution_process_log_chunks WHERE execution_id = $1",
+    )
+    .bind(execution_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(chunks, 3);
+    assert_eq!(uncompressed, 0);
+
+    let entry = ExecutionProcessLogs::find_entry(&pool, execution_id, count - 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stdout(&entry), format!("line {}", count - 1));
+    assert_eq!(
+        ExecutionProcessLogs::stream_entries(pool.clone(), execution_id, 0)
+            .count()
+            .await,
+        count
+    );
+}
+
+#[test]
+fn lines_split_by_count_and_size() {
+    let lines: Vec<String> = (0..LOG_CHUNK_MAX_ENTRIES + 1).map(line).collect();
+    let groups = ExecutionProcessLogs::split_lines(&lines);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].len(), LOG_CHUNK_MAX_ENTRIES);
+    assert_eq!(groups[1].len(), 1);
+
+    let big = "x".repeat(LOG_CHUNK_MAX_BYTES / 2);
+    let lines = vec![big.clone(), big.clone(), big];
+    assert_eq!(ExecutionProcessLogs::split_lines(&lines).len(), 3);
+
+    // A single oversized entry still gets a chunk of its own
+    let lines = vec!["y".repeat(LOG_CHUNK_MAX_BYTES * 2)];
+    assert_eq!(ExecutionProcessLogs::split_lines(&lines).len(), 1);
+    assert!(ExecutionProcessLogs::split_lines(&[]).is_empty());
+}
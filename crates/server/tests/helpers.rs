@@ -63,6 +63,31 @@ pub async fn create_test_project(pool: &SqlitePool) -> Project {
             dev_script: None,
             cleanup_script: None,
             copy_files: None,
+            merge_strategy: None,
+            merge_commit_template: None,
+            commit_message_strategy: None,
+            commit_message_template: None,
+            commit_message_validation: None,
+            commit_message_pattern: None,
+            commit_author_name: None,
+            commit_author_email: None,
+            commit_co_authors: None,
+            commit_signing_format: None,
+            commit_signing_key: None,
+            container_image: None,
+            container_cpus: None,
+            container_memory: None,
+            worktree_retention_hours: None,
+            worktree_keep_recent: None,
+            worktree_keep_in_review: None,
+            worktree_max_size_mb: None,
+            worktree_snapshot_enabled: None,
+            dependency_cache: None,
+            dev_server_health_check: None,
+            dev_server_restart_policy: None,
+            dev_server_max_restarts: None,
+            working_dir: None,
+            sparse_checkout: None,
         },
         Uuid::new_v4(),
     )
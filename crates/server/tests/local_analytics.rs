@@ -0,0 +1,202 @@
+mod helpers;
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use db::{
+    DBService,
+    models::analytics_event::{AnalyticsEvent, WeeklyMerges},
+};
+use serde_json::json;
+use services::services::{
+    config::Config,
+    local_analytics::{
+        AnalyticsReportQuery, ExecutorUsage, LocalAnalyticsError, LocalAnalyticsSink,
+        analytics_report,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+
+use crate::helpers::*;
+
+async fn insert_event(
+    pool: &SqlitePool,
+    event_name: &str,
+    properties: serde_json::Value,
+    at: &str,
+) {
+    sqlx::query(
+        "INSERT INTO analytics_events (event_name, properties, created_at) VALUES ($1, $2, $3)",
+    )
+    .bind(event_name)
+    .bind(properties.to_string())
+    .bind(at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn events_are_only_stored_when_enabled() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let config = Arc::new(RwLock::new(Config::default()));
+    let sink = LocalAnalyticsSink::new(DBService { pool: pool.clone() }, config.clone());
+    let query = AnalyticsReportQuery::default();
+
+    sink.track_event("task_created", &json!({})).await;
+    assert_eq!(analytics_report(&pool, &query).await.unwrap().events, 0);
+
+    config.write().await.local_analytics_enabled = true;
+    sink.track_event("task_created", &json!({})).await;
+    sink.track_event("$identify", &json!({ "username": "someone" }))
+        .await;
+    assert_eq!(analytics_report(&pool, &query).await.unwrap().events, 1);
+
+    assert_eq!(AnalyticsEvent::delete_all(&pool).await.unwrap(), 1);
+    assert_eq!(analytics_report(&pool, &query).await.unwrap().events, 0);
+}
+
+#[tokio::test]
+async fn report_aggregates_usage_per_executor_and_week() {
+    let (pool, _temp_dir) = create_test_db().await;
+
+    let claude = json!({ "executor": "CLAUDE_CODE", "variant": null });
+    insert_event(
+        &pool,
+        "task_attempt_started",
+        claude.clone(),
+        "2025-11-03 09:00:00.000",
+    )
+    .await;
+    insert_event(
+        &pool,
+        "task_attempt_started",
+        claude,
+        "2025-11-04 09:00:00.000",
+    )
+    .await;
+    insert_event(
+        &pool,
+        "task_attempt_started",
+        json!({ "executor": "AMP", "variant": "PLAN" }),
+        "2025-11-04 09:00:00.000",
+    )
+    .await;
+    insert_event(
+        &pool,
+        "task_attempt_finished",
+        json!({
+            "executor": "CLAUDE_CODE",
+            "variant": null,
+            "execution_process_id": "run-1",
+            "execution_success": true,
+            "runtime_seconds": 60.0,
+        }),
+        "2025-11-03 09:01:00.000",
+    )
+    .await;
+    insert_event(
+        &pool,
+        "task_attempt_finished",
+        json!({
+            "executor": "CLAUDE_CODE",
+            "variant": null,
+            "execution_process_id": "run-2",
+            "execution_success": false,
+            "runtime_seconds": 180.0,
+        }),
+        "2025-11-04 09:03:00.000",
+    )
+    .await;
+    for _ in 0..3 {
+        insert_event(
+            &pool,
+            "approval_responded",
+            json!({ "execution_process_id": "run-1" }),
+            "2025-11-03 09:00:30.000",
+        )
+        .await;
+    }
+    insert_event(
+        &pool,
+        "task_attempt_merged",
+        json!({}),
+        "2025-11-05 12:00:00.000",
+    )
+    .await;
+    // Sunday still belongs to the week starting on Monday the 3rd
+    insert_event(&pool, "pr_merged", json!({}), "2025-11-09 12:00:00.000").await;
+    insert_event(
+        &pool,
+        "task_attempt_repository_merged",
+        json!({}),
+        "2025-11-10 12:00:00.000",
+    )
+    .await;
+    // Outside the window
+    insert_event(
+        &pool,
+        "task_attempt_merged",
+        json!({}),
+        "2025-10-01 12:00:00.000",
+    )
+    .await;
+
+    let query = AnalyticsReportQuery {
+        from: Some(Utc.with_ymd_and_hms(2025, 11, 1, 0, 0, 0).unwrap()),
+        to: Some(Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap()),
+    };
+    let report = analytics_report(&pool, &query).await.unwrap();
+
+    assert_eq!(report.events, 11);
+    assert_eq!(
+        report.executors,
+        vec![
+            ExecutorUsage {
+                executor: Some("AMP".to_string()),
+                variant: Some("PLAN".to_string()),
+                attempts: 1,
+                runs: 0,
+                failed_runs: 0,
+                failure_rate: None,
+                average_runtime_seconds: None,
+                approvals_per_run: None,
+            },
+            ExecutorUsage {
+                executor: Some("CLAUDE_CODE".to_string()),
+                variant: None,
+                attempts: 2,
+                runs: 2,
+                failed_runs: 1,
+                failure_rate: Some(0.5),
+                average_runtime_seconds: Some(120.0),
+                approvals_per_run: Some(1.5),
+            },
+        ]
+    );
+    assert_eq!(
+        report.merges_per_week,
+        vec![
+            WeeklyMerges {
+                week_start: "2025-11-03".to_string(),
+                merges: 1,
+                pr_merges: 1,
+            },
+            WeeklyMerges {
+                week_start: "2025-11-10".to_string(),
+                merges: 1,
+                pr_merges: 0,
+            },
+        ]
+    );
+
+    let query = AnalyticsReportQuery {
+        from: query.to,
+        to: query.from,
+    };
+    assert!(matches!(
+        analytics_report(&pool, &query).await,
+        Err(LocalAnalyticsError::InvalidWindow)
+    ));
+}
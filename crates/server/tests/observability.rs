@@ -0,0 +1,135 @@
+mod helpers;
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use server::routes;
+use tower::ServiceExt;
+use tracing_subscriber::prelude::*;
+use utils::otel;
+
+use crate::helpers::*;
+
+/// Stand-in for an OTLP collector: answers every request with 200 and hands over its path
+/// and body
+fn spawn_collector() -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: application/x-protobuf\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .unwrap();
+
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let _ = tx.send((path, body));
+        }
+    });
+    (format!("http://{addr}/v1/traces"), rx)
+}
+
+#[test]
+fn spans_are_exported_to_the_collector() {
+    let (endpoint, requests) = spawn_collector();
+    let provider = otel::tracer_provider(&endpoint).unwrap();
+    let subscriber = tracing_subscriber::registry().with(otel::otlp_layer(&provider));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("start_execution", run_reason = "codingagent");
+        let _entered = span.enter();
+        tracing::info_span!("merge_changes").in_scope(|| {});
+    });
+    provider.force_flush().unwrap();
+
+    let (path, body) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(path, "/v1/traces");
+    // Names are plain UTF-8 within the protobuf payload
+    let contains = |needle: &str| body.windows(needle.len()).any(|w| w == needle.as_bytes());
+    assert!(contains("start_execution"));
+    assert!(contains("merge_changes"));
+    assert!(contains("vibe-kanban"));
+
+    provider.shutdown().unwrap();
+}
+
+#[tokio::test]
+async fn metrics_are_served_in_prometheus_format() {
+    let (deployment, _temp_dir) = create_test_deployment().await;
+    let app = routes::router_for_testing(deployment);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4")
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    for line in [
+        "# TYPE vibe_kanban_running_execution_processes gauge",
+        "vibe_kanban_running_execution_processes{run_reason=\"codingagent\"} 0",
+        "vibe_kanban_queued_follow_ups 0",
+        "vibe_kanban_pending_approvals 0",
+        "# TYPE vibe_kanban_approval_wait_seconds summary",
+        "vibe_kanban_approval_wait_seconds_count 0",
+    ] {
+        assert!(
+            body.lines().any(|l| l == line),
+            "missing `{line}` in:\n{body}"
+        );
+    }
+    assert!(
+        body.lines()
+            .any(|l| l.starts_with("vibe_kanban_db_pool_max_connections "))
+    );
+}
@@ -0,0 +1,290 @@
+mod helpers;
+
+use std::{fs, path::Path};
+
+use db::models::{project::Project, task_attempt::TaskAttempt};
+use deployment::Deployment;
+use git2::{BranchType, Repository, RepositoryInitOptions, build::CheckoutBuilder};
+use services::services::stack::{NewBase, restack_children};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+fn commit_file(repo: &Repository, name: &str, content: &str, message: &str) {
+    fs::write(repo.workdir().unwrap().join(name), content).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(name)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parents: Vec<git2::Commit> = repo
+        .head()
+        .ok()
+        .map(|head| head.peel_to_commit().unwrap())
+        .into_iter()
+        .collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+        .unwrap();
+}
+
+fn switch_to_new_branch(repo: &Repository, name: &str) {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch(name, &head, false).unwrap();
+    checkout(repo, name);
+}
+
+fn checkout(repo: &Repository, name: &str) {
+    repo.set_head(&format!("refs/heads/{name}")).unwrap();
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))
+        .unwrap();
+}
+
+fn branch_commit<'a>(repo: &'a Repository, name: &str) -> git2::Commit<'a> {
+    repo.find_branch(name, BranchType::Local)
+        .unwrap()
+        .get()
+        .peel_to_commit()
+        .unwrap()
+}
+
+async fn create_attempt(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    worktree: &Path,
+    branch: &str,
+    target_branch: &str,
+    parent_attempt_id: Option<Uuid>,
+) -> TaskAttempt {
+    let attempt_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, parent_attempt_id)
+         VALUES ($1, $2, $3, $4, $5, 'CLAUDE_CODE', $6)",
+    )
+    .bind(attempt_id)
+    .bind(task_id)
+    .bind(worktree.to_string_lossy().to_string())
+    .bind(branch)
+    .bind(target_branch)
+    .bind(parent_attempt_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    TaskAttempt::find_by_id(pool, attempt_id)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn merged_parent_restacks_children_onto_its_target() {
+    let (deployment, temp_dir) = create_test_deployment().await;
+    let pool = &deployment.db().pool;
+
+    // main <- parent <- child <- grandchild, then parent is squash-merged into main
+    let repo_path = temp_dir.path().join("repo");
+    let repo = Repository::init_opts(
+        &repo_path,
+        RepositoryInitOptions::new().initial_head("main"),
+    )
+    .unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    commit_file(&repo, "base.txt", "base\n", "Base");
+    switch_to_new_branch(&repo, "parent");
+    commit_file(&repo, "parent.txt", "parent\n", "Parent");
+    switch_to_new_branch(&repo, "child");
+    commit_file(&repo, "child.txt", "child\n", "Child");
+    switch_to_new_branch(&repo, "grandchild");
+    commit_file(&repo, "grandchild.txt", "grandchild\n", "Grandchild");
+    checkout(&repo, "main");
+    commit_file(&repo, "parent.txt", "parent\n", "Parent (squashed)");
+    let old_parent_head = branch_commit(&repo, "parent").id().to_string();
+
+    let project = create_test_project(pool).await;
+    sqlx::query("UPDATE projects SET git_repo_path = $1 WHERE id = $2")
+        .bind(repo_path.to_string_lossy().to_string())
+        .bind(project.id)
+        .execute(pool)
+        .await
+        .unwrap();
+    let project = Project::find_by_id(pool, project.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let task = create_test_task(pool, project.id).await;
+    let worktrees = temp_dir.path().join("worktrees");
+    let parent = create_attempt(
+        pool,
+        task.id,
+        &worktrees.join("parent"),
+        "parent",
+        "main",
+        None,
+    )
+    .await;
+    let child = create_attempt(
+        pool,
+        task.id,
+        &worktrees.join("child"),
+        "child",
+        "parent",
+        Some(parent.id),
+    )
+    .await;
+    let grandchild = create_attempt(
+        pool,
+        task.id,
+        &worktrees.join("grandchild"),
+        "grandchild",
+        "child",
+        Some(child.id),
+    )
+    .await;
+
+    let restacked = restack_children(
+        deployment.container(),
+        &project,
+        &parent,
+        &old_parent_head,
+        NewBase::branch("main"),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(restacked, vec![child.id, grandchild.id]);
+
+    // Each branch now carries only its own commit on top of its new base
+    let main = branch_commit(&repo, "main");
+    let child_head = branch_commit(&repo, "child");
+    let grandchild_head = branch_commit(&repo, "grandchild");
+    assert_eq!(child_head.parent_count(), 1);
+    assert_eq!(child_head.parent_id(0).unwrap(), main.id());
+    assert_eq!(child_head.summary(), Some("Child"));
+    assert_eq!(grandchild_head.parent_id(0).unwrap(), child_head.id());
+    assert_eq!(grandchild_head.summary(), Some("Grandchild"));
+    assert!(worktrees.join("child").join("child.txt").exists());
+    assert!(worktrees.join("grandchild").join("parent.txt").exists());
+
+    // The child now targets what its parent was merged into; the grandchild stays stacked
+    let child = TaskAttempt::find_by_id(pool, child.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(child.target_branch, "main");
+    let grandchild = TaskAttempt::find_by_id(pool, grandchild.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(grandchild.target_branch, "child");
+}
+
+#[tokio::test]
+async fn rebased_parent_restacks_children_onto_its_new_head() {
+    let (deployment, temp_dir) = create_test_deployment().await;
+    let pool = &deployment.db().pool;
+
+    // main <- parent <- child <- grandchild, then main moves on and parent is rebased onto it
+    let repo_path = temp_dir.path().join("repo");
+    let repo = Repository::init_opts(
+        &repo_path,
+        RepositoryInitOptions::new().initial_head("main"),
+    )
+    .unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    commit_file(&repo, "base.txt", "base\n", "Base");
+    switch_to_new_branch(&repo, "parent");
+    commit_file(&repo, "parent.txt", "parent\n", "Parent");
+    switch_to_new_branch(&repo, "child");
+    commit_file(&repo, "child.txt", "child\n", "Child");
+    switch_to_new_branch(&repo, "grandchild");
+    commit_file(&repo, "grandchild.txt", "grandchild\n", "Grandchild");
+    checkout(&repo, "main");
+    commit_file(&repo, "main.txt", "main\n", "Main");
+    let old_parent_head = branch_commit(&repo, "parent").id().to_string();
+    let main = branch_commit(&repo, "main");
+    repo.branch("parent", &main, true).unwrap();
+    checkout(&repo, "parent");
+    commit_file(&repo, "parent.txt", "parent\n", "Parent");
+    checkout(&repo, "main");
+
+    let project = create_test_project(pool).await;
+    sqlx::query("UPDATE projects SET git_repo_path = $1 WHERE id = $2")
+        .bind(repo_path.to_string_lossy().to_string())
+        .bind(project.id)
+        .execute(pool)
+        .await
+        .unwrap();
+    let project = Project::find_by_id(pool, project.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let task = create_test_task(pool, project.id).await;
+    let worktrees = temp_dir.path().join("worktrees");
+    let parent = create_attempt(
+        pool,
+        task.id,
+        &worktrees.join("parent"),
+        "parent",
+        "main",
+        None,
+    )
+    .await;
+    let child = create_attempt(
+        pool,
+        task.id,
+        &worktrees.join("child"),
+        "child",
+        "parent",
+        Some(parent.id),
+    )
+    .await;
+    let grandchild = create_attempt(
+        pool,
+        task.id,
+        &worktrees.join("grandchild"),
+        "grandchild",
+        "child",
+        Some(child.id),
+    )
+    .await;
+
+    let restacked = restack_children(
+        deployment.container(),
+        &project,
+        &parent,
+        &old_parent_head,
+        NewBase::branch("parent"),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(restacked, vec![child.id, grandchild.id]);
+
+    // The children replay only their own commits on top of the rewritten parent
+    let parent_head = branch_commit(&repo, "parent");
+    let child_head = branch_commit(&repo, "child");
+    let grandchild_head = branch_commit(&repo, "grandchild");
+    assert_eq!(child_head.parent_id(0).unwrap(), parent_head.id());
+    assert_eq!(child_head.summary(), Some("Child"));
+    assert_eq!(grandchild_head.parent_id(0).unwrap(), child_head.id());
+    assert_eq!(grandchild_head.summary(), Some("Grandchild"));
+    assert!(worktrees.join("child").join("main.txt").exists());
+    assert!(worktrees.join("grandchild").join("main.txt").exists());
+
+    // Both stay stacked on the same branches
+    let child = TaskAttempt::find_by_id(pool, child.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(child.target_branch, "parent");
+    let grandchild = TaskAttempt::find_by_id(pool, grandchild.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(grandchild.target_branch, "child");
+}
@@ -0,0 +1,343 @@
+mod helpers;
+
+use std::time::Duration;
+
+use db::models::{
+    execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus},
+    merge::{Merge, MergeStatus},
+    task::{Task, TaskStatus},
+    task_relationship_history::RelationshipChange,
+    tool_approval::{ToolApproval, ToolApprovalStatus},
+};
+use services::services::task_activity::{TaskActivityEvent, row_activity, task_timeline};
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+/// Give each step its own timestamp, so the timeline order is unambiguous
+async fn tick() {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+}
+
+#[tokio::test]
+async fn timeline_covers_the_task_history_in_order() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let task = create_test_task(&pool, project.id).await;
+    tick().await;
+
+    Task::update_status(&pool, task.id, TaskStatus::InProgress)
+        .await
+        .unwrap();
+    tick().await;
+
+    let other = create_test_task(&pool, project.id).await;
+    let relationship_type = create_test_relationship_type(&pool, "depends", true, false).await;
+    let relationship_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO task_relationships (id, source_task_id, target_task_id, relationship_type_id)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(relationship_id)
+    .bind(task.id)
+    .bind(other.id)
+    .bind(relationship_type.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    tick().await;
+    sqlx::query("DELETE FROM task_relationships WHERE id = $1")
+        .bind(relationship_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    tick().await;
+
+    let attempt_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO task_attempts (id, task_id, branch, target_branch, executor)
+         VALUES ($1, $2, 'vk/test', 'main', 'CLAUDE_CODE')",
+    )
+    .bind(attempt_id)
+    .bind(task.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    tick().await;
+
+    let process_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes
+             (id, task_attempt_id, run_reason, executor_action, status, before_head_commit)
+         VALUES ($1, $2, 'codingagent', '{}', 'running', 'aaa')",
+    )
+    .bind(process_id)
+    .bind(attempt_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    tick().await;
+
+    ToolApproval::create(
+        &pool,
+        "approval-1",
+        process_id,
+        "Bash",
+        "rm -rf target",
+        chrono::Utc::now(),
+    )
+    .await
+    .unwrap();
+    tick().await;
+    ToolApproval::resolve(&pool, "approval-1", ToolApprovalStatus::Denied, Some("no"))
+        .await
+        .unwrap();
+    // A late timeout does not overwrite the user's decision
+    ToolApproval::resolve(&pool, "approval-1", ToolApprovalStatus::TimedOut, None)
+        .await
+        .unwrap();
+    tick().await;
+
+    sqlx::query(
+        "UPDATE execution_processes
+         SET status = 'completed', exit_code = 0, after_head_commit = 'bbb',
+             completed_at = datetime('now', 'subsec')
+         WHERE id = $1",
+    )
+    .bind(process_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO executor_sessions (id, task_attempt_id, execution_process_id, summary)
+         VALUES ($1, $2, $3, 'Cleaned up')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(attempt_id)
+    .bind(process_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    tick().await;
+
+    let pr = Merge::create_pr(&pool, attempt_id, "main", 7, "https://example.com/pr/7")
+        .await
+        .unwrap();
+    tick().await;
+    Merge::update_status(&pool, pr.id, MergeStatus::Closed, None)
+        .await
+        .unwrap();
+
+    let timeline = task_timeline(&pool, task.id).await.unwrap();
+    let events: Vec<TaskActivityEvent> = timeline.into_iter().map(|entry| entry.event).collect();
+
+    assert_eq!(
+        events,
+        vec![
+            TaskActivityEvent::StatusChanged {
+                from: None,
+                to: TaskStatus::Todo,
+            },
+            TaskActivityEvent::StatusChanged {
+                from: Some(TaskStatus::Todo),
+                to: TaskStatus::InProgress,
+            },
+            TaskActivityEvent::RelationshipChanged {
+                change: RelationshipChange::Added,
+                outgoing: true,
+                other_task_id: other.id,
+                other_task_title: "Test Task".to_string(),
+                label: Some("depends forward".to_string()),
+            },
+            TaskActivityEvent::RelationshipChanged {
+                change: RelationshipChange::Removed,
+                outgoing: true,
+                other_task_id: other.id,
+                other_task_title: "Test Task".to_string(),
+                label: Some("depends forward".to_string()),
+            },
+            TaskActivityEvent::AttemptStarted {
+                task_attempt_id: attempt_id,
+                executor: "CLAUDE_CODE".to_string(),
+                branch: "vk/test".to_string(),
+                target_branch: "main".to_string(),
+            },
+            TaskActivityEvent::ProcessStarted {
+                task_attempt_id: attempt_id,
+                execution_process_id: process_id,
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+                prompt: None,
+            },
+            TaskActivityEvent::ApprovalRequested {
+                task_attempt_id: attempt_id,
+                execution_process_id: process_id,
+                approval_id: "approval-1".to_string(),
+                tool_name: "Bash".to_string(),
+                tool_input_summary: "rm -rf target".to_string(),
+            },
+            TaskActivityEvent::ApprovalResolved {
+                task_attempt_id: attempt_id,
+                execution_process_id: process_id,
+                approval_id: "approval-1".to_string(),
+                tool_name: "Bash".to_string(),
+                status: ToolApprovalStatus::Denied,
+                denial_reason: Some("no".to_string()),
+            },
+            TaskActivityEvent::ProcessFinished {
+                task_attempt_id: attempt_id,
+                execution_process_id: process_id,
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+                status: ExecutionProcessStatus::Completed,
+                exit_code: Some(0),
+                summary: Some("Cleaned up".to_string()),
+            },
+            TaskActivityEvent::Committed {
+                task_attempt_id: attempt_id,
+                execution_process_id: process_id,
+                commit: "bbb".to_string(),
+            },
+            TaskActivityEvent::PrOpened {
+                task_attempt_id: attempt_id,
+                number: 7,
+                url: "https://example.com/pr/7".to_string(),
+                target_branch: "main".to_string(),
+            },
+            TaskActivityEvent::PrClosed {
+                task_attempt_id: attempt_id,
+                number: 7,
+                url: "https://example.com/pr/7".to_string(),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn relationship_history_is_kept_for_both_tasks() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let source = create_test_task(&pool, project.id).await;
+    let target = create_test_task(&pool, project.id).await;
+    let relationship_type = create_test_relationship_type(&pool, "depends", true, false).await;
+
+    sqlx::query(
+        "INSERT INTO task_relationships (id, source_task_id, target_task_id, relationship_type_id)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(source.id)
+    .bind(target.id)
+    .bind(relationship_type.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let events: Vec<TaskActivityEvent> = task_timeline(&pool, target.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.event)
+        .filter(|event| matches!(event, TaskActivityEvent::RelationshipChanged { .. }))
+        .collect();
+    assert_eq!(
+        events,
+        vec![TaskActivityEvent::RelationshipChanged {
+            change: RelationshipChange::Added,
+            outgoing: false,
+            other_task_id: source.id,
+            other_task_title: "Test Task".to_string(),
+            label: Some("depends reverse".to_string()),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn changed_rows_update_the_timeline_entries_they_show_up_as() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let source = create_test_task(&pool, project.id).await;
+    let target = create_test_task(&pool, project.id).await;
+    let relationship_type = create_test_relationship_type(&pool, "depends", true, false).await;
+
+    sqlx::query(
+        "INSERT INTO task_relationships (id, source_task_id, target_task_id, relationship_type_id)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(source.id)
+    .bind(target.id)
+    .bind(relationship_type.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let change_id: i64 = sqlx::query_scalar("SELECT MAX(id) FROM task_relationship_history")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let changes = row_activity(&pool, "task_relationship_history", change_id)
+        .await
+        .unwrap();
+    assert_eq!(changes.len(), 2);
+    for (task_id, entry) in changes {
+        let timeline = task_timeline(&pool, task_id).await.unwrap();
+        assert!(timeline.contains(&entry));
+    }
+
+    let attempt_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO task_attempts (id, task_id, branch, target_branch, executor)
+         VALUES ($1, $2, 'vk/test', 'main', 'CLAUDE_CODE')",
+    )
+    .bind(attempt_id)
+    .bind(source.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let process_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes
+             (id, task_attempt_id, run_reason, executor_action, status)
+         VALUES ($1, $2, 'codingagent', '{}', 'running')",
+    )
+    .bind(process_id)
+    .bind(attempt_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    ToolApproval::create(
+        &pool,
+        "approval-1",
+        process_id,
+        "Bash",
+        "cargo test",
+        chrono::Utc::now(),
+    )
+    .await
+    .unwrap();
+    tick().await;
+    ToolApproval::resolve(&pool, "approval-1", ToolApprovalStatus::Approved, None)
+        .await
+        .unwrap();
+    let approval_rowid: i64 =
+        sqlx::query_scalar("SELECT rowid FROM tool_approvals WHERE id = 'approval-1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+    // The request and its resolution are separate entries of the approval's process
+    let approval = row_activity(&pool, "tool_approvals", approval_rowid)
+        .await
+        .unwrap();
+    let timeline = task_timeline(&pool, source.id).await.unwrap();
+    assert_eq!(approval.len(), 2);
+    assert!(
+        approval
+            .iter()
+            .all(|(task_id, entry)| *task_id == source.id && timeline.contains(entry))
+    );
+    assert!(approval.iter().all(|(_, entry)| {
+        entry
+            .key
+            .starts_with(&format!("attempt:{attempt_id}:process:{process_id}:"))
+    }));
+}
@@ -0,0 +1,273 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use db::models::{
+    execution_process::{CodingAgentRun, ExecutionProcessStatus, ExecutorActionField},
+    task::{Task, TaskStatus},
+    task_status_history::TaskStatusChange,
+};
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
+use services::services::task_metrics::{
+    AgentRunStats, DurationStats, TaskMetricsError, TaskMetricsQuery, compute_task_metrics,
+    project_task_metrics,
+};
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 11, 3, 0, 0, 0).unwrap()
+}
+
+fn hours(n: i64) -> DateTime<Utc> {
+    start() + Duration::hours(n)
+}
+
+/// A task's status changes, the first one being its creation
+fn history(task_id: Uuid, steps: &[(TaskStatus, DateTime<Utc>)]) -> Vec<TaskStatusChange> {
+    let mut from_status = None;
+    steps
+        .iter()
+        .enumerate()
+        .map(|(index, (status, at))| {
+            let change = TaskStatusChange {
+                id: index as i64,
+                task_id,
+                from_status: from_status.clone(),
+                to_status: status.clone(),
+                changed_at: *at,
+            };
+            from_status = Some(status.clone());
+            change
+        })
+        .collect()
+}
+
+fn run(
+    task_id: Uuid,
+    executor: BaseCodingAgent,
+    status: ExecutionProcessStatus,
+    started_at: DateTime<Utc>,
+) -> CodingAgentRun {
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: "Do the task".to_string(),
+            executor_profile_id: ExecutorProfileId::new(executor),
+        }),
+        None,
+    );
+    CodingAgentRun {
+        execution_process_id: Uuid::new_v4(),
+        task_id,
+        executor_action: sqlx::types::Json(ExecutorActionField::ExecutorAction(action)),
+        status,
+        started_at,
+    }
+}
+
+fn stats(count: i64, mean: f64, median: f64, p85: f64) -> DurationStats {
+    DurationStats {
+        count,
+        mean_seconds: Some(mean * 3600.0),
+        median_seconds: Some(median * 3600.0),
+        p85_seconds: Some(p85 * 3600.0),
+    }
+}
+
+#[test]
+fn metrics_cover_tasks_done_within_the_window() {
+    use TaskStatus::*;
+
+    // Went back from review once and was finished by a second agent
+    let reworked = Uuid::new_v4();
+    // Created before the window
+    let older = Uuid::new_v4();
+    let done_before = Uuid::new_v4();
+    let reopened = Uuid::new_v4();
+    let done_after = Uuid::new_v4();
+
+    let mut changes = Vec::new();
+    changes.extend(history(
+        reworked,
+        &[
+            (Todo, hours(0)),
+            (InProgress, hours(1)),
+            (InReview, hours(3)),
+            (InProgress, hours(4)),
+            (InReview, hours(5)),
+            (Done, hours(7)),
+        ],
+    ));
+    changes.extend(history(
+        older,
+        &[
+            (Todo, hours(-24)),
+            (InProgress, hours(24)),
+            (Done, hours(48)),
+        ],
+    ));
+    changes.extend(history(
+        done_before,
+        &[(Todo, hours(-48)), (Done, hours(-1))],
+    ));
+    changes.extend(history(
+        reopened,
+        &[(Todo, hours(0)), (Done, hours(2)), (Todo, hours(3))],
+    ));
+    changes.extend(history(
+        done_after,
+        &[
+            (Todo, hours(0)),
+            (InProgress, hours(1)),
+            (Done, hours(24 * 8)),
+        ],
+    ));
+
+    let runs = vec![
+        run(
+            done_before,
+            BaseCodingAgent::ClaudeCode,
+            ExecutionProcessStatus::Completed,
+            hours(-2),
+        ),
+        run(
+            reworked,
+            BaseCodingAgent::ClaudeCode,
+            ExecutionProcessStatus::Completed,
+            hours(1),
+        ),
+        run(
+            reworked,
+            BaseCodingAgent::Amp,
+            ExecutionProcessStatus::Failed,
+            hours(4),
+        ),
+        run(
+            older,
+            BaseCodingAgent::ClaudeCode,
+            ExecutionProcessStatus::Completed,
+            hours(24),
+        ),
+        run(
+            done_after,
+            BaseCodingAgent::ClaudeCode,
+            ExecutionProcessStatus::Killed,
+            hours(48),
+        ),
+        run(
+            done_after,
+            BaseCodingAgent::ClaudeCode,
+            ExecutionProcessStatus::Running,
+            hours(49),
+        ),
+    ];
+
+    let metrics = compute_task_metrics(&changes, &runs, start(), start() + Duration::weeks(1));
+
+    let overall = &metrics.overall;
+    assert_eq!(overall.tasks_completed, 2);
+    assert_eq!(overall.throughput_per_week, 2.0);
+    assert_eq!(overall.lead_time, stats(2, 39.5, 7.0, 72.0));
+    assert_eq!(overall.cycle_time, stats(2, 15.0, 6.0, 24.0));
+    assert_eq!(overall.time_in_progress, stats(2, 13.5, 3.0, 24.0));
+    assert_eq!(overall.time_in_review, stats(1, 3.0, 3.0, 3.0));
+    assert_eq!(
+        overall.agent_runs,
+        AgentRunStats {
+            runs: 4,
+            succeeded: 2,
+            failed: 1,
+            killed: 1,
+            success_rate: Some(2.0 / 3.0),
+        }
+    );
+
+    let profiles: Vec<_> = metrics
+        .by_executor_profile
+        .iter()
+        .map(|entry| {
+            (
+                entry.executor_profile_id.executor,
+                entry.metrics.tasks_completed,
+                entry.metrics.agent_runs.success_rate,
+            )
+        })
+        .collect();
+    assert_eq!(
+        profiles,
+        vec![
+            (BaseCodingAgent::Amp, 1, Some(0.0)),
+            (BaseCodingAgent::ClaudeCode, 1, Some(1.0)),
+        ]
+    );
+    // The reworked task counts for the agent that finished it
+    assert_eq!(
+        metrics.by_executor_profile[0].metrics.lead_time,
+        stats(1, 7.0, 7.0, 7.0)
+    );
+}
+
+#[test]
+fn empty_window_has_no_durations() {
+    let metrics = compute_task_metrics(&[], &[], start(), start() + Duration::days(1));
+    assert_eq!(metrics.overall.tasks_completed, 0);
+    assert_eq!(metrics.overall.lead_time, DurationStats::default());
+    assert_eq!(metrics.overall.agent_runs.success_rate, None);
+    assert!(metrics.by_executor_profile.is_empty());
+}
+
+#[tokio::test]
+async fn status_updates_are_recorded_and_measured() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let task = create_test_task(&pool, project.id).await;
+
+    for status in [
+        TaskStatus::InProgress,
+        TaskStatus::InReview,
+        TaskStatus::Done,
+    ] {
+        Task::update_status(&pool, task.id, status).await.unwrap();
+    }
+    // Setting the same status again is not a transition
+    Task::update_status(&pool, task.id, TaskStatus::Done)
+        .await
+        .unwrap();
+
+    let transitions: Vec<_> = TaskStatusChange::find_by_task_id(&pool, task.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|change| (change.from_status, change.to_status))
+        .collect();
+    assert_eq!(
+        transitions,
+        vec![
+            (None, TaskStatus::Todo),
+            (Some(TaskStatus::Todo), TaskStatus::InProgress),
+            (Some(TaskStatus::InProgress), TaskStatus::InReview),
+            (Some(TaskStatus::InReview), TaskStatus::Done),
+        ]
+    );
+
+    let metrics = project_task_metrics(&pool, project.id, &TaskMetricsQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(metrics.overall.tasks_completed, 1);
+    assert_eq!(metrics.overall.cycle_time.count, 1);
+
+    let query = TaskMetricsQuery {
+        from: Some(start()),
+        to: Some(start()),
+    };
+    assert!(matches!(
+        project_task_metrics(&pool, project.id, &query).await,
+        Err(TaskMetricsError::InvalidWindow)
+    ));
+}
@@ -0,0 +1,317 @@
+mod helpers;
+
+use chrono::{Duration, TimeZone, Utc};
+use db::models::transcript_entry::{
+    TranscriptEntry, TranscriptEntryKind, TranscriptSearchQuery, fts_query,
+};
+use executors::logs::{
+    ActionType, FileChange, NormalizedEntry, NormalizedEntryType, ToolStatus,
+    utils::patch::ConversationPatch,
+};
+use services::services::{
+    conversation_export::indexed_entries_from_patches, transcript_search::transcript_entries,
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type,
+        content: content.to_string(),
+        metadata: None,
+    }
+}
+
+fn tool(action_type: ActionType) -> NormalizedEntry {
+    entry(
+        NormalizedEntryType::ToolUse {
+            tool_name: "tool".to_string(),
+            action_type,
+            status: ToolStatus::Success,
+        },
+        "",
+    )
+}
+
+fn text(kind: TranscriptEntryKind, entry_index: i64, content: &str) -> TranscriptEntry {
+    TranscriptEntry {
+        entry_index,
+        kind,
+        content: content.to_string(),
+    }
+}
+
+/// Insert a finished coding agent process under a new attempt of the task
+async fn create_process(pool: &SqlitePool, task_id: Uuid, executor: &str) -> Uuid {
+    let attempt_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO task_attempts (id, task_id, branch, target_branch, executor)
+         VALUES ($1, $2, 'vk/test', 'main', $3)",
+    )
+    .bind(attempt_id)
+    .bind(task_id)
+    .bind(executor)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let process_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes (id, task_attempt_id, run_reason, executor_action, status)
+         VALUES ($1, $2, 'codingagent', '{}', 'completed')",
+    )
+    .bind(process_id)
+    .bind(attempt_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    process_id
+}
+
+#[test]
+fn queries_are_quoted_prefix_terms() {
+    assert_eq!(fts_query("  "), None);
+    assert_eq!(
+        fts_query("login redirect").as_deref(),
+        Some(r#""login"* "redirect"*"#)
+    );
+    // Query syntax and quotes are matched literally
+    assert_eq!(
+        fts_query(r#"NOT a"b"#).as_deref(),
+        Some(r#""NOT"* "a""b"*"#)
+    );
+}
+
+#[test]
+fn entries_keep_their_normalized_log_positions() {
+    let patches = [
+        ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::UserMessage, "Fix the login"),
+        ),
+        ConversationPatch::add_stderr(1, "warning".to_string()),
+        ConversationPatch::add_normalized_entry(
+            2,
+            entry(NormalizedEntryType::AssistantMessage, "Reading the router"),
+        ),
+        ConversationPatch::add_normalized_entry(
+            3,
+            tool(ActionType::CommandRun {
+                command: "cargo test".to_string(),
+                result: None,
+            }),
+        ),
+        ConversationPatch::add_normalized_entry(
+            4,
+            tool(ActionType::FileEdit {
+                path: "src/old.rs".to_string(),
+                changes: vec![FileChange::Rename {
+                    new_path: "src/new.rs".to_string(),
+                }],
+            }),
+        ),
+        ConversationPatch::add_normalized_entry(
+            5,
+            tool(ActionType::Search {
+                query: "login".to_string(),
+            }),
+        ),
+    ];
+
+    let entries = transcript_entries(&indexed_entries_from_patches(&patches));
+    assert_eq!(
+        entries,
+        vec![
+            text(
+                TranscriptEntryKind::AssistantMessage,
+                2,
+                "Reading the router"
+            ),
+            text(TranscriptEntryKind::Command, 3, "cargo test"),
+            text(TranscriptEntryKind::FilePath, 4, "src/old.rs\nsrc/new.rs"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn search_filters_and_reindexing_replaces_entries() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let task = create_test_task(&pool, project.id).await;
+    let claude = create_process(&pool, task.id, "CLAUDE_CODE").await;
+    let codex = create_process(&pool, task.id, "CODEX").await;
+
+    let unindexed = TranscriptEntry::find_unindexed_process_ids(&pool, 10)
+        .await
+        .unwrap();
+    assert_eq!(unindexed.len(), 2);
+
+    TranscriptEntry::replace_for_process(
+        &pool,
+        claude,
+        &[
+            text(
+                TranscriptEntryKind::AssistantMessage,
+                0,
+                "The redirect loops forever",
+            ),
+            text(TranscriptEntryKind::Command, 1, "npm run redirects"),
+        ],
+    )
+    .await
+    .unwrap();
+    TranscriptEntry::replace_for_process(
+        &pool,
+        codex,
+        &[text(TranscriptEntryKind::FilePath, 3, "src/redirect.ts")],
+    )
+    .await
+    .unwrap();
+    assert!(
+        TranscriptEntry::find_unindexed_process_ids(&pool, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let search = |query: TranscriptSearchQuery| {
+        let pool = pool.clone();
+        async move { TranscriptEntry::search(&pool, &query).await.unwrap() }
+    };
+
+    let all = search(TranscriptSearchQuery {
+        q: "redirect".to_string(),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(all.len(), 3);
+    assert!(all.iter().all(|result| result.task_id == task.id
+        && result.project_id == project.id
+        && result.task_title == task.title));
+    let message = all
+        .iter()
+        .find(|result| result.kind == TranscriptEntryKind::AssistantMessage)
+        .unwrap();
+    assert_eq!(message.execution_process_id, claude);
+    assert_eq!(message.entry_index, 0);
+    assert!(message.snippet.contains("\u{2}redirect\u{3}"));
+
+    let commands = search(TranscriptSearchQuery {
+        q: "redirect".to_string(),
+        kind: Some(TranscriptEntryKind::Command),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].entry_index, 1);
+
+    let by_codex = search(TranscriptSearchQuery {
+        q: "redirect".to_string(),
+        executor: Some("CODEX".to_string()),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(by_codex.len(), 1);
+    assert_eq!(by_codex[0].execution_process_id, codex);
+
+    let other_project = search(TranscriptSearchQuery {
+        q: "redirect".to_string(),
+        project_id: Some(Uuid::new_v4()),
+        ..Default::default()
+    })
+    .await;
+    assert!(other_project.is_empty());
+
+    let future = search(TranscriptSearchQuery {
+        q: "redirect".to_string(),
+        from: Some(Utc::now() + Duration::days(1)),
+        ..Default::default()
+    })
+    .await;
+    assert!(future.is_empty());
+
+    // Reindexing drops the process's previous entries from the index
+    TranscriptEntry::replace_for_process(
+        &pool,
+        claude,
+        &[text(TranscriptEntryKind::AssistantMessage, 0, "All fixed")],
+    )
+    .await
+    .unwrap();
+    let remaining = search(TranscriptSearchQuery {
+        q: "redirect".to_string(),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].execution_process_id, codex);
+    assert_eq!(
+        search(TranscriptSearchQuery {
+            q: "fixed".to_string(),
+            ..Default::default()
+        })
+        .await
+        .len(),
+        1
+    );
+
+    // Entries keep their place in the full-text index when the database is compacted
+    sqlx::query("VACUUM").execute(&pool).await.unwrap();
+    let fixed = search(TranscriptSearchQuery {
+        q: "fixed".to_string(),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(fixed.len(), 1);
+    assert_eq!(fixed[0].execution_process_id, claude);
+    assert!(fixed[0].snippet.contains("\u{2}fixed\u{3}"));
+}
+
+#[tokio::test]
+async fn time_filters_compare_sub_second_timestamps() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let task = create_test_task(&pool, project.id).await;
+    let process = create_process(&pool, task.id, "CLAUDE_CODE").await;
+    sqlx::query("UPDATE execution_processes SET started_at = $1 WHERE id = $2")
+        .bind("2025-11-19 10:00:00.500")
+        .bind(process)
+        .execute(&pool)
+        .await
+        .unwrap();
+    TranscriptEntry::replace_for_process(
+        &pool,
+        process,
+        &[text(TranscriptEntryKind::Command, 0, "cargo test")],
+    )
+    .await
+    .unwrap();
+
+    let at = |millis| {
+        Utc.with_ymd_and_hms(2025, 11, 19, 10, 0, 0).unwrap() + Duration::milliseconds(millis)
+    };
+    let found = |from, to| {
+        let pool = pool.clone();
+        async move {
+            TranscriptEntry::search(
+                &pool,
+                &TranscriptSearchQuery {
+                    q: "cargo".to_string(),
+                    from,
+                    to,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .len()
+        }
+    };
+    assert_eq!(found(Some(at(400)), None).await, 1);
+    assert_eq!(found(Some(at(600)), None).await, 0);
+    assert_eq!(found(None, Some(at(600))).await, 1);
+    assert_eq!(found(None, Some(at(500))).await, 0);
+}
@@ -0,0 +1,114 @@
+mod helpers;
+
+use db::{
+    DBService,
+    models::webhook::{
+        CreateWebhook, UpdateWebhook, Webhook, WebhookDelivery, WebhookDeliveryStatus,
+        WebhookEventType,
+    },
+};
+use services::services::webhooks::{DISABLED_ERROR, WebhookService};
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+fn create_webhook(secret: Option<&str>) -> CreateWebhook {
+    CreateWebhook {
+        name: "receiver".to_string(),
+        url: "http://127.0.0.1:9/hook".to_string(),
+        secret: secret.map(str::to_string),
+        events: vec![],
+        project_id: None,
+    }
+}
+
+#[tokio::test]
+async fn secrets_are_never_serialized() {
+    let (pool, _temp_dir) = create_test_db().await;
+
+    let signed = Webhook::create(&pool, &create_webhook(Some("s3cr3t")))
+        .await
+        .unwrap();
+    let json = serde_json::to_value(&signed).unwrap();
+    assert!(json.get("secret").is_none());
+    assert_eq!(json["has_secret"], true);
+    // Still available for signing
+    assert_eq!(signed.secret.as_deref(), Some("s3cr3t"));
+
+    let unsigned = Webhook::create(&pool, &create_webhook(None)).await.unwrap();
+    assert_eq!(
+        serde_json::to_value(&unsigned).unwrap()["has_secret"],
+        false
+    );
+}
+
+#[tokio::test]
+async fn disabling_a_webhook_fails_its_pending_deliveries() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let webhooks = WebhookService::new(DBService { pool: pool.clone() });
+    let webhook = Webhook::create(&pool, &create_webhook(None)).await.unwrap();
+    let other = Webhook::create(&pool, &create_webhook(None)).await.unwrap();
+
+    let delivery = |webhook_id| {
+        let pool = pool.clone();
+        async move {
+            WebhookDelivery::create(
+                &pool,
+                Uuid::new_v4(),
+                webhook_id,
+                WebhookEventType::Ping,
+                "{}",
+            )
+            .await
+            .unwrap()
+        }
+    };
+    let pending = delivery(webhook.id).await;
+    let succeeded = delivery(webhook.id).await;
+    WebhookDelivery::record_attempt(
+        &pool,
+        succeeded.id,
+        WebhookDeliveryStatus::Succeeded,
+        Some(200),
+        None,
+    )
+    .await
+    .unwrap();
+    let elsewhere = delivery(other.id).await;
+
+    Webhook::update(
+        &pool,
+        webhook.id,
+        &UpdateWebhook {
+            name: None,
+            url: None,
+            secret: None,
+            events: None,
+            enabled: Some(false),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(webhooks.fail_pending(webhook.id).await.unwrap(), 1);
+
+    let status = |id| {
+        let pool = pool.clone();
+        async move {
+            WebhookDelivery::find_by_id(&pool, id)
+                .await
+                .unwrap()
+                .unwrap()
+        }
+    };
+    let failed = status(pending.id).await;
+    assert_eq!(failed.status, WebhookDeliveryStatus::Failed);
+    assert_eq!(failed.error.as_deref(), Some(DISABLED_ERROR));
+    assert_eq!(
+        status(succeeded.id).await.status,
+        WebhookDeliveryStatus::Succeeded
+    );
+    assert_eq!(
+        status(elsewhere.id).await.status,
+        WebhookDeliveryStatus::Pending
+    );
+}
@@ -0,0 +1,73 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::task_attempt::TaskAttempt;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+async fn create_attempt(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    updated_at: DateTime<Utc>,
+    worktree_deleted: bool,
+) -> Uuid {
+    let attempt_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, updated_at)
+         VALUES ($1, $2, $3, 'vk/test', 'main', 'CLAUDE_CODE', $4, $5)",
+    )
+    .bind(attempt_id)
+    .bind(task_id)
+    .bind(format!("/tmp/worktrees/{attempt_id}"))
+    .bind(worktree_deleted)
+    .bind(updated_at)
+    .execute(pool)
+    .await
+    .unwrap();
+    attempt_id
+}
+
+async fn create_process(pool: &SqlitePool, attempt_id: Uuid, completed_at: Option<DateTime<Utc>>) {
+    sqlx::query(
+        "INSERT INTO execution_processes (id, task_attempt_id, run_reason, executor_action, status, completed_at)
+         VALUES ($1, $2, 'codingagent', '{}', 'completed', $3)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(attempt_id)
+    .bind(completed_at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn cleanup_candidates_are_ordered_by_last_activity() {
+    let (pool, _temp_dir) = create_test_db().await;
+    let project = create_test_project(&pool).await;
+    let task = create_test_task(&pool, project.id).await;
+    let now = Utc::now();
+
+    // Last activity is the latest finished process, falling back to the attempt itself
+    let recent_process = create_attempt(&pool, task.id, now - Duration::days(30), false).await;
+    create_process(&pool, recent_process, Some(now - Duration::hours(1))).await;
+    let idle = create_attempt(&pool, task.id, now - Duration::days(10), false).await;
+    let running = create_attempt(&pool, task.id, now - Duration::days(5), false).await;
+    create_process(&pool, running, None).await;
+    create_attempt(&pool, task.id, now - Duration::days(60), true).await;
+
+    let candidates = TaskAttempt::find_worktree_cleanup_candidates(&pool)
+        .await
+        .unwrap();
+
+    let ids: Vec<Uuid> = candidates.iter().map(|c| c.attempt_id).collect();
+    assert_eq!(ids, vec![idle, running, recent_process]);
+    assert!(candidates[1].running);
+    assert!(!candidates[0].running && !candidates[2].running);
+    assert_eq!(candidates[0].project_id, project.id);
+    assert_eq!(
+        candidates[2].last_activity_at.timestamp(),
+        (now - Duration::hours(1)).timestamp()
+    );
+}
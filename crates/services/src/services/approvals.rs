@@ -2,10 +2,15 @@ pub mod executor_approvals;
 
 use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
 
+use chrono::Utc;
 use dashmap::DashMap;
-use db::models::{
-    execution_process::ExecutionProcess,
-    task::{Task, TaskStatus},
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess,
+        task::{Task, TaskStatus},
+        tool_approval::{ToolApproval, ToolApprovalStatus},
+    },
 };
 use executors::{
     approvals::ToolCallMetadata,
@@ -25,6 +30,16 @@ use utils::{
 };
 use uuid::Uuid;
 
+use crate::services::{
+    config::Config, notification::NotificationService, webhooks::WebhookService,
+};
+
+/// How long before an approval times out to remind that it is still waiting
+const APPROVAL_REMINDER_LEAD: StdDuration = StdDuration::from_secs(5 * 60);
+
+/// Longest tool input summary shown in approval notifications
+const TOOL_INPUT_SUMMARY_LIMIT: usize = 200;
+
 #[derive(Debug)]
 struct PendingApproval {
     entry_index: usize,
@@ -47,6 +62,9 @@ pub struct Approvals {
     pending: Arc<DashMap<String, PendingApproval>>,
     completed: Arc<DashMap<String, ApprovalStatus>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    webhooks: WebhookService,
 }
 
 #[derive(Debug, Error)]
@@ -66,11 +84,19 @@ pub enum ApprovalError {
 }
 
 impl Approvals {
-    pub fn new(msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>) -> Self {
+    pub fn new(
+        msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        webhooks: WebhookService,
+    ) -> Self {
         Self {
             pending: Arc::new(DashMap::new()),
             completed: Arc::new(DashMap::new()),
             msg_stores,
+            db,
+            config,
+            webhooks,
         }
     }
 
@@ -85,6 +111,19 @@ impl Approvals {
             .shared();
         let req_id = request.id.clone();
 
+        if let Err(e) = ToolApproval::create(
+            &self.db.pool,
+            &req_id,
+            request.execution_process_id,
+            &request.tool_name,
+            &summarize_tool_input(&request.tool_input),
+            request.created_at,
+        )
+        .await
+        {
+            tracing::warn!("Failed to record approval {}: {}", req_id, e);
+        }
+
         if let Some(store) = self.msg_store_by_id(&request.execution_process_id).await {
             // Find the matching tool use entry by name and input
             let matching_tool = find_matching_tool_use(store.clone(), &request.tool_call_id);
@@ -129,6 +168,11 @@ impl Approvals {
             );
         }
 
+        self.webhooks.dispatch_approval_requested(request.clone());
+        // Without a pending entry the waiter resolves right away, so there is nothing to wait on
+        if self.pending.contains_key(&req_id) {
+            self.spawn_notifications(request.clone(), waiter.clone());
+        }
         self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, waiter.clone());
         Ok((request, waiter))
     }
@@ -199,6 +243,7 @@ impl Approvals {
         let pending = self.pending.clone();
         let completed = self.completed.clone();
         let msg_stores = self.msg_stores.clone();
+        let db = self.db.clone();
 
         let now = chrono::Utc::now();
         let to_wait = (timeout_at - now)
@@ -216,6 +261,7 @@ impl Approvals {
 
             let is_timeout = matches!(&status, ApprovalStatus::TimedOut);
             completed.insert(id.clone(), status.clone());
+            record_resolution(&db.pool, &id, &status).await;
 
             if is_timeout && let Some((_, pending_approval)) = pending.remove(&id) {
                 if pending_approval.response_tx.send(status.clone()).is_err() {
@@ -252,12 +298,57 @@ impl Approvals {
         });
     }
 
+    /// Notify that an approval is waiting, and again shortly before it times out
+    fn spawn_notifications(&self, request: ApprovalRequest, waiter: ApprovalWaiter) {
+        let db = self.db.clone();
+        let config = self.config.clone();
+
+        tokio::spawn(async move {
+            let ctx = match ExecutionProcess::load_context(&db.pool, request.execution_process_id)
+                .await
+            {
+                Ok(ctx) => ctx,
+                Err(e) => {
+                    tracing::warn!("Failed to load approval context for notifications: {}", e);
+                    return;
+                }
+            };
+
+            // Measured once; the reminder is then scheduled on the runtime's clock
+            let time_left = (request.timeout_at - Utc::now())
+                .to_std()
+                .unwrap_or_default();
+            notify_until_resolved(time_left, waiter, |reminder| {
+                let (config, ctx, request) = (&config, &ctx, &request);
+                async move {
+                    let notify_cfg = config.read().await.notifications.clone();
+                    NotificationService::notify_approval_pending(notify_cfg, ctx, request, reminder)
+                        .await;
+                }
+            })
+            .await;
+        });
+    }
+
     async fn msg_store_by_id(&self, execution_process_id: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores.read().await;
         map.get(execution_process_id).cloned()
     }
 }
 
+/// Store how an approval was resolved, for task timelines
+async fn record_resolution(pool: &SqlitePool, id: &str, status: &ApprovalStatus) {
+    let (status, denial_reason) = match status {
+        ApprovalStatus::Pending => return,
+        ApprovalStatus::Approved => (ToolApprovalStatus::Approved, None),
+        ApprovalStatus::Denied { reason } => (ToolApprovalStatus::Denied, reason.as_deref()),
+        ApprovalStatus::TimedOut => (ToolApprovalStatus::TimedOut, None),
+    };
+    if let Err(e) = ToolApproval::resolve(pool, id, status, denial_reason).await {
+        tracing::warn!("Failed to record resolution of approval {}: {}", id, e);
+    }
+}
+
 pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_id: Uuid) {
     if let Ok(ctx) = ExecutionProcess::load_context(pool, execution_process_id).await
         && ctx.task.status == TaskStatus::InProgress
@@ -270,6 +361,35 @@ pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_i
     }
 }
 
+/// One line describing what a tool will do, preferring the input field that names its target
+pub fn summarize_tool_input(input: &serde_json::Value) -> String {
+    let summary = [
+        "command",
+        "file_path",
+        "path",
+        "url",
+        "pattern",
+        "query",
+        "description",
+    ]
+    .iter()
+    .find_map(|key| input.get(key).and_then(|value| value.as_str()))
+    .map(str::to_string)
+    .unwrap_or_else(|| match input {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    });
+
+    let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+    if summary.chars().count() > TOOL_INPUT_SUMMARY_LIMIT {
+        let truncated: String = summary.chars().take(TOOL_INPUT_SUMMARY_LIMIT).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        summary
+    }
+}
+
 /// Find a matching tool use entry that hasn't been assigned to an approval yet
 /// Matches by tool call id from tool metadata
 fn find_matching_tool_use(
@@ -308,6 +428,32 @@ fn find_matching_tool_use(
     None
 }
 
+/// Send the first notification right away, then a reminder once the approval, which times out
+/// in `time_left`, is within [`APPROVAL_REMINDER_LEAD`] of timing out, unless `waiter`
+/// resolves first
+async fn notify_until_resolved<F, Fut>(
+    time_left: StdDuration,
+    waiter: ApprovalWaiter,
+    mut notify: F,
+) where
+    F: FnMut(bool) -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    notify(false).await;
+
+    // Approvals shorter than the lead time only get the first notification
+    let Some(to_wait) = time_left.checked_sub(APPROVAL_REMINDER_LEAD) else {
+        return;
+    };
+
+    tokio::select! {
+        biased;
+
+        _ = waiter => {}
+        _ = tokio::time::sleep(to_wait) => notify(true).await,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -398,4 +544,89 @@ mod tests {
             "Should not match different tool ids"
         );
     }
+
+    #[test]
+    fn test_tool_input_summary() {
+        let input = serde_json::json!({
+            "command": "cargo test\n  --workspace",
+            "description": "Run the tests",
+        });
+        assert_eq!(summarize_tool_input(&input), "cargo test --workspace");
+
+        let input = serde_json::json!({ "file_path": "src/main.rs", "content": "fn main() {}" });
+        assert_eq!(summarize_tool_input(&input), "src/main.rs");
+
+        // Unknown shapes fall back to the JSON itself
+        let input = serde_json::json!({ "todos": [1, 2] });
+        assert_eq!(summarize_tool_input(&input), r#"{"todos":[1,2]}"#);
+        assert_eq!(summarize_tool_input(&serde_json::Value::Null), "");
+
+        let long = "x".repeat(TOOL_INPUT_SUMMARY_LIMIT + 10);
+        let summary = summarize_tool_input(&serde_json::json!({ "command": long }));
+        assert_eq!(summary.chars().count(), TOOL_INPUT_SUMMARY_LIMIT + 1);
+        assert!(summary.ends_with('…'));
+    }
+
+    fn waiter() -> (oneshot::Sender<ApprovalStatus>, ApprovalWaiter) {
+        let (tx, rx) = oneshot::channel();
+        let waiter = rx
+            .map(|status| status.unwrap_or(ApprovalStatus::TimedOut))
+            .boxed()
+            .shared();
+        (tx, waiter)
+    }
+
+    fn spawn_recording(
+        timeout: StdDuration,
+        waiter: ApprovalWaiter,
+    ) -> Arc<std::sync::Mutex<Vec<bool>>> {
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = sent.clone();
+        tokio::spawn(notify_until_resolved(timeout, waiter, move |reminder| {
+            recorded.lock().unwrap().push(reminder);
+            async {}
+        }));
+        sent
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reminder_fires_while_still_pending() {
+        let (_tx, waiter) = waiter();
+        let sent = spawn_recording(StdDuration::from_secs(3600), waiter);
+
+        tokio::task::yield_now().await;
+        assert_eq!(*sent.lock().unwrap(), vec![false]);
+
+        // Just short of the reminder point nothing more is sent
+        tokio::time::sleep(
+            StdDuration::from_secs(3600) - APPROVAL_REMINDER_LEAD - StdDuration::from_secs(1),
+        )
+        .await;
+        assert_eq!(*sent.lock().unwrap(), vec![false]);
+
+        tokio::time::sleep(StdDuration::from_secs(2)).await;
+        assert_eq!(*sent.lock().unwrap(), vec![false, true]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_no_reminder_once_resolved() {
+        let (tx, waiter) = waiter();
+        let sent = spawn_recording(StdDuration::from_secs(3600), waiter);
+
+        tokio::task::yield_now().await;
+        assert_eq!(*sent.lock().unwrap(), vec![false]);
+
+        tx.send(ApprovalStatus::Approved).unwrap();
+        tokio::time::sleep(StdDuration::from_secs(3600)).await;
+        assert_eq!(*sent.lock().unwrap(), vec![false]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_short_approval_gets_no_reminder() {
+        let (_tx, waiter) = waiter();
+        let sent = spawn_recording(APPROVAL_REMINDER_LEAD / 2, waiter);
+
+        tokio::time::sleep(APPROVAL_REMINDER_LEAD).await;
+        assert_eq!(*sent.lock().unwrap(), vec![false]);
+    }
 }
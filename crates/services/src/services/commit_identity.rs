@@ -0,0 +1,237 @@
+//! Per-project identity, co-author trailers and signing for the commits we create.
+//!
+//! Git CLI commands receive the identity as `-c` config overrides, so commits made by
+//! `git commit`, `git merge`, `git rebase` and friends pick it up without touching the
+//! repository's own config. Commits written through libgit2 are signed explicitly with
+//! [`CommitIdentity::sign`].
+
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use db::models::project::{CommitSigningFormat, Project};
+use utils::{path::expand_tilde, shell::resolve_executable_path};
+
+/// Key used to sign commits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitSigningKey {
+    /// GPG key id or fingerprint
+    Gpg(String),
+    /// Path to an SSH key, or a literal public key (`key::ssh-ed25519 ...`) held by the agent
+    Ssh(String),
+}
+
+/// Identity applied to every commit `GitService` creates. The default leaves git's own
+/// configuration in charge.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitIdentity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// `Name <email>` entries added as `Co-authored-by` trailers
+    pub co_authors: Vec<String>,
+    pub signing_key: Option<CommitSigningKey>,
+}
+
+impl CommitIdentity {
+    pub fn from_project(project: &Project) -> Self {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let signing_key = non_empty(&project.commit_signing_key).and_then(|key| {
+            match project.commit_signing_format {
+                CommitSigningFormat::None => None,
+                CommitSigningFormat::Gpg => Some(CommitSigningKey::Gpg(key)),
+                CommitSigningFormat::Ssh => Some(CommitSigningKey::Ssh(key)),
+            }
+        });
+        Self {
+            name: non_empty(&project.commit_author_name),
+            email: non_empty(&project.commit_author_email),
+            co_authors: parse_co_authors(project.commit_co_authors.as_deref().unwrap_or_default()),
+            signing_key,
+        }
+    }
+
+    /// `git -c` overrides that make CLI commits use this identity.
+    pub fn cli_config(&self) -> Vec<(String, String)> {
+        let mut config = Vec::new();
+        if let Some(name) = &self.name {
+            config.push(("user.name".to_string(), name.clone()));
+        }
+        if let Some(email) = &self.email {
+            config.push(("user.email".to_string(), email.clone()));
+        }
+        match &self.signing_key {
+            Some(CommitSigningKey::Gpg(key)) => {
+                config.push(("gpg.format".to_string(), "openpgp".to_string()));
+                config.push(("user.signingKey".to_string(), key.clone()));
+                config.push(("commit.gpgSign".to_string(), "true".to_string()));
+            }
+            Some(CommitSigningKey::Ssh(key)) => {
+                let key = match key.strip_prefix("key::") {
+                    Some(_) => key.clone(),
+                    None if key.starts_with("ssh-") => format!("key::{key}"),
+                    None => expand_tilde(key).to_string_lossy().to_string(),
+                };
+                config.push(("gpg.format".to_string(), "ssh".to_string()));
+                config.push(("user.signingKey".to_string(), key));
+                config.push(("commit.gpgSign".to_string(), "true".to_string()));
+            }
+            None => {}
+        }
+        config
+    }
+
+    /// Append `Co-authored-by` trailers for co-authors the message doesn't name yet.
+    pub fn with_trailers(&self, message: &str) -> String {
+        let missing: Vec<&String> = self
+            .co_authors
+            .iter()
+            .filter(|co_author| !message.contains(&format!("Co-authored-by: {co_author}")))
+            .collect();
+        if missing.is_empty() {
+            return message.to_string();
+        }
+
+        let mut out = message.trim_end().to_string();
+        // Trailers form their own paragraph unless the message already ends with one
+        let ends_with_trailer = out.lines().last().is_some_and(|line| {
+            line.starts_with("Co-authored-by:") || line.starts_with("Signed-off-by:")
+        });
+        out.push_str(if ends_with_trailer { "\n" } else { "\n\n" });
+        let trailers: Vec<String> = missing
+            .into_iter()
+            .map(|co_author| format!("Co-authored-by: {co_author}"))
+            .collect();
+        out.push_str(&trailers.join("\n"));
+        out
+    }
+
+    /// Sign a raw commit buffer (as produced by `Repository::commit_create_buffer`).
+    /// Returns `None` when no signing key is configured.
+    pub fn sign(&self, buffer: &str) -> Result<Option<String>, std::io::Error> {
+        match &self.signing_key {
+            None => Ok(None),
+            Some(CommitSigningKey::Gpg(key)) => sign_with_gpg(key, buffer).map(Some),
+            Some(CommitSigningKey::Ssh(key)) => sign_with_ssh(key, buffer).map(Some),
+        }
+    }
+}
+
+/// Split the co-author setting into `Name <email>` entries, ignoring blank lines and an
+/// optional `Co-authored-by:` prefix.
+pub fn parse_co_authors(value: &str) -> Vec<String> {
+    value
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix("Co-authored-by:")
+                .map(str::trim)
+                .unwrap_or(line)
+        })
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Check project commit identity settings, returning a user-facing reason when invalid.
+pub fn validate_commit_identity(
+    email: Option<&str>,
+    co_authors: Option<&str>,
+    signing_format: CommitSigningFormat,
+    signing_key: Option<&str>,
+) -> Result<(), String> {
+    if let Some(email) = email.map(str::trim).filter(|e| !e.is_empty())
+        && (!email.contains('@') || email.contains(char::is_whitespace))
+    {
+        return Err(format!("Invalid commit author email '{email}'"));
+    }
+    for co_author in parse_co_authors(co_authors.unwrap_or_default()) {
+        let valid = co_author.split_once('<').is_some_and(|(name, rest)| {
+            !name.trim().is_empty() && rest.ends_with('>') && rest.contains('@')
+        });
+        if !valid {
+            return Err(format!(
+                "Invalid co-author '{co_author}': expected 'Name <email>'"
+            ));
+        }
+    }
+    if signing_format != CommitSigningFormat::None
+        && signing_key.is_none_or(|key| key.trim().is_empty())
+    {
+        return Err("A signing key is required to sign commits".to_string());
+    }
+    Ok(())
+}
+
+fn signing_command_failed(program: &str, stderr: &[u8]) -> std::io::Error {
+    std::io::Error::other(format!(
+        "{program} failed to sign commit: {}",
+        String::from_utf8_lossy(stderr).trim()
+    ))
+}
+
+fn sign_with_gpg(key: &str, buffer: &str) -> Result<String, std::io::Error> {
+    let gpg = resolve_executable_path("gpg")
+        .ok_or_else(|| std::io::Error::other("gpg not found on PATH"))?;
+    let mut child = Command::new(gpg)
+        .args(["--status-fd=2", "-bsau", key])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| std::io::Error::other("failed to open gpg stdin"))?
+        .write_all(buffer.as_bytes())?;
+    let out = child.wait_with_output()?;
+    if !out.status.success() {
+        return Err(signing_command_failed("gpg", &out.stderr));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+fn sign_with_ssh(key: &str, buffer: &str) -> Result<String, std::io::Error> {
+    let ssh_keygen = resolve_executable_path("ssh-keygen")
+        .ok_or_else(|| std::io::Error::other("ssh-keygen not found on PATH"))?;
+    let dir = tempfile::tempdir()?;
+
+    // Literal public keys are signed by the ssh-agent holding the private half
+    let literal = key
+        .strip_prefix("key::")
+        .or_else(|| key.starts_with("ssh-").then_some(key));
+    let key_path = match literal {
+        Some(public_key) => {
+            let path = dir.path().join("signing_key.pub");
+            std::fs::write(&path, public_key)?;
+            path
+        }
+        None => expand_tilde(key),
+    };
+
+    let payload = dir.path().join("commit");
+    std::fs::write(&payload, buffer)?;
+    let out = Command::new(ssh_keygen)
+        .args(["-Y", "sign", "-n", "git", "-f"])
+        .arg(&key_path)
+        .arg(&payload)
+        .stdin(Stdio::null())
+        .output()?;
+    if !out.status.success() {
+        return Err(signing_command_failed("ssh-keygen", &out.stderr));
+    }
+    std::fs::read_to_string(signature_path(&payload))
+}
+
+fn signature_path(payload: &Path) -> std::path::PathBuf {
+    let mut path = payload.as_os_str().to_owned();
+    path.push(".sig");
+    path.into()
+}
@@ -0,0 +1,185 @@
+//! Commit message templates, agent-provided messages and message validation.
+//!
+//! Templates use `{placeholder}` syntax. Unknown placeholders are left untouched so a
+//! typo shows up in the resulting commit instead of silently disappearing.
+
+use std::sync::LazyLock;
+
+use db::models::{project::CommitMessageValidation, task::Task, task_attempt::TaskAttempt};
+use regex::Regex;
+
+/// Message format used for direct merges when a project has no template configured.
+pub const DEFAULT_MERGE_COMMIT_TEMPLATE: &str =
+    "{task_title} (vibe-kanban {task_id_short})\n\n{task_description}";
+
+/// Values available to commit message templates.
+#[derive(Debug, Clone)]
+pub struct CommitMessageContext<'a> {
+    pub task: &'a Task,
+    pub task_attempt: &'a TaskAttempt,
+    /// The coding agent's final message, when rendering a commit for an agent run
+    pub summary: Option<&'a str>,
+}
+
+impl CommitMessageContext<'_> {
+    fn placeholder(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "task_title" => self.task.title.clone(),
+            "task_description" => self.task.description.clone().unwrap_or_default(),
+            "task_id" => self.task.id.to_string(),
+            "task_id_short" => short_id(&self.task.id.to_string()),
+            "attempt_id" => self.task_attempt.id.to_string(),
+            "attempt_id_short" => short_id(&self.task_attempt.id.to_string()),
+            "branch" => self.task_attempt.branch.clone(),
+            "target_branch" => self.task_attempt.target_branch.clone(),
+            "executor" => self.task_attempt.executor.clone(),
+            "summary" => self.summary.unwrap_or_default().trim().to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+fn short_id(id: &str) -> String {
+    id.split('-').next().unwrap_or(id).to_string()
+}
+
+/// Render `template` for the given task attempt. Trailing whitespace is trimmed so
+/// optional trailing placeholders (e.g. an empty description) leave no blank lines.
+pub fn render_commit_message(template: &str, ctx: &CommitMessageContext<'_>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}') {
+            Some(close) => match ctx.placeholder(&after[..close]) {
+                Some(value) => {
+                    out.push_str(&value);
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            },
+            None => {
+                out.push_str(&rest[open..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out.trim_end().to_string()
+}
+
+/// Render the message for a direct merge, falling back to the default template when the
+/// project has none (or an empty one) configured.
+pub fn merge_commit_message(template: Option<&str>, ctx: &CommitMessageContext<'_>) -> String {
+    let template = template
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(DEFAULT_MERGE_COMMIT_TEMPLATE);
+    render_commit_message(template, ctx)
+}
+
+/// Prefix of the line a coding agent uses to hand back its commit message.
+pub const COMMIT_MESSAGE_MARKER: &str = "COMMIT_MESSAGE:";
+
+/// Append the instruction asking the agent to finish with a conventional-commit message.
+pub fn with_conventional_commit_request(prompt: &str) -> String {
+    format!(
+        "{prompt}\n\nWhen you are done, end your final message with a single line of the form \
+         `{COMMIT_MESSAGE_MARKER} <type>(<optional scope>): <description>` following the \
+         Conventional Commits specification (e.g. `{COMMIT_MESSAGE_MARKER} fix(api): handle \
+         empty payloads`). Do not create any commits yourself."
+    )
+}
+
+/// Extract the commit message an agent announced with [`COMMIT_MESSAGE_MARKER`]. The last
+/// marker wins, since agents sometimes restate the instruction before answering it.
+pub fn extract_commit_message(summary: &str) -> Option<String> {
+    summary
+        .lines()
+        .rev()
+        .filter_map(|line| {
+            let line = line.trim().trim_start_matches(['*', '-', '`']).trim_start();
+            line.strip_prefix(COMMIT_MESSAGE_MARKER)
+        })
+        .map(|msg| msg.trim().trim_matches(['`', '*']).trim().to_string())
+        .find(|msg| !msg.is_empty())
+}
+
+static CONVENTIONAL_COMMIT_SUBJECT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z]+(\([^()\r\n]+\))?!?: \S").unwrap());
+
+/// Whether `message` follows the Conventional Commits format: a `type(scope)!: subject`
+/// header, optionally followed by a blank line and a body.
+pub fn is_conventional_commit(message: &str) -> bool {
+    let mut lines = message.lines();
+    let Some(subject) = lines.next() else {
+        return false;
+    };
+    CONVENTIONAL_COMMIT_SUBJECT.is_match(subject)
+        && lines.next().is_none_or(|line| line.trim().is_empty())
+}
+
+/// Check a project's commit message rules before they are saved: a regex rule needs a
+/// pattern that compiles, since an empty one would match every message.
+pub fn validate_commit_message_settings(
+    validation: CommitMessageValidation,
+    pattern: Option<&str>,
+) -> Result<(), String> {
+    match pattern.filter(|p| !p.trim().is_empty()) {
+        Some(pattern) => Regex::new(pattern)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid commit message pattern: {e}")),
+        None if validation == CommitMessageValidation::Regex => {
+            Err("Regex commit message validation needs a pattern".to_string())
+        }
+        None => Ok(()),
+    }
+}
+
+/// The commit message a pull request squash-merges as: its title, then its body.
+pub fn pull_request_commit_message(title: &str, body: Option<&str>) -> String {
+    match body.map(str::trim).filter(|body| !body.is_empty()) {
+        Some(body) => format!("{}\n\n{body}", title.trim()),
+        None => title.trim().to_string(),
+    }
+}
+
+/// Validate `message` against a project's commit message rules, returning a
+/// user-facing reason when it doesn't comply.
+pub fn validate_commit_message(
+    validation: CommitMessageValidation,
+    pattern: Option<&str>,
+    message: &str,
+) -> Result<(), String> {
+    let subject = message.lines().next().unwrap_or_default();
+    match validation {
+        CommitMessageValidation::None => Ok(()),
+        CommitMessageValidation::ConventionalCommit => {
+            if is_conventional_commit(message) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Commit message '{subject}' does not follow the Conventional Commits format"
+                ))
+            }
+        }
+        CommitMessageValidation::Regex => {
+            let Some(pattern) = pattern.filter(|p| !p.trim().is_empty()) else {
+                return Err("Regex commit message validation has no pattern configured".into());
+            };
+            let re =
+                Regex::new(pattern).map_err(|e| format!("Invalid commit message pattern: {e}"))?;
+            if re.is_match(message) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Commit message '{subject}' does not match the pattern '{pattern}'"
+                ))
+            }
+        }
+    }
+}
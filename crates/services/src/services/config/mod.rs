@@ -16,6 +16,10 @@ pub enum ConfigError {
 
 pub type Config = versions::v7::Config;
 pub type NotificationConfig = versions::v7::NotificationConfig;
+pub type NotificationChannelConfig = versions::v7::NotificationChannelConfig;
+pub type NotificationChannelKind = versions::v7::NotificationChannelKind;
+pub type NotificationEvent = versions::v7::NotificationEvent;
+pub type SmtpSecurity = versions::v7::SmtpSecurity;
 pub type EditorConfig = versions::v7::EditorConfig;
 pub type ThemeMode = versions::v7::ThemeMode;
 pub type SoundFile = versions::v7::SoundFile;
@@ -3,7 +3,7 @@ use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumString;
 use ts_rs::TS;
-pub use v6::{EditorConfig, EditorType, GitHubConfig, NotificationConfig, SoundFile, UiLanguage};
+pub use v6::{EditorConfig, EditorType, GitHubConfig, SoundFile, UiLanguage};
 
 use crate::services::config::versions::v6;
 
@@ -17,6 +17,106 @@ pub struct ShowcaseState {
     pub seen_features: Vec<String>,
 }
 
+/// Events that can be routed to notification channels
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    AttemptCompleted,
+    AttemptFailed,
+    ApprovalPending,
+    PrMerged,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plain text, only sensible for relays on the local machine
+    None,
+    /// Upgrade the connection with STARTTLS, usually on port 587
+    #[default]
+    Starttls,
+    /// TLS from the start, usually on port 465
+    Tls,
+}
+
+/// Where a notification channel sends messages
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationChannelKind {
+    /// Slack incoming webhook
+    Slack { webhook_url: String },
+    /// Discord channel webhook
+    Discord { webhook_url: String },
+    Ntfy {
+        server_url: String,
+        topic: String,
+        /// Access token for protected topics
+        token: Option<String>,
+    },
+    Gotify {
+        server_url: String,
+        /// Application token
+        token: String,
+    },
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        #[serde(default)]
+        security: SmtpSecurity,
+        username: Option<String>,
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct NotificationChannelConfig {
+    pub name: String,
+    pub enabled: bool,
+    /// Events sent to this channel, empty for all
+    #[serde(default)]
+    pub events: Vec<NotificationEvent>,
+    pub channel: NotificationChannelKind,
+}
+
+impl NotificationChannelConfig {
+    pub fn accepts(&self, event: NotificationEvent) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.contains(&event))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct NotificationConfig {
+    pub sound_enabled: bool,
+    pub push_enabled: bool,
+    pub sound_file: SoundFile,
+    /// Chat, push and email channels that also receive notifications
+    #[serde(default)]
+    pub channels: Vec<NotificationChannelConfig>,
+    /// Address of this instance used for links in notifications, defaults to the local server
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+impl From<v6::NotificationConfig> for NotificationConfig {
+    fn from(old: v6::NotificationConfig) -> Self {
+        Self {
+            sound_enabled: old.sound_enabled,
+            push_enabled: old.push_enabled,
+            sound_file: old.sound_file,
+            channels: Vec::new(),
+            base_url: None,
+        }
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self::from(v6::NotificationConfig::default())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS, EnumString)]
 #[ts(use_ts_enum)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -40,6 +140,9 @@ pub struct Config {
     pub editor: EditorConfig,
     pub github: GitHubConfig,
     pub analytics_enabled: Option<bool>,
+    /// Also keep analytics events in the local database, regardless of `analytics_enabled`
+    #[serde(default)]
+    pub local_analytics_enabled: bool,
     pub workspace_dir: Option<String>,
     pub last_app_version: Option<String>,
     pub show_release_notes: bool,
@@ -89,10 +192,11 @@ impl Config {
             onboarding_acknowledged: old_config.onboarding_acknowledged,
             github_login_acknowledged: old_config.github_login_acknowledged,
             telemetry_acknowledged: old_config.telemetry_acknowledged,
-            notifications: old_config.notifications,
+            notifications: NotificationConfig::from(old_config.notifications),
             editor: old_config.editor,
             github: old_config.github,
             analytics_enabled: old_config.analytics_enabled,
+            local_analytics_enabled: false,
             workspace_dir: old_config.workspace_dir,
             last_app_version: old_config.last_app_version,
             show_release_notes: old_config.show_release_notes,
@@ -138,6 +242,7 @@ impl Default for Config {
             editor: EditorConfig::default(),
             github: GitHubConfig::default(),
             analytics_enabled: None,
+            local_analytics_enabled: false,
             workspace_dir: None,
             last_app_version: None,
             show_release_notes: false,
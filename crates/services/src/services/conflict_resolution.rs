@@ -0,0 +1,89 @@
+//! Prompt generation for agent-assisted conflict resolution.
+//!
+//! When a rebase or merge stops on conflicts, the attempt's coding agent can be
+//! asked to resolve them in place. The agent only edits files; staging and
+//! continuing the operation is done by `GitService::continue_conflicts` after the
+//! run completes, so the prompt explicitly tells the agent not to touch git state.
+
+use super::git::{ConflictHunk, ConflictOp};
+
+// Keep the inlined hunks bounded so huge conflicts don't blow the agent's context.
+// Files beyond the budget are still listed by path.
+const MAX_PROMPT_HUNK_BYTES: usize = 64 * 1024;
+
+fn op_description(op: &ConflictOp) -> &'static str {
+    match op {
+        ConflictOp::Rebase => "a rebase",
+        ConflictOp::Merge => "a merge",
+        ConflictOp::CherryPick => "a cherry-pick",
+        ConflictOp::Revert => "a revert",
+    }
+}
+
+/// Build the follow-up prompt asking the agent to resolve the given conflicts.
+pub fn build_conflict_resolution_prompt(
+    op: &ConflictOp,
+    attempt_branch: &str,
+    target_branch: &str,
+    conflicted_files: &[String],
+    hunks: &[ConflictHunk],
+) -> String {
+    let mut prompt = format!(
+        "Git stopped with merge conflicts during {} while bringing '{target_branch}' into branch '{attempt_branch}'.\n\n",
+        op_description(op),
+    );
+    prompt.push_str(
+        "Resolve every conflict below by editing the files so they contain the correct combined result, \
+         keeping the intent of both sides. Remove all conflict markers (<<<<<<<, |||||||, =======, >>>>>>>).\n\
+         Do not run `git add`, `git commit`, `git rebase` or `git merge` yourself; the operation is \
+         continued automatically once you finish and no markers remain.\n\n",
+    );
+
+    prompt.push_str("Conflicted files:\n");
+    for file in conflicted_files {
+        prompt.push_str(&format!("- {file}\n"));
+    }
+    if matches!(op, ConflictOp::Rebase) {
+        prompt.push_str(
+            "\nDuring a rebase, \"ours\" is the updated base branch and \"theirs\" is the attempt's commit being replayed.\n",
+        );
+    }
+
+    let mut budget = MAX_PROMPT_HUNK_BYTES;
+    let mut omitted = 0usize;
+    for hunk in hunks {
+        let rendered = render_hunk(hunk);
+        if rendered.len() > budget {
+            omitted += 1;
+            continue;
+        }
+        budget -= rendered.len();
+        prompt.push('\n');
+        prompt.push_str(&rendered);
+    }
+    if omitted > 0 {
+        prompt.push_str(&format!(
+            "\n{omitted} more conflict hunk(s) were omitted for length; open the files above to find them.\n"
+        ));
+    }
+    prompt
+}
+
+fn render_hunk(hunk: &ConflictHunk) -> String {
+    let mut out = format!(
+        "### {} (lines {}-{})\n",
+        hunk.path, hunk.start_line, hunk.end_line
+    );
+    out.push_str("Ours:\n```\n");
+    out.push_str(&hunk.ours);
+    out.push_str("```\n");
+    if let Some(base) = &hunk.base {
+        out.push_str("Common ancestor:\n```\n");
+        out.push_str(base);
+        out.push_str("```\n");
+    }
+    out.push_str("Theirs:\n```\n");
+    out.push_str(&hunk.theirs);
+    out.push_str("```\n");
+    out
+}
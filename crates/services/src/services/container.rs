@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -13,8 +14,11 @@ use db::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
         },
-        execution_process_logs::ExecutionProcessLogs,
+        execution_process_logs::{
+            ExecutionProcessLogs, LOG_CHUNK_MAX_BYTES, LOG_CHUNK_MAX_ENTRIES,
+        },
         executor_session::{CreateExecutorSession, ExecutorSession},
+        project::Project,
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
@@ -30,7 +34,7 @@ use executors::{
     profile::{ExecutorConfigs, ExecutorProfileId, to_default_variant},
 };
 use futures::{StreamExt, future};
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
 use utils::{
@@ -41,18 +45,44 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    copy_files::CopyFilesReport,
+    dev_server::DevServers,
     git::{GitService, GitServiceError},
     image::ImageService,
+    project_scope::ProjectScope,
+    workspace::AttemptWorkspace,
     worktree_manager::{WorktreeError, WorktreeManager},
+    worktree_snapshot,
 };
 pub type ContainerRef = String;
 
+/// Longest a running process's log entries wait in memory before being written
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Write buffered log lines as one chunk and clear them
+async fn flush_log_chunk(pool: &SqlitePool, execution_id: Uuid, pending: &mut Vec<String>) {
+    if pending.is_empty() {
+        return;
+    }
+    if let Err(e) = ExecutionProcessLogs::append_chunk(pool, execution_id, pending).await {
+        tracing::error!(
+            "Failed to write {} log entries for execution {}: {}",
+            pending.len(),
+            execution_id,
+            e
+        );
+    }
+    pending.clear();
+}
+
 /// Data needed for background worktree cleanup (doesn't require DB access)
 #[derive(Debug, Clone)]
 pub struct WorktreeCleanupData {
     pub attempt_id: Uuid,
     pub worktree_path: PathBuf,
     pub git_repo_path: Option<PathBuf>,
+    /// Workspace directory of a multi-repository attempt, removed once its worktrees are
+    pub workspace_root: Option<PathBuf>,
 }
 
 /// Cleanup worktrees without requiring database access
@@ -77,6 +107,28 @@ pub async fn cleanup_worktrees_direct(data: &[WorktreeCleanupData]) -> Result<()
             );
             // Continue with other cleanups even if one fails
         }
+        if let Err(e) = worktree_snapshot::discard_attempt_snapshots(
+            &worktree_snapshot::snapshot_dir(),
+            cleanup_data.attempt_id,
+        ) {
+            tracing::warn!(
+                "Failed to discard snapshots of task attempt {}: {}",
+                cleanup_data.attempt_id,
+                e
+            );
+        }
+    }
+    // Workspace directories go last, once every worktree inside them is cleaned up
+    let workspace_roots: std::collections::HashSet<&PathBuf> = data
+        .iter()
+        .filter_map(|cleanup_data| cleanup_data.workspace_root.as_ref())
+        .collect();
+    for root in workspace_roots {
+        if let Err(e) = tokio::fs::remove_dir_all(root).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove workspace {}: {}", root.display(), e);
+        }
     }
     Ok(())
 }
@@ -109,8 +161,43 @@ pub trait ContainerService {
 
     fn git(&self) -> &GitService;
 
+    fn dev_servers(&self) -> &DevServers;
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf;
 
+    /// Directory the attempt's coding agents run in: its worktree or the project's working
+    /// directory inside it, or the workspace holding all of its worktrees when the project
+    /// owns several repositories
+    async fn task_attempt_to_execution_dir(&self, task_attempt: &TaskAttempt) -> PathBuf {
+        let worktree_path = self.task_attempt_to_current_dir(task_attempt);
+        let pool = &self.db().pool;
+        let agent_dir = async {
+            let Some(task) = task_attempt.parent_task(pool).await? else {
+                return Ok(None);
+            };
+            let Some(project) = task.parent_project(pool).await? else {
+                return Ok(None);
+            };
+            let workspace = AttemptWorkspace::load(pool, &project, task_attempt).await?;
+            Ok::<_, SqlxError>(
+                workspace
+                    .map(|workspace| workspace.agent_dir(&ProjectScope::from_project(&project))),
+            )
+        };
+        match agent_dir.await {
+            Ok(Some(dir)) => dir,
+            Ok(None) => worktree_path,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load the workspace of task attempt {}: {}",
+                    task_attempt.id,
+                    e
+                );
+                worktree_path
+            }
+        }
+    }
+
     async fn create(&self, task_attempt: &TaskAttempt) -> Result<ContainerRef, ContainerError>;
 
     async fn delete(&self, task_attempt: &TaskAttempt) -> Result<(), ContainerError> {
@@ -155,6 +242,7 @@ pub trait ContainerService {
                     script,
                     language: ScriptRequestLanguage::Bash,
                     context: ScriptContext::CleanupScript,
+                    env: HashMap::new(),
                 }),
                 None,
             ))
@@ -206,12 +294,13 @@ pub trait ContainerService {
 
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
+    /// Copy the project's `copy_files` from the repository into a new worktree
     async fn copy_project_files(
         &self,
-        source_dir: &Path,
-        target_dir: &Path,
-        copy_files: &str,
-    ) -> Result<(), ContainerError>;
+        project: &Project,
+        task_attempt: &TaskAttempt,
+        worktree_path: &Path,
+    ) -> Result<CopyFilesReport, ContainerError>;
 
     /// Stream diff updates as LogMsg for WebSocket endpoints.
     async fn stream_diff(
@@ -257,34 +346,28 @@ pub trait ContainerService {
                     .boxed(),
             );
         } else {
-            // Fallback: load from DB and create direct stream
-            let logs_record =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(Some(record)) => record,
-                    Ok(None) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
-
-            let messages = match logs_record.parse_logs() {
-                Ok(msgs) => msgs,
+            // Fallback: stream from the stored log chunks
+            match ExecutionProcessLogs::exists(&self.db().pool, *id).await {
+                Ok(true) => {}
+                Ok(false) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
-            };
+            }
 
-            // Direct stream from parsed messages
-            let stream = futures::stream::iter(
-                messages
-                    .into_iter()
-                    .filter(|m| matches!(m, LogMsg::Stdout(_) | LogMsg::Stderr(_)))
-                    .chain(std::iter::once(LogMsg::Finished))
-                    .map(Ok::<_, std::io::Error>),
-            )
-            .boxed();
+            let stream = ExecutionProcessLogs::stream_entries(self.db().pool.clone(), *id, 0)
+                .filter_map(|entry| {
+                    future::ready(match entry {
+                        Ok(msg @ (LogMsg::Stdout(_) | LogMsg::Stderr(_))) => Some(Ok(msg)),
+                        Ok(_) => None,
+                        Err(e) => Some(Err(std::io::Error::other(e))),
+                    })
+                })
+                .chain(futures::stream::once(async {
+                    Ok::<_, std::io::Error>(LogMsg::Finished)
+                }))
+                .boxed();
 
             Some(stream)
         }
@@ -306,33 +389,15 @@ pub trait ContainerService {
                     .boxed(),
             )
         } else {
-            // Fallback: load from DB and normalize
-            let logs_record =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(Some(record)) => record,
-                    Ok(None) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
-
-            let raw_messages = match logs_record.parse_logs() {
-                Ok(msgs) => msgs,
+            // Fallback: load from the stored log chunks and normalize
+            match ExecutionProcessLogs::exists(&self.db().pool, *id).await {
+                Ok(true) => {}
+                Ok(false) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
-            };
-
-            // Create temporary store and populate
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_)) {
-                    temp_store.push(msg);
-                }
             }
-            temp_store.push_finished();
 
             let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
                 Ok(Some(process)) => process,
@@ -371,38 +436,8 @@ pub trait ContainerService {
                 );
             }
 
-            let current_dir = self.task_attempt_to_current_dir(&task_attempt);
-
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
-                tracing::error!(
-                    "Failed to parse executor action: {:?}",
-                    process.executor_action()
-                );
-                return None;
-            };
-
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
-                }
-            }
+            let current_dir = self.task_attempt_to_execution_dir(&task_attempt).await;
+            let temp_store = self.normalize_stored_logs(&process, &current_dir).await?;
             Some(
                 temp_store
                     .history_plus_stream()
@@ -415,6 +450,56 @@ pub trait ContainerService {
         }
     }
 
+    /// Load a process's stored logs into a new store and spawn its executor's normalizer on
+    /// it. Nothing is done to the worktree, so paths are only as accurate as `current_dir`.
+    async fn normalize_stored_logs(
+        &self,
+        process: &ExecutionProcess,
+        current_dir: &Path,
+    ) -> Option<Arc<MsgStore>> {
+        let executor_action = if let Ok(executor_action) = process.executor_action() {
+            executor_action
+        } else {
+            tracing::error!(
+                "Failed to parse executor action: {:?}",
+                process.executor_action()
+            );
+            return None;
+        };
+        let profile_id = match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => &request.executor_profile_id,
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => &request.executor_profile_id,
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
+                    process.executor_action()
+                );
+                return None;
+            }
+        };
+
+        // Create temporary store and populate
+        let temp_store = Arc::new(MsgStore::new());
+        let mut entries =
+            ExecutionProcessLogs::stream_entries(self.db().pool.clone(), process.id, 0);
+        while let Some(entry) = entries.next().await {
+            match entry {
+                Ok(msg @ (LogMsg::Stdout(_) | LogMsg::Stderr(_))) => temp_store.push(msg),
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("Failed to read logs for execution {}: {}", process.id, e);
+                    return None;
+                }
+            }
+        }
+        temp_store.push_finished();
+
+        // Spawn normalizer on populated store
+        let executor = ExecutorConfigs::get_cached().get_coding_agent_or_default(profile_id);
+        executor.normalize_logs(temp_store.clone(), current_dir);
+        Some(temp_store)
+    }
+
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
         let execution_id = *execution_id;
         let msg_stores = self.msg_stores().clone();
@@ -429,28 +514,35 @@ pub trait ContainerService {
 
             if let Some(store) = store {
                 let mut stream = store.history_plus_stream();
+                // Entries are written in chunks, flushed when full or after a short wait
+                let mut pending: Vec<String> = Vec::new();
+                let mut pending_bytes = 0;
+                let mut flush_interval = tokio::time::interval(LOG_FLUSH_INTERVAL);
+
+                loop {
+                    let msg = tokio::select! {
+                        msg = stream.next() => match msg {
+                            Some(Ok(msg)) => msg,
+                            _ => break,
+                        },
+                        _ = flush_interval.tick() => {
+                            flush_log_chunk(&db.pool, execution_id, &mut pending).await;
+                            pending_bytes = 0;
+                            continue;
+                        }
+                    };
 
-                while let Some(Ok(msg)) = stream.next().await {
                     match &msg {
                         LogMsg::Stdout(_) | LogMsg::Stderr(_) => {
-                            // Serialize this individual message as a JSONL line
                             match serde_json::to_string(&msg) {
-                                Ok(jsonl_line) => {
-                                    let jsonl_line_with_newline = format!("{jsonl_line}\n");
-
-                                    // Append this line to the database
-                                    if let Err(e) = ExecutionProcessLogs::append_log_line(
-                                        &db.pool,
-                                        execution_id,
-                                        &jsonl_line_with_newline,
-                                    )
-                                    .await
+                                Ok(line) => {
+                                    pending_bytes += line.len();
+                                    pending.push(line);
+                                    if pending.len() >= LOG_CHUNK_MAX_ENTRIES
+                                        || pending_bytes >= LOG_CHUNK_MAX_BYTES
                                     {
-                                        tracing::error!(
-                                            "Failed to append log line for execution {}: {}",
-                                            execution_id,
-                                            e
-                                        );
+                                        flush_log_chunk(&db.pool, execution_id, &mut pending).await;
+                                        pending_bytes = 0;
                                     }
                                 }
                                 Err(e) => {
@@ -485,6 +577,7 @@ pub trait ContainerService {
                         LogMsg::JsonPatch(_) => continue,
                     }
                 }
+                flush_log_chunk(&db.pool, execution_id, &mut pending).await;
             }
         })
     }
@@ -532,6 +625,7 @@ pub trait ContainerService {
                     script: setup_script,
                     language: ScriptRequestLanguage::Bash,
                     context: ScriptContext::SetupScript,
+                    env: HashMap::new(),
                 }),
                 // once the setup script is done, run the initial coding agent request
                 Some(Box::new(ExecutorAction::new(
@@ -568,6 +662,10 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    #[tracing::instrument(
+        skip_all,
+        fields(task_attempt_id = %task_attempt.id, run_reason = ?run_reason)
+    )]
     async fn start_execution(
         &self,
         task_attempt: &TaskAttempt,
@@ -655,13 +753,12 @@ pub trait ContainerService {
 
             // Persist the error to process stderr logs
             let log_message = LogMsg::Stderr(format!("Failed to start execution: {start_error}"));
-            if let Ok(json_line) = serde_json::to_string(&log_message)
-                && let Err(err) = ExecutionProcessLogs::append_log_line(
-                    &self.db().pool,
-                    execution_process.id,
-                    &format!("{json_line}\n"),
-                )
-                .await
+            if let Err(err) = ExecutionProcessLogs::append_entry(
+                &self.db().pool,
+                execution_process.id,
+                &log_message,
+            )
+            .await
             {
                 tracing::error!(
                     "Failed to write to process log table {}: {}",
@@ -674,16 +771,14 @@ pub trait ContainerService {
         }
 
         // Start processing normalised logs for executor requests and follow ups
+        let execution_dir = self.task_attempt_to_execution_dir(task_attempt).await;
         match executor_action.typ() {
             ExecutorActionType::CodingAgentInitialRequest(request) => {
                 if let Some(msg_store) = self.get_msg_store_by_id(&execution_process.id).await {
                     if let Some(executor) =
                         ExecutorConfigs::get_cached().get_coding_agent(&request.executor_profile_id)
                     {
-                        executor.normalize_logs(
-                            msg_store,
-                            &self.task_attempt_to_current_dir(task_attempt),
-                        );
+                        executor.normalize_logs(msg_store, &execution_dir);
                     } else {
                         tracing::error!(
                             "Failed to resolve profile '{:?}' for normalization",
@@ -697,10 +792,7 @@ pub trait ContainerService {
                     if let Some(executor) =
                         ExecutorConfigs::get_cached().get_coding_agent(&request.executor_profile_id)
                     {
-                        executor.normalize_logs(
-                            msg_store,
-                            &self.task_attempt_to_current_dir(task_attempt),
-                        );
+                        executor.normalize_logs(msg_store, &execution_dir);
                     } else {
                         tracing::error!(
                             "Failed to resolve profile '{:?}' for normalization",
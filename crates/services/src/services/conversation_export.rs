@@ -0,0 +1,699 @@
+//! Export of agent conversations.
+//!
+//! Normalized logs only exist as JSON patches over a list of entries. `entries_from_patches`
+//! replays them into the final `NormalizedEntry` list, which `ConversationExport` renders as
+//! Markdown, a standalone HTML page or schema-versioned JSON.
+
+use std::{fmt::Write, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task::Task,
+    task_attempt::TaskAttempt,
+};
+use executors::{
+    actions::ExecutorActionType,
+    logs::{
+        ActionType, CommandExitStatus, FileChange, NormalizedEntry, NormalizedEntryType,
+        ToolResultValueType, ToolStatus,
+    },
+};
+use json_patch::Patch;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use ts_rs::TS;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+use super::container::ContainerService;
+
+/// Bumped whenever the JSON export changes incompatibly
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// How long a normalizer gets to process a whole stored log
+const NORMALIZER_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Html => "text/html; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// One execution process of an exported conversation
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedProcess {
+    pub id: Uuid,
+    pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    pub executor: Option<String>,
+    /// Prompt the process was started with
+    pub prompt: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub entries: Vec<NormalizedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationExport {
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub task_attempt_id: Uuid,
+    pub branch: String,
+    pub processes: Vec<ExportedProcess>,
+}
+
+/// Replay normalized log patches and return the resulting conversation entries
+pub fn entries_from_patches<'a>(
+    patches: impl IntoIterator<Item = &'a Patch>,
+) -> Vec<NormalizedEntry> {
+    indexed_entries_from_patches(patches)
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+/// Like `entries_from_patches`, but each entry comes with its position in the normalized log,
+/// which counts stdout and stderr entries too
+pub fn indexed_entries_from_patches<'a>(
+    patches: impl IntoIterator<Item = &'a Patch>,
+) -> Vec<(usize, NormalizedEntry)> {
+    let mut doc = json!({ "entries": [] });
+    for patch in patches {
+        if let Err(e) = json_patch::patch(&mut doc, &patch.0) {
+            tracing::debug!("Skipping normalized log patch that does not apply: {}", e);
+        }
+    }
+
+    let Value::Object(mut doc) = doc else {
+        return Vec::new();
+    };
+    let Some(Value::Array(entries)) = doc.remove("entries") else {
+        return Vec::new();
+    };
+    entries
+        .into_iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.get("type").and_then(Value::as_str) == Some("NORMALIZED_ENTRY"))
+        .filter_map(|(index, mut entry)| {
+            Some((
+                index,
+                serde_json::from_value(entry.get_mut("content")?.take()).ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Patches of the process's normalized log as it stands, without waiting for a running
+/// process to finish or recreating the worktree of a finished one. Empty if the log couldn't
+/// be normalized.
+pub async fn process_patches<C>(container: &C, process: &ExecutionProcess) -> Vec<Patch>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    match container.get_msg_store_by_id(&process.id).await {
+        Some(store) => json_patches(store.get_history()),
+        None => stored_process_patches(container, process)
+            .await
+            .unwrap_or_default(),
+    }
+}
+
+/// Patches of a finished process's whole normalized log, from its stored logs. `None` if they
+/// couldn't be read or normalizing them didn't finish.
+pub async fn stored_process_patches<C>(
+    container: &C,
+    process: &ExecutionProcess,
+) -> Option<Vec<Patch>>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let task_attempt = match process.parent_task_attempt(&container.db().pool).await {
+        Ok(task_attempt) => task_attempt?,
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch task attempt {}: {}",
+                process.task_attempt_id,
+                e
+            );
+            return None;
+        }
+    };
+    let current_dir = container.task_attempt_to_execution_dir(&task_attempt).await;
+    let store = container.normalize_stored_logs(process, &current_dir).await?;
+    drained_patches(process.id, store).await
+}
+
+/// Patches of a store's normalized log once every task writing to it has finished, giving up
+/// after `NORMALIZER_TIMEOUT`. The caller must have pushed `LogMsg::Finished` so that the
+/// normalizer's streams end.
+pub async fn drained_patches(execution_id: Uuid, store: Arc<MsgStore>) -> Option<Vec<Patch>> {
+    let history = store.history_when_dropped();
+    drop(store);
+    match tokio::time::timeout(NORMALIZER_TIMEOUT, history).await {
+        Ok(Ok(history)) => Some(json_patches(history)),
+        Ok(Err(_)) => None,
+        Err(_) => {
+            tracing::warn!(
+                "Normalizing the logs of execution {} did not finish in {:?}",
+                execution_id,
+                NORMALIZER_TIMEOUT
+            );
+            None
+        }
+    }
+}
+
+fn json_patches(history: Vec<LogMsg>) -> Vec<Patch> {
+    history
+        .into_iter()
+        .filter_map(|msg| match msg {
+            LogMsg::JsonPatch(patch) => Some(patch),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The process's conversation entries
+pub async fn process_entries<C>(container: &C, process: &ExecutionProcess) -> Vec<NormalizedEntry>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    entries_from_patches(&process_patches(container, process).await)
+}
+
+/// Export the given processes of an attempt, in order
+pub async fn export_conversation<C>(
+    container: &C,
+    task: &Task,
+    task_attempt: &TaskAttempt,
+    processes: &[ExecutionProcess],
+) -> ConversationExport
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let mut exported = Vec::with_capacity(processes.len());
+    for process in processes {
+        let action = process.executor_action().ok();
+        let prompt = action.and_then(|action| match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => Some(request.prompt.clone()),
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => Some(request.prompt.clone()),
+            ExecutorActionType::ScriptRequest(_) => None,
+        });
+        exported.push(ExportedProcess {
+            id: process.id,
+            run_reason: process.run_reason.clone(),
+            status: process.status.clone(),
+            executor: action
+                .and_then(|action| action.base_executor())
+                .map(|executor| executor.to_string()),
+            prompt,
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+            entries: process_entries(container, process).await,
+        });
+    }
+
+    ConversationExport {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        exported_at: Utc::now(),
+        task_id: task.id,
+        task_title: task.title.clone(),
+        task_attempt_id: task_attempt.id,
+        branch: task_attempt.branch.clone(),
+        processes: exported,
+    }
+}
+
+fn run_reason_label(reason: &ExecutionProcessRunReason) -> &'static str {
+    match reason {
+        ExecutionProcessRunReason::SetupScript => "Setup script",
+        ExecutionProcessRunReason::CleanupScript => "Cleanup script",
+        ExecutionProcessRunReason::CodingAgent => "Coding agent",
+        ExecutionProcessRunReason::DevServer => "Dev server",
+    }
+}
+
+fn status_label(status: &ExecutionProcessStatus) -> &'static str {
+    match status {
+        ExecutionProcessStatus::Running => "running",
+        ExecutionProcessStatus::Completed => "completed",
+        ExecutionProcessStatus::Failed => "failed",
+        ExecutionProcessStatus::Killed => "killed",
+    }
+}
+
+/// Outcome of a tool use, including how its approval went
+fn tool_status_label(status: &ToolStatus) -> Option<String> {
+    match status {
+        ToolStatus::Created => None,
+        ToolStatus::Success => Some("succeeded".to_string()),
+        ToolStatus::Failed => Some("failed".to_string()),
+        ToolStatus::Denied {
+            reason: Some(reason),
+        } => Some(format!("denied: {reason}")),
+        ToolStatus::Denied { reason: None } => Some("denied".to_string()),
+        ToolStatus::PendingApproval { .. } => Some("awaiting approval".to_string()),
+        ToolStatus::TimedOut => Some("approval timed out".to_string()),
+    }
+}
+
+fn exit_status_label(status: &CommandExitStatus) -> String {
+    match status {
+        CommandExitStatus::ExitCode { code } => format!("exit code {code}"),
+        CommandExitStatus::Success { success: true } => "succeeded".to_string(),
+        CommandExitStatus::Success { success: false } => "failed".to_string(),
+    }
+}
+
+/// A tool result as text, unwrapping Markdown results stored as JSON strings
+fn tool_result_text(r#type: &ToolResultValueType, value: &Value) -> (String, &'static str) {
+    match (r#type, value) {
+        (ToolResultValueType::Markdown, Value::String(text)) => (text.clone(), ""),
+        _ => (
+            serde_json::to_string_pretty(value).unwrap_or_default(),
+            "json",
+        ),
+    }
+}
+
+/// A fenced code block whose fence is longer than any backtick run in `content`
+fn code_block(content: &str, lang: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "{fence}{lang}\n{}\n{fence}\n\n",
+        content.trim_end_matches('\n')
+    )
+}
+
+fn blockquote(label: &str, content: &str) -> String {
+    let mut out = format!("> **{label}:**");
+    for line in content.lines() {
+        out.push_str("\n> ");
+        out.push_str(line);
+    }
+    out.push_str("\n\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+const HTML_STYLE: &str = "
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; max-width: 56rem; margin: 2rem auto; padding: 0 1rem; color: #1f2328; line-height: 1.5; }
+header { border-bottom: 1px solid #d0d7de; margin-bottom: 1.5rem; }
+.meta { color: #656d76; font-size: 0.875rem; }
+.entry { margin: 1rem 0; padding: 0.75rem 1rem; border-radius: 6px; border: 1px solid #d0d7de; }
+.entry h4 { margin: 0 0 0.5rem; font-size: 0.875rem; }
+.user { background: #ddf4ff; }
+.system { background: #f6f8fa; }
+.error { background: #ffebe9; border-color: #ff8182; }
+.tool { background: #fbfbfb; }
+.text { white-space: pre-wrap; overflow-wrap: anywhere; }
+pre { background: #f6f8fa; padding: 0.75rem; border-radius: 6px; overflow-x: auto; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.85em; }
+.status { color: #656d76; font-weight: normal; }
+";
+
+impl ConversationExport {
+    pub fn render(&self, format: ExportFormat) -> Result<String, serde_json::Error> {
+        match format {
+            ExportFormat::Markdown => Ok(self.to_markdown()),
+            ExportFormat::Html => Ok(self.to_html()),
+            ExportFormat::Json => serde_json::to_string_pretty(self),
+        }
+    }
+
+    /// File name for a download of this export
+    pub fn file_name(&self, format: ExportFormat) -> String {
+        let id = self.task_attempt_id.to_string();
+        format!("conversation-{}.{}", &id[..8], format.extension())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.task_title);
+        let _ = writeln!(
+            out,
+            "- Attempt: `{}` on branch `{}`",
+            self.task_attempt_id, self.branch
+        );
+        let _ = writeln!(out, "- Exported: {}\n", format_time(&self.exported_at));
+
+        for (i, process) in self.processes.iter().enumerate() {
+            let _ = write!(
+                out,
+                "## {}. {}",
+                i + 1,
+                run_reason_label(&process.run_reason)
+            );
+            if let Some(executor) = &process.executor {
+                let _ = write!(out, " ({executor})");
+            }
+            let _ = write!(
+                out,
+                "\n\n_{}, started {}",
+                status_label(&process.status),
+                format_time(&process.started_at)
+            );
+            if let Some(completed_at) = &process.completed_at {
+                let _ = write!(out, ", finished {}", format_time(completed_at));
+            }
+            out.push_str("_\n\n");
+
+            if let Some(prompt) = &process.prompt {
+                let _ = write!(out, "### Prompt\n\n{}\n\n", prompt.trim_end());
+            }
+            for entry in &process.entries {
+                out.push_str(&entry_markdown(entry));
+            }
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let title = escape_html(&self.task_title);
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+             <header>\n<h1>{title}</h1>\n<p class=\"meta\">Attempt <code>{}</code> on branch \
+             <code>{}</code> &middot; exported {}</p>\n</header>\n",
+            self.task_attempt_id,
+            escape_html(&self.branch),
+            format_time(&self.exported_at)
+        );
+
+        for (i, process) in self.processes.iter().enumerate() {
+            let _ = write!(
+                out,
+                "<section>\n<h2>{}. {}",
+                i + 1,
+                run_reason_label(&process.run_reason)
+            );
+            if let Some(executor) = &process.executor {
+                let _ = write!(out, " ({})", escape_html(executor));
+            }
+            let _ = write!(
+                out,
+                "</h2>\n<p class=\"meta\">{}, started {}",
+                status_label(&process.status),
+                format_time(&process.started_at)
+            );
+            if let Some(completed_at) = &process.completed_at {
+                let _ = write!(out, ", finished {}", format_time(completed_at));
+            }
+            out.push_str("</p>\n");
+
+            if let Some(prompt) = &process.prompt {
+                let _ = writeln!(
+                    out,
+                    "<div class=\"entry user\"><h4>Prompt</h4><div class=\"text\">{}</div></div>",
+                    escape_html(prompt.trim_end())
+                );
+            }
+            for entry in &process.entries {
+                out.push_str(&entry_html(entry));
+            }
+            out.push_str("</section>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn entry_markdown(entry: &NormalizedEntry) -> String {
+    let content = entry.content.trim_end();
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage => format!("### User\n\n{content}\n\n"),
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            format!("### User feedback on `{denied_tool}`\n\n{content}\n\n")
+        }
+        NormalizedEntryType::AssistantMessage => format!("### Assistant\n\n{content}\n\n"),
+        NormalizedEntryType::Thinking => {
+            format!("<details>\n<summary>Thinking</summary>\n\n{content}\n\n</details>\n\n")
+        }
+        NormalizedEntryType::SystemMessage => blockquote("System", content),
+        NormalizedEntryType::ErrorMessage => blockquote("Error", content),
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            let mut out = format!("#### Tool: {tool_name}");
+            if let Some(label) = tool_status_label(status) {
+                let _ = write!(out, " ({label})");
+            }
+            out.push_str("\n\n");
+            if !content.is_empty() {
+                let _ = write!(out, "{content}\n\n");
+            }
+            out.push_str(&action_markdown(action_type));
+            out
+        }
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => String::new(),
+    }
+}
+
+fn action_markdown(action: &ActionType) -> String {
+    match action {
+        ActionType::FileRead { path } => format!("Read `{path}`\n\n"),
+        ActionType::FileEdit { path, changes } => {
+            let mut out = String::new();
+            for change in changes {
+                match change {
+                    FileChange::Write { content } => {
+                        let _ = write!(out, "Wrote `{path}`\n\n{}", code_block(content, ""));
+                    }
+                    FileChange::Delete => {
+                        let _ = write!(out, "Deleted `{path}`\n\n");
+                    }
+                    FileChange::Rename { new_path } => {
+                        let _ = write!(out, "Renamed `{path}` to `{new_path}`\n\n");
+                    }
+                    FileChange::Edit { unified_diff, .. } => {
+                        let _ = write!(
+                            out,
+                            "Edited `{path}`\n\n{}",
+                            code_block(unified_diff, "diff")
+                        );
+                    }
+                }
+            }
+            out
+        }
+        ActionType::CommandRun { command, result } => {
+            let mut out = code_block(&format!("$ {command}"), "sh");
+            if let Some(result) = result {
+                if let Some(output) = result.output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    out.push_str(&code_block(output, ""));
+                }
+                if let Some(exit_status) = &result.exit_status {
+                    let _ = write!(out, "_{}_\n\n", exit_status_label(exit_status));
+                }
+            }
+            out
+        }
+        ActionType::Search { query } => format!("Searched for `{query}`\n\n"),
+        ActionType::WebFetch { url } => format!("Fetched <{url}>\n\n"),
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            let mut out = String::new();
+            if let Some(arguments) = arguments {
+                let arguments = serde_json::to_string_pretty(arguments).unwrap_or_default();
+                out.push_str(&code_block(&arguments, "json"));
+            }
+            if let Some(result) = result {
+                let (text, lang) = tool_result_text(&result.r#type, &result.value);
+                out.push_str(&code_block(&text, lang));
+            }
+            out
+        }
+        ActionType::TaskCreate { description } => format!("Started a task: {description}\n\n"),
+        ActionType::PlanPresentation { plan } => format!("{}\n\n", plan.trim_end()),
+        ActionType::TodoManagement { todos, .. } => {
+            let mut out = String::new();
+            for todo in todos {
+                let mark = if todo.status == "completed" { "x" } else { " " };
+                let _ = writeln!(out, "- [{mark}] {}", todo.content);
+            }
+            out.push('\n');
+            out
+        }
+        ActionType::Other { .. } => String::new(),
+    }
+}
+
+fn html_block(class: &str, heading: &str, body: &str) -> String {
+    format!("<div class=\"entry {class}\"><h4>{heading}</h4>{body}</div>\n")
+}
+
+fn html_text(content: &str) -> String {
+    if content.is_empty() {
+        String::new()
+    } else {
+        format!("<div class=\"text\">{}</div>", escape_html(content))
+    }
+}
+
+fn html_pre(content: &str) -> String {
+    format!(
+        "<pre><code>{}</code></pre>",
+        escape_html(content.trim_end_matches('\n'))
+    )
+}
+
+fn entry_html(entry: &NormalizedEntry) -> String {
+    let content = entry.content.trim_end();
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage => html_block("user", "User", &html_text(content)),
+        NormalizedEntryType::UserFeedback { denied_tool } => html_block(
+            "user",
+            &format!("User feedback on <code>{}</code>", escape_html(denied_tool)),
+            &html_text(content),
+        ),
+        NormalizedEntryType::AssistantMessage => {
+            html_block("assistant", "Assistant", &html_text(content))
+        }
+        NormalizedEntryType::Thinking => format!(
+            "<details class=\"entry system\"><summary>Thinking</summary>{}</details>\n",
+            html_text(content)
+        ),
+        NormalizedEntryType::SystemMessage => html_block("system", "System", &html_text(content)),
+        NormalizedEntryType::ErrorMessage => html_block("error", "Error", &html_text(content)),
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            let mut heading = format!("Tool: {}", escape_html(tool_name));
+            if let Some(label) = tool_status_label(status) {
+                let _ = write!(
+                    heading,
+                    " <span class=\"status\">({})</span>",
+                    escape_html(&label)
+                );
+            }
+            let body = html_text(content) + &action_html(action_type);
+            html_block("tool", &heading, &body)
+        }
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => String::new(),
+    }
+}
+
+fn action_html(action: &ActionType) -> String {
+    let line = |text: String| format!("<p>{text}</p>");
+    let code = |text: &str| format!("<code>{}</code>", escape_html(text));
+    match action {
+        ActionType::FileRead { path } => line(format!("Read {}", code(path))),
+        ActionType::FileEdit { path, changes } => changes
+            .iter()
+            .map(|change| match change {
+                FileChange::Write { content } => {
+                    line(format!("Wrote {}", code(path))) + &html_pre(content)
+                }
+                FileChange::Delete => line(format!("Deleted {}", code(path))),
+                FileChange::Rename { new_path } => {
+                    line(format!("Renamed {} to {}", code(path), code(new_path)))
+                }
+                FileChange::Edit { unified_diff, .. } => {
+                    line(format!("Edited {}", code(path))) + &html_pre(unified_diff)
+                }
+            })
+            .collect(),
+        ActionType::CommandRun { command, result } => {
+            let mut out = html_pre(&format!("$ {command}"));
+            if let Some(result) = result {
+                if let Some(output) = result.output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    out.push_str(&html_pre(output));
+                }
+                if let Some(exit_status) = &result.exit_status {
+                    out.push_str(&line(format!(
+                        "<em>{}</em>",
+                        exit_status_label(exit_status)
+                    )));
+                }
+            }
+            out
+        }
+        ActionType::Search { query } => line(format!("Searched for {}", code(query))),
+        ActionType::WebFetch { url } => line(format!("Fetched {}", code(url))),
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            let mut out = String::new();
+            if let Some(arguments) = arguments {
+                out.push_str(&html_pre(
+                    &serde_json::to_string_pretty(arguments).unwrap_or_default(),
+                ));
+            }
+            if let Some(result) = result {
+                out.push_str(&html_pre(
+                    &tool_result_text(&result.r#type, &result.value).0,
+                ));
+            }
+            out
+        }
+        ActionType::TaskCreate { description } => {
+            line(format!("Started a task: {}", escape_html(description)))
+        }
+        ActionType::PlanPresentation { plan } => html_text(plan.trim_end()),
+        ActionType::TodoManagement { todos, .. } => {
+            let items: String = todos
+                .iter()
+                .map(|todo| {
+                    let mark = if todo.status == "completed" {
+                        "&#9745;"
+                    } else {
+                        "&#9744;"
+                    };
+                    format!("<li>{mark} {}</li>", escape_html(&todo.content))
+                })
+                .collect();
+            format!("<ul>{items}</ul>")
+        }
+        ActionType::Other { .. } => String::new(),
+    }
+}
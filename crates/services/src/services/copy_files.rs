@@ -0,0 +1,442 @@
+//! Copying untracked project files (`.env`, local configuration) into new worktrees.
+//!
+//! `Project.copy_files` holds one entry per line; commas also separate entries, except inside
+//! `{a,b}` alternations. An entry is a glob relative to the repository root, optionally
+//! followed by options:
+//!
+//! ```text
+//! .env template
+//! config/**/*.local.json
+//! !config/secrets.local.json
+//! fixtures/ depth=2
+//! .cache symlink
+//! ```
+//!
+//! Entries starting with `!` exclude paths from every other entry. An entry naming a path
+//! that exists is copied as that exact path, as entries were before they could be globs, even
+//! when the name contains glob syntax such as `[id]`. A matching directory is copied with
+//! everything below it. `depth=N` descends at most N levels below the pattern's
+//! literal prefix, `symlink` links to the original instead of copying it, and `template`
+//! renders `{{branch}}`, `{{port}}` and the other [`CopyTemplateValues`] in copied files.
+//! Double braces keep JSON and YAML braces intact.
+
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::LazyLock,
+};
+
+use ignore::{
+    WalkBuilder,
+    gitignore::{Gitignore, GitignoreBuilder},
+};
+use regex::{Captures, Regex};
+
+static TEMPLATE_PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_]+)\s*\}\}").expect("valid placeholder regex"));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CopyMode {
+    #[default]
+    Copy,
+    Symlink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyFilesEntry {
+    /// Glob relative to the repository root
+    pub pattern: String,
+    pub mode: CopyMode,
+    /// Render placeholders in copied files
+    pub template: bool,
+    /// Directory levels to descend below the pattern's literal prefix
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyFilesSpec {
+    pub includes: Vec<CopyFilesEntry>,
+    pub excludes: Vec<String>,
+}
+
+impl CopyFilesSpec {
+    pub fn has_templates(&self) -> bool {
+        self.includes.iter().any(|entry| entry.template)
+    }
+}
+
+/// Per-attempt values available to `template` entries as `{{name}}`
+#[derive(Debug, Clone, Default)]
+pub struct CopyTemplateValues {
+    pub branch: String,
+    pub base_branch: String,
+    pub attempt_id: String,
+    pub task_id: String,
+    pub worktree: String,
+    /// The attempt's managed dev server port
+    pub port: Option<u16>,
+}
+
+impl CopyTemplateValues {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "branch" => Some(self.branch.clone()),
+            "base_branch" => Some(self.base_branch.clone()),
+            "attempt_id" => Some(self.attempt_id.clone()),
+            "task_id" => Some(self.task_id.clone()),
+            "worktree" => Some(self.worktree.clone()),
+            "port" => self.port.map(|port| port.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Replace known `{{name}}` placeholders; unknown ones are left as written.
+    pub fn render(&self, text: &str) -> String {
+        TEMPLATE_PLACEHOLDER
+            .replace_all(text, |caps: &Captures| {
+                self.get(&caps[1]).unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+}
+
+/// Outcome of copying a project's files, written to the setup script's log
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CopyFilesReport {
+    pub copied: usize,
+    pub linked: usize,
+    pub rendered: usize,
+    /// Include patterns that matched nothing
+    pub unmatched: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl CopyFilesReport {
+    pub fn log_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "copy_files: copied {} files ({} rendered from templates), linked {}",
+            self.copied, self.rendered, self.linked
+        )];
+        lines.extend(
+            self.unmatched
+                .iter()
+                .map(|pattern| format!("copy_files: '{pattern}' did not match any files")),
+        );
+        lines.extend(self.errors.iter().map(|e| format!("copy_files: {e}")));
+        lines
+    }
+}
+
+/// Split entries on newlines, and on commas outside `{...}` alternations
+fn split_entries(spec: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    for line in spec.lines() {
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in line.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    entries.push(&line[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        entries.push(&line[start..]);
+    }
+    entries
+        .into_iter()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && !entry.starts_with('#'))
+        .collect()
+}
+
+fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.is_empty() {
+        return Err("Empty copy_files pattern".to_string());
+    }
+    let path = Path::new(pattern);
+    if path.is_absolute()
+        || path
+            .components()
+            .any(|c| matches!(c, Component::ParentDir | Component::Prefix(_)))
+    {
+        return Err(format!(
+            "copy_files pattern '{pattern}' must stay inside the repository"
+        ));
+    }
+    GitignoreBuilder::new("")
+        .add_line(None, &anchored(pattern))
+        .map(|_| ())
+        .map_err(|e| format!("Invalid copy_files pattern '{pattern}': {e}"))
+}
+
+/// Parse a project's `copy_files`, returning a user-facing reason when invalid.
+pub fn parse_copy_files(spec: &str) -> Result<CopyFilesSpec, String> {
+    let mut parsed = CopyFilesSpec::default();
+    for entry in split_entries(spec) {
+        if let Some(pattern) = entry.strip_prefix('!') {
+            let pattern = pattern.trim();
+            validate_pattern(pattern)?;
+            parsed.excludes.push(pattern.to_string());
+            continue;
+        }
+
+        let mut pattern = entry;
+        let mut mode = CopyMode::Copy;
+        let mut template = false;
+        let mut max_depth = None;
+        // Options are trailing words; anything else belongs to the pattern
+        while let Some((head, option)) = pattern.rsplit_once(char::is_whitespace) {
+            match option {
+                "symlink" => mode = CopyMode::Symlink,
+                "template" => template = true,
+                _ => match option.strip_prefix("depth=") {
+                    Some(depth) => {
+                        max_depth = Some(depth.parse::<usize>().map_err(|_| {
+                            format!("Invalid depth '{depth}' for copy_files pattern '{head}'")
+                        })?)
+                    }
+                    None => break,
+                },
+            }
+            pattern = head.trim_end();
+        }
+        validate_pattern(pattern)?;
+        if template && mode == CopyMode::Symlink {
+            return Err(format!(
+                "copy_files pattern '{pattern}' can't be both a symlink and a template"
+            ));
+        }
+        parsed.includes.push(CopyFilesEntry {
+            pattern: pattern.to_string(),
+            mode,
+            template,
+            max_depth,
+        });
+    }
+    Ok(parsed)
+}
+
+pub fn validate_copy_files(spec: Option<&str>) -> Result<(), String> {
+    spec.map(parse_copy_files).transpose().map(|_| ())
+}
+
+/// Patterns are relative to the repository root rather than matching at any depth
+fn anchored(pattern: &str) -> String {
+    format!("/{pattern}")
+}
+
+fn matcher(root: &Path, patterns: &[String]) -> Result<Gitignore, ignore::Error> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder.add_line(None, &anchored(pattern))?;
+    }
+    builder.build()
+}
+
+/// Leading path components without glob syntax, where walking can start
+fn literal_prefix(pattern: &str) -> PathBuf {
+    Path::new(pattern.trim_end_matches('/'))
+        .components()
+        .take_while(|c| {
+            !c.as_os_str()
+                .to_string_lossy()
+                .contains(['*', '?', '[', '{', '\\'])
+        })
+        .collect()
+}
+
+struct Selected {
+    /// Path relative to the repository root
+    relative: PathBuf,
+    is_dir: bool,
+    is_symlink: bool,
+}
+
+fn select(
+    source_dir: &Path,
+    entry: &CopyFilesEntry,
+    excludes: &Gitignore,
+) -> Result<Vec<Selected>, ignore::Error> {
+    let exact = Path::new(entry.pattern.trim_end_matches('/'));
+    let is_exact = source_dir.join(exact).symlink_metadata().is_ok();
+    let base = if is_exact {
+        source_dir.join(exact)
+    } else {
+        source_dir.join(literal_prefix(&entry.pattern))
+    };
+    if base.symlink_metadata().is_err() {
+        return Ok(Vec::new());
+    }
+    let include = matcher(source_dir, std::slice::from_ref(&entry.pattern))?;
+    let included = |path: &Path, relative: &Path, is_dir: bool| {
+        if is_exact {
+            relative == exact
+        } else {
+            include.matched(path, is_dir).is_ignore()
+        }
+    };
+
+    let prune = excludes.clone();
+    let walker = WalkBuilder::new(&base)
+        .standard_filters(false)
+        .follow_links(false)
+        .max_depth(entry.max_depth)
+        .filter_entry(move |dent| {
+            let is_dir = dent.file_type().is_some_and(|t| t.is_dir());
+            dent.file_name() != ".git"
+                && !prune
+                    .matched_path_or_any_parents(dent.path(), is_dir)
+                    .is_ignore()
+        })
+        .build();
+
+    let mut selected = Vec::new();
+    let mut matched_dirs: Vec<PathBuf> = Vec::new();
+    for dent in walker {
+        let dent = dent?;
+        let Ok(relative) = dent.path().strip_prefix(source_dir) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let file_type = dent.file_type();
+        let is_dir = file_type.is_some_and(|t| t.is_dir());
+        let is_symlink = file_type.is_some_and(|t| t.is_symlink());
+        if excludes
+            .matched_path_or_any_parents(dent.path(), is_dir)
+            .is_ignore()
+        {
+            continue;
+        }
+        let inside_match = matched_dirs.iter().any(|dir| relative.starts_with(dir));
+        if inside_match {
+            // A linked directory brings its contents along
+            if entry.mode == CopyMode::Symlink {
+                continue;
+            }
+        } else if included(dent.path(), relative, is_dir) {
+            if is_dir {
+                matched_dirs.push(relative.to_path_buf());
+            }
+        } else {
+            continue;
+        }
+        selected.push(Selected {
+            relative: relative.to_path_buf(),
+            is_dir,
+            is_symlink,
+        });
+    }
+    Ok(selected)
+}
+
+#[cfg(unix)]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    if original.is_dir() {
+        std::os::windows::fs::symlink_dir(original, link)
+    } else {
+        std::os::windows::fs::symlink_file(original, link)
+    }
+}
+
+fn copy_one(
+    source_dir: &Path,
+    target_dir: &Path,
+    item: &Selected,
+    entry: &CopyFilesEntry,
+    values: &CopyTemplateValues,
+    report: &mut CopyFilesReport,
+) -> io::Result<()> {
+    let from = source_dir.join(&item.relative);
+    let to = target_dir.join(&item.relative);
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if entry.mode == CopyMode::Symlink {
+        if to.symlink_metadata().is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists in the worktree", item.relative.display()),
+            ));
+        }
+        symlink(&from, &to)?;
+        report.linked += 1;
+    } else if item.is_dir {
+        fs::create_dir_all(&to)?;
+    } else if item.is_symlink {
+        if to.symlink_metadata().is_ok() {
+            fs::remove_file(&to)?;
+        }
+        symlink(&fs::read_link(&from)?, &to)?;
+        report.copied += 1;
+    } else if entry.template {
+        match fs::read_to_string(&from) {
+            Ok(text) => {
+                fs::write(&to, values.render(&text))?;
+                fs::set_permissions(&to, fs::metadata(&from)?.permissions())?;
+                report.rendered += 1;
+            }
+            // Binary files are copied untouched
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                fs::copy(&from, &to)?;
+            }
+            Err(e) => return Err(e),
+        }
+        report.copied += 1;
+    } else {
+        fs::copy(&from, &to)?;
+        report.copied += 1;
+    }
+    Ok(())
+}
+
+/// Copy everything `spec` selects from `source_dir` into the worktree at `target_dir`.
+/// Failures are collected in the report rather than stopping the copy.
+pub fn copy_project_files(
+    source_dir: &Path,
+    target_dir: &Path,
+    spec: &CopyFilesSpec,
+    values: &CopyTemplateValues,
+) -> CopyFilesReport {
+    let mut report = CopyFilesReport::default();
+    let excludes = match matcher(source_dir, &spec.excludes) {
+        Ok(excludes) => excludes,
+        Err(e) => {
+            report.errors.push(format!("invalid exclude pattern: {e}"));
+            return report;
+        }
+    };
+
+    for entry in &spec.includes {
+        let selected = match select(source_dir, entry, &excludes) {
+            Ok(selected) => selected,
+            Err(e) => {
+                report.errors.push(format!("'{}': {e}", entry.pattern));
+                continue;
+            }
+        };
+        if selected.is_empty() {
+            report.unmatched.push(entry.pattern.clone());
+            continue;
+        }
+        for item in &selected {
+            if let Err(e) = copy_one(source_dir, target_dir, item, entry, values, &mut report) {
+                report
+                    .errors
+                    .push(format!("failed to copy {}: {e}", item.relative.display()));
+            }
+        }
+    }
+    report
+}
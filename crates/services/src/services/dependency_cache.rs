@@ -0,0 +1,317 @@
+//! Shared dependency directories (`node_modules`, `target`, ...) reused across worktrees.
+//!
+//! A project lists the directories to cache, one per line, optionally followed by the
+//! lockfiles that decide when the cache is stale:
+//!
+//! ```text
+//! node_modules
+//! frontend/node_modules: package-lock.json
+//! target: Cargo.lock
+//! ```
+//!
+//! Lockfiles are resolved next to the cached directory; well-known directory names fall back
+//! to their ecosystem's lockfiles. After a successful setup script the directories are
+//! copied into a warm base keyed by a hash of those lockfiles, and new worktrees with the
+//! same lockfiles start with the directories already in place. Copies are reflinks where the
+//! filesystem supports copy-on-write, and plain copies otherwise. Files are never hardlinked:
+//! tools that rewrite files in place would leak changes from a worktree into the warm base.
+
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use sha2::{Digest, Sha256};
+use utils::path::get_vibe_kanban_temp_dir;
+use uuid::Uuid;
+
+/// A cached directory and the lockfiles its cache key is derived from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCacheEntry {
+    /// Path relative to the worktree root
+    pub dir: String,
+    /// Paths relative to the cached directory's parent
+    pub lockfiles: Vec<String>,
+}
+
+/// How a cached directory was copied into a worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkMode {
+    Reflink,
+    Copy,
+}
+
+fn default_lockfiles(dir: &str) -> &'static [&'static str] {
+    let name = Path::new(dir)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    match name {
+        "node_modules" => &[
+            "package-lock.json",
+            "npm-shrinkwrap.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "bun.lock",
+            "bun.lockb",
+        ],
+        "target" => &["Cargo.lock"],
+        ".venv" | "venv" => &["uv.lock", "poetry.lock", "Pipfile.lock", "requirements.txt"],
+        "vendor" => &["composer.lock", "Gemfile.lock", "go.sum"],
+        _ => &[],
+    }
+}
+
+fn is_plain_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Parse a project's dependency cache configuration, returning a user-facing reason when
+/// invalid.
+pub fn parse_dependency_caches(spec: &str) -> Result<Vec<DependencyCacheEntry>, String> {
+    let mut entries = Vec::new();
+    for line in spec.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (dir, lockfiles) = match line.split_once(':') {
+            Some((dir, lockfiles)) => (
+                dir.trim(),
+                lockfiles
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            ),
+            None => (line, Vec::new()),
+        };
+        let dir = dir.trim_end_matches('/');
+        if !is_plain_relative(dir) {
+            return Err(format!(
+                "Cache directory '{dir}' must be a relative path inside the repository"
+            ));
+        }
+        if let Some(lockfile) = lockfiles.iter().find(|l| !is_plain_relative(l)) {
+            return Err(format!(
+                "Lockfile '{lockfile}' must be a relative path without '..'"
+            ));
+        }
+        let lockfiles = if lockfiles.is_empty() {
+            default_lockfiles(dir)
+                .iter()
+                .map(|l| l.to_string())
+                .collect()
+        } else {
+            lockfiles
+        };
+        if lockfiles.is_empty() {
+            return Err(format!(
+                "Cache directory '{dir}' needs lockfiles, e.g. '{dir}: deps.lock'"
+            ));
+        }
+        entries.push(DependencyCacheEntry {
+            dir: dir.to_string(),
+            lockfiles,
+        });
+    }
+    Ok(entries)
+}
+
+pub fn validate_dependency_caches(spec: Option<&str>) -> Result<(), String> {
+    spec.map(parse_dependency_caches).transpose().map(|_| ())
+}
+
+/// Warm bases for a machine, shared by all worktrees of each project
+#[derive(Debug, Clone)]
+pub struct DependencyCache {
+    root: PathBuf,
+}
+
+impl Default for DependencyCache {
+    fn default() -> Self {
+        // Next to the worktrees so reflinks stay on one filesystem
+        Self::new(get_vibe_kanban_temp_dir().join("dependency-cache"))
+    }
+}
+
+impl DependencyCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Hash of the entry's lockfiles in `worktree_path`, or `None` when none of them exist
+    pub fn cache_key(
+        worktree_path: &Path,
+        entry: &DependencyCacheEntry,
+    ) -> io::Result<Option<String>> {
+        let base = worktree_path
+            .join(&entry.dir)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| worktree_path.to_path_buf());
+        let mut hasher = Sha256::new();
+        let mut found = false;
+        for lockfile in &entry.lockfiles {
+            let path = base.join(lockfile);
+            if !path.is_file() {
+                continue;
+            }
+            hasher.update(lockfile.as_bytes());
+            hasher.update([0]);
+            hasher.update(fs::read(&path)?);
+            hasher.update([0]);
+            found = true;
+        }
+        Ok(found.then(|| format!("{:x}", hasher.finalize())))
+    }
+
+    fn entry_dir(&self, project_id: Uuid, entry: &DependencyCacheEntry) -> PathBuf {
+        self.root
+            .join(project_id.to_string())
+            .join(entry.dir.replace(['/', '\\'], "_"))
+    }
+
+    /// Fill `entry.dir` in a new worktree from the warm base matching its lockfiles. Returns
+    /// `None` on a cache miss or when the directory already exists.
+    pub fn populate(
+        &self,
+        project_id: Uuid,
+        worktree_path: &Path,
+        entry: &DependencyCacheEntry,
+    ) -> io::Result<Option<LinkMode>> {
+        let target = worktree_path.join(&entry.dir);
+        if target.exists() {
+            return Ok(None);
+        }
+        let Some(key) = Self::cache_key(worktree_path, entry)? else {
+            return Ok(None);
+        };
+        let base = self.entry_dir(project_id, entry).join(key);
+        if !base.is_dir() {
+            return Ok(None);
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if reflink_tree(&base, &target) {
+            return Ok(Some(LinkMode::Reflink));
+        }
+        if let Err(e) = copy_tree(&base, &target) {
+            remove_dir_if_exists(&target)?;
+            return Err(e);
+        }
+        Ok(Some(LinkMode::Copy))
+    }
+
+    /// Store `entry.dir` from a worktree whose setup just succeeded as the warm base for its
+    /// lockfiles, replacing bases for older lockfiles. Returns whether a base was written.
+    pub fn warm(
+        &self,
+        project_id: Uuid,
+        worktree_path: &Path,
+        entry: &DependencyCacheEntry,
+    ) -> io::Result<bool> {
+        let source = worktree_path.join(&entry.dir);
+        if !source.is_dir() {
+            return Ok(false);
+        }
+        let Some(key) = Self::cache_key(worktree_path, entry)? else {
+            return Ok(false);
+        };
+        let entry_dir = self.entry_dir(project_id, entry);
+        let base = entry_dir.join(&key);
+        if base.is_dir() {
+            return Ok(false);
+        }
+
+        fs::create_dir_all(&entry_dir)?;
+        let partial = entry_dir.join(format!("{key}.partial-{}", Uuid::new_v4()));
+        if !reflink_tree(&source, &partial) {
+            remove_dir_if_exists(&partial)?;
+            if let Err(e) = copy_tree(&source, &partial) {
+                remove_dir_if_exists(&partial)?;
+                return Err(e);
+            }
+        }
+        if fs::rename(&partial, &base).is_err() {
+            // Another worktree warmed the same lockfiles first
+            remove_dir_if_exists(&partial)?;
+            return Ok(false);
+        }
+
+        for stale in fs::read_dir(&entry_dir)? {
+            let stale = stale?;
+            let name = stale.file_name();
+            let name = name.to_string_lossy();
+            if name != key.as_str() && !name.contains(".partial-") {
+                remove_dir_if_exists(&stale.path())?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Drop every warm base of a project
+    pub fn clear_project(&self, project_id: Uuid) -> io::Result<()> {
+        remove_dir_if_exists(&self.root.join(project_id.to_string()))
+    }
+}
+
+fn remove_dir_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Copy-on-write copy of a directory tree using the platform `cp`
+fn reflink_tree(source: &Path, target: &Path) -> bool {
+    let args: &[&str] = if cfg!(target_os = "linux") {
+        &["-a", "--reflink=always"]
+    } else if cfg!(target_os = "macos") {
+        &["-a", "-c"]
+    } else {
+        return false;
+    };
+    let copied = Command::new("cp")
+        .args(args)
+        .arg(source)
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !copied {
+        let _ = remove_dir_if_exists(target);
+    }
+    copied
+}
+
+/// Recreate `source` at `target`, copying files and preserving symlinks
+fn copy_tree(source: &Path, target: &Path) -> io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let from = entry.path();
+        let to = target.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_tree(&from, &to)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(&from)?, &to)?;
+            #[cfg(not(unix))]
+            if from.is_file() {
+                fs::copy(&from, &to)?;
+            }
+        } else {
+            fs::copy(&from, &to)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! Managed ports and detected URLs for attempts' dev servers.
+//!
+//! Each attempt gets its own port so dev servers of different attempts can run side by side.
+//! The port is derived from the attempt id where possible, so it usually survives restarts of
+//! the server, and is passed to the dev script as `PORT` and `VK_DEV_SERVER_PORT`. The URL a
+//! dev server prints once it is listening is picked out of its logs; the preview proxy at
+//! `/preview/{attempt_id}/` forwards to that URL, or to the managed port until one is seen.
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::TcpListener,
+    ops::RangeInclusive,
+    sync::{Arc, LazyLock, RwLock},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use regex::Regex;
+use reqwest::Url;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+use crate::services::dev_server_health::STABLE_UPTIME;
+
+/// Ports handed out to dev servers, clear of the usual framework defaults
+const PORT_RANGE: RangeInclusive<u16> = 41000..=48999;
+
+pub const PORT_ENV: &str = "PORT";
+pub const DEV_SERVER_PORT_ENV: &str = "VK_DEV_SERVER_PORT";
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").expect("valid ANSI regex"));
+
+static URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bhttps?://[^\s/'`]+(?:/[^\s'`]*)?").expect("valid URL regex")
+});
+
+static LOCAL_HOST_PORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:localhost|127\.0\.0\.1):(\d{2,5})\b").expect("valid host:port regex")
+});
+
+/// Environment for a dev script listening on `port`
+pub fn dev_server_env(port: u16) -> HashMap<String, String> {
+    HashMap::from([
+        (PORT_ENV.to_string(), port.to_string()),
+        (DEV_SERVER_PORT_ENV.to_string(), port.to_string()),
+    ])
+}
+
+pub fn preview_path(attempt_id: Uuid) -> String {
+    format!("/preview/{attempt_id}/")
+}
+
+/// The loopback URL a dev server announces in a log line. Only `localhost`, `127.0.0.1` and
+/// `[::1]` are accepted: the preview proxy forwards to this URL, so any other host printed to
+/// the logs would let it reach arbitrary machines. Wildcard hosts such as `0.0.0.0` are
+/// ignored too and the proxy keeps using the managed port.
+pub fn detect_dev_server_url(line: &str) -> Option<String> {
+    let line = ANSI_ESCAPE.replace_all(line, "");
+    let loopback = URL.find_iter(&line).find_map(|found| {
+        Url::parse(found.as_str().trim_end_matches(['.', ',', ')']))
+            .ok()
+            .filter(|url| matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]")))
+    });
+    if let Some(url) = loopback {
+        return Some(url.to_string());
+    }
+    let port = LOCAL_HOST_PORT.captures(&line)?[1].parse::<u16>().ok()?;
+    Some(format!("http://localhost:{port}/"))
+}
+
+/// An attempt's dev server as seen by the UI and the preview proxy
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DevServerInfo {
+    pub attempt_id: Uuid,
+    /// Port passed to the dev script
+    pub port: u16,
+    /// URL the running dev server printed, once it has printed one
+    pub url: Option<String>,
+    /// Path of the preview proxy for this attempt
+    pub preview_path: String,
+}
+
+impl DevServerInfo {
+    /// Scheme, host and port the preview proxy forwards to
+    pub fn upstream(&self) -> String {
+        self.url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .map(|url| url.origin().ascii_serialization())
+            .unwrap_or_else(|| format!("http://localhost:{}", self.port))
+    }
+}
+
+#[derive(Debug)]
+struct Slot {
+    port: u16,
+    /// Detected URL and the execution process that printed it
+    url: Option<(Uuid, String)>,
+}
+
+/// Ports and URLs of every attempt's dev server
+#[derive(Debug, Clone, Default)]
+pub struct DevServers {
+    slots: Arc<RwLock<HashMap<Uuid, Slot>>>,
+    /// Automatic restarts in a row, per attempt
+    restarts: Arc<RwLock<HashMap<Uuid, u32>>>,
+}
+
+fn port_is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+impl DevServers {
+    /// The attempt's port, allocating one on first use. `None` when every port in the range
+    /// is taken.
+    pub fn port_for(&self, attempt_id: Uuid) -> Option<u16> {
+        let mut slots = self.slots.write().unwrap();
+        if let Some(slot) = slots.get(&attempt_id) {
+            return Some(slot.port);
+        }
+        let taken: HashSet<u16> = slots.values().map(|slot| slot.port).collect();
+        let (start, end) = (*PORT_RANGE.start(), *PORT_RANGE.end());
+        let span = end - start + 1;
+        let preferred = (attempt_id.as_u128() % u128::from(span)) as u16;
+        let port = (0..span)
+            .map(|offset| start + (preferred + offset) % span)
+            .find(|port| !taken.contains(port) && port_is_free(*port))?;
+        slots.insert(attempt_id, Slot { port, url: None });
+        Some(port)
+    }
+
+    pub fn get(&self, attempt_id: Uuid) -> Option<DevServerInfo> {
+        let slots = self.slots.read().unwrap();
+        let slot = slots.get(&attempt_id)?;
+        Some(DevServerInfo {
+            attempt_id,
+            port: slot.port,
+            url: slot.url.as_ref().map(|(_, url)| url.clone()),
+            preview_path: preview_path(attempt_id),
+        })
+    }
+
+    pub fn record_url(&self, attempt_id: Uuid, execution_process_id: Uuid, url: String) {
+        if let Some(slot) = self.slots.write().unwrap().get_mut(&attempt_id) {
+            slot.url = Some((execution_process_id, url));
+        }
+    }
+
+    /// Forget the URL printed by a dev server that has stopped. URLs recorded by a newer dev
+    /// server of the same attempt are kept.
+    pub fn clear_url(&self, attempt_id: Uuid, execution_process_id: Uuid) {
+        if let Some(slot) = self.slots.write().unwrap().get_mut(&attempt_id)
+            && slot
+                .url
+                .as_ref()
+                .is_some_and(|(id, _)| *id == execution_process_id)
+        {
+            slot.url = None;
+        }
+    }
+
+    /// Count an automatic restart of the attempt's dev server, returning how many happened
+    /// in a row. A dev server that stayed up for [`STABLE_UPTIME`] before failing starts the
+    /// count afresh.
+    pub fn record_restart(&self, attempt_id: Uuid, uptime: Duration) -> u32 {
+        let mut restarts = self.restarts.write().unwrap();
+        let count = restarts.entry(attempt_id).or_default();
+        if uptime >= STABLE_UPTIME {
+            *count = 0;
+        }
+        *count += 1;
+        *count
+    }
+
+    /// Count restarts afresh after a manual start or a passing health check
+    pub fn reset_restarts(&self, attempt_id: Uuid) {
+        self.restarts.write().unwrap().remove(&attempt_id);
+    }
+
+    /// Free the attempt's port once its worktree is gone
+    pub fn release(&self, attempt_id: Uuid) {
+        self.slots.write().unwrap().remove(&attempt_id);
+        self.reset_restarts(attempt_id);
+    }
+
+    /// Watch a dev server's output for the URL it listens on until the process finishes
+    pub async fn track_url(
+        self,
+        attempt_id: Uuid,
+        execution_process_id: Uuid,
+        store: Arc<MsgStore>,
+    ) {
+        let mut lines =
+            futures::stream::select(store.stdout_lines_stream(), store.stderr_lines_stream());
+        let mut found = false;
+        while let Some(line) = lines.next().await {
+            if found {
+                continue;
+            }
+            if let Ok(line) = line
+                && let Some(url) = detect_dev_server_url(&line)
+            {
+                tracing::info!("Dev server for attempt {} is at {}", attempt_id, url);
+                self.record_url(attempt_id, execution_process_id, url);
+                found = true;
+            }
+        }
+        self.clear_url(attempt_id, execution_process_id);
+    }
+}
@@ -0,0 +1,234 @@
+//! Health checks and restart backoff for dev servers.
+//!
+//! A project's `dev_server_health_check` is one of:
+//!
+//! ```text
+//! http                           GET / on the dev server
+//! http:/api/health               GET a path on the dev server
+//! http://localhost:8080/ready    GET a full URL
+//! tcp                            connect to the attempt's dev server port
+//! tcp:5432                       connect to another local port
+//! command: ./scripts/ready.sh    run a script in the worktree; exit code 0 passes
+//! ```
+//!
+//! Command checks of projects that run executions in containers run inside the dev server's
+//! container.
+//!
+//! HTTP checks pass on any response below 500, so servers without a route at `/` still count
+//! as up. Failures are ignored while the server starts, until a check passes or
+//! [`STARTUP_GRACE`] runs out; after that [`FAILURES_BEFORE_UNHEALTHY`] failures in a row
+//! mark it unhealthy.
+
+use std::{path::Path, process::Stdio, sync::LazyLock, time::Duration};
+
+use db::models::execution_process::DevServerHealth;
+use reqwest::Url;
+use tokio::{net::TcpStream, process::Command};
+use utils::shell::get_shell_command;
+use uuid::Uuid;
+
+use crate::services::{
+    dev_server::{DevServerInfo, dev_server_env},
+    oci_runtime::OciRuntime,
+};
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+pub const STARTUP_GRACE: Duration = Duration::from_secs(120);
+pub const FAILURES_BEFORE_UNHEALTHY: u32 = 3;
+/// Uptime after which a dev server's automatic restarts are counted afresh
+pub const STABLE_UPTIME: Duration = Duration::from_secs(300);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .build()
+        .expect("valid health check client")
+});
+
+/// Where command checks run
+#[derive(Debug, Clone, Copy)]
+pub enum ProbeTarget<'a> {
+    Host,
+    /// Inside the container of the dev server's execution process
+    Container {
+        runtime: &'a OciRuntime,
+        execution_id: Uuid,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthCheck {
+    /// Path requested from the dev server's URL
+    HttpPath(String),
+    HttpUrl(String),
+    /// Local port; `None` uses the attempt's dev server port
+    Tcp(Option<u16>),
+    Command(String),
+}
+
+/// Parse a project's dev server health check, returning a user-facing reason when invalid.
+/// Blank specs mean no health check.
+pub fn parse_health_check(spec: &str) -> Result<Option<HealthCheck>, String> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Ok(None);
+    }
+    let check = if spec.starts_with("http://") || spec.starts_with("https://") {
+        Url::parse(spec).map_err(|e| format!("Invalid health check URL '{spec}': {e}"))?;
+        HealthCheck::HttpUrl(spec.to_string())
+    } else if spec == "http" {
+        HealthCheck::HttpPath("/".to_string())
+    } else if let Some(path) = spec.strip_prefix("http:") {
+        let path = path.trim();
+        if !path.starts_with('/') {
+            return Err(format!(
+                "Health check path '{path}' must start with '/', e.g. 'http:/health'"
+            ));
+        }
+        HealthCheck::HttpPath(path.to_string())
+    } else if spec == "tcp" {
+        HealthCheck::Tcp(None)
+    } else if let Some(port) = spec.strip_prefix("tcp:") {
+        let port = port
+            .trim()
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid health check port '{}'", port.trim()))?;
+        HealthCheck::Tcp(Some(port))
+    } else if let Some(command) = spec.strip_prefix("command:") {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err("Health check command is empty".to_string());
+        }
+        HealthCheck::Command(command.to_string())
+    } else {
+        return Err(format!(
+            "Unknown health check '{spec}'; use http, http:/path, a URL, tcp, tcp:port or command: <script>"
+        ));
+    };
+    Ok(Some(check))
+}
+
+pub fn validate_dev_server_health(
+    health_check: Option<&str>,
+    max_restarts: Option<i64>,
+) -> Result<(), String> {
+    health_check.map(parse_health_check).transpose()?;
+    if max_restarts.is_some_and(|max| max < 0) {
+        return Err("Maximum dev server restarts cannot be negative".to_string());
+    }
+    Ok(())
+}
+
+impl HealthCheck {
+    /// Run the check once against `dev_server`, returning why it failed
+    pub async fn probe(
+        &self,
+        dev_server: &DevServerInfo,
+        worktree: &Path,
+        target: ProbeTarget<'_>,
+    ) -> Result<(), String> {
+        match self {
+            HealthCheck::HttpPath(path) => {
+                Self::probe_http(&format!("{}{}", dev_server.upstream(), path)).await
+            }
+            HealthCheck::HttpUrl(url) => Self::probe_http(url).await,
+            HealthCheck::Tcp(port) => {
+                let port = port.unwrap_or(dev_server.port);
+                match tokio::time::timeout(CHECK_TIMEOUT, TcpStream::connect(("localhost", port)))
+                    .await
+                {
+                    Ok(Ok(_)) => Ok(()),
+                    Ok(Err(e)) => Err(format!("port {port}: {e}")),
+                    Err(_) => Err(format!("port {port}: timed out")),
+                }
+            }
+            HealthCheck::Command(script) => {
+                let env = dev_server_env(dev_server.port);
+                let mut command = match target {
+                    ProbeTarget::Host => {
+                        let (shell, shell_arg) = get_shell_command();
+                        let mut command = Command::new(shell);
+                        command
+                            .arg(shell_arg)
+                            .arg(script)
+                            .current_dir(worktree)
+                            .envs(env);
+                        command
+                    }
+                    ProbeTarget::Container {
+                        runtime,
+                        execution_id,
+                    } => runtime.exec_command(execution_id, worktree, &env, script),
+                };
+                command
+                    .kill_on_drop(true)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null());
+                match tokio::time::timeout(CHECK_TIMEOUT, command.status()).await {
+                    Ok(Ok(status)) if status.success() => Ok(()),
+                    Ok(Ok(status)) => Err(format!("command exited with {status}")),
+                    Ok(Err(e)) => Err(format!("command failed to start: {e}")),
+                    Err(_) => Err("command timed out".to_string()),
+                }
+            }
+        }
+    }
+
+    async fn probe_http(url: &str) -> Result<(), String> {
+        match CLIENT.get(url).send().await {
+            Ok(response) if !response.status().is_server_error() => Ok(()),
+            Ok(response) => Err(format!("{url} answered {}", response.status())),
+            Err(e) => Err(format!("{url}: {e}")),
+        }
+    }
+}
+
+/// Turns check results into a dev server's health
+#[derive(Debug)]
+pub struct HealthTracker {
+    state: DevServerHealth,
+    consecutive_failures: u32,
+}
+
+impl Default for HealthTracker {
+    fn default() -> Self {
+        Self {
+            state: DevServerHealth::Starting,
+            consecutive_failures: 0,
+        }
+    }
+}
+
+impl HealthTracker {
+    pub fn state(&self) -> DevServerHealth {
+        self.state
+    }
+
+    /// Record one check result, `since_start` after the dev server started. Returns the new
+    /// health when it changed.
+    pub fn record(&mut self, passed: bool, since_start: Duration) -> Option<DevServerHealth> {
+        let next = if passed {
+            self.consecutive_failures = 0;
+            DevServerHealth::Healthy
+        } else {
+            self.consecutive_failures += 1;
+            let starting = self.state == DevServerHealth::Starting && since_start < STARTUP_GRACE;
+            if starting || self.consecutive_failures < FAILURES_BEFORE_UNHEALTHY {
+                return None;
+            }
+            DevServerHealth::Unhealthy
+        };
+        (next != self.state).then(|| {
+            self.state = next;
+            next
+        })
+    }
+}
+
+/// Delay before the `restart`th automatic restart in a row (counting from 1)
+pub fn restart_backoff(restart: u32) -> Duration {
+    Duration::from_secs(1u64 << restart.saturating_sub(1).min(6)).min(MAX_RESTART_BACKOFF)
+}
@@ -22,6 +22,8 @@ use utils::{
 use crate::services::{
     filesystem_watcher::{self, FilesystemWatcherError},
     git::{Commit, DiffTarget, GitService, GitServiceError},
+    project_scope::ProjectScope,
+    workspace::prefix_diff_paths,
 };
 
 /// Maximum cumulative diff bytes to stream before omitting content (200MB)
@@ -29,6 +31,24 @@ pub const MAX_CUMULATIVE_DIFF_BYTES: usize = 200 * 1024 * 1024;
 
 const DIFF_STREAM_CHANNEL_CAPACITY: usize = 1000;
 
+/// Which of a worktree's changed paths a diff stream reports, and how
+#[derive(Debug, Clone, Default)]
+pub struct DiffPaths {
+    /// Paths are reported as `<prefix>/<path>`, for worktrees that are one of several in an
+    /// attempt's workspace
+    pub prefix: Option<String>,
+    /// Only paths in the scope are reported
+    pub scope: ProjectScope,
+}
+
+impl DiffPaths {
+    fn report(&self, diff: &mut Diff) {
+        if let Some(prefix) = &self.prefix {
+            prefix_diff_paths(diff, prefix);
+        }
+    }
+}
+
 /// Errors that can occur during diff stream creation and operation
 #[derive(Error, Debug)]
 pub enum DiffStreamError {
@@ -87,6 +107,7 @@ struct DiffWatcherContext {
     cumulative: Arc<AtomicUsize>,
     full_sent: Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    paths: Arc<DiffPaths>,
     tx: mpsc::Sender<Result<LogMsg, io::Error>>,
 }
 
@@ -109,6 +130,7 @@ impl DiffWatcherContext {
         let cumulative = self.cumulative.clone();
         let full_sent = self.full_sent.clone();
         let stats_only = self.stats_only;
+        let paths = self.paths.clone();
 
         match tokio::task::spawn_blocking(move || {
             process_file_changes(
@@ -119,6 +141,7 @@ impl DiffWatcherContext {
                 &cumulative,
                 &full_sent,
                 stats_only,
+                &paths,
             )
         })
         .await
@@ -142,11 +165,13 @@ impl DiffWatcherContext {
     }
 }
 
+/// Stream the worktree's diff against `base_commit`, followed by live updates as files change.
 pub async fn create(
     git_service: GitService,
     worktree_path: PathBuf,
     base_commit: Commit,
     stats_only: bool,
+    paths: DiffPaths,
 ) -> Result<DiffStreamHandle, DiffStreamError> {
     let initial_diffs_raw = git_service.get_diffs(
         DiffTarget::Worktree {
@@ -160,6 +185,10 @@ pub async fn create(
     let full_sent = Arc::new(std::sync::RwLock::new(HashSet::<String>::new()));
     let mut initial_diffs = Vec::with_capacity(initial_diffs_raw.len());
     for mut diff in initial_diffs_raw {
+        if !paths.scope.contains_diff(&diff) {
+            continue;
+        }
+        paths.report(&mut diff);
         apply_stream_omit_policy(&mut diff, &cumulative, stats_only);
         initial_diffs.push(diff);
     }
@@ -186,6 +215,7 @@ pub async fn create(
         cumulative,
         full_sent,
         stats_only,
+        paths: Arc::new(paths),
         tx: tx_clone,
     };
 
@@ -336,6 +366,7 @@ fn extract_changed_paths(
         .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_file_changes(
     git_service: &GitService,
     worktree_path: &Path,
@@ -344,7 +375,15 @@ fn process_file_changes(
     cumulative_bytes: &Arc<AtomicUsize>,
     full_sent_paths: &Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    paths: &DiffPaths,
 ) -> Result<Vec<LogMsg>, DiffStreamError> {
+    let changed_paths: Vec<&String> = changed_paths
+        .iter()
+        .filter(|path| paths.scope.contains(path))
+        .collect();
+    if changed_paths.is_empty() {
+        return Ok(Vec::new());
+    }
     let path_filter: Vec<&str> = changed_paths.iter().map(|s| s.as_str()).collect();
 
     let current_diffs = git_service.get_diffs(
@@ -359,6 +398,10 @@ fn process_file_changes(
     let mut files_with_diffs = HashSet::new();
 
     for mut diff in current_diffs {
+        if !paths.scope.contains_diff(&diff) {
+            continue;
+        }
+        paths.report(&mut diff);
         let file_path = GitService::diff_path(&diff);
         files_with_diffs.insert(file_path.clone());
         apply_stream_omit_policy(&mut diff, cumulative_bytes, stats_only);
@@ -377,8 +420,12 @@ fn process_file_changes(
     }
 
     for changed_path in changed_paths {
-        if !files_with_diffs.contains(changed_path) {
-            let patch = ConversationPatch::remove_diff(escape_json_pointer_segment(changed_path));
+        let changed_path = match &paths.prefix {
+            Some(prefix) => format!("{prefix}/{changed_path}"),
+            None => changed_path.to_string(),
+        };
+        if !files_with_diffs.contains(&changed_path) {
+            let patch = ConversationPatch::remove_diff(escape_json_pointer_segment(&changed_path));
             msgs.push(LogMsg::JsonPatch(patch));
         }
     }
@@ -5,7 +5,7 @@ use db::{
     models::{
         draft::{Draft, DraftType},
         execution_process::ExecutionProcess,
-        task::Task,
+        task::{Task, TaskStatus},
         task_attempt::TaskAttempt,
     },
 };
@@ -15,6 +15,11 @@ use tokio::sync::RwLock;
 use utils::msg_store::MsgStore;
 use uuid::Uuid;
 
+use crate::services::{
+    task_activity::{ACTIVITY_TABLES, row_activity},
+    webhooks::{WebhookEvent, WebhookService},
+};
+
 #[path = "events/patches.rs"]
 pub mod patches;
 #[path = "events/streams.rs"]
@@ -22,7 +27,9 @@ mod streams;
 #[path = "events/types.rs"]
 pub mod types;
 
-pub use patches::{draft_patch, execution_process_patch, task_attempt_patch, task_patch};
+pub use patches::{
+    draft_patch, execution_process_patch, task_activity_patch, task_attempt_patch, task_patch,
+};
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
 #[derive(Clone)]
@@ -74,11 +81,56 @@ impl EventService {
         Ok(())
     }
 
+    /// Tell webhooks about new tasks and attempts
+    async fn dispatch_insert_webhooks(
+        pool: &SqlitePool,
+        webhooks: &WebhookService,
+        record: &RecordTypes,
+    ) -> Result<(), SqlxError> {
+        match record {
+            RecordTypes::Task(task) => webhooks.dispatch(
+                task.project_id,
+                WebhookEvent::TaskCreated { task: task.clone() },
+            ),
+            RecordTypes::TaskAttempt(attempt) => {
+                if let Some(task) = Task::find_by_id(pool, attempt.task_id).await? {
+                    webhooks.dispatch(
+                        task.project_id,
+                        WebhookEvent::AttemptStarted {
+                            task,
+                            attempt: attempt.clone(),
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Position of `column` in `table`; the preupdate hook only exposes columns by index
+    async fn column_index(
+        conn: &mut sqlx::sqlite::SqliteConnection,
+        table: &str,
+        column: &str,
+    ) -> Result<Option<i32>, SqlxError> {
+        let columns: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_info($1) ORDER BY cid")
+                .bind(table)
+                .fetch_all(conn)
+                .await?;
+        Ok(columns
+            .iter()
+            .position(|name| name == column)
+            .map(|index| index as i32))
+    }
+
     /// Creates the hook function that should be used with DBService::new_with_after_connect
     pub fn create_hook(
         msg_store: Arc<MsgStore>,
         entry_count: Arc<RwLock<usize>>,
         db_service: DBService,
+        webhooks: WebhookService,
     ) -> impl for<'a> Fn(
         &'a mut sqlx::sqlite::SqliteConnection,
     ) -> std::pin::Pin<
@@ -90,12 +142,55 @@ impl EventService {
             let msg_store_for_hook = msg_store.clone();
             let entry_count_for_hook = entry_count.clone();
             let db_for_hook = db_service.clone();
+            let webhooks_for_hook = webhooks.clone();
             Box::pin(async move {
+                let task_status_column = Self::column_index(conn, "tasks", "status").await?;
                 let mut handle = conn.lock_handle().await?;
                 let runtime_handle = tokio::runtime::Handle::current();
                 handle.set_preupdate_hook({
                     let msg_store_for_preupdate = msg_store_for_hook.clone();
+                    let db_for_preupdate = db_for_hook.clone();
+                    let webhooks_for_preupdate = webhooks_for_hook.clone();
+                    let runtime_handle = runtime_handle.clone();
                     move |preupdate: sqlx::sqlite::PreupdateHookResult<'_>| {
+                        // Status changes are only visible here, while the old row is available
+                        if preupdate.operation == SqliteOperation::Update
+                            && preupdate.table == "tasks"
+                        {
+                            let old_status = task_status_column
+                                .and_then(|column| preupdate.get_old_column_value(column).ok())
+                                .and_then(|val| <TaskStatus as Decode<Sqlite>>::decode(val).ok());
+                            let new_status = task_status_column
+                                .and_then(|column| preupdate.get_new_column_value(column).ok())
+                                .and_then(|val| <TaskStatus as Decode<Sqlite>>::decode(val).ok());
+                            let task_id = preupdate
+                                .get_old_column_value(0)
+                                .ok()
+                                .and_then(|val| <Uuid as Decode<Sqlite>>::decode(val).ok());
+
+                            if let (Some(previous_status), Some(new_status), Some(task_id)) =
+                                (old_status, new_status, task_id)
+                                && previous_status != new_status
+                            {
+                                let db = db_for_preupdate.clone();
+                                let webhooks = webhooks_for_preupdate.clone();
+                                runtime_handle.spawn(async move {
+                                    if let Ok(Some(task)) =
+                                        Task::find_by_id(&db.pool, task_id).await
+                                    {
+                                        webhooks.dispatch(
+                                            task.project_id,
+                                            WebhookEvent::TaskStatusChanged {
+                                                task,
+                                                previous_status,
+                                            },
+                                        );
+                                    }
+                                });
+                            }
+                            return;
+                        }
+
                         if preupdate.operation != SqliteOperation::Delete {
                             return;
                         }
@@ -160,6 +255,30 @@ impl EventService {
                     let entry_count_for_hook = entry_count_for_hook.clone();
                     let msg_store_for_hook = msg_store_for_hook.clone();
                     let db = db_for_hook.clone();
+                    let webhooks = webhooks_for_hook.clone();
+
+                    // Deleted rows only take timeline entries away along with their attempt or
+                    // process, whose removal patches the activity stream already follows
+                    if hook.operation != SqliteOperation::Delete
+                        && ACTIVITY_TABLES.contains(&hook.table)
+                    {
+                        let pool = db.pool.clone();
+                        let msg_store = msg_store_for_hook.clone();
+                        let table = hook.table.to_string();
+                        let rowid = hook.rowid;
+                        runtime_handle.spawn(async move {
+                            match row_activity(&pool, &table, rowid).await {
+                                Ok(activity) => {
+                                    for (task_id, entry) in activity {
+                                        msg_store.push_patch(task_activity_patch::add(task_id, &entry));
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::error!("Failed to load {} activity: {:?}", table, e)
+                                }
+                            }
+                        });
+                    }
 
                     if let Ok(table) = HookTables::from_str(hook.table) {
                         let rowid = hook.rowid;
@@ -238,6 +357,17 @@ impl EventService {
                                 }
                             };
 
+                            if hook.operation == SqliteOperation::Insert
+                                && let Err(e) = EventService::dispatch_insert_webhooks(
+                                    &db.pool,
+                                    &webhooks,
+                                    &record_type,
+                                )
+                                .await
+                            {
+                                tracing::error!("Failed to dispatch webhooks: {:?}", e);
+                            }
+
                             let db_op: &str = match hook.operation {
                                 SqliteOperation::Insert => "insert",
                                 SqliteOperation::Delete => "delete",
@@ -7,8 +7,10 @@ use db::models::{
 use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation, ReplaceOperation};
 use uuid::Uuid;
 
+use crate::services::task_activity::TaskActivity;
+
 // Shared helper to escape JSON Pointer segments
-fn escape_pointer_segment(s: &str) -> String {
+pub(super) fn escape_pointer_segment(s: &str) -> String {
     s.replace('~', "~0").replace('/', "~1")
 }
 
@@ -202,3 +204,27 @@ pub mod task_attempt_patch {
         })])
     }
 }
+
+/// Helper functions for creating task activity patches
+pub mod task_activity_patch {
+    use super::*;
+
+    fn activity_path(task_id: Uuid, key: &str) -> String {
+        format!(
+            "/task_activity/{}/{}",
+            escape_pointer_segment(&task_id.to_string()),
+            escape_pointer_segment(key)
+        )
+    }
+
+    /// Create patch for adding an entry to a task's timeline, or updating it
+    pub fn add(task_id: Uuid, activity: &TaskActivity) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: activity_path(task_id, &activity.key)
+                .try_into()
+                .expect("Task activity path should be valid"),
+            value: serde_json::to_value(activity)
+                .expect("Task activity serialization should not fail"),
+        })])
+    }
+}
@@ -1,9 +1,12 @@
+use std::collections::HashSet;
+
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
     task::{Task, TaskWithAttemptStatus},
 };
 use futures::StreamExt;
+use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation};
 use serde_json::json;
 use tokio_stream::wrappers::BroadcastStream;
 use utils::log_msg::LogMsg;
@@ -11,9 +14,78 @@ use uuid::Uuid;
 
 use super::{
     EventService,
-    patches::execution_process_patch,
+    patches::{escape_pointer_segment, execution_process_patch},
     types::{EventError, EventPatch, RecordTypes},
 };
+use crate::services::task_activity::{TaskActivity, task_timeline};
+
+/// The task's activity timeline as an object keyed by entry, replacing what was sent before
+fn task_activity_replace(activity: &[TaskActivity]) -> LogMsg {
+    let activity: serde_json::Map<String, serde_json::Value> = activity
+        .iter()
+        .map(|entry| (entry.key.clone(), serde_json::to_value(entry).unwrap()))
+        .collect();
+    let patch = json!([{
+        "op": "replace",
+        "path": "/activity",
+        "value": activity
+    }]);
+    LogMsg::JsonPatch(serde_json::from_value(patch).unwrap())
+}
+
+fn task_activity_path(key: &str) -> String {
+    format!("/activity/{}", escape_pointer_segment(key))
+}
+
+/// Turn a patch into updates of the activity of the task whose entries are pushed under
+/// `activity_prefix`: its own entries are added, and removing an attempt or process removes
+/// the entries under it. `sent` holds the keys of the entries the subscriber has.
+fn task_activity_ops(
+    patch: Patch,
+    activity_prefix: &str,
+    sent: &mut HashSet<String>,
+) -> Vec<PatchOperation> {
+    let mut ops = Vec::new();
+    for op in patch.0 {
+        let path = op.path().to_string();
+        match op {
+            PatchOperation::Add(AddOperation { value, .. }) => {
+                let Some(segment) = path.strip_prefix(activity_prefix) else {
+                    continue;
+                };
+                sent.insert(segment.replace("~1", "/").replace("~0", "~"));
+                ops.push(PatchOperation::Add(AddOperation {
+                    path: format!("/activity/{segment}")
+                        .try_into()
+                        .expect("Task activity path should be valid"),
+                    value,
+                }));
+            }
+            PatchOperation::Remove(_) => {
+                let removed = |key: &String| {
+                    if let Some(id) = path.strip_prefix("/task_attempts/") {
+                        key.starts_with(&format!("attempt:{id}:"))
+                    } else if let Some(id) = path.strip_prefix("/execution_processes/") {
+                        key.contains(&format!(":process:{id}:"))
+                    } else {
+                        false
+                    }
+                };
+                let keys: Vec<String> = sent.iter().filter(|key| removed(key)).cloned().collect();
+                for key in keys {
+                    sent.remove(&key);
+                    ops.push(PatchOperation::Remove(RemoveOperation {
+                        path: task_activity_path(&key)
+                            .try_into()
+                            .expect("Task activity path should be valid"),
+                    }));
+                }
+            }
+            _ => {}
+        }
+    }
+    ops
+}
 
 impl EventService {
     /// Stream raw task messages for a specific project with initial snapshot
@@ -371,4 +443,55 @@ impl EventService {
         let combined_stream = initial_stream.chain(filtered_stream).boxed();
         Ok(combined_stream)
     }
+
+    /// Stream a task's activity timeline: all of it at first, then the entries that are added,
+    /// change or go away
+    pub async fn stream_task_activity_raw(
+        &self,
+        task_id: Uuid,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let pool = self.db.pool.clone();
+        let initial = task_timeline(&pool, task_id)
+            .await
+            .map_err(|e| EventError::Other(e.into()))?;
+        let initial_msg = task_activity_replace(&initial);
+        let sent: HashSet<String> = initial.into_iter().map(|entry| entry.key).collect();
+
+        let activity_prefix = format!("/task_activity/{task_id}/");
+        let receiver = BroadcastStream::new(self.msg_store.get_receiver());
+
+        let updates = futures::stream::unfold((receiver, sent), move |(mut receiver, mut sent)| {
+            let pool = pool.clone();
+            let activity_prefix = activity_prefix.clone();
+            async move {
+                loop {
+                    match receiver.next().await? {
+                        Ok(LogMsg::JsonPatch(patch)) => {
+                            let ops = task_activity_ops(patch, &activity_prefix, &mut sent);
+                            if !ops.is_empty() {
+                                let msg = LogMsg::JsonPatch(Patch(ops));
+                                return Some((Ok(msg), (receiver, sent)));
+                            }
+                        }
+                        Ok(_) => {}
+                        // Lagged behind; reload in case something relevant was missed
+                        Err(_) => match task_timeline(&pool, task_id).await {
+                            Ok(activity) => {
+                                let msg = task_activity_replace(&activity);
+                                let sent = activity.into_iter().map(|entry| entry.key).collect();
+                                return Some((Ok(msg), (receiver, sent)));
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to load activity of task {}: {}", task_id, e)
+                            }
+                        },
+                    }
+                }
+            }
+        });
+
+        let initial_stream = futures::stream::once(async move { Ok(initial_msg) });
+        Ok(initial_stream.chain(updates).boxed())
+    }
 }
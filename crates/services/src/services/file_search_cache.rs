@@ -21,6 +21,7 @@ use ts_rs::TS;
 use super::{
     file_ranker::{FileRanker, FileStats},
     git::GitService,
+    project_scope::ProjectScope,
 };
 
 /// Search mode for different use cases
@@ -133,12 +134,13 @@ impl FileSearchCache {
         }
     }
 
-    /// Search files in repository using cache
+    /// Search files in repository using cache, limited to the project's scope
     pub async fn search(
         &self,
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        scope: &ProjectScope,
     ) -> Result<Vec<SearchResult>, CacheError> {
         let repo_path_buf = repo_path.to_path_buf();
 
@@ -148,7 +150,7 @@ impl FileSearchCache {
             && head_info.oid == cached.head_sha
         {
             // Cache hit - perform fast search with mode-based filtering
-            return Ok(self.search_in_cache(&cached, query, mode).await);
+            return Ok(self.search_in_cache(&cached, query, mode, scope).await);
         }
 
         // Cache miss - trigger background refresh and return error
@@ -221,13 +223,16 @@ impl FileSearchCache {
         cached: &CachedRepo,
         query: &str,
         mode: SearchMode,
+        scope: &ProjectScope,
     ) -> Vec<SearchResult> {
         let query_lower = query.to_lowercase();
         let mut results = Vec::new();
 
         // Search through indexed files with mode-based filtering
         for indexed_file in &cached.indexed_files {
-            if indexed_file.path_lowercase.contains(&query_lower) {
+            if indexed_file.path_lowercase.contains(&query_lower)
+                && scope.contains(&indexed_file.path)
+            {
                 // Apply mode-based filtering
                 match mode {
                     SearchMode::TaskForm => {
@@ -255,6 +260,7 @@ impl FileSearchCache {
 
         // Limit to top 10 results
         results.truncate(10);
+        scope_result_paths(&mut results, mode, scope);
         results
     }
 
@@ -499,6 +505,16 @@ impl FileSearchCache {
     }
 }
 
+/// Task forms hand paths to coding agents, which run in the project's working directory, so
+/// every path is made relative to it
+pub fn scope_result_paths(results: &mut [SearchResult], mode: SearchMode, scope: &ProjectScope) {
+    if matches!(mode, SearchMode::TaskForm) {
+        for result in results {
+            result.path = scope.relative_to_working_dir(&result.path);
+        }
+    }
+}
+
 impl Default for FileSearchCache {
     fn default() -> Self {
         Self::new()
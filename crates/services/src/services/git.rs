@@ -1,6 +1,7 @@
 use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
+use db::models::merge::MergeStrategy;
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
     Repository, Sort, build::CheckoutBuilder,
@@ -12,7 +13,11 @@ use utils::diff::{Diff, DiffChangeKind, FileDiffDetails};
 
 // Import for file ranking functionality
 use super::file_ranker::FileStat;
-use super::git_cli::{ChangeType, GitCli, GitCliError, StatusDiffEntry, StatusDiffOptions};
+use super::{
+    commit_identity::CommitIdentity,
+    git_cli::{ChangeType, GitCli, GitCliError, StatusDiffEntry, StatusDiffOptions},
+    project_scope::ProjectScope,
+};
 use crate::services::github_service::GitHubRepoInfo;
 
 #[derive(Debug, Error)]
@@ -40,7 +45,9 @@ pub enum GitServiceError {
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
-pub struct GitService {}
+pub struct GitService {
+    commit_identity: CommitIdentity,
+}
 
 // Max inline diff size for UI (in bytes). Files larger than this will have
 // their contents omitted from the diff stream to avoid UI crashes.
@@ -56,6 +63,20 @@ pub enum ConflictOp {
     Revert,
 }
 
+/// A single conflicted region (`<<<<<<<` .. `>>>>>>>`) inside a worktree file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictHunk {
+    pub path: String,
+    /// 1-based line of the opening `<<<<<<<` marker
+    pub start_line: usize,
+    /// 1-based line of the closing `>>>>>>>` marker
+    pub end_line: usize,
+    pub ours: String,
+    /// Common ancestor section, only present with `merge.conflictStyle=diff3`
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct GitBranch {
     pub name: String,
@@ -148,7 +169,20 @@ impl Default for GitService {
 impl GitService {
     /// Create a new GitService for the given repository path
     pub fn new() -> Self {
-        Self {}
+        Self {
+            commit_identity: CommitIdentity::default(),
+        }
+    }
+
+    /// A copy of this service that authors, co-authors and signs every commit it creates
+    /// with the given identity.
+    pub fn with_commit_identity(&self, commit_identity: CommitIdentity) -> Self {
+        Self { commit_identity }
+    }
+
+    /// Git CLI carrying the commit identity for commands that create commits
+    fn commit_cli(&self) -> GitCli {
+        GitCli::new().with_config(self.commit_identity.cli_config())
     }
 
     /// Open the repository
@@ -171,16 +205,75 @@ impl GitService {
         Ok(())
     }
 
-    /// Get a signature for libgit2 commits with a safe fallback identity.
+    /// Get a signature for libgit2 commits: the commit identity where set, then the repo
+    /// config, then a safe fallback identity.
     fn signature_with_fallback<'a>(
         &self,
         repo: &'a Repository,
     ) -> Result<git2::Signature<'a>, GitServiceError> {
-        match repo.signature() {
-            Ok(sig) => Ok(sig),
-            Err(_) => git2::Signature::now("Vibe Kanban", "noreply@vibekanban.com")
-                .map_err(GitServiceError::from),
+        let configured = repo.signature().ok();
+        let name = self
+            .commit_identity
+            .name
+            .clone()
+            .or_else(|| {
+                configured
+                    .as_ref()
+                    .and_then(|s| s.name().map(str::to_string))
+            })
+            .unwrap_or_else(|| "Vibe Kanban".to_string());
+        let email = self
+            .commit_identity
+            .email
+            .clone()
+            .or_else(|| {
+                configured
+                    .as_ref()
+                    .and_then(|s| s.email().map(str::to_string))
+            })
+            .unwrap_or_else(|| "noreply@vibekanban.com".to_string());
+        git2::Signature::now(&name, &email).map_err(GitServiceError::from)
+    }
+
+    /// Write a commit with libgit2, adding co-author trailers and signing it when the
+    /// commit identity asks for it, then point `update_ref` (if any) at it.
+    fn create_commit(
+        &self,
+        repo: &Repository,
+        update_ref: Option<&str>,
+        signature: &git2::Signature,
+        message: &str,
+        tree: &git2::Tree,
+        parents: &[&git2::Commit],
+    ) -> Result<git2::Oid, GitServiceError> {
+        let message = self.commit_identity.with_trailers(message);
+        if self.commit_identity.signing_key.is_none() {
+            return Ok(repo.commit(update_ref, signature, signature, &message, tree, parents)?);
+        }
+
+        let buffer = repo.commit_create_buffer(signature, signature, &message, tree, parents)?;
+        let buffer = buffer.as_str().ok_or_else(|| {
+            GitServiceError::InvalidRepository("Commit buffer is not valid UTF-8".to_string())
+        })?;
+        let commit_signature = self.commit_identity.sign(buffer)?.unwrap_or_default();
+        let oid = repo.commit_signed(buffer, &commit_signature, None)?;
+
+        match update_ref {
+            Some("HEAD") => {
+                let head = repo.head()?;
+                if head.is_branch() {
+                    let refname = head.name().unwrap_or("HEAD").to_string();
+                    repo.reference(&refname, oid, true, "commit (signed)")?;
+                } else {
+                    repo.set_head_detached(oid)?;
+                }
+            }
+            Some(refname) => {
+                repo.reference(refname, oid, true, "commit (signed)")?;
+            }
+            None => {}
         }
+        Ok(oid)
     }
 
     pub fn default_remote_name(&self, repo: &Repository) -> String {
@@ -265,9 +358,22 @@ impl GitService {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, message))]
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
+        self.commit_in_scope(path, message, &ProjectScope::default())
+    }
+
+    /// Commit every change inside `scope`. Changes outside it are left unstaged in the
+    /// worktree: diffs don't show them, so they must not reach the branch either.
+    #[tracing::instrument(skip(self, message, scope))]
+    pub fn commit_in_scope(
+        &self,
+        path: &Path,
+        message: &str,
+        scope: &ProjectScope,
+    ) -> Result<bool, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
-        let git = GitCli::new();
+        let git = self.commit_cli();
         let has_changes = git
             .has_changes(path)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
@@ -278,9 +384,34 @@ impl GitService {
 
         git.add_all(path)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
+        if scope.is_scoped() {
+            let outside: Vec<String> = git
+                .staged_paths(path)
+                .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))?
+                .into_iter()
+                .filter(|p| !scope.contains(p))
+                .collect();
+            if !outside.is_empty() {
+                tracing::warn!(
+                    "Not committing changes outside the project's scope in {}: {}",
+                    path.display(),
+                    outside.join(", ")
+                );
+                git.unstage(path, &outside).map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("git reset failed: {e}"))
+                })?;
+                let has_staged = git.has_staged_changes(path).map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("git diff failed: {e}"))
+                })?;
+                if !has_staged {
+                    tracing::debug!("No changes in scope to commit!");
+                    return Ok(false);
+                }
+            }
+        }
         // Only ensure identity once we know we're about to commit
         self.ensure_cli_commit_identity(path)?;
-        git.commit(path, message)
+        git.commit(path, &self.commit_identity.with_trailers(message))
             .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
         Ok(true)
     }
@@ -760,7 +891,12 @@ impl GitService {
         Ok(None)
     }
 
-    /// Merge changes from a task branch into the base branch.
+    /// Merge changes from a task branch into the base branch using the given strategy.
+    ///
+    /// When the base branch has moved ahead of the task branch, `RebaseFastForward` first
+    /// replays the task commits onto the base in the task worktree; the other strategies
+    /// refuse the merge.
+    #[tracing::instrument(skip(self, commit_message))]
     pub fn merge_changes(
         &self,
         base_worktree_path: &Path,
@@ -768,7 +904,10 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+        strategy: MergeStrategy,
     ) -> Result<String, GitServiceError> {
+        let commit_message = &self.commit_identity.with_trailers(commit_message);
+
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
         let base_repo = self.open_repo(base_worktree_path)?;
@@ -779,16 +918,19 @@ impl GitService {
             self.get_branch_status(base_worktree_path, task_branch_name, base_branch_name)?;
 
         if task_behind > 0 {
-            return Err(GitServiceError::BranchesDiverged(format!(
-                "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
-            )));
+            if strategy != MergeStrategy::RebaseFastForward {
+                return Err(GitServiceError::BranchesDiverged(format!(
+                    "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
+                )));
+            }
+            self.rebase_onto_base(task_worktree_path, base_branch_name, task_branch_name)?;
         }
 
         // Check where base branch is checked out (if anywhere)
         match self.find_checkout_path_for_branch(base_worktree_path, base_branch_name)? {
             Some(base_checkout_path) => {
                 // base branch is checked out somewhere - use CLI merge
-                let git_cli = GitCli::new();
+                let git_cli = self.commit_cli();
 
                 // Safety check: base branch has no staged changes
                 if git_cli
@@ -805,16 +947,28 @@ impl GitService {
 
                 // Use CLI merge in base context
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
-                let sha = git_cli
-                    .merge_squash_commit(
+                let sha = match strategy {
+                    MergeStrategy::Squash => git_cli.merge_squash_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
                         commit_message,
-                    )
-                    .map_err(|e| {
-                        GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
-                    })?;
+                    ),
+                    MergeStrategy::RebaseFastForward => git_cli.merge_fast_forward(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                    ),
+                    MergeStrategy::MergeCommit => git_cli.merge_no_ff_commit(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                        commit_message,
+                    ),
+                }
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
+                })?;
 
                 // Update task branch ref for continuity
                 let task_refname = format!("refs/heads/{task_branch_name}");
@@ -835,31 +989,90 @@ impl GitService {
                 let base_commit = base_branch.get().peel_to_commit()?;
                 let task_commit = task_branch.get().peel_to_commit()?;
 
-                // Create the squash commit in-memory (no checkout) and update the base branch ref
+                // Create the new commit in-memory (no checkout) and update the base branch ref
                 let signature = self.signature_with_fallback(&task_repo)?;
-                let squash_commit_id = self.perform_squash_merge(
-                    &task_repo,
-                    &base_commit,
-                    &task_commit,
-                    &signature,
-                    commit_message,
-                    base_branch_name,
-                )?;
+                let new_base_id = match strategy {
+                    MergeStrategy::Squash => self.perform_squash_merge(
+                        &task_repo,
+                        &base_commit,
+                        &task_commit,
+                        &signature,
+                        commit_message,
+                        base_branch_name,
+                    )?,
+                    MergeStrategy::RebaseFastForward => self.perform_fast_forward(
+                        &task_repo,
+                        &base_commit,
+                        &task_commit,
+                        base_branch_name,
+                    )?,
+                    MergeStrategy::MergeCommit => self.perform_merge_commit(
+                        &task_repo,
+                        &base_commit,
+                        &task_commit,
+                        &signature,
+                        commit_message,
+                        base_branch_name,
+                    )?,
+                };
 
-                // Update the task branch to the new squash commit so follow-up
+                // Update the task branch to the new base head so follow-up
                 // work can continue from the merged state without conflicts.
                 let task_refname = format!("refs/heads/{task_branch_name}");
                 base_repo.reference(
                     &task_refname,
-                    squash_commit_id,
+                    new_base_id,
                     true,
-                    "Reset task branch after squash merge",
+                    "Reset task branch after merge",
                 )?;
 
-                Ok(squash_commit_id.to_string())
+                Ok(new_base_id.to_string())
+            }
+        }
+    }
+
+    /// Replay the task commits onto the tip of the base branch ahead of a fast-forward merge.
+    /// A conflicting rebase is aborted so the task worktree is left as it was.
+    fn rebase_onto_base(
+        &self,
+        task_worktree_path: &Path,
+        base_branch_name: &str,
+        task_branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let worktree_repo = Repository::open(task_worktree_path)?;
+        self.check_worktree_clean(&worktree_repo)?;
+
+        let git = self.commit_cli();
+        self.ensure_cli_commit_identity(task_worktree_path)?;
+        match git.rebase_onto(
+            task_worktree_path,
+            base_branch_name,
+            base_branch_name,
+            task_branch_name,
+        ) {
+            Ok(()) => Ok(()),
+            Err(GitCliError::RebaseInProgress) => Err(GitServiceError::RebaseInProgress),
+            Err(e) => {
+                let conflicts = git
+                    .get_conflicted_files(task_worktree_path)
+                    .unwrap_or_default();
+                if let Err(abort_err) = git.abort_rebase(task_worktree_path) {
+                    tracing::warn!("Failed to abort rebase before merge: {}", abort_err);
+                }
+                if conflicts.is_empty() {
+                    Err(GitServiceError::InvalidRepository(format!(
+                        "Rebase before merge failed: {e}"
+                    )))
+                } else {
+                    Err(GitServiceError::MergeConflicts(format!(
+                        "Rebasing '{task_branch_name}' onto '{base_branch_name}' before merging hit conflicts in: {}. Rebase the attempt and resolve them, then merge again.",
+                        conflicts.join(", ")
+                    )))
+                }
             }
         }
     }
+
     fn get_branch_status_inner(
         &self,
         repo: &Repository,
@@ -955,9 +1168,21 @@ impl GitService {
         let statuses = repo.statuses(Some(&mut status_options))?;
 
         if !statuses.is_empty() {
+            // Paths outside a sparse checkout are missing on disk but unchanged
+            let index = repo.index()?;
+            let outside_sparse_checkout = |path: &str| {
+                index.get_path(Path::new(path), 0).is_some_and(|entry| {
+                    entry.flags_extended & git2::IndexEntryExtendedFlag::SKIP_WORKTREE.bits() != 0
+                })
+            };
             let mut dirty_files = Vec::new();
             for entry in statuses.iter() {
                 let status = entry.status();
+                if status == git2::Status::WT_DELETED
+                    && entry.path().is_some_and(outside_sparse_checkout)
+                {
+                    continue;
+                }
                 // Only consider files that are actually tracked and modified
                 if status.intersects(
                     git2::Status::INDEX_MODIFIED
@@ -1052,6 +1277,27 @@ impl GitService {
         ))
     }
 
+    /// Text content of a file at a commit; `None` when the file doesn't exist there or isn't
+    /// UTF-8
+    pub fn get_file_content_at_commit(
+        &self,
+        repo_path: &Path,
+        commit: &Commit,
+        path: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let tree = repo.find_commit(commit.as_oid())?.tree()?;
+        let entry = match tree.get_path(Path::new(path)) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let Ok(blob) = entry.to_object(&repo)?.peel_to_blob() else {
+            return Ok(None);
+        };
+        Ok(std::str::from_utf8(blob.content()).ok().map(str::to_string))
+    }
+
     /// Get the subject/summary line for a given commit OID
     pub fn get_commit_subject(
         &self,
@@ -1065,6 +1311,29 @@ impl GitService {
         Ok(commit.summary().unwrap_or("(no subject)").to_string())
     }
 
+    /// Messages of the commits on `branch_name` that are not on `base_branch_name`,
+    /// oldest first
+    pub fn get_commit_messages(
+        &self,
+        repo_path: &Path,
+        base_branch_name: &str,
+        branch_name: &str,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch = Self::find_branch(&repo, branch_name)?;
+        let base_branch = Self::find_branch(&repo, base_branch_name)?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(branch.get().peel_to_commit()?.id())?;
+        revwalk.hide(base_branch.get().peel_to_commit()?.id())?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        revwalk
+            .map(|oid| -> Result<String, GitServiceError> {
+                let commit = repo.find_commit(oid?)?;
+                Ok(commit.message().unwrap_or_default().trim_end().to_string())
+            })
+            .collect()
+    }
+
     /// Compare two OIDs and return (ahead, behind) counts: how many commits
     /// `from_oid` is ahead of and behind `to_oid`.
     pub fn ahead_behind_commits_by_oid(
@@ -1106,6 +1375,7 @@ impl GitService {
 
     /// Evaluate whether any action is needed to reset to `target_commit_oid` and
     /// optionally perform the actions.
+    #[tracing::instrument(skip(self))]
     pub fn reconcile_worktree_to_commit(
         &self,
         worktree_path: &Path,
@@ -1219,6 +1489,7 @@ impl GitService {
     }
 
     /// Add a worktree for a branch, optionally creating the branch
+    #[tracing::instrument(skip(self))]
     pub fn add_worktree(
         &self,
         repo_path: &Path,
@@ -1361,10 +1632,10 @@ impl GitService {
         let tree = repo.find_tree(tree_id)?;
 
         // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
+        let squash_commit_id = self.create_commit(
+            repo,
             None,           // Don't update any reference yet
-            signature,      // Author
-            signature,      // Committer
+            signature,      // Author and committer
             commit_message, // Custom message
             &tree,          // Merged tree content
             &[base_commit], // Single parent: base branch commit
@@ -1377,7 +1648,68 @@ impl GitService {
         Ok(squash_commit_id)
     }
 
+    fn perform_fast_forward(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        base_branch_name: &str,
+    ) -> Result<git2::Oid, GitServiceError> {
+        if base_commit.id() != task_commit.id()
+            && !repo.graph_descendant_of(task_commit.id(), base_commit.id())?
+        {
+            return Err(GitServiceError::BranchesDiverged(format!(
+                "Cannot fast-forward '{base_branch_name}': the task branch does not contain its head commit"
+            )));
+        }
+
+        let refname = format!("refs/heads/{base_branch_name}");
+        repo.reference(&refname, task_commit.id(), true, "Fast-forward merge")?;
+
+        Ok(task_commit.id())
+    }
+
+    fn perform_merge_commit(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+        commit_message: &str,
+        base_branch_name: &str,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let mut merge_opts = git2::MergeOptions::new();
+        merge_opts.find_renames(true);
+        merge_opts.fail_on_conflict(true);
+        let mut index = repo.merge_commits(base_commit, task_commit, Some(&merge_opts))?;
+
+        if index.has_conflicts() {
+            return Err(GitServiceError::MergeConflicts(
+                "Merge failed due to conflicts. Please resolve conflicts manually.".to_string(),
+            ));
+        }
+
+        let tree_id = index.write_tree_to(repo)?;
+        let tree = repo.find_tree(tree_id)?;
+
+        // Always record both parents, even when a fast-forward would be possible
+        let merge_commit_id = self.create_commit(
+            repo,
+            None,
+            signature,
+            commit_message,
+            &tree,
+            &[base_commit, task_commit],
+        )?;
+
+        let refname = format!("refs/heads/{base_branch_name}");
+        repo.reference(&refname, merge_commit_id, true, "Merge commit")?;
+
+        Ok(merge_commit_id)
+    }
+
     /// Rebase a worktree branch onto a new base
+    #[tracing::instrument(skip(self, github_token))]
     pub fn rebase_branch(
         &self,
         repo_path: &Path,
@@ -1397,7 +1729,7 @@ impl GitService {
 
         // If a rebase is already in progress, refuse to proceed instead of
         // aborting (which might destroy user changes mid-rebase).
-        let git = GitCli::new();
+        let git = self.commit_cli();
         if git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
             return Err(GitServiceError::RebaseInProgress);
         }
@@ -1593,6 +1925,95 @@ impl GitService {
         Ok(())
     }
 
+    /// Collect the conflicted regions of every unmerged file in the worktree.
+    pub fn get_conflict_hunks(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<ConflictHunk>, GitServiceError> {
+        let mut hunks = Vec::new();
+        for file in self.get_conflicted_files(worktree_path)? {
+            // Binary or deleted-side conflicts have no textual markers to show
+            let Ok(content) = std::fs::read_to_string(worktree_path.join(&file)) else {
+                continue;
+            };
+            hunks.extend(parse_conflict_hunks(&file, &content));
+        }
+        Ok(hunks)
+    }
+
+    /// Return the changed files in the worktree that still contain conflict markers.
+    /// Files already staged by the resolver are checked too, since staging
+    /// clears the unmerged state without proving the markers are gone.
+    pub fn files_with_conflict_markers(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let git = GitCli::new();
+        let status = git
+            .get_worktree_status(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
+        let files: Vec<String> = status
+            .entries
+            .into_iter()
+            .map(|e| e.path)
+            .filter(|p| {
+                std::fs::read_to_string(worktree_path.join(p))
+                    .map(|content| !parse_conflict_hunks(p, &content).is_empty())
+                    .unwrap_or(false)
+            })
+            .collect();
+        Ok(files)
+    }
+
+    /// Stage the resolved worktree and continue the in-progress rebase, merge,
+    /// cherry-pick or revert. Refuses to continue while conflict markers remain.
+    /// Returns the operation that was continued, or None if nothing was in progress.
+    pub fn continue_conflicts(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Option<ConflictOp>, GitServiceError> {
+        let Some(op) = self.detect_conflict_op(worktree_path)? else {
+            return Ok(None);
+        };
+
+        let remaining = self.files_with_conflict_markers(worktree_path)?;
+        if !remaining.is_empty() {
+            return Err(GitServiceError::MergeConflicts(format!(
+                "Conflict markers remain in: {}",
+                remaining.join(", ")
+            )));
+        }
+
+        let git = self.commit_cli();
+        git.add_all(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
+        // Continuing creates commits, so make sure an identity exists
+        self.ensure_cli_commit_identity(worktree_path)?;
+
+        let result = match op {
+            ConflictOp::Rebase => git.continue_rebase(worktree_path),
+            ConflictOp::Merge => git.continue_merge(worktree_path),
+            ConflictOp::CherryPick => git.continue_cherry_pick(worktree_path),
+            ConflictOp::Revert => git.continue_revert(worktree_path),
+        };
+        match result {
+            Ok(()) => Ok(Some(op)),
+            Err(GitCliError::CommandFailed(stderr))
+                if stderr.contains("CONFLICT") || stderr.contains("could not apply") =>
+            {
+                // A later commit in the sequence conflicted; the operation is paused again
+                let conflicts = self.get_conflicted_files(worktree_path).unwrap_or_default();
+                Err(GitServiceError::MergeConflicts(format!(
+                    "Continuing the {op:?} stopped on new conflicts in: {}",
+                    conflicts.join(", ")
+                )))
+            }
+            Err(e) => Err(GitServiceError::InvalidRepository(format!(
+                "Failed to continue {op:?}: {e}"
+            ))),
+        }
+    }
+
     pub fn find_branch<'a>(
         repo: &'a Repository,
         branch_name: &str,
@@ -1645,10 +2066,10 @@ impl GitService {
         let parent_commit = head.peel_to_commit()?;
 
         let commit_message = format!("Delete file: {file_path}");
-        let commit_id = repo.commit(
+        let commit_id = self.create_commit(
+            &repo,
             Some("HEAD"),
             &signature,
-            &signature,
             &commit_message,
             &tree,
             &[&parent_commit],
@@ -1692,6 +2113,26 @@ impl GitService {
         })
     }
 
+    /// Fetch a branch from the remote it tracks (or the default remote) and return the name
+    /// of its freshly updated remote-tracking branch, e.g. `origin/main`
+    pub fn fetch_remote_tracking_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        github_token: &str,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let remote_name = repo
+            .branch_upstream_remote(&format!("refs/heads/{branch_name}"))
+            .ok()
+            .and_then(|name| name.as_str().map(str::to_string))
+            .unwrap_or_else(|| self.default_remote_name(&repo));
+        let remote = repo.find_remote(&remote_name)?;
+        let refspec = format!("+refs/heads/{branch_name}:refs/remotes/{remote_name}/{branch_name}");
+        self.fetch_from_remote(&repo, github_token, &remote, &refspec)?;
+        Ok(format!("{remote_name}/{branch_name}"))
+    }
+
     pub fn get_remote_name_from_branch_name(
         &self,
         repo_path: &Path,
@@ -1729,6 +2170,7 @@ impl GitService {
         })
     }
 
+    #[tracing::instrument(skip(self, github_token))]
     pub fn push_to_github(
         &self,
         worktree_path: &Path,
@@ -1977,6 +2419,61 @@ impl GitService {
     }
 }
 
+fn is_conflict_marker(line: &str, marker: &str) -> bool {
+    // Markers are exactly seven characters, optionally followed by a space and a label
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+/// Parse conflict regions out of a file's content. Incomplete regions (a
+/// `<<<<<<<` without a closing `>>>>>>>`) are ignored.
+pub fn parse_conflict_hunks(path: &str, content: &str) -> Vec<ConflictHunk> {
+    enum Section {
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut hunks = Vec::new();
+    let mut current: Option<(usize, Section, String, Option<String>, String)> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        if is_conflict_marker(line, "<<<<<<<") {
+            current = Some((line_no, Section::Ours, String::new(), None, String::new()));
+            continue;
+        }
+        let Some((start, section, ours, base, theirs)) = current.as_mut() else {
+            continue;
+        };
+        if is_conflict_marker(line, "|||||||") {
+            *section = Section::Base;
+            *base = Some(String::new());
+        } else if line == "=======" {
+            *section = Section::Theirs;
+        } else if is_conflict_marker(line, ">>>>>>>") {
+            hunks.push(ConflictHunk {
+                path: path.to_string(),
+                start_line: *start,
+                end_line: line_no,
+                ours: std::mem::take(ours),
+                base: base.take(),
+                theirs: std::mem::take(theirs),
+            });
+            current = None;
+        } else {
+            let target = match section {
+                Section::Ours => ours,
+                Section::Base => base.get_or_insert_with(String::new),
+                Section::Theirs => theirs,
+            };
+            target.push_str(line);
+            target.push('\n');
+        }
+    }
+    hunks
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use tempfile::TempDir;
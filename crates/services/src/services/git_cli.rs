@@ -42,7 +42,10 @@ pub enum GitCliError {
 }
 
 #[derive(Clone, Default)]
-pub struct GitCli;
+pub struct GitCli {
+    /// `-c key=value` overrides passed to every invocation
+    config: Vec<(String, String)>,
+}
 
 /// Parsed change type from `git diff --name-status` output
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -80,7 +83,14 @@ pub struct StatusDiffOptions {
 
 impl GitCli {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Pass `-c key=value` config overrides to every git invocation (e.g. commit identity
+    /// and signing settings), leaving the repository's config untouched.
+    pub fn with_config(mut self, config: Vec<(String, String)>) -> Self {
+        self.config = config;
+        self
     }
 
     /// Run `git -C <repo> worktree add <path> <branch>` (optionally creating the branch with -b)
@@ -109,6 +119,70 @@ impl GitCli {
         Ok(())
     }
 
+    /// Add a worktree of an existing branch that only checks out paths matching `patterns`
+    /// (`git sparse-checkout --no-cone` syntax). The patterns stay local to the new worktree.
+    pub fn worktree_add_sparse(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        patterns: &[String],
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "add".into(),
+            "--no-checkout".into(),
+            worktree_path.as_os_str().into(),
+            OsString::from(branch),
+        ];
+        self.git(repo_path, args)?;
+        self.sparse_checkout_set(worktree_path, patterns)?;
+        // The worktree starts with an empty index; checking out populates the sparse paths
+        self.git(worktree_path, ["checkout", branch])?;
+        Ok(())
+    }
+
+    /// Replace the worktree's sparse checkout patterns and update its files to match
+    pub fn sparse_checkout_set(
+        &self,
+        worktree_path: &Path,
+        patterns: &[String],
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> =
+            vec!["sparse-checkout".into(), "set".into(), "--no-cone".into()];
+        args.extend(patterns.iter().map(OsString::from));
+        self.git(worktree_path, args)?;
+        Ok(())
+    }
+
+    /// The worktree's sparse checkout patterns; empty when it checks out every path
+    pub fn sparse_checkout_patterns(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<String>, GitCliError> {
+        // `git config` exits with 1 when the key is unset
+        let enabled = self
+            .git(worktree_path, ["config", "--bool", "core.sparseCheckout"])
+            .is_ok_and(|out| out.trim() == "true");
+        if !enabled {
+            return Ok(Vec::new());
+        }
+        let out = self.git(worktree_path, ["sparse-checkout", "list"])?;
+        Ok(out
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Check out every path in the worktree again
+    pub fn sparse_checkout_disable(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["sparse-checkout", "disable"])?;
+        Ok(())
+    }
+
     /// Run `git -C <repo> worktree remove <path>`
     pub fn worktree_remove(
         &self,
@@ -514,6 +588,31 @@ impl GitCli {
         }
     }
 
+    /// Paths with staged changes; both sides of renames are listed
+    pub fn staged_paths(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
+        let out = self.git(
+            worktree_path,
+            ["diff", "--cached", "--name-only", "--no-renames", "-z"],
+        )?;
+        Ok(out
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Reset the index entries of `paths` to HEAD, keeping their changes in the working tree
+    pub fn unstage(&self, worktree_path: &Path, paths: &[String]) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec!["reset".into(), "-q".into(), "--".into()];
+        args.extend(
+            paths
+                .iter()
+                .map(|path| OsString::from(format!(":(literal){path}"))),
+        );
+        self.git(worktree_path, args)?;
+        Ok(())
+    }
+
     /// Reset index to HEAD (mixed reset). Does not modify working tree.
     pub fn reset(&self, repo_path: &Path) -> Result<(), GitCliError> {
         self.git(repo_path, ["reset"]).map(|_| ())
@@ -538,6 +637,41 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Checkout base branch and fast-forward it to from_branch. Returns new HEAD sha.
+    pub fn merge_fast_forward(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--ff-only", from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
+    /// Checkout base branch and merge from_branch with an explicit merge commit. Returns new HEAD sha.
+    pub fn merge_no_ff_commit(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+        message: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--no-ff", "-m", message, from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -571,6 +705,45 @@ impl GitCli {
         self.git(worktree_path, ["revert", "--abort"]).map(|_| ())
     }
 
+    /// Continue an in-progress rebase once conflicts have been resolved and staged.
+    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["rebase", "--continue"],
+            &Self::non_interactive_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    /// Conclude an in-progress merge by committing the staged resolution with
+    /// the prepared merge message.
+    pub fn continue_merge(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["commit", "--no-edit"],
+            &Self::non_interactive_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_cherry_pick(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["cherry-pick", "--continue"],
+            &Self::non_interactive_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_revert(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["revert", "--continue"],
+            &Self::non_interactive_editor_env(),
+        )
+        .map(|_| ())
+    }
+
     /// List files currently in a conflicted (unmerged) state in the worktree.
     pub fn get_conflicted_files(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
         // `--diff-filter=U` lists paths with unresolved conflicts
@@ -623,6 +796,12 @@ impl GitCli {
         ]
     }
 
+    /// `--continue` opens an editor for the commit message by default; accept the
+    /// prepared message instead so the command never blocks on a terminal.
+    fn non_interactive_editor_env() -> Vec<(OsString, OsString)> {
+        vec![(OsString::from("GIT_EDITOR"), OsString::from("true"))]
+    }
+
     /// Ensure `git` is available on PATH
     fn ensure_available(&self) -> Result<(), GitCliError> {
         let git = resolve_executable_path("git").ok_or(GitCliError::NotAvailable)?;
@@ -659,6 +838,9 @@ impl GitCli {
         let git = resolve_executable_path("git").ok_or(GitCliError::NotAvailable)?;
         let mut cmd = Command::new(&git);
         cmd.arg("-C").arg(repo_path);
+        for (key, value) in &self.config {
+            cmd.arg("-c").arg(format!("{key}={value}"));
+        }
         for a in args {
             cmd.arg(a);
         }
@@ -687,6 +869,9 @@ impl GitCli {
         let git = resolve_executable_path("git").ok_or(GitCliError::NotAvailable)?;
         let mut cmd = Command::new(&git);
         cmd.arg("-C").arg(repo_path);
+        for (key, value) in &self.config {
+            cmd.arg("-c").arg(format!("{key}={value}"));
+        }
         for (k, v) in envs {
             cmd.env(k, v);
         }
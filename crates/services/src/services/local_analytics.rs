@@ -0,0 +1,150 @@
+//! Opt-in storage of analytics events in the local database, and reports built from them, so
+//! usage can be looked at without sending anything off the machine.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use db::{
+    DBService,
+    models::analytics_event::{AnalyticsEvent, WeeklyMerges},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use ts_rs::TS;
+
+use crate::services::config::Config;
+
+/// Window used when the query doesn't give a start
+pub const DEFAULT_REPORT_WINDOW_DAYS: i64 = 30;
+
+#[derive(Debug, Error)]
+pub enum LocalAnalyticsError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("`from` must be before `to`")]
+    InvalidWindow,
+}
+
+/// Records events in the local database while `local_analytics_enabled` is set
+#[derive(Clone)]
+pub struct LocalAnalyticsSink {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+}
+
+impl LocalAnalyticsSink {
+    pub fn new(db: DBService, config: Arc<RwLock<Config>>) -> Self {
+        Self { db, config }
+    }
+
+    /// Store the event if local analytics are enabled. PostHog's own events such as `$identify`
+    /// describe the user rather than usage and are not stored.
+    pub async fn track_event(&self, event_name: &str, properties: &Value) {
+        if event_name.starts_with('$') || !self.config.read().await.local_analytics_enabled {
+            return;
+        }
+        if let Err(e) = AnalyticsEvent::create(&self.db.pool, event_name, properties).await {
+            tracing::warn!("Failed to record analytics event '{}': {}", event_name, e);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct AnalyticsReportQuery {
+    /// Start of the window; defaults to 30 days before `to`
+    #[ts(type = "Date | null")]
+    pub from: Option<DateTime<Utc>>,
+    /// End of the window; defaults to now
+    #[ts(type = "Date | null")]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Coding agent usage of one executor profile
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct ExecutorUsage {
+    /// `None` for runs recorded before the executor was part of the event
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    #[ts(type = "number")]
+    pub attempts: i64,
+    /// Finished coding agent runs, including follow-ups
+    #[ts(type = "number")]
+    pub runs: i64,
+    #[ts(type = "number")]
+    pub failed_runs: i64,
+    pub failure_rate: Option<f64>,
+    pub average_runtime_seconds: Option<f64>,
+    /// Tool approvals answered per finished run
+    pub approvals_per_run: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct AnalyticsReport {
+    #[ts(type = "Date")]
+    pub from: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub to: DateTime<Utc>,
+    /// Events recorded within the window
+    #[ts(type = "number")]
+    pub events: i64,
+    pub executors: Vec<ExecutorUsage>,
+    pub merges_per_week: Vec<WeeklyMerges>,
+}
+
+pub async fn analytics_report(
+    pool: &SqlitePool,
+    query: &AnalyticsReportQuery,
+) -> Result<AnalyticsReport, LocalAnalyticsError> {
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_REPORT_WINDOW_DAYS));
+    if from >= to {
+        return Err(LocalAnalyticsError::InvalidWindow);
+    }
+
+    let mut executors: BTreeMap<(Option<String>, Option<String>), ExecutorUsage> = BTreeMap::new();
+
+    for row in AnalyticsEvent::attempts_by_executor(pool, from, to).await? {
+        let entry = executors
+            .entry((row.executor.clone(), row.variant.clone()))
+            .or_insert_with(|| empty_usage(row.executor, row.variant));
+        entry.attempts = row.attempts;
+    }
+    for row in AnalyticsEvent::runs_by_executor(pool, from, to).await? {
+        let entry = executors
+            .entry((row.executor.clone(), row.variant.clone()))
+            .or_insert_with(|| empty_usage(row.executor, row.variant));
+        entry.runs = row.runs;
+        entry.failed_runs = row.failed_runs;
+        entry.average_runtime_seconds = row.average_runtime_seconds;
+        if row.runs > 0 {
+            entry.failure_rate = Some(row.failed_runs as f64 / row.runs as f64);
+            entry.approvals_per_run = Some(row.approvals as f64 / row.runs as f64);
+        }
+    }
+
+    Ok(AnalyticsReport {
+        from,
+        to,
+        events: AnalyticsEvent::count(pool, from, to).await?,
+        executors: executors.into_values().collect(),
+        merges_per_week: AnalyticsEvent::merges_per_week(pool, from, to).await?,
+    })
+}
+
+fn empty_usage(executor: Option<String>, variant: Option<String>) -> ExecutorUsage {
+    ExecutorUsage {
+        executor,
+        variant,
+        attempts: 0,
+        runs: 0,
+        failed_runs: 0,
+        failure_rate: None,
+        average_runtime_seconds: None,
+        approvals_per_run: None,
+    }
+}
@@ -1,8 +1,16 @@
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
+pub mod commit_identity;
+pub mod commit_message;
 pub mod config;
+pub mod conflict_resolution;
 pub mod container;
+pub mod conversation_export;
+pub mod copy_files;
+pub mod dependency_cache;
+pub mod dev_server;
+pub mod dev_server_health;
 pub mod diff_stream;
 pub mod drafts;
 pub mod events;
@@ -14,6 +22,20 @@ pub mod git;
 pub mod git_cli;
 pub mod github_service;
 pub mod image;
+pub mod local_analytics;
 pub mod notification;
+pub mod notification_channels;
+pub mod oci_runtime;
 pub mod pr_monitor;
+pub mod project_scope;
+pub mod prometheus;
+pub mod review_comments;
+pub mod stack;
+pub mod task_activity;
+pub mod task_metrics;
+pub mod transcript_search;
+pub mod webhooks;
+pub mod workspace;
 pub mod worktree_manager;
+pub mod worktree_retention;
+pub mod worktree_snapshot;
@@ -1,9 +1,14 @@
-use std::sync::OnceLock;
+use std::sync::{LazyLock, OnceLock};
 
+use chrono::Utc;
 use db::models::execution_process::{ExecutionContext, ExecutionProcessStatus};
-use utils;
+use utils::{self, approvals::ApprovalRequest, port_file::read_port_file};
 
-use crate::services::config::SoundFile;
+use crate::services::{
+    approvals::summarize_tool_input,
+    config::{NotificationEvent, SoundFile},
+    notification_channels::{Notification, build_channel},
+};
 
 /// Service for handling cross-platform notifications including sound alerts and push notifications
 #[derive(Debug, Clone)]
@@ -13,6 +18,9 @@ use crate::services::config::NotificationConfig;
 /// Cache for WSL root path from PowerShell
 static WSL_ROOT_PATH_CACHE: OnceLock<Option<String>> = OnceLock::new();
 
+/// Shared client for chat and push notification channels
+static CHANNEL_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
 impl NotificationService {
     pub async fn notify_execution_halted(mut config: NotificationConfig, ctx: &ExecutionContext) {
         // If the process was intentionally killed by user, suppress sound
@@ -42,9 +50,117 @@ impl NotificationService {
                 return;
             }
         };
+        let event = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => Some(NotificationEvent::AttemptCompleted),
+            ExecutionProcessStatus::Failed => Some(NotificationEvent::AttemptFailed),
+            _ => None,
+        };
+        if let Some(event) = event {
+            let url = Self::link(
+                &config,
+                &format!(
+                    "/projects/{}/tasks/{}/attempts/{}",
+                    ctx.task.project_id, ctx.task.id, ctx.task_attempt.id
+                ),
+            )
+            .await;
+            Self::notify_channels(
+                &config,
+                Notification {
+                    event,
+                    title: title.clone(),
+                    message: message.clone(),
+                    url,
+                },
+            );
+        }
+
+        Self::notify(config, &title, &message).await;
+    }
+
+    /// Notify that a tool is waiting for approval, or as a `reminder` that it will soon time out
+    pub async fn notify_approval_pending(
+        config: NotificationConfig,
+        ctx: &ExecutionContext,
+        request: &ApprovalRequest,
+        reminder: bool,
+    ) {
+        let summary = summarize_tool_input(&request.tool_input);
+        let (title, headline) = if reminder {
+            let minutes_left = (request.timeout_at - Utc::now()).num_minutes().max(1);
+            (
+                format!("Approval Expiring: {}", ctx.task.title),
+                format!(
+                    "⏳ '{}' approval for '{}' times out in {} min",
+                    request.tool_name, ctx.task.title, minutes_left
+                ),
+            )
+        } else {
+            (
+                format!("Approval Needed: {}", ctx.task.title),
+                format!(
+                    "✋ '{}' is waiting for approval in '{}'",
+                    request.tool_name, ctx.task.title
+                ),
+            )
+        };
+        let message = if summary.is_empty() {
+            headline
+        } else {
+            format!("{headline}\n{summary}")
+        };
+
+        let url = Self::link(
+            &config,
+            &format!(
+                "/projects/{}/tasks/{}/attempts/{}?approval={}",
+                ctx.task.project_id, ctx.task.id, ctx.task_attempt.id, request.id
+            ),
+        )
+        .await;
+        Self::notify_channels(
+            &config,
+            Notification {
+                event: NotificationEvent::ApprovalPending,
+                title: title.clone(),
+                message: message.clone(),
+                url,
+            },
+        );
+
         Self::notify(config, &title, &message).await;
     }
 
+    /// Send a notification to every enabled channel routed its event, without waiting
+    pub fn notify_channels(config: &NotificationConfig, notification: Notification) {
+        for channel_config in config
+            .channels
+            .iter()
+            .filter(|channel| channel.accepts(notification.event))
+        {
+            let channel = build_channel(&channel_config.channel, &CHANNEL_CLIENT);
+            let name = channel_config.name.clone();
+            let notification = notification.clone();
+            tokio::spawn(async move {
+                if let Err(e) = channel.send(&notification).await {
+                    tracing::warn!("Failed to send notification to '{}': {}", name, e);
+                }
+            });
+        }
+    }
+
+    /// Absolute link to a frontend path, using the configured base URL or the local server
+    pub async fn link(config: &NotificationConfig, path: &str) -> Option<String> {
+        let base_url = match config.base_url.as_deref().map(str::trim) {
+            Some(base_url) if !base_url.is_empty() => base_url.trim_end_matches('/').to_string(),
+            _ => {
+                let port = read_port_file("vibe-kanban").await.ok()?;
+                format!("http://127.0.0.1:{port}")
+            }
+        };
+        Some(format!("{base_url}{path}"))
+    }
+
     /// Send both sound and push notifications if enabled
     pub async fn notify(config: NotificationConfig, title: &str, message: &str) {
         if config.sound_enabled {
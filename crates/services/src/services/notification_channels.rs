@@ -0,0 +1,285 @@
+use async_trait::async_trait;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use reqwest::{RequestBuilder, StatusCode};
+use serde_json::json;
+use thiserror::Error;
+
+use crate::services::config::{NotificationChannelKind, NotificationEvent, SmtpSecurity};
+
+/// Longest message Discord accepts in a webhook's `content`
+const DISCORD_CONTENT_LIMIT: usize = 2000;
+
+#[derive(Debug, Error)]
+pub enum NotificationChannelError {
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("{service} responded with {status}")]
+    Status {
+        service: &'static str,
+        status: StatusCode,
+    },
+    #[error(transparent)]
+    Smtp(#[from] lettre::transport::smtp::Error),
+    #[error(transparent)]
+    Email(#[from] lettre::error::Error),
+    #[error("Invalid email address: {0}")]
+    Address(#[from] lettre::address::AddressError),
+}
+
+/// A message routed to notification channels
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub message: String,
+    /// Opens the related attempt or approval in Vibe Kanban
+    pub url: Option<String>,
+}
+
+impl Notification {
+    /// The message followed by its link, for channels with a single plain text body
+    pub fn text(&self) -> String {
+        match &self.url {
+            Some(url) => format!("{}\n\n{}", self.message, url),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Somewhere notifications can be delivered besides the desktop
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationChannelError>;
+}
+
+/// Build the channel described by a notification channel config
+pub fn build_channel(
+    kind: &NotificationChannelKind,
+    client: &reqwest::Client,
+) -> Box<dyn NotificationChannel> {
+    match kind.clone() {
+        NotificationChannelKind::Slack { webhook_url } => Box::new(SlackChannel {
+            client: client.clone(),
+            webhook_url,
+        }),
+        NotificationChannelKind::Discord { webhook_url } => Box::new(DiscordChannel {
+            client: client.clone(),
+            webhook_url,
+        }),
+        NotificationChannelKind::Ntfy {
+            server_url,
+            topic,
+            token,
+        } => Box::new(NtfyChannel {
+            client: client.clone(),
+            server_url,
+            topic,
+            token,
+        }),
+        NotificationChannelKind::Gotify { server_url, token } => Box::new(GotifyChannel {
+            client: client.clone(),
+            server_url,
+            token,
+        }),
+        NotificationChannelKind::Email {
+            smtp_host,
+            smtp_port,
+            security,
+            username,
+            password,
+            from,
+            to,
+        } => Box::new(EmailChannel {
+            smtp_host,
+            smtp_port,
+            security,
+            credentials: username.zip(password),
+            from,
+            to,
+        }),
+    }
+}
+
+async fn send_request(
+    service: &'static str,
+    request: RequestBuilder,
+) -> Result<(), NotificationChannelError> {
+    let response = request.send().await?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(NotificationChannelError::Status {
+            service,
+            status: response.status(),
+        })
+    }
+}
+
+/// Posts to a Slack incoming webhook
+pub struct SlackChannel {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl SlackChannel {
+    /// Slack treats these as control characters in message text
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for SlackChannel {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationChannelError> {
+        let mut text = format!(
+            "*{}*\n{}",
+            Self::escape(&notification.title),
+            Self::escape(&notification.message)
+        );
+        if let Some(url) = &notification.url {
+            text.push_str(&format!("\n<{url}|Open in Vibe Kanban>"));
+        }
+        send_request(
+            "Slack",
+            self.client
+                .post(&self.webhook_url)
+                .json(&json!({ "text": text })),
+        )
+        .await
+    }
+}
+
+/// Posts to a Discord channel webhook
+pub struct DiscordChannel {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+#[async_trait]
+impl NotificationChannel for DiscordChannel {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationChannelError> {
+        let content: String = format!("**{}**\n{}", notification.title, notification.text())
+            .chars()
+            .take(DISCORD_CONTENT_LIMIT)
+            .collect();
+        send_request(
+            "Discord",
+            self.client
+                .post(&self.webhook_url)
+                .json(&json!({ "content": content })),
+        )
+        .await
+    }
+}
+
+/// Publishes to an ntfy topic
+pub struct NtfyChannel {
+    client: reqwest::Client,
+    server_url: String,
+    topic: String,
+    token: Option<String>,
+}
+
+#[async_trait]
+impl NotificationChannel for NtfyChannel {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationChannelError> {
+        // JSON publishing keeps non-ASCII titles intact, unlike the Title header
+        let mut request = self
+            .client
+            .post(self.server_url.trim_end_matches('/'))
+            .json(&json!({
+                "topic": self.topic,
+                "title": notification.title,
+                "message": notification.message,
+                "click": notification.url,
+            }));
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        send_request("ntfy", request).await
+    }
+}
+
+/// Sends messages through a Gotify application
+pub struct GotifyChannel {
+    client: reqwest::Client,
+    server_url: String,
+    token: String,
+}
+
+#[async_trait]
+impl NotificationChannel for GotifyChannel {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationChannelError> {
+        let mut body = json!({
+            "title": notification.title,
+            "message": notification.message,
+            "priority": 5,
+        });
+        if let Some(url) = &notification.url {
+            body["extras"] = json!({ "client::notification": { "click": { "url": url } } });
+        }
+        send_request(
+            "Gotify",
+            self.client
+                .post(format!("{}/message", self.server_url.trim_end_matches('/')))
+                .header("X-Gotify-Key", &self.token)
+                .json(&body),
+        )
+        .await
+    }
+}
+
+/// Sends plain text email through an SMTP server
+pub struct EmailChannel {
+    smtp_host: String,
+    smtp_port: u16,
+    security: SmtpSecurity,
+    credentials: Option<(String, String)>,
+    from: String,
+    to: Vec<String>,
+}
+
+impl EmailChannel {
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, NotificationChannelError> {
+        let builder = match self.security {
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?,
+            SmtpSecurity::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)?
+            }
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.smtp_host)
+            }
+        };
+        let mut builder = builder.port(self.smtp_port);
+        if let Some((username, password)) = &self.credentials {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(builder.build())
+    }
+
+    fn message(&self, notification: &Notification) -> Result<Message, NotificationChannelError> {
+        let mut builder = Message::builder()
+            .from(self.from.parse::<Mailbox>()?)
+            .subject(&notification.title)
+            .header(ContentType::TEXT_PLAIN);
+        for recipient in &self.to {
+            builder = builder.to(recipient.parse::<Mailbox>()?);
+        }
+        Ok(builder.body(notification.text())?)
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for EmailChannel {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationChannelError> {
+        let message = self.message(notification)?;
+        self.transport()?.send(message).await?;
+        Ok(())
+    }
+}
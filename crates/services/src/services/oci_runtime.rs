@@ -0,0 +1,407 @@
+//! Runs executions inside OCI containers (Docker or Podman) for projects with a container
+//! image.
+//!
+//! Worktrees stay on the host. Each execution gets a small wrapper script that executors use
+//! as their shell (see [`utils::shell::with_execution_shell`]). The wrapper starts a throwaway
+//! container with the worktree and the repository's git directory bind-mounted at their host
+//! paths and runs the command there with stdio attached, so log streaming, exit codes and
+//! stopping the process group behave exactly as they do on the host.
+//!
+//! Containers only see the worktree, the git directory and paths listed in
+//! `VK_CONTAINER_MOUNTS` (comma separated, `~` for the home directory, read-only unless
+//! suffixed with `:rw`, e.g. `~/.claude:rw,~/.gitconfig`). `HOME` points at a throwaway
+//! directory in the container, so allow-listed credentials under it are still found where
+//! agents expect them. Containers get their own network; a dev server's port is published on
+//! the host's loopback interface, so dev servers must listen on all interfaces inside the
+//! container.
+//!
+//! Variables the executor sets on top of the server's own environment, or sets to a different
+//! value, are forwarded into the container by name, as are host variables listed in
+//! `VK_CONTAINER_ENV` (comma separated).
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::LazyLock,
+};
+
+use db::models::project::Project;
+use regex::Regex;
+use utils::shell::resolve_executable_path;
+use uuid::Uuid;
+
+/// Set by `sh` itself rather than by the executor
+const SHELL_VARS: &[&str] = &["PWD", "OLDPWD", "SHLVL", "_"];
+
+static MEMORY_LIMIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d+(\.\d+)?[bkmgBKMG]?$").expect("valid memory regex"));
+
+/// Image and resource limits for a project's execution containers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionContainer {
+    pub image: String,
+    /// Passed to `--cpus`
+    pub cpus: Option<String>,
+    /// Passed to `--memory`
+    pub memory: Option<String>,
+}
+
+impl ExecutionContainer {
+    /// `None` when the project runs its executions directly on the host.
+    pub fn from_project(project: &Project) -> Option<Self> {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        Some(Self {
+            image: non_empty(&project.container_image)?,
+            cpus: non_empty(&project.container_cpus),
+            memory: non_empty(&project.container_memory),
+        })
+    }
+}
+
+/// Check project container settings, returning a user-facing reason when invalid.
+pub fn validate_execution_container(
+    image: Option<&str>,
+    cpus: Option<&str>,
+    memory: Option<&str>,
+) -> Result<(), String> {
+    if let Some(image) = image.map(str::trim).filter(|i| !i.is_empty())
+        && image.contains(char::is_whitespace)
+    {
+        return Err(format!("Invalid container image '{image}'"));
+    }
+    if let Some(cpus) = cpus.map(str::trim).filter(|c| !c.is_empty())
+        && !cpus
+            .parse::<f64>()
+            .is_ok_and(|value| value.is_finite() && value > 0.0)
+    {
+        return Err(format!(
+            "Invalid CPU limit '{cpus}': expected a positive number"
+        ));
+    }
+    if let Some(memory) = memory.map(str::trim).filter(|m| !m.is_empty())
+        && !MEMORY_LIMIT.is_match(memory)
+    {
+        return Err(format!(
+            "Invalid memory limit '{memory}': expected a size such as 512m or 4g"
+        ));
+    }
+    Ok(())
+}
+
+/// A host path bind-mounted into every execution container at the same path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerMount {
+    pub path: PathBuf,
+    pub read_only: bool,
+}
+
+impl ContainerMount {
+    /// Parse a `VK_CONTAINER_MOUNTS` entry: a path, optionally starting with `~/` and
+    /// ending in `:ro` or `:rw`
+    pub fn parse(entry: &str, home: Option<&Path>) -> Option<Self> {
+        let entry = entry.trim();
+        let (path, read_only) = match entry.rsplit_once(':') {
+            Some((path, "rw")) => (path, false),
+            Some((path, "ro")) => (path, true),
+            _ => (entry, true),
+        };
+        let path = match path.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+                home?.join(rest.trim_start_matches('/'))
+            }
+            _ => PathBuf::from(path),
+        };
+        path.is_absolute().then_some(Self { path, read_only })
+    }
+}
+
+/// Docker or Podman CLI used to start execution containers
+#[derive(Debug, Clone)]
+pub struct OciRuntime {
+    program: String,
+    /// Daemon socket, exported as `DOCKER_HOST` / `CONTAINER_HOST`
+    socket: Option<String>,
+    /// Host environment variables forwarded into every container
+    forward_env: Vec<String>,
+    /// Host paths mounted into every container
+    mounts: Vec<ContainerMount>,
+}
+
+impl OciRuntime {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            socket: None,
+            forward_env: Vec::new(),
+            mounts: Vec::new(),
+        }
+    }
+
+    pub fn with_mounts(mut self, mounts: Vec<ContainerMount>) -> Self {
+        self.mounts = mounts;
+        self
+    }
+
+    /// Pick the runtime from `VK_CONTAINER_RUNTIME`, falling back to `docker` and then
+    /// `podman` on the PATH. `VK_CONTAINER_SOCKET` selects a non-default daemon socket.
+    pub fn detect() -> Option<Self> {
+        let non_empty_var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let program = match non_empty_var("VK_CONTAINER_RUNTIME") {
+            Some(runtime) => resolve_executable_path(&runtime)?,
+            None => {
+                resolve_executable_path("docker").or_else(|| resolve_executable_path("podman"))?
+            }
+        };
+        let forward_env = non_empty_var("VK_CONTAINER_ENV")
+            .map(|vars| {
+                vars.split(',')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let home = dirs::home_dir();
+        let mounts = non_empty_var("VK_CONTAINER_MOUNTS")
+            .map(|entries| {
+                entries
+                    .split(',')
+                    .filter(|entry| !entry.trim().is_empty())
+                    .filter_map(|entry| {
+                        let mount = ContainerMount::parse(entry, home.as_deref());
+                        if mount.is_none() {
+                            tracing::warn!("Ignoring container mount '{}'", entry.trim());
+                        }
+                        mount
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            program,
+            socket: non_empty_var("VK_CONTAINER_SOCKET"),
+            forward_env,
+            mounts,
+        })
+    }
+
+    pub fn container_name(execution_id: Uuid) -> String {
+        format!("vk-exec-{execution_id}")
+    }
+
+    fn socket_env_var(&self) -> &'static str {
+        let is_podman = Path::new(&self.program)
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().contains("podman"));
+        if is_podman {
+            "CONTAINER_HOST"
+        } else {
+            "DOCKER_HOST"
+        }
+    }
+
+    /// Render the wrapper script that runs `<script> -c <command>` inside `container`.
+    /// `mounts` are bind-mounted read-write at the same path inside the container and
+    /// `ports` are published on the host's loopback interface.
+    pub fn execution_shell_script(
+        &self,
+        container: &ExecutionContainer,
+        execution_id: Uuid,
+        mounts: &[&Path],
+        ports: &[u16],
+    ) -> String {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/home/vibe-kanban"));
+        let home = home.to_string_lossy();
+        let mut run_args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "-i".to_string(),
+            "--init".to_string(),
+            "--name".to_string(),
+            shell_quote(&Self::container_name(execution_id)),
+            "--label".to_string(),
+            shell_quote(&format!("vibe-kanban.execution={execution_id}")),
+            "--user".to_string(),
+            "\"$(id -u):$(id -g)\"".to_string(),
+            "--tmpfs".to_string(),
+            shell_quote(&format!("{home}:exec,mode=1777")),
+            "-e".to_string(),
+            shell_quote(&format!("HOME={home}")),
+        ];
+        for port in ports {
+            run_args.push("-p".to_string());
+            run_args.push(format!("127.0.0.1:{port}:{port}"));
+        }
+        for mount in mounts {
+            let mount = mount.to_string_lossy();
+            run_args.push("-v".to_string());
+            run_args.push(shell_quote(&format!("{mount}:{mount}")));
+        }
+        // Allow-listed host paths; missing ones are skipped rather than created by the runtime
+        for mount in self.mounts.iter().filter(|mount| mount.path.exists()) {
+            let path = mount.path.to_string_lossy();
+            let mode = if mount.read_only { "ro" } else { "rw" };
+            run_args.push("-v".to_string());
+            run_args.push(shell_quote(&format!("{path}:{path}:{mode}")));
+        }
+        run_args.push("-w".to_string());
+        run_args.push("\"$PWD\"".to_string());
+        if let Some(cpus) = &container.cpus {
+            run_args.push("--cpus".to_string());
+            run_args.push(shell_quote(cpus));
+        }
+        if let Some(memory) = &container.memory {
+            run_args.push("--memory".to_string());
+            run_args.push(shell_quote(memory));
+        }
+
+        // Server variables the executor left untouched, as `NAME=<cksum of value>`. Anything
+        // else in the wrapper's environment was set or overridden by the executor.
+        let server_vars: Vec<String> = std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value)))
+            .filter(|(name, _)| name != "HOME" && !self.forward_env.contains(name))
+            .map(|(name, value)| {
+                let (crc, len) = posix_cksum(value.as_encoded_bytes());
+                format!("{name}={crc}_{len}")
+            })
+            .collect();
+        let mut skipped_vars: Vec<&str> = SHELL_VARS.to_vec();
+        skipped_vars.push("HOME");
+
+        let mut script = format!(
+            "#!/bin/sh\n# vibe-kanban execution {execution_id} in {}\n[ \"$1\" = \"-c\" ] && shift\ncommand=$1\nset --\n",
+            container.image
+        );
+        script.push_str(&format!(
+            "server_vars={}\nfor name in $(env | sed -n 's/^\\([A-Za-z_][A-Za-z0-9_]*\\)=.*/\\1/p'); do\n  case {} in\n    *\" $name \"*) continue ;;\n  esac\n  eval \"value=\\${{$name}}\"\n  fingerprint=$(printf '%s' \"$value\" | cksum | tr -s ' \\t' '_')\n  case $server_vars in\n    *\" $name=$fingerprint \"*) ;;\n    *) set -- \"$@\" -e \"$name\" ;;\n  esac\ndone\n",
+            shell_quote(&format!(" {} ", server_vars.join(" "))),
+            shell_quote(&format!(" {} ", skipped_vars.join(" ")))
+        ));
+        if let Some(socket) = &self.socket {
+            script.push_str(&format!(
+                "export {}={}\n",
+                self.socket_env_var(),
+                shell_quote(socket)
+            ));
+        }
+        script.push_str(&format!(
+            "exec {} {} \"$@\" {} sh -c \"$command\"\n",
+            shell_quote(&self.program),
+            run_args.join(" "),
+            shell_quote(&container.image)
+        ));
+        script
+    }
+
+    /// Write the wrapper script for an execution into `dir` and make it executable.
+    pub fn write_execution_shell(
+        &self,
+        container: &ExecutionContainer,
+        execution_id: Uuid,
+        mounts: &[&Path],
+        ports: &[u16],
+        dir: &Path,
+    ) -> std::io::Result<PathBuf> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::create_dir_all(dir)?;
+            let path = dir.join(format!("{}.sh", Self::container_name(execution_id)));
+            std::fs::write(
+                &path,
+                self.execution_shell_script(container, execution_id, mounts, ports),
+            )?;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+            Ok(path)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (container, execution_id, mounts, ports, dir);
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "container executions are only supported on Unix hosts",
+            ))
+        }
+    }
+
+    /// Command that runs `script` with `sh -c` inside the running container of an execution,
+    /// in `cwd` with `env` set on top of the container's environment
+    pub fn exec_command(
+        &self,
+        execution_id: Uuid,
+        cwd: &Path,
+        env: &HashMap<String, String>,
+        script: &str,
+    ) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(&self.program);
+        command.arg("exec").arg("-w").arg(cwd);
+        for (name, value) in env {
+            command.arg("-e").arg(format!("{name}={value}"));
+        }
+        command
+            .arg(Self::container_name(execution_id))
+            .args(["sh", "-c", script]);
+        if let Some(socket) = &self.socket {
+            command.env(self.socket_env_var(), socket);
+        }
+        command
+    }
+
+    /// Force-remove an execution's container. Killing the wrapper's process group stops the
+    /// runtime client, but the container itself keeps running until it is removed.
+    pub async fn remove_execution_container(&self, execution_id: Uuid) {
+        let mut command = tokio::process::Command::new(&self.program);
+        command
+            .args(["rm", "-f", &Self::container_name(execution_id)])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(socket) = &self.socket {
+            command.env(self.socket_env_var(), socket);
+        }
+        if let Err(e) = command.status().await {
+            tracing::warn!(
+                "Failed to remove container for execution {}: {}",
+                execution_id,
+                e
+            );
+        }
+    }
+}
+
+/// Checksum and length of `data` as printed by POSIX `cksum`
+fn posix_cksum(data: &[u8]) -> (u32, usize) {
+    fn feed(crc: u32, byte: u8) -> u32 {
+        (0..8).fold(crc ^ (u32::from(byte) << 24), |crc, _| {
+            if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            }
+        })
+    }
+    let mut crc = data.iter().fold(0, |crc, byte| feed(crc, *byte));
+    let mut len = data.len();
+    while len > 0 {
+        crc = feed(crc, (len & 0xff) as u8);
+        len >>= 8;
+    }
+    (!crc, data.len())
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
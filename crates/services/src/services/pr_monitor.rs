@@ -16,8 +16,14 @@ use tracing::{debug, error, info, warn};
 
 use crate::services::{
     analytics::AnalyticsContext,
-    config::Config,
+    config::{Config, NotificationEvent},
+    container::ContainerService,
     github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    local_analytics::LocalAnalyticsSink,
+    notification::NotificationService,
+    notification_channels::Notification,
+    stack::{self, NewBase},
+    webhooks::{WebhookEvent, WebhookService},
 };
 
 #[derive(Debug, Error)]
@@ -33,24 +39,36 @@ enum PrMonitorError {
 }
 
 /// Service to monitor GitHub PRs and update task status when they are merged
-pub struct PrMonitorService {
+pub struct PrMonitorService<C> {
     db: DBService,
     config: Arc<RwLock<Config>>,
+    container: C,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
+    local_analytics: LocalAnalyticsSink,
+    webhooks: WebhookService,
 }
 
-impl PrMonitorService {
+impl<C> PrMonitorService<C>
+where
+    C: ContainerService + Send + Sync + 'static,
+{
     pub async fn spawn(
         db: DBService,
         config: Arc<RwLock<Config>>,
+        container: C,
         analytics: Option<AnalyticsContext>,
+        local_analytics: LocalAnalyticsSink,
+        webhooks: WebhookService,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
             config,
+            container,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
+            local_analytics,
+            webhooks,
         };
         tokio::spawn(async move {
             service.start().await;
@@ -101,6 +119,82 @@ impl PrMonitorService {
         Ok(())
     }
 
+    /// Rebase the attempts stacked on an attempt whose PR was merged onto the remote branch
+    /// it was merged into, leaving them targeting that branch
+    async fn restack_children(&self, task_attempt: &TaskAttempt, github_token: &str) {
+        let project = match task_attempt.parent_task(&self.db.pool).await {
+            Ok(Some(task)) => match task.parent_project(&self.db.pool).await {
+                Ok(Some(project)) => project,
+                Ok(None) => return,
+                Err(e) => {
+                    error!(
+                        "Failed to load project of attempt {}: {}",
+                        task_attempt.id, e
+                    );
+                    return;
+                }
+            },
+            Ok(None) => return,
+            Err(e) => {
+                error!("Failed to load task of attempt {}: {}", task_attempt.id, e);
+                return;
+            }
+        };
+        let git = self.container.git();
+        let repo_path = project.git_repo_path.as_path();
+        let old_head = match git.get_branch_oid(repo_path, &task_attempt.branch) {
+            Ok(oid) => oid,
+            Err(e) => {
+                warn!(
+                    "Cannot restack attempts stacked on {}: {}",
+                    task_attempt.id, e
+                );
+                return;
+            }
+        };
+        // The merge only exists on the remote, so fetch it and rebase onto the
+        // remote-tracking branch
+        let rebase_onto = match git.fetch_remote_tracking_branch(
+            repo_path,
+            &task_attempt.target_branch,
+            github_token,
+        ) {
+            Ok(branch) => branch,
+            Err(e) => {
+                warn!(
+                    "Cannot restack attempts stacked on {}: failed to fetch '{}': {}",
+                    task_attempt.id, task_attempt.target_branch, e
+                );
+                return;
+            }
+        };
+
+        match stack::restack_children(
+            &self.container,
+            &project,
+            task_attempt,
+            &old_head,
+            NewBase {
+                target_branch: task_attempt.target_branch.clone(),
+                rebase_onto,
+            },
+            Some(github_token.to_string()),
+        )
+        .await
+        {
+            Ok(restacked) if !restacked.is_empty() => info!(
+                "Restacked {} attempts stacked on {}",
+                restacked.len(),
+                task_attempt.id
+            ),
+            Ok(_) => {}
+            Err(e) => error!(
+                "Failed to restack attempts stacked on {}: {}",
+                task_attempt.id, e
+            ),
+        }
+    }
+
     /// Check the status of a specific PR
     async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
         let github_config = self.config.read().await.github.clone();
@@ -126,7 +220,7 @@ impl PrMonitorService {
                 &self.db.pool,
                 pr_merge.id,
                 pr_status.status.clone(),
-                pr_status.merge_commit_sha,
+                pr_status.merge_commit_sha.clone(),
             )
             .await?;
 
@@ -141,20 +235,60 @@ impl PrMonitorService {
                 );
                 Task::update_status(&self.db.pool, task_attempt.task_id, TaskStatus::Done).await?;
 
+                // Attempts stacked on the merged branch now build on what it was merged into
+                self.restack_children(&task_attempt, &github_token).await;
+
+                if let Some(task) = Task::find_by_id(&self.db.pool, task_attempt.task_id).await? {
+                    let notify_cfg = self.config.read().await.notifications.clone();
+                    let url = NotificationService::link(
+                        &notify_cfg,
+                        &format!(
+                            "/projects/{}/tasks/{}/attempts/{}",
+                            task.project_id, task.id, task_attempt.id
+                        ),
+                    )
+                    .await;
+                    NotificationService::notify_channels(
+                        &notify_cfg,
+                        Notification {
+                            event: NotificationEvent::PrMerged,
+                            title: format!("PR Merged: {}", task.title),
+                            message: format!(
+                                "🎉 PR #{} for '{}' was merged\n{}",
+                                pr_merge.pr_info.number, task.title, pr_merge.pr_info.url
+                            ),
+                            url,
+                        },
+                    );
+
+                    self.webhooks.dispatch(
+                        task.project_id,
+                        WebhookEvent::PrMerged {
+                            task,
+                            attempt: task_attempt.clone(),
+                            pull_request: pr_status.clone(),
+                        },
+                    );
+                }
+
                 // Track analytics event
-                if let Some(analytics) = &self.analytics
-                    && let Ok(Some(task)) =
-                        Task::find_by_id(&self.db.pool, task_attempt.task_id).await
+                if let Ok(Some(task)) = Task::find_by_id(&self.db.pool, task_attempt.task_id).await
                 {
-                    analytics.analytics_service.track_event(
-                        &analytics.user_id,
-                        "pr_merged",
-                        Some(json!({
-                            "task_id": task_attempt.task_id.to_string(),
-                            "task_attempt_id": task_attempt.id.to_string(),
-                            "project_id": task.project_id.to_string(),
-                        })),
-                    );
+                    let properties = json!({
+                        "task_id": task_attempt.task_id.to_string(),
+                        "task_attempt_id": task_attempt.id.to_string(),
+                        "project_id": task.project_id.to_string(),
+                    });
+                    self.local_analytics
+                        .track_event("pr_merged", &properties)
+                        .await;
+                    if let Some(analytics) = &self.analytics {
+                        analytics.analytics_service.track_event(
+                            &analytics.user_id,
+                            "pr_merged",
+                            Some(properties),
+                        );
+                    }
                 }
             }
         }
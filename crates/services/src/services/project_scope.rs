@@ -0,0 +1,177 @@
+//! Scoping monorepo projects to the part of the repository they work on.
+//!
+//! A project can set a working directory, relative to the repository root, and sparse
+//! checkout patterns, one per line in `git sparse-checkout --no-cone` (gitignore) syntax:
+//!
+//! ```text
+//! /packages/web/
+//! /packages/shared/
+//! /package.json
+//! ```
+//!
+//! With patterns, the project's worktrees only check out matching paths; the working directory
+//! is always part of the checkout. Existing worktrees pick up changed patterns when they are
+//! next ensured. Coding agents run in the working directory, while scripts still run at the
+//! worktree root. Diffs, file search and automatic commits only cover paths in the working
+//! directory or matching the patterns. Attempts of projects with several repositories run
+//! their agents in the workspace holding all of them, but are scoped otherwise.
+
+use std::path::{Component, Path, PathBuf};
+
+use db::models::project::Project;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use utils::diff::Diff;
+
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScope {
+    working_dir: Option<String>,
+    sparse_patterns: Vec<String>,
+    matcher: Option<Gitignore>,
+}
+
+impl ProjectScope {
+    /// Invalid settings are rejected when saved; patterns that fail to parse anyway are
+    /// ignored here
+    pub fn new(working_dir: Option<&str>, sparse_checkout: Option<&str>) -> Self {
+        let sparse_patterns = sparse_checkout.map(parse_patterns).unwrap_or_default();
+        let matcher = if sparse_patterns.is_empty() {
+            None
+        } else {
+            let mut builder = GitignoreBuilder::new("");
+            for pattern in &sparse_patterns {
+                if let Err(e) = builder.add_line(None, pattern) {
+                    tracing::warn!("Ignoring sparse checkout pattern '{}': {}", pattern, e);
+                }
+            }
+            builder.build().ok()
+        };
+        Self {
+            working_dir: working_dir.and_then(normalize_working_dir),
+            sparse_patterns,
+            matcher,
+        }
+    }
+
+    pub fn from_project(project: &Project) -> Self {
+        Self::new(
+            project.working_dir.as_deref(),
+            project.sparse_checkout.as_deref(),
+        )
+    }
+
+    pub fn is_scoped(&self) -> bool {
+        self.working_dir.is_some() || !self.sparse_patterns.is_empty()
+    }
+
+    pub fn working_dir(&self) -> Option<&str> {
+        self.working_dir.as_deref()
+    }
+
+    /// Directory coding agents run in, inside the worktree at `worktree_path`
+    pub fn agent_dir(&self, worktree_path: &Path) -> PathBuf {
+        match &self.working_dir {
+            Some(dir) => worktree_path.join(dir),
+            None => worktree_path.to_path_buf(),
+        }
+    }
+
+    /// Patterns for `git sparse-checkout set --no-cone`; empty for full checkouts
+    pub fn sparse_checkout_patterns(&self) -> Vec<String> {
+        if self.sparse_patterns.is_empty() {
+            return Vec::new();
+        }
+        let mut patterns = self.sparse_patterns.clone();
+        if let Some(dir) = &self.working_dir {
+            let pattern = format!("/{dir}/");
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+        patterns
+    }
+
+    /// Whether a path relative to the repository root is in scope
+    pub fn contains(&self, path: &str) -> bool {
+        if !self.is_scoped() {
+            return true;
+        }
+        if let Some(dir) = &self.working_dir
+            && path
+                .strip_prefix(dir.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        {
+            return true;
+        }
+        self.matcher
+            .as_ref()
+            .is_some_and(|matcher| matcher.matched_path_or_any_parents(path, false).is_ignore())
+    }
+
+    /// Whether either side of a diff, with paths relative to the repository root, is in scope
+    pub fn contains_diff(&self, diff: &Diff) -> bool {
+        [&diff.old_path, &diff.new_path]
+            .into_iter()
+            .flatten()
+            .any(|path| self.contains(path))
+    }
+
+    /// A path relative to the repository root, made relative to the working directory; paths
+    /// outside it climb out with `../`
+    pub fn relative_to_working_dir(&self, path: &str) -> String {
+        let Some(dir) = self.working_dir.as_deref() else {
+            return path.to_string();
+        };
+        match path.strip_prefix(dir) {
+            Some("") => return ".".to_string(),
+            Some(rest) if rest.starts_with('/') => return rest[1..].to_string(),
+            _ => {}
+        }
+        format!("{}{path}", "../".repeat(dir.split('/').count()))
+    }
+}
+
+/// `dir`, `./dir/` and `/dir` all name the same working directory; `None` for the root
+pub fn normalize_working_dir(dir: &str) -> Option<String> {
+    let dir = dir.trim();
+    let dir = dir.strip_prefix("./").unwrap_or(dir).trim_matches('/');
+    (!dir.is_empty() && dir != ".").then(|| dir.to_string())
+}
+
+fn parse_patterns(spec: &str) -> Vec<String> {
+    spec.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Check a project's working directory and sparse checkout patterns, returning a
+/// user-facing reason when invalid
+pub fn validate_project_scope(
+    working_dir: Option<&str>,
+    sparse_checkout: Option<&str>,
+) -> Result<(), String> {
+    if let Some(dir) = working_dir.and_then(normalize_working_dir)
+        && !Path::new(&dir)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!(
+            "Working directory '{dir}' must be a path inside the repository"
+        ));
+    }
+    for pattern in sparse_checkout.map(parse_patterns).unwrap_or_default() {
+        // Patterns are passed to git as arguments
+        if pattern.starts_with('-') {
+            return Err(format!(
+                "Sparse checkout pattern '{pattern}' can't start with '-'"
+            ));
+        }
+        // An unclosed `[` would silently match nothing
+        GitignoreBuilder::new("")
+            .allow_unclosed_class(false)
+            .add_line(None, &pattern)
+            .map_err(|e| format!("Invalid sparse checkout pattern '{pattern}': {e}"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+//! Operational metrics in the Prometheus text exposition format, gathered when scraped.
+
+use std::{
+    fmt::Write,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use db::models::{
+    draft::Draft,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    tool_approval::{ApprovalWaitStats, ToolApproval},
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+
+use crate::services::worktree_manager::WorktreeManager;
+
+const PREFIX: &str = "vibe_kanban";
+
+/// Walking every worktree is slow, so sizes are reused across scrapes for this long
+const WORKTREE_USAGE_TTL: Duration = Duration::from_secs(60);
+
+static WORKTREE_USAGE: LazyLock<Mutex<Option<(Instant, WorktreeUsage)>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WorktreeUsage {
+    pub worktrees: u64,
+    pub disk_usage_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DbPoolStats {
+    pub connections: u32,
+    pub idle_connections: u64,
+    pub max_connections: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Running execution processes per run reason; every reason is present
+    pub running_processes: Vec<(ExecutionProcessRunReason, u64)>,
+    /// Follow-ups queued behind a running process
+    pub queued_follow_ups: i64,
+    pub approvals: ApprovalWaitStats,
+    /// Seconds the oldest pending approval has been waiting
+    pub oldest_pending_approval_seconds: f64,
+    /// `None` when the worktree directory couldn't be read
+    pub worktrees: Option<WorktreeUsage>,
+    pub db_pool: DbPoolStats,
+}
+
+impl MetricsSnapshot {
+    pub async fn collect(pool: &SqlitePool) -> Result<Self, sqlx::Error> {
+        let running = ExecutionProcess::find_running(pool).await?;
+        let running_processes = [
+            ExecutionProcessRunReason::SetupScript,
+            ExecutionProcessRunReason::CleanupScript,
+            ExecutionProcessRunReason::CodingAgent,
+            ExecutionProcessRunReason::DevServer,
+        ]
+        .into_iter()
+        .map(|reason| {
+            let count = running.iter().filter(|p| p.run_reason == reason).count() as u64;
+            (reason, count)
+        })
+        .collect();
+
+        let approvals = ToolApproval::wait_stats(pool).await?;
+        let oldest_pending_approval_seconds = approvals
+            .oldest_pending_at
+            .map(|at| (Utc::now() - at).num_milliseconds().max(0) as f64 / 1000.0)
+            .unwrap_or(0.0);
+
+        Ok(Self {
+            running_processes,
+            queued_follow_ups: Draft::count_queued(pool).await?,
+            approvals,
+            oldest_pending_approval_seconds,
+            worktrees: worktree_usage().await,
+            db_pool: DbPoolStats {
+                connections: pool.size(),
+                idle_connections: pool.num_idle() as u64,
+                max_connections: pool.options().get_max_connections(),
+            },
+        })
+    }
+
+    /// Render in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "running_execution_processes",
+            "gauge",
+            "Execution processes currently running",
+        );
+        for (reason, count) in &self.running_processes {
+            let _ = writeln!(
+                out,
+                "{PREFIX}_running_execution_processes{{run_reason=\"{}\"}} {count}",
+                run_reason_label(reason)
+            );
+        }
+
+        gauge(
+            &mut out,
+            "queued_follow_ups",
+            "Follow-ups waiting for their attempt's running process to finish",
+            self.queued_follow_ups,
+        );
+        gauge(
+            &mut out,
+            "pending_approvals",
+            "Tool approvals waiting on an answer",
+            self.approvals.pending,
+        );
+        gauge(
+            &mut out,
+            "oldest_pending_approval_wait_seconds",
+            "How long the oldest pending tool approval has been waiting",
+            self.oldest_pending_approval_seconds,
+        );
+        header(
+            &mut out,
+            "approval_wait_seconds",
+            "summary",
+            "Time tool approvals waited before being answered or timing out",
+        );
+        let _ = writeln!(
+            out,
+            "{PREFIX}_approval_wait_seconds_sum {}",
+            self.approvals.total_wait_seconds
+        );
+        let _ = writeln!(
+            out,
+            "{PREFIX}_approval_wait_seconds_count {}",
+            self.approvals.resolved
+        );
+
+        if let Some(worktrees) = self.worktrees {
+            gauge(
+                &mut out,
+                "worktrees",
+                "Worktree directories on disk",
+                worktrees.worktrees,
+            );
+            gauge(
+                &mut out,
+                "worktree_disk_usage_bytes",
+                "Bytes used by all worktree directories",
+                worktrees.disk_usage_bytes,
+            );
+        }
+
+        gauge(
+            &mut out,
+            "db_pool_connections",
+            "Open database connections",
+            self.db_pool.connections,
+        );
+        gauge(
+            &mut out,
+            "db_pool_idle_connections",
+            "Open database connections not in use",
+            self.db_pool.idle_connections,
+        );
+        gauge(
+            &mut out,
+            "db_pool_max_connections",
+            "Most database connections the pool will open",
+            self.db_pool.max_connections,
+        );
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {PREFIX}_{name} {help}");
+    let _ = writeln!(out, "# TYPE {PREFIX}_{name} {kind}");
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: impl std::fmt::Display) {
+    header(out, name, "gauge", help);
+    let _ = writeln!(out, "{PREFIX}_{name} {value}");
+}
+
+fn run_reason_label(reason: &ExecutionProcessRunReason) -> &'static str {
+    match reason {
+        ExecutionProcessRunReason::SetupScript => "setupscript",
+        ExecutionProcessRunReason::CleanupScript => "cleanupscript",
+        ExecutionProcessRunReason::CodingAgent => "codingagent",
+        ExecutionProcessRunReason::DevServer => "devserver",
+    }
+}
+
+async fn worktree_usage() -> Option<WorktreeUsage> {
+    let mut cached = WORKTREE_USAGE.lock().await;
+    if let Some((at, usage)) = *cached
+        && at.elapsed() < WORKTREE_USAGE_TTL
+    {
+        return Some(usage);
+    }
+    match WorktreeManager::disk_usage().await {
+        Ok(dirs) => {
+            let usage = WorktreeUsage {
+                worktrees: dirs.len() as u64,
+                disk_usage_bytes: dirs.iter().map(|(_, size)| size).sum(),
+            };
+            *cached = Some((Instant::now(), usage));
+            Some(usage)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to measure worktree disk usage: {}", e);
+            None
+        }
+    }
+}
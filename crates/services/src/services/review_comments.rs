@@ -0,0 +1,145 @@
+//! Review comments on attempt diffs.
+//!
+//! Comments are anchored to a line range of one side of a file's diff and keep the content
+//! of those lines. As the agent keeps editing, `reanchor_comments` moves each unresolved
+//! comment to wherever its lines now are, or marks it outdated when they are gone. Sending a
+//! review bundles the open comments into one follow-up prompt built by `build_review_prompt`.
+
+use std::collections::HashMap;
+
+use db::models::review_comment::{ReviewComment, ReviewCommentSide};
+use sqlx::SqlitePool;
+
+use super::{
+    git::{Commit, GitService},
+    workspace::AttemptWorkspace,
+};
+
+/// Where a comment's lines are in the current version of its file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    Unchanged,
+    Moved { line_start: i64, line_end: i64 },
+    Outdated,
+}
+
+/// Find the comment's `code` in `content`, preferring its original position and otherwise
+/// the closest occurrence. Comments without code only need their lines to still exist.
+pub fn reanchor(content: &str, line_start: i64, line_end: i64, code: Option<&str>) -> Anchor {
+    let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+    let anchor: Vec<&str> = code
+        .map(|code| code.lines().map(str::trim_end).collect())
+        .unwrap_or_default();
+    let len = lines.len() as i64;
+    if anchor.is_empty() {
+        return if line_start >= 1 && line_end <= len {
+            Anchor::Unchanged
+        } else {
+            Anchor::Outdated
+        };
+    }
+
+    let matches_at = |start: usize| {
+        lines
+            .get(start..start + anchor.len())
+            .is_some_and(|window| window == anchor.as_slice())
+    };
+    let original = (line_start - 1).max(0) as usize;
+    if line_start >= 1 && matches_at(original) {
+        return Anchor::Unchanged;
+    }
+    let span = anchor.len() as i64 - 1;
+    (0..lines.len())
+        .filter(|&start| matches_at(start))
+        .min_by_key(|&start| (start as i64 - original as i64).abs())
+        .map(|start| Anchor::Moved {
+            line_start: start as i64 + 1,
+            line_end: start as i64 + 1 + span,
+        })
+        .unwrap_or(Anchor::Outdated)
+}
+
+/// Re-anchor the attempt's unresolved comments against its worktrees, saving comments that
+/// moved or became outdated
+pub async fn reanchor_comments(
+    pool: &SqlitePool,
+    git: &GitService,
+    workspace: &AttemptWorkspace,
+    branch: &str,
+    comments: &mut [ReviewComment],
+) -> Result<(), sqlx::Error> {
+    let mut base_commits: HashMap<String, Option<Commit>> = HashMap::new();
+    for comment in comments.iter_mut().filter(|c| c.resolved_at.is_none()) {
+        let (repo, path) = workspace.resolve_path(&comment.file_path);
+        let content = match comment.side {
+            ReviewCommentSide::New => std::fs::read_to_string(repo.worktree_path.join(path)).ok(),
+            ReviewCommentSide::Old => {
+                let base_commit = base_commits.entry(repo.name.clone()).or_insert_with(|| {
+                    git.get_base_commit(&repo.worktree_path, branch, &repo.target_branch)
+                        .ok()
+                });
+                // Without a base to compare against, leave the comment where it is
+                let Some(base_commit) = base_commit else {
+                    continue;
+                };
+                git.get_file_content_at_commit(&repo.worktree_path, base_commit, path)
+                    .ok()
+                    .flatten()
+            }
+        };
+
+        let (line_start, line_end, outdated) = match content.map(|content| {
+            reanchor(
+                &content,
+                comment.line_start,
+                comment.line_end,
+                comment.code.as_deref(),
+            )
+        }) {
+            Some(Anchor::Unchanged) => (comment.line_start, comment.line_end, false),
+            Some(Anchor::Moved {
+                line_start,
+                line_end,
+            }) => (line_start, line_end, false),
+            Some(Anchor::Outdated) | None => (comment.line_start, comment.line_end, true),
+        };
+        if (line_start, line_end, outdated)
+            != (comment.line_start, comment.line_end, comment.outdated)
+        {
+            ReviewComment::update_anchor(pool, comment.id, line_start, line_end, outdated).await?;
+            comment.line_start = line_start;
+            comment.line_end = line_end;
+            comment.outdated = outdated;
+        }
+    }
+    Ok(())
+}
+
+/// Build the follow-up prompt asking the agent to address review comments
+pub fn build_review_prompt(comments: &[ReviewComment]) -> String {
+    let mut prompt = format!("## Review Comments ({})\n", comments.len());
+    for comment in comments {
+        let lines = if comment.line_start == comment.line_end {
+            format!("Line {}", comment.line_start)
+        } else {
+            format!("Lines {}-{}", comment.line_start, comment.line_end)
+        };
+        let side = match comment.side {
+            ReviewCommentSide::Old => ", removed",
+            ReviewCommentSide::New => "",
+        };
+        prompt.push_str(&format!("\n**{}** ({lines}{side})\n", comment.file_path));
+        if let Some(code) = comment.code.as_deref().filter(|code| !code.is_empty()) {
+            let fence = if code.contains("```") { "````" } else { "```" };
+            prompt.push_str(&format!(
+                "{fence}\n{}\n{fence}\n",
+                code.trim_end_matches('\n')
+            ));
+        }
+        for line in comment.body.trim().lines() {
+            prompt.push_str(&format!("\n> {line}"));
+        }
+        prompt.push('\n');
+    }
+    prompt
+}
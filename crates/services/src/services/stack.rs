@@ -0,0 +1,127 @@
+//! Stacked attempts: attempts whose branch is based on another attempt's branch.
+//!
+//! A child stays "stacked" while its target branch is the parent's branch. When the
+//! parent's history is rewritten (rebase) or the parent lands on its own target (merge,
+//! locally or through its pull request), children are rebased so they only carry their own
+//! commits on top of the new base.
+
+use std::{collections::VecDeque, path::PathBuf};
+
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    project::Project,
+    task_attempt::TaskAttempt,
+};
+use uuid::Uuid;
+
+use super::{
+    commit_identity::CommitIdentity,
+    container::{ContainerError, ContainerService},
+    git::GitServiceError,
+};
+
+/// Where the direct children of a restacked attempt go
+#[derive(Debug, Clone)]
+pub struct NewBase {
+    /// Branch the children target from now on
+    pub target_branch: String,
+    /// Branch they are rebased onto: `target_branch` itself, or its remote-tracking branch
+    /// when the parent was merged on the remote
+    pub rebase_onto: String,
+}
+
+impl NewBase {
+    pub fn branch(branch: &str) -> Self {
+        Self {
+            target_branch: branch.to_string(),
+            rebase_onto: branch.to_string(),
+        }
+    }
+}
+
+/// Restack every attempt stacked (directly or transitively) on `parent`.
+///
+/// `old_parent_head` is the parent's head commit before it changed and `new_base` where the
+/// direct children should now sit: the parent's branch after a rebase, or the parent's
+/// target branch after a merge. Children whose rebase stops on conflicts are left
+/// mid-rebase for the user (or an agent) to resolve; their own children are not touched
+/// until then. Returns the ids of the attempts that were restacked.
+pub async fn restack_children<C>(
+    container: &C,
+    project: &Project,
+    parent: &TaskAttempt,
+    old_parent_head: &str,
+    new_base: NewBase,
+    github_token: Option<String>,
+) -> Result<Vec<Uuid>, ContainerError>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let running = ExecutionProcess::find_running(pool).await?;
+    let repo_path = project.git_repo_path.as_path();
+    let git = container
+        .git()
+        .with_commit_identity(CommitIdentity::from_project(project));
+
+    let mut restacked = Vec::new();
+    let mut queue = VecDeque::from([(parent.clone(), old_parent_head.to_string(), new_base)]);
+
+    while let Some((parent, old_parent_head, new_base)) = queue.pop_front() {
+        for child in TaskAttempt::find_children(pool, parent.id).await? {
+            // The child was retargeted elsewhere; it's no longer part of the stack
+            if child.target_branch != parent.branch {
+                continue;
+            }
+            if running.iter().any(|p| {
+                p.task_attempt_id == child.id
+                    && !matches!(p.run_reason, ExecutionProcessRunReason::DevServer)
+            }) {
+                tracing::warn!(
+                    "Skipping restack of attempt {} onto '{}': a process is still running",
+                    child.id,
+                    new_base.rebase_onto
+                );
+                continue;
+            }
+
+            if new_base.target_branch != parent.branch {
+                TaskAttempt::update_target_branch(pool, child.id, &new_base.target_branch).await?;
+            }
+
+            let old_child_head = git.get_branch_oid(repo_path, &child.branch)?;
+            let worktree_path = PathBuf::from(container.ensure_container_exists(&child).await?);
+            match git.rebase_branch(
+                repo_path,
+                &worktree_path,
+                &new_base.rebase_onto,
+                &old_parent_head,
+                &child.branch,
+                github_token.clone(),
+            ) {
+                Ok(_) => {
+                    tracing::info!(
+                        "Restacked attempt {} onto '{}'",
+                        child.id,
+                        new_base.rebase_onto
+                    );
+                    restacked.push(child.id);
+                    let child_base = NewBase::branch(&child.branch);
+                    queue.push_back((child, old_child_head, child_base));
+                }
+                Err(GitServiceError::MergeConflicts(msg)) => {
+                    tracing::warn!(
+                        "Restack of attempt {} stopped on conflicts: {}",
+                        child.id,
+                        msg
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to restack attempt {}: {}", child.id, e);
+                }
+            }
+        }
+    }
+
+    Ok(restacked)
+}
@@ -0,0 +1,472 @@
+//! A task's history as one chronological timeline, assembled from its status history, attempts,
+//! execution processes, approvals, merges and relationship changes.
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    executor_session::ExecutorSession,
+    merge::{Merge, MergeStatus},
+    task::TaskStatus,
+    task_attempt::{TaskAttempt, TaskAttemptError},
+    task_relationship_history::{RelationshipChange, TaskRelationshipChange},
+    task_status_history::TaskStatusChange,
+    tool_approval::{ToolApproval, ToolApprovalStatus},
+};
+use executors::actions::ExecutorActionType;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskActivityEvent {
+    /// `from` is `None` when the task was created
+    StatusChanged {
+        from: Option<TaskStatus>,
+        to: TaskStatus,
+    },
+    AttemptStarted {
+        task_attempt_id: Uuid,
+        executor: String,
+        branch: String,
+        target_branch: String,
+    },
+    /// A script or the first coding agent run of an attempt started
+    ProcessStarted {
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        run_reason: ExecutionProcessRunReason,
+        prompt: Option<String>,
+    },
+    /// A coding agent was sent a follow-up, starting a new process
+    FollowUpSent {
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        prompt: String,
+    },
+    ProcessFinished {
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        run_reason: ExecutionProcessRunReason,
+        status: ExecutionProcessStatus,
+        #[ts(type = "number | null")]
+        exit_code: Option<i64>,
+        /// The coding agent's final message
+        summary: Option<String>,
+    },
+    ApprovalRequested {
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        approval_id: String,
+        tool_name: String,
+        tool_input_summary: String,
+    },
+    ApprovalResolved {
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        approval_id: String,
+        tool_name: String,
+        status: ToolApprovalStatus,
+        denial_reason: Option<String>,
+    },
+    /// A process left new commits on the attempt's branch
+    Committed {
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        commit: String,
+    },
+    Merged {
+        task_attempt_id: Uuid,
+        merge_commit: String,
+        target_branch: String,
+    },
+    PrOpened {
+        task_attempt_id: Uuid,
+        #[ts(type = "number")]
+        number: i64,
+        url: String,
+        target_branch: String,
+    },
+    PrMerged {
+        task_attempt_id: Uuid,
+        #[ts(type = "number")]
+        number: i64,
+        url: String,
+        merge_commit_sha: Option<String>,
+    },
+    PrClosed {
+        task_attempt_id: Uuid,
+        #[ts(type = "number")]
+        number: i64,
+        url: String,
+    },
+    RelationshipChanged {
+        change: RelationshipChange,
+        /// Whether this task is the relationship's source
+        outgoing: bool,
+        other_task_id: Uuid,
+        other_task_title: String,
+        label: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct TaskActivity {
+    /// Identifies the entry in the task's timeline, so an update to it can replace it. Entries
+    /// of an attempt start with `attempt:<id>:`, and those of a process within it continue with
+    /// `process:<id>:`.
+    pub key: String,
+    #[ts(type = "Date")]
+    pub at: DateTime<Utc>,
+    pub event: TaskActivityEvent,
+}
+
+/// Tables whose rows show up in task timelines
+pub const ACTIVITY_TABLES: &[&str] = &[
+    "task_status_history",
+    "task_attempts",
+    "execution_processes",
+    "executor_sessions",
+    "tool_approvals",
+    "merges",
+    "task_relationship_history",
+];
+
+/// Everything that happened to the task, oldest first
+pub async fn task_timeline(
+    pool: &SqlitePool,
+    task_id: Uuid,
+) -> Result<Vec<TaskActivity>, TaskAttemptError> {
+    let mut activity = Vec::new();
+
+    for change in TaskStatusChange::find_by_task_id(pool, task_id).await? {
+        activity.push(status_activity(change));
+    }
+
+    for attempt in TaskAttempt::fetch_all(pool, Some(task_id)).await? {
+        activity.push(attempt_activity(&attempt));
+
+        let sessions = ExecutorSession::find_by_task_attempt_id(pool, attempt.id).await?;
+        for process in ExecutionProcess::find_by_task_attempt_id(pool, attempt.id, true).await? {
+            let summary = sessions
+                .iter()
+                .find(|session| session.execution_process_id == process.id)
+                .and_then(|session| session.summary.clone());
+            activity.extend(process_activity(&process, summary));
+        }
+
+        for merge in Merge::find_by_task_attempt_id(pool, attempt.id).await? {
+            activity.extend(merge_activity(pool, merge).await?);
+        }
+    }
+
+    for approval in ToolApproval::find_by_task_id(pool, task_id).await? {
+        activity.extend(approval_activity(approval));
+    }
+
+    for change in TaskRelationshipChange::find_by_task_id(pool, task_id).await? {
+        activity.push(relationship_activity(change));
+    }
+
+    // Stable, so events at the same instant keep the order they were pushed in
+    activity.sort_by_key(|entry| entry.at);
+    Ok(activity)
+}
+
+/// The timeline entries of a row of one of the [`ACTIVITY_TABLES`] that was just inserted or
+/// updated, with the task they belong to. A relationship change belongs to both its tasks.
+pub async fn row_activity(
+    pool: &SqlitePool,
+    table: &str,
+    rowid: i64,
+) -> Result<Vec<(Uuid, TaskActivity)>, TaskAttemptError> {
+    let (task_attempt_id, activity) = match table {
+        "task_status_history" => {
+            return Ok(TaskStatusChange::find_by_id(pool, rowid)
+                .await?
+                .map(|change| (change.task_id, status_activity(change)))
+                .into_iter()
+                .collect());
+        }
+        "task_relationship_history" => {
+            return Ok(TaskRelationshipChange::find_by_id(pool, rowid)
+                .await?
+                .into_iter()
+                .map(|(task_id, change)| (task_id, relationship_activity(change)))
+                .collect());
+        }
+        "task_attempts" => {
+            return Ok(TaskAttempt::find_by_rowid(pool, rowid)
+                .await?
+                .map(|attempt| (attempt.task_id, attempt_activity(&attempt)))
+                .into_iter()
+                .collect());
+        }
+        "execution_processes" => {
+            let Some(process) = ExecutionProcess::find_by_rowid(pool, rowid).await? else {
+                return Ok(Vec::new());
+            };
+            let summary = ExecutorSession::find_by_execution_process_id(pool, process.id)
+                .await?
+                .and_then(|session| session.summary);
+            (process.task_attempt_id, process_activity(&process, summary))
+        }
+        // Sessions hold the summary of their process's final message
+        "executor_sessions" => {
+            let Some(session) = ExecutorSession::find_by_rowid(pool, rowid).await? else {
+                return Ok(Vec::new());
+            };
+            let Some(process) =
+                ExecutionProcess::find_by_id(pool, session.execution_process_id).await?
+            else {
+                return Ok(Vec::new());
+            };
+            (
+                process.task_attempt_id,
+                process_activity(&process, session.summary),
+            )
+        }
+        "tool_approvals" => {
+            let Some(approval) = ToolApproval::find_by_rowid(pool, rowid).await? else {
+                return Ok(Vec::new());
+            };
+            (approval.task_attempt_id, approval_activity(approval))
+        }
+        "merges" => {
+            let Some(merge) = Merge::find_by_rowid(pool, rowid).await? else {
+                return Ok(Vec::new());
+            };
+            let task_attempt_id = match &merge {
+                Merge::Direct(direct) => direct.task_attempt_id,
+                Merge::Pr(pr) => pr.task_attempt_id,
+            };
+            (task_attempt_id, merge_activity(pool, merge).await?)
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    let Some(attempt) = TaskAttempt::find_by_id(pool, task_attempt_id).await? else {
+        return Ok(Vec::new());
+    };
+    Ok(activity
+        .into_iter()
+        .map(|entry| (attempt.task_id, entry))
+        .collect())
+}
+
+fn attempt_key(task_attempt_id: Uuid) -> String {
+    format!("attempt:{task_attempt_id}")
+}
+
+fn process_key(task_attempt_id: Uuid, execution_process_id: Uuid) -> String {
+    format!(
+        "{}:process:{execution_process_id}",
+        attempt_key(task_attempt_id)
+    )
+}
+
+fn status_activity(change: TaskStatusChange) -> TaskActivity {
+    TaskActivity {
+        key: format!("status:{}", change.id),
+        at: change.changed_at,
+        event: TaskActivityEvent::StatusChanged {
+            from: change.from_status,
+            to: change.to_status,
+        },
+    }
+}
+
+fn attempt_activity(attempt: &TaskAttempt) -> TaskActivity {
+    TaskActivity {
+        key: format!("{}:started", attempt_key(attempt.id)),
+        at: attempt.created_at,
+        event: TaskActivityEvent::AttemptStarted {
+            task_attempt_id: attempt.id,
+            executor: attempt.executor.clone(),
+            branch: attempt.branch.clone(),
+            target_branch: attempt.target_branch.clone(),
+        },
+    }
+}
+
+fn relationship_activity(change: TaskRelationshipChange) -> TaskActivity {
+    TaskActivity {
+        key: format!("relationship:{}", change.id),
+        at: change.changed_at,
+        event: TaskActivityEvent::RelationshipChanged {
+            change: change.change,
+            outgoing: change.outgoing,
+            other_task_id: change.other_task_id,
+            other_task_title: change.other_task_title,
+            label: change.label,
+        },
+    }
+}
+
+/// An approval being requested and resolved
+fn approval_activity(approval: ToolApproval) -> Vec<TaskActivity> {
+    let key = format!(
+        "{}:approval:{}",
+        process_key(approval.task_attempt_id, approval.execution_process_id),
+        approval.id
+    );
+    let mut activity = vec![TaskActivity {
+        key: format!("{key}:requested"),
+        at: approval.requested_at,
+        event: TaskActivityEvent::ApprovalRequested {
+            task_attempt_id: approval.task_attempt_id,
+            execution_process_id: approval.execution_process_id,
+            approval_id: approval.id.clone(),
+            tool_name: approval.tool_name.clone(),
+            tool_input_summary: approval.tool_input_summary,
+        },
+    }];
+    if let Some(resolved_at) = approval.resolved_at {
+        activity.push(TaskActivity {
+            key: format!("{key}:resolved"),
+            at: resolved_at,
+            event: TaskActivityEvent::ApprovalResolved {
+                task_attempt_id: approval.task_attempt_id,
+                execution_process_id: approval.execution_process_id,
+                approval_id: approval.id,
+                tool_name: approval.tool_name,
+                status: approval.status,
+                denial_reason: approval.denial_reason,
+            },
+        });
+    }
+    activity
+}
+
+/// A direct merge, or a pull request being opened and then merged or closed
+async fn merge_activity(
+    pool: &SqlitePool,
+    merge: Merge,
+) -> Result<Vec<TaskActivity>, TaskAttemptError> {
+    let pr = match merge {
+        Merge::Direct(direct) => {
+            return Ok(vec![TaskActivity {
+                key: format!(
+                    "{}:merge:{}",
+                    attempt_key(direct.task_attempt_id),
+                    direct.id
+                ),
+                at: direct.created_at,
+                event: TaskActivityEvent::Merged {
+                    task_attempt_id: direct.task_attempt_id,
+                    merge_commit: direct.merge_commit,
+                    target_branch: direct.target_branch_name,
+                },
+            }]);
+        }
+        Merge::Pr(pr) => pr,
+    };
+
+    let key = format!("{}:merge:{}", attempt_key(pr.task_attempt_id), pr.id);
+    let mut activity = vec![TaskActivity {
+        key: key.clone(),
+        at: pr.created_at,
+        event: TaskActivityEvent::PrOpened {
+            task_attempt_id: pr.task_attempt_id,
+            number: pr.pr_info.number,
+            url: pr.pr_info.url.clone(),
+            target_branch: pr.target_branch_name.clone(),
+        },
+    }];
+    match pr.pr_info.status {
+        MergeStatus::Merged => {
+            if let Some(merged_at) = pr.pr_info.merged_at {
+                activity.push(TaskActivity {
+                    key: format!("{key}:pr_merged"),
+                    at: merged_at,
+                    event: TaskActivityEvent::PrMerged {
+                        task_attempt_id: pr.task_attempt_id,
+                        number: pr.pr_info.number,
+                        url: pr.pr_info.url,
+                        merge_commit_sha: pr.pr_info.merge_commit_sha,
+                    },
+                });
+            }
+        }
+        MergeStatus::Closed => {
+            if let Some(closed_at) = Merge::pr_closed_at(pool, pr.id).await? {
+                activity.push(TaskActivity {
+                    key: format!("{key}:pr_closed"),
+                    at: closed_at,
+                    event: TaskActivityEvent::PrClosed {
+                        task_attempt_id: pr.task_attempt_id,
+                        number: pr.pr_info.number,
+                        url: pr.pr_info.url,
+                    },
+                });
+            }
+        }
+        MergeStatus::Open | MergeStatus::Unknown => {}
+    }
+    Ok(activity)
+}
+
+/// A process starting, finishing and committing
+fn process_activity(process: &ExecutionProcess, summary: Option<String>) -> Vec<TaskActivity> {
+    let key = process_key(process.task_attempt_id, process.id);
+    let mut activity = Vec::new();
+    let action = process.executor_action().ok().map(|action| action.typ());
+    let started = match action {
+        Some(ExecutorActionType::CodingAgentFollowUpRequest(request)) => {
+            TaskActivityEvent::FollowUpSent {
+                task_attempt_id: process.task_attempt_id,
+                execution_process_id: process.id,
+                prompt: request.prompt.clone(),
+            }
+        }
+        action => TaskActivityEvent::ProcessStarted {
+            task_attempt_id: process.task_attempt_id,
+            execution_process_id: process.id,
+            run_reason: process.run_reason.clone(),
+            prompt: match action {
+                Some(ExecutorActionType::CodingAgentInitialRequest(request)) => {
+                    Some(request.prompt.clone())
+                }
+                _ => None,
+            },
+        },
+    };
+    activity.push(TaskActivity {
+        key: format!("{key}:started"),
+        at: process.started_at,
+        event: started,
+    });
+
+    let Some(completed_at) = process.completed_at else {
+        return activity;
+    };
+    activity.push(TaskActivity {
+        key: format!("{key}:finished"),
+        at: completed_at,
+        event: TaskActivityEvent::ProcessFinished {
+            task_attempt_id: process.task_attempt_id,
+            execution_process_id: process.id,
+            run_reason: process.run_reason.clone(),
+            status: process.status.clone(),
+            exit_code: process.exit_code,
+            summary,
+        },
+    });
+
+    // Sorts after the finish it shares its time with
+    if let (Some(before), Some(after)) = (&process.before_head_commit, &process.after_head_commit)
+        && before != after
+    {
+        activity.push(TaskActivity {
+            key: format!("{key}:finished:commit"),
+            at: completed_at,
+            event: TaskActivityEvent::Committed {
+                task_attempt_id: process.task_attempt_id,
+                execution_process_id: process.id,
+                commit: after.clone(),
+            },
+        });
+    }
+    activity
+}
@@ -0,0 +1,312 @@
+//! Flow metrics for a project's tasks: lead and cycle time, time spent in progress and in review,
+//! throughput and how often coding agent runs succeed, overall and per executor profile.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::{
+    execution_process::{CodingAgentRun, ExecutionProcess, ExecutionProcessStatus},
+    task::TaskStatus,
+    task_status_history::TaskStatusChange,
+};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Window used when the query doesn't give a start
+pub const DEFAULT_METRICS_WINDOW_DAYS: i64 = 30;
+
+#[derive(Debug, Error)]
+pub enum TaskMetricsError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("`from` must be before `to`")]
+    InvalidWindow,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct TaskMetricsQuery {
+    /// Start of the window; defaults to 30 days before `to`
+    #[ts(type = "Date | null")]
+    pub from: Option<DateTime<Utc>>,
+    /// End of the window; defaults to now
+    #[ts(type = "Date | null")]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Summary of a set of durations, in seconds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+pub struct DurationStats {
+    #[ts(type = "number")]
+    pub count: i64,
+    pub mean_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+    pub p85_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+pub struct AgentRunStats {
+    /// Finished runs
+    #[ts(type = "number")]
+    pub runs: i64,
+    #[ts(type = "number")]
+    pub succeeded: i64,
+    #[ts(type = "number")]
+    pub failed: i64,
+    /// Runs stopped by the user; they don't count towards the success rate
+    #[ts(type = "number")]
+    pub killed: i64,
+    /// Share of succeeded runs among succeeded and failed ones
+    pub success_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct FlowMetrics {
+    /// Tasks that were done at the end of the window and became done within it
+    #[ts(type = "number")]
+    pub tasks_completed: i64,
+    /// Completed tasks per week of the window
+    pub throughput_per_week: f64,
+    /// From creation to done
+    pub lead_time: DurationStats,
+    /// From first moving to in progress to done
+    pub cycle_time: DurationStats,
+    /// Total time spent in progress by completed tasks that were ever in progress
+    pub time_in_progress: DurationStats,
+    /// Total time spent in review by completed tasks that were ever in review
+    pub time_in_review: DurationStats,
+    /// Coding agent runs started within the window
+    pub agent_runs: AgentRunStats,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct ExecutorProfileMetrics {
+    pub executor_profile_id: ExecutorProfileId,
+    pub metrics: FlowMetrics,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct TaskMetrics {
+    #[ts(type = "Date")]
+    pub from: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub to: DateTime<Utc>,
+    pub overall: FlowMetrics,
+    /// Completed tasks are attributed to the profile of their last coding agent run; tasks
+    /// finished without one only count towards `overall`
+    pub by_executor_profile: Vec<ExecutorProfileMetrics>,
+}
+
+pub async fn project_task_metrics(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    query: &TaskMetricsQuery,
+) -> Result<TaskMetrics, TaskMetricsError> {
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_METRICS_WINDOW_DAYS));
+    if from >= to {
+        return Err(TaskMetricsError::InvalidWindow);
+    }
+
+    let changes = TaskStatusChange::find_by_project_id(pool, project_id).await?;
+    let runs = ExecutionProcess::find_coding_agent_runs_by_project(pool, project_id).await?;
+    Ok(compute_task_metrics(&changes, &runs, from, to))
+}
+
+/// Metrics over `[from, to)` from the tasks' status changes and coding agent runs
+pub fn compute_task_metrics(
+    changes: &[TaskStatusChange],
+    runs: &[CodingAgentRun],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> TaskMetrics {
+    let weeks = (to - from).num_seconds() as f64 / Duration::weeks(1).num_seconds() as f64;
+    let mut overall = Accumulator::default();
+    let mut by_profile: HashMap<ExecutorProfileId, Accumulator> = HashMap::new();
+
+    let mut history_by_task: HashMap<Uuid, Vec<&TaskStatusChange>> = HashMap::new();
+    for change in changes.iter().filter(|change| change.changed_at < to) {
+        history_by_task
+            .entry(change.task_id)
+            .or_default()
+            .push(change);
+    }
+
+    for (task_id, mut history) in history_by_task {
+        history.sort_by_key(|change| (change.changed_at, change.id));
+        let Some(completion) = completion(&history, from) else {
+            continue;
+        };
+        overall.add_completion(&completion);
+
+        let profile = runs
+            .iter()
+            .filter(|run| run.task_id == task_id && run.started_at <= completion.done_at)
+            .filter_map(|run| run.executor_profile_id())
+            .next_back();
+        if let Some(profile) = profile {
+            by_profile
+                .entry(profile.clone())
+                .or_default()
+                .add_completion(&completion);
+        }
+    }
+
+    for run in runs
+        .iter()
+        .filter(|run| run.started_at >= from && run.started_at < to)
+    {
+        overall.add_run(run);
+        if let Some(profile) = run.executor_profile_id() {
+            by_profile.entry(profile.clone()).or_default().add_run(run);
+        }
+    }
+
+    let mut by_executor_profile: Vec<ExecutorProfileMetrics> = by_profile
+        .into_iter()
+        .map(
+            |(executor_profile_id, accumulator)| ExecutorProfileMetrics {
+                executor_profile_id,
+                metrics: accumulator.finish(weeks),
+            },
+        )
+        .collect();
+    by_executor_profile.sort_by_key(|entry| {
+        (
+            entry.executor_profile_id.executor.to_string(),
+            entry.executor_profile_id.variant.clone(),
+        )
+    });
+
+    TaskMetrics {
+        from,
+        to,
+        overall: overall.finish(weeks),
+        by_executor_profile,
+    }
+}
+
+/// How a task that ended up done got there
+struct Completion {
+    done_at: DateTime<Utc>,
+    lead_time: Duration,
+    cycle_time: Option<Duration>,
+    time_in_progress: Duration,
+    time_in_review: Duration,
+}
+
+/// The task's path to done, if its history (oldest first) ends in done within the window
+fn completion(history: &[&TaskStatusChange], from: DateTime<Utc>) -> Option<Completion> {
+    let created_at = history.first()?.changed_at;
+    let last = history.last()?;
+    if last.to_status != TaskStatus::Done || last.changed_at < from {
+        return None;
+    }
+    let done_at = last.changed_at;
+
+    let started_at = history
+        .iter()
+        .find(|change| change.to_status == TaskStatus::InProgress)
+        .map(|change| change.changed_at);
+
+    let mut time_in_progress = Duration::zero();
+    let mut time_in_review = Duration::zero();
+    for pair in history.windows(2) {
+        let spent = pair[1].changed_at - pair[0].changed_at;
+        match pair[0].to_status {
+            TaskStatus::InProgress => time_in_progress += spent,
+            TaskStatus::InReview => time_in_review += spent,
+            _ => {}
+        }
+    }
+
+    Some(Completion {
+        done_at,
+        lead_time: done_at - created_at,
+        cycle_time: started_at.map(|started_at| done_at - started_at),
+        time_in_progress,
+        time_in_review,
+    })
+}
+
+#[derive(Default)]
+struct Accumulator {
+    lead_times: Vec<f64>,
+    cycle_times: Vec<f64>,
+    times_in_progress: Vec<f64>,
+    times_in_review: Vec<f64>,
+    runs: AgentRunStats,
+}
+
+impl Accumulator {
+    fn add_completion(&mut self, completion: &Completion) {
+        self.lead_times.push(seconds(completion.lead_time));
+        if let Some(cycle_time) = completion.cycle_time {
+            self.cycle_times.push(seconds(cycle_time));
+        }
+        if completion.time_in_progress > Duration::zero() {
+            self.times_in_progress
+                .push(seconds(completion.time_in_progress));
+        }
+        if completion.time_in_review > Duration::zero() {
+            self.times_in_review
+                .push(seconds(completion.time_in_review));
+        }
+    }
+
+    fn add_run(&mut self, run: &CodingAgentRun) {
+        match run.status {
+            ExecutionProcessStatus::Running => return,
+            ExecutionProcessStatus::Completed => self.runs.succeeded += 1,
+            ExecutionProcessStatus::Failed => self.runs.failed += 1,
+            ExecutionProcessStatus::Killed => self.runs.killed += 1,
+        }
+        self.runs.runs += 1;
+    }
+
+    fn finish(self, weeks: f64) -> FlowMetrics {
+        let mut runs = self.runs;
+        let decided = runs.succeeded + runs.failed;
+        runs.success_rate = (decided > 0).then(|| runs.succeeded as f64 / decided as f64);
+        let tasks_completed = self.lead_times.len() as i64;
+
+        FlowMetrics {
+            tasks_completed,
+            throughput_per_week: tasks_completed as f64 / weeks,
+            lead_time: duration_stats(self.lead_times),
+            cycle_time: duration_stats(self.cycle_times),
+            time_in_progress: duration_stats(self.times_in_progress),
+            time_in_review: duration_stats(self.times_in_review),
+            agent_runs: runs,
+        }
+    }
+}
+
+fn seconds(duration: Duration) -> f64 {
+    duration.num_milliseconds() as f64 / 1000.0
+}
+
+fn duration_stats(mut values: Vec<f64>) -> DurationStats {
+    values.sort_by(f64::total_cmp);
+    let count = values.len();
+    DurationStats {
+        count: count as i64,
+        mean_seconds: (count > 0).then(|| values.iter().sum::<f64>() / count as f64),
+        median_seconds: percentile(&values, 50.0),
+        p85_seconds: percentile(&values, 85.0),
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
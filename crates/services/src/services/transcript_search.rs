@@ -0,0 +1,129 @@
+//! Full-text search over agent transcripts.
+//!
+//! A coding agent process's conversation is indexed from its in-memory normalized log once the
+//! process has exited and the normalizer has drained the log. Processes that finished before
+//! the index existed, or whose indexing was missed, are indexed from their stored logs by
+//! `backfill_transcript_index`.
+
+use std::collections::HashSet;
+
+use db::models::{
+    execution_process::ExecutionProcess,
+    transcript_entry::{TranscriptEntry, TranscriptEntryKind},
+};
+use executors::logs::{ActionType, FileChange, NormalizedEntry, NormalizedEntryType};
+use json_patch::Patch;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::{
+    container::ContainerService,
+    conversation_export::{indexed_entries_from_patches, stored_process_patches},
+};
+
+/// Processes indexed per backfill query
+const BACKFILL_BATCH_SIZE: i64 = 20;
+
+/// The searchable text of normalized entries, given with their positions in the normalized log:
+/// assistant messages, commands and file paths
+pub fn transcript_entries(entries: &[(usize, NormalizedEntry)]) -> Vec<TranscriptEntry> {
+    entries
+        .iter()
+        .filter_map(|(index, entry)| {
+            let (kind, content) = match &entry.entry_type {
+                NormalizedEntryType::AssistantMessage => {
+                    (TranscriptEntryKind::AssistantMessage, entry.content.clone())
+                }
+                NormalizedEntryType::ToolUse { action_type, .. } => match action_type {
+                    ActionType::CommandRun { command, .. } => {
+                        (TranscriptEntryKind::Command, command.clone())
+                    }
+                    ActionType::FileRead { path } => (TranscriptEntryKind::FilePath, path.clone()),
+                    ActionType::FileEdit { path, changes } => {
+                        let mut paths = vec![path.clone()];
+                        paths.extend(changes.iter().filter_map(|change| match change {
+                            FileChange::Rename { new_path } => Some(new_path.clone()),
+                            _ => None,
+                        }));
+                        (TranscriptEntryKind::FilePath, paths.join("\n"))
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            (!content.trim().is_empty()).then_some(TranscriptEntry {
+                entry_index: *index as i64,
+                kind,
+                content,
+            })
+        })
+        .collect()
+}
+
+/// Index a process's conversation from its complete normalized log, replacing what was indexed
+/// for it before
+pub async fn index_patches(
+    pool: &SqlitePool,
+    execution_process_id: Uuid,
+    patches: &[Patch],
+) -> Result<usize, sqlx::Error> {
+    let entries = transcript_entries(&indexed_entries_from_patches(patches));
+    TranscriptEntry::replace_for_process(pool, execution_process_id, &entries).await?;
+    Ok(entries.len())
+}
+
+/// Index a finished process's conversation from its stored logs. `None`, with nothing
+/// recorded, if the process is still running or its logs couldn't be fully normalized.
+pub async fn index_process<C>(
+    container: &C,
+    process: &ExecutionProcess,
+) -> Result<Option<usize>, sqlx::Error>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    if container.get_msg_store_by_id(&process.id).await.is_some() {
+        return Ok(None);
+    }
+    match stored_process_patches(container, process).await {
+        Some(patches) => index_patches(&container.db().pool, process.id, &patches)
+            .await
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Index every finished coding agent process that hasn't been, returning how many were.
+/// Processes that can't be indexed are skipped and left for the next backfill.
+pub async fn backfill_transcript_index<C>(container: &C) -> Result<usize, sqlx::Error>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let mut indexed = 0;
+    let mut skipped = HashSet::new();
+    loop {
+        let ids: Vec<Uuid> = TranscriptEntry::find_unindexed_process_ids(
+            pool,
+            BACKFILL_BATCH_SIZE + skipped.len() as i64,
+        )
+        .await?
+        .into_iter()
+        .filter(|id| !skipped.contains(id))
+        .collect();
+        if ids.is_empty() {
+            return Ok(indexed);
+        }
+        for id in ids {
+            // Deleted since it was listed; nothing left to index
+            let Some(process) = ExecutionProcess::find_by_id(pool, id).await? else {
+                continue;
+            };
+            match index_process(container, &process).await? {
+                Some(_) => indexed += 1,
+                None => {
+                    skipped.insert(id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! Outbound webhooks. Lifecycle events are serialized into a JSON payload, signed with the
+//! webhook's secret and POSTed to every subscribed webhook. Failed requests are retried with
+//! backoff, and each delivery is logged with the outcome of its last attempt.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+        merge::PullRequestInfo,
+        task::{Task, TaskStatus},
+        task_attempt::TaskAttempt,
+        webhook::{Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookEventType},
+    },
+};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::Error as SqlxError;
+use thiserror::Error;
+use utils::approvals::ApprovalRequest;
+use uuid::Uuid;
+
+/// `sha256=<hex>` HMAC-SHA256 of the request body, keyed with the webhook's secret
+pub const SIGNATURE_HEADER: &str = "X-Vibe-Kanban-Signature-256";
+pub const EVENT_HEADER: &str = "X-Vibe-Kanban-Event";
+/// Same for every attempt of a delivery, so receivers can drop duplicates
+pub const DELIVERY_HEADER: &str = "X-Vibe-Kanban-Delivery";
+
+/// Attempts before a delivery is given up on
+pub const MAX_ATTEMPTS: i64 = 6;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Recorded on deliveries dropped because their webhook was disabled
+pub const DISABLED_ERROR: &str = "Webhook was disabled";
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error(transparent)]
+    Database(#[from] SqlxError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+/// The parts of an execution process a webhook receiver cares about
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSummary {
+    pub id: Uuid,
+    pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<&ExecutionProcess> for ProcessSummary {
+    fn from(process: &ExecutionProcess) -> Self {
+        Self {
+            id: process.id,
+            run_reason: process.run_reason.clone(),
+            status: process.status.clone(),
+            exit_code: process.exit_code,
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+        }
+    }
+}
+
+/// An event sent to webhooks, serialized as `{"event": "<type>", "data": {...}}`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    TaskCreated {
+        task: Task,
+    },
+    TaskStatusChanged {
+        task: Task,
+        previous_status: TaskStatus,
+    },
+    AttemptStarted {
+        task: Task,
+        attempt: TaskAttempt,
+    },
+    ProcessCompleted {
+        task: Task,
+        attempt: TaskAttempt,
+        process: ProcessSummary,
+    },
+    ProcessFailed {
+        task: Task,
+        attempt: TaskAttempt,
+        process: ProcessSummary,
+    },
+    ApprovalRequested {
+        task: Task,
+        attempt: TaskAttempt,
+        approval: ApprovalRequest,
+    },
+    PrMerged {
+        task: Task,
+        attempt: TaskAttempt,
+        pull_request: PullRequestInfo,
+    },
+    Ping {
+        webhook_id: Uuid,
+    },
+}
+
+impl WebhookEvent {
+    pub fn event_type(&self) -> WebhookEventType {
+        match self {
+            WebhookEvent::TaskCreated { .. } => WebhookEventType::TaskCreated,
+            WebhookEvent::TaskStatusChanged { .. } => WebhookEventType::TaskStatusChanged,
+            WebhookEvent::AttemptStarted { .. } => WebhookEventType::AttemptStarted,
+            WebhookEvent::ProcessCompleted { .. } => WebhookEventType::ProcessCompleted,
+            WebhookEvent::ProcessFailed { .. } => WebhookEventType::ProcessFailed,
+            WebhookEvent::ApprovalRequested { .. } => WebhookEventType::ApprovalRequested,
+            WebhookEvent::PrMerged { .. } => WebhookEventType::PrMerged,
+            WebhookEvent::Ping { .. } => WebhookEventType::Ping,
+        }
+    }
+
+    /// The event for a finished execution process, if webhooks are told about it.
+    /// Processes stopped by the user aren't reported.
+    pub fn for_finished_process(
+        task: Task,
+        attempt: TaskAttempt,
+        process: &ExecutionProcess,
+    ) -> Option<Self> {
+        let summary = ProcessSummary::from(process);
+        match process.status {
+            ExecutionProcessStatus::Completed => Some(WebhookEvent::ProcessCompleted {
+                task,
+                attempt,
+                process: summary,
+            }),
+            ExecutionProcessStatus::Failed => Some(WebhookEvent::ProcessFailed {
+                task,
+                attempt,
+                process: summary,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Request body sent to webhooks
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload<'a> {
+    /// The delivery's id
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub project_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub event: &'a WebhookEvent,
+}
+
+/// HMAC-SHA256 of `body` keyed with `secret`, as lowercase hex
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Wait before retrying a delivery that has failed `attempts` times: 10s, tripling with each
+/// failure up to about 40 minutes
+pub fn retry_delay(attempts: i64) -> Duration {
+    let exponent = attempts.clamp(1, 6) as u32 - 1;
+    Duration::from_secs(10 * 3u64.pow(exponent))
+}
+
+/// POST a delivery's payload to a webhook once. Returns the response status, and the error
+/// when the request failed or the receiver didn't answer with a 2xx.
+pub async fn send_delivery(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    delivery: &WebhookDelivery,
+) -> (Option<i64>, Option<String>) {
+    let mut request = client
+        .post(&webhook.url)
+        .timeout(REQUEST_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, delivery.event_type.to_string())
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .body(delivery.payload.clone());
+    if let Some(secret) = webhook.secret.as_deref().filter(|s| !s.is_empty()) {
+        let signature = sign_payload(secret.as_bytes(), delivery.payload.as_bytes());
+        request = request.header(SIGNATURE_HEADER, format!("sha256={signature}"));
+    }
+
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status();
+            let error = (!status.is_success()).then(|| format!("Webhook responded with {status}"));
+            (Some(status.as_u16() as i64), error)
+        }
+        Err(e) => (None, Some(e.to_string())),
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    db: DBService,
+    client: reqwest::Client,
+}
+
+impl WebhookService {
+    pub fn new(db: DBService) -> Self {
+        Self {
+            db,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Deliver an event to every webhook subscribed to it, in the background
+    pub fn dispatch(&self, project_id: Uuid, event: WebhookEvent) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.enqueue(project_id, &event).await {
+                tracing::error!(
+                    "Failed to queue '{}' webhook deliveries: {}",
+                    event.event_type(),
+                    e
+                );
+            }
+        });
+    }
+
+    /// Tell webhooks an agent is waiting for a tool call to be approved
+    pub fn dispatch_approval_requested(&self, approval: ApprovalRequest) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            match ExecutionProcess::load_context(&service.db.pool, approval.execution_process_id)
+                .await
+            {
+                Ok(ctx) => service.dispatch(
+                    ctx.task.project_id,
+                    WebhookEvent::ApprovalRequested {
+                        task: ctx.task,
+                        attempt: ctx.task_attempt,
+                        approval,
+                    },
+                ),
+                Err(e) => tracing::warn!("Failed to load approval context for webhooks: {}", e),
+            }
+        });
+    }
+
+    async fn enqueue(&self, project_id: Uuid, event: &WebhookEvent) -> Result<(), WebhookError> {
+        let event_type = event.event_type();
+        let webhooks = Webhook::find_enabled_for_project(&self.db.pool, project_id).await?;
+        for webhook in webhooks
+            .into_iter()
+            .filter(|webhook| webhook.accepts(project_id, event_type))
+        {
+            let delivery = self
+                .create_delivery(webhook.id, Some(project_id), event)
+                .await?;
+            self.spawn_delivery(webhook, delivery);
+        }
+        Ok(())
+    }
+
+    async fn create_delivery(
+        &self,
+        webhook_id: Uuid,
+        project_id: Option<Uuid>,
+        event: &WebhookEvent,
+    ) -> Result<WebhookDelivery, WebhookError> {
+        let id = Uuid::new_v4();
+        let payload = serde_json::to_string(&WebhookPayload {
+            id,
+            timestamp: Utc::now(),
+            project_id,
+            event,
+        })?;
+        Ok(
+            WebhookDelivery::create(&self.db.pool, id, webhook_id, event.event_type(), &payload)
+                .await?,
+        )
+    }
+
+    fn spawn_delivery(&self, webhook: Webhook, delivery: WebhookDelivery) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.deliver(webhook, delivery).await {
+                tracing::error!("Failed to record webhook delivery: {}", e);
+            }
+        });
+    }
+
+    /// Send a delivery until it succeeds or runs out of attempts. Stops early if the webhook is
+    /// disabled or deleted between attempts.
+    async fn deliver(
+        &self,
+        mut webhook: Webhook,
+        mut delivery: WebhookDelivery,
+    ) -> Result<(), WebhookError> {
+        loop {
+            let (response_status, error) = send_delivery(&self.client, &webhook, &delivery).await;
+            let status = if error.is_none() {
+                WebhookDeliveryStatus::Succeeded
+            } else if delivery.attempts + 1 >= MAX_ATTEMPTS {
+                WebhookDeliveryStatus::Failed
+            } else {
+                WebhookDeliveryStatus::Pending
+            };
+            delivery = WebhookDelivery::record_attempt(
+                &self.db.pool,
+                delivery.id,
+                status,
+                response_status,
+                error.as_deref(),
+            )
+            .await?;
+            if delivery.status != WebhookDeliveryStatus::Pending {
+                return Ok(());
+            }
+
+            tracing::debug!(
+                "Webhook delivery {} failed ({}), retrying",
+                delivery.id,
+                error.unwrap_or_default()
+            );
+            tokio::time::sleep(retry_delay(delivery.attempts)).await;
+            match Webhook::find_by_id(&self.db.pool, webhook.id).await? {
+                Some(current) if current.enabled => webhook = current,
+                Some(current) => {
+                    self.fail_pending(current.id).await?;
+                    return Ok(());
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Send a test event to a webhook once, without retrying
+    pub async fn ping(&self, webhook: &Webhook) -> Result<WebhookDelivery, WebhookError> {
+        let delivery = self
+            .create_delivery(
+                webhook.id,
+                webhook.project_id,
+                &WebhookEvent::Ping {
+                    webhook_id: webhook.id,
+                },
+            )
+            .await?;
+        let (response_status, error) = send_delivery(&self.client, webhook, &delivery).await;
+        let status = if error.is_none() {
+            WebhookDeliveryStatus::Succeeded
+        } else {
+            WebhookDeliveryStatus::Failed
+        };
+        Ok(WebhookDelivery::record_attempt(
+            &self.db.pool,
+            delivery.id,
+            status,
+            response_status,
+            error.as_deref(),
+        )
+        .await?)
+    }
+
+    /// Mark the webhook's pending deliveries failed once it has been disabled, so they don't
+    /// sit in its log as pending forever
+    pub async fn fail_pending(&self, webhook_id: Uuid) -> Result<u64, WebhookError> {
+        Ok(WebhookDelivery::fail_pending(&self.db.pool, webhook_id, DISABLED_ERROR).await?)
+    }
+
+    /// Restart deliveries that were still pending when the app last stopped
+    pub async fn resume_pending(&self) -> Result<(), WebhookError> {
+        for delivery in WebhookDelivery::find_pending(&self.db.pool).await? {
+            match Webhook::find_by_id(&self.db.pool, delivery.webhook_id).await? {
+                Some(webhook) if webhook.enabled => self.spawn_delivery(webhook, delivery),
+                Some(webhook) => {
+                    self.fail_pending(webhook.id).await?;
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+//! Workspaces of attempts whose project owns several repositories.
+//!
+//! Such an attempt gets a worktree of every repository, each on the attempt's branch, side by
+//! side in one workspace directory:
+//!
+//! ```text
+//! <worktrees>/<attempt dir>/    executions run here
+//!     backend/                  the project's own repository; the attempt's container_ref
+//!     frontend/                 a project repository, named after ProjectRepository::name
+//! ```
+//!
+//! Attempts of single-repository projects keep their worktree directly at
+//! `<worktrees>/<attempt dir>`. Paths within a workspace, such as the paths of its diffs,
+//! start with the directory name of the repository they belong to.
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    project::Project, task_attempt::TaskAttempt, task_attempt_repository::TaskAttemptRepository,
+};
+use sqlx::SqlitePool;
+use utils::diff::Diff;
+use uuid::Uuid;
+
+use super::project_scope::ProjectScope;
+
+#[derive(Debug, Clone)]
+pub struct WorkspaceRepo {
+    /// `None` for the project's own repository
+    pub attempt_repository: Option<TaskAttemptRepository>,
+    /// The project's id for its own repository, the project repository's id otherwise
+    pub repo_id: Uuid,
+    /// Directory of the worktree inside the workspace
+    pub name: String,
+    pub repo_path: PathBuf,
+    pub worktree_path: PathBuf,
+    pub target_branch: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AttemptWorkspace {
+    /// Directory executions run in
+    pub root: PathBuf,
+    /// The project's own repository first
+    pub repos: Vec<WorkspaceRepo>,
+}
+
+impl AttemptWorkspace {
+    /// Workspace of an attempt whose own worktree is at `container_ref`
+    pub fn new(
+        project: &Project,
+        task_attempt: &TaskAttempt,
+        container_ref: &Path,
+        extra_repos: Vec<TaskAttemptRepository>,
+    ) -> Self {
+        let root = if extra_repos.is_empty() {
+            container_ref.to_path_buf()
+        } else {
+            container_ref
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| container_ref.to_path_buf())
+        };
+        let primary = WorkspaceRepo {
+            attempt_repository: None,
+            repo_id: project.id,
+            name: container_ref
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            repo_path: project.git_repo_path.clone(),
+            worktree_path: container_ref.to_path_buf(),
+            target_branch: task_attempt.target_branch.clone(),
+        };
+        let extras = extra_repos.into_iter().map(|repo| WorkspaceRepo {
+            repo_id: repo.project_repository_id,
+            name: repo.name.clone(),
+            repo_path: repo.git_repo_path.clone(),
+            worktree_path: root.join(&repo.name),
+            target_branch: repo.target_branch.clone(),
+            attempt_repository: Some(repo),
+        });
+        let repos = std::iter::once(primary).chain(extras).collect();
+        Self { root, repos }
+    }
+
+    /// The attempt's workspace, or `None` before its worktree has been created
+    pub async fn load(
+        pool: &SqlitePool,
+        project: &Project,
+        task_attempt: &TaskAttempt,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let Some(container_ref) = task_attempt.container_ref.as_deref() else {
+            return Ok(None);
+        };
+        let extra_repos =
+            TaskAttemptRepository::find_by_task_attempt_id(pool, task_attempt.id).await?;
+        Ok(Some(Self::new(
+            project,
+            task_attempt,
+            Path::new(container_ref),
+            extra_repos,
+        )))
+    }
+
+    pub fn is_multi_repo(&self) -> bool {
+        self.repos.len() > 1
+    }
+
+    pub fn primary(&self) -> &WorkspaceRepo {
+        &self.repos[0]
+    }
+
+    pub fn extra_repos(&self) -> &[WorkspaceRepo] {
+        &self.repos[1..]
+    }
+
+    /// Directory coding agents run in. Agents of multi-repository workspaces need all of
+    /// them, so only single-repository workspaces use the project's working directory.
+    pub fn agent_dir(&self, scope: &ProjectScope) -> PathBuf {
+        if self.is_multi_repo() {
+            self.root.clone()
+        } else {
+            scope.agent_dir(&self.root)
+        }
+    }
+
+    /// The repository a workspace-relative path belongs to, and the path inside its worktree.
+    /// Paths of single-repository workspaces are already relative to the worktree.
+    pub fn resolve_path<'a>(&self, path: &'a str) -> (&WorkspaceRepo, &'a str) {
+        if self.is_multi_repo() {
+            for repo in &self.repos {
+                if let Some(rest) = path.strip_prefix(repo.name.as_str())
+                    && let Some(rest) = rest.strip_prefix('/')
+                {
+                    return (repo, rest);
+                }
+            }
+        }
+        (self.primary(), path)
+    }
+}
+
+/// Directory name of the project's own repository inside a workspace
+pub fn primary_repo_dir_name(git_repo_path: &Path) -> String {
+    git_repo_path
+        .file_name()
+        .map(|name| sanitize_dir_name(&name.to_string_lossy()))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "repo".to_string())
+}
+
+fn sanitize_dir_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('.')
+        .to_string()
+}
+
+/// Check a project repository's name, which becomes its directory in attempt workspaces
+pub fn validate_repository_name(
+    name: &str,
+    project_git_repo_path: &Path,
+    taken: &[&str],
+) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Repository name is required".to_string());
+    }
+    if sanitize_dir_name(name) != name {
+        return Err(format!(
+            "Repository name '{name}' may only contain letters, digits, '-', '_' and '.', and can't start or end with '.'"
+        ));
+    }
+    if name == primary_repo_dir_name(project_git_repo_path) {
+        return Err(format!(
+            "'{name}' is the directory of the project's own repository; choose another name"
+        ));
+    }
+    if taken.contains(&name) {
+        return Err(format!(
+            "The project already has a repository named '{name}'"
+        ));
+    }
+    Ok(())
+}
+
+/// Make a diff's paths relative to the workspace rather than the repository's worktree
+pub fn prefix_diff_paths(diff: &mut Diff, prefix: &str) {
+    for path in [&mut diff.old_path, &mut diff.new_path]
+        .into_iter()
+        .flatten()
+    {
+        *path = format!("{prefix}/{path}");
+    }
+}
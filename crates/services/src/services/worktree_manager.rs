@@ -6,12 +6,13 @@ use std::{
 
 use git2::{Error as GitError, Repository};
 use thiserror::Error;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use utils::shell::get_shell_command;
 
 use super::{
     git::{GitService, GitServiceError},
-    git_cli::GitCli,
+    git_cli::{GitCli, GitCliError},
+    worktree_snapshot::{self, SnapshotLocation},
 };
 
 // Global synchronization for worktree creation to prevent race conditions
@@ -43,13 +44,16 @@ pub enum WorktreeError {
 pub struct WorktreeManager;
 
 impl WorktreeManager {
-    /// Create a worktree with a new branch
+    /// Create a worktree with a new branch. With `sparse_checkout` patterns, only matching
+    /// paths are checked out.
+    #[tracing::instrument(skip(sparse_checkout))]
     pub async fn create_worktree(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
         base_branch: &str,
         create_branch: bool,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         if create_branch {
             let repo_path_owned = repo_path.to_path_buf();
@@ -71,15 +75,21 @@ impl WorktreeManager {
             .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
         }
 
-        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path).await
+        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path, sparse_checkout, None)
+            .await
     }
 
     /// Ensure worktree exists, recreating if necessary with proper synchronization
     /// This is the main entry point for ensuring a worktree exists and prevents race conditions
+    ///
+    /// A recreated worktree gets the local state archived at `snapshot`, if any, back.
+    #[tracing::instrument(skip(sparse_checkout))]
     pub async fn ensure_worktree_exists(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_checkout: &[String],
+        snapshot: Option<&SnapshotLocation>,
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
@@ -98,12 +108,61 @@ impl WorktreeManager {
         // Check if worktree already exists and is properly set up
         if Self::is_worktree_properly_set_up(repo_path, worktree_path).await? {
             debug!("Worktree already properly set up at path: {}", path_str);
+            Self::sync_sparse_checkout(worktree_path, sparse_checkout).await;
             return Ok(());
         }
 
         // If worktree doesn't exist or isn't properly set up, recreate it
         info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path, sparse_checkout)
+            .await?;
+
+        // Bring back local state archived when retention cleanup removed the worktree
+        let Some(snapshot) = snapshot.cloned() else {
+            return Ok(());
+        };
+        let worktree_path_owned = worktree_path.to_path_buf();
+        let restored = tokio::task::spawn_blocking(move || {
+            worktree_snapshot::restore_snapshot(&snapshot, &worktree_path_owned)
+        })
+        .await;
+        match restored {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => warn!("Failed to restore snapshot of {}: {}", path_str, e),
+            Err(e) => warn!("Snapshot restore task for {} failed: {}", path_str, e),
+        }
+        Ok(())
+    }
+
+    /// Apply the project's current sparse checkout patterns to an existing worktree. Files
+    /// git can't remove, e.g. ones with local changes, stay in place; failures are only
+    /// logged.
+    async fn sync_sparse_checkout(worktree_path: &Path, sparse_checkout: &[String]) {
+        let worktree = worktree_path.to_path_buf();
+        let patterns = sparse_checkout.to_vec();
+        let synced = tokio::task::spawn_blocking(move || {
+            let git = GitCli::new();
+            if git.sparse_checkout_patterns(&worktree)? == patterns {
+                return Ok(false);
+            }
+            if patterns.is_empty() {
+                git.sparse_checkout_disable(&worktree)?;
+            } else {
+                git.sparse_checkout_set(&worktree, &patterns)?;
+            }
+            Ok::<_, GitCliError>(true)
+        })
+        .await;
+        match synced {
+            Ok(Ok(true)) => info!("Updated sparse checkout of {}", worktree_path.display()),
+            Ok(Ok(false)) => {}
+            Ok(Err(e)) => warn!(
+                "Failed to update sparse checkout of {}: {}",
+                worktree_path.display(),
+                e
+            ),
+            Err(e) => warn!("Sparse checkout update task failed: {}", e),
+        }
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -111,6 +170,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
         let branch_name_owned = branch_name.to_string();
@@ -153,8 +213,8 @@ impl WorktreeManager {
             git_repo_path,
             &branch_name_owned,
             &worktree_path_owned,
-            &worktree_name,
             &path_str,
+            sparse_checkout,
         )
         .await
     }
@@ -175,13 +235,13 @@ impl WorktreeManager {
 
             // Check 2: Worktree must be registered in git metadata using find_worktree
             let repo = Repository::open(&repo_path).map_err(WorktreeError::Git)?;
-            let worktree_name = worktree_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| WorktreeError::InvalidPath("Invalid worktree path".to_string()))?;
+            let Some(worktree_name) = Self::worktree_metadata_name(&repo_path, &worktree_path)
+            else {
+                return Ok(false);
+            };
 
             // Try to find the worktree - if it exists and is valid, we're good
-            match repo.find_worktree(worktree_name) {
+            match repo.find_worktree(&worktree_name) {
                 Ok(_) => Ok(true),
                 Err(_) => Ok(false),
             }
@@ -208,7 +268,9 @@ impl WorktreeManager {
         }
 
         // Step 2: Always force cleanup metadata directory (proactive cleanup)
-        if let Err(e) = Self::force_cleanup_worktree_metadata(&git_repo_path, worktree_name) {
+        if let Some(metadata_name) = Self::worktree_metadata_name(&git_repo_path, worktree_path)
+            && let Err(e) = Self::force_cleanup_worktree_metadata(&git_repo_path, &metadata_name)
+        {
             debug!("Metadata cleanup failed (non-fatal): {}", e);
         }
 
@@ -283,19 +345,31 @@ impl WorktreeManager {
         git_repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
-        worktree_name: &str,
         path_str: &str,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         let git_repo_path = git_repo_path.to_path_buf();
         let branch_name = branch_name.to_string();
         let worktree_path = worktree_path.to_path_buf();
-        let worktree_name = worktree_name.to_string();
         let path_str = path_str.to_string();
+        let sparse_checkout = sparse_checkout.to_vec();
 
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             // Prefer git CLI for worktree add to inherit sparse-checkout semantics
             let git = GitCli::new();
-            match git.worktree_add(&git_repo_path, &worktree_path, &branch_name, false) {
+            let add = || {
+                if sparse_checkout.is_empty() {
+                    git.worktree_add(&git_repo_path, &worktree_path, &branch_name, false)
+                } else {
+                    git.worktree_add_sparse(
+                        &git_repo_path,
+                        &worktree_path,
+                        &branch_name,
+                        &sparse_checkout,
+                    )
+                }
+            };
+            match add() {
                 Ok(()) => {
                     if !worktree_path.exists() {
                         return Err(WorktreeError::Repository(format!(
@@ -314,11 +388,13 @@ impl WorktreeManager {
                         e
                     );
                     // Force cleanup metadata and try one more time
-                    Self::force_cleanup_worktree_metadata(&git_repo_path, &worktree_name)
-                        .map_err(WorktreeError::Io)?;
-                    if let Err(e2) =
-                        git.worktree_add(&git_repo_path, &worktree_path, &branch_name, false)
+                    if let Some(metadata_name) =
+                        Self::worktree_metadata_name(&git_repo_path, &worktree_path)
                     {
+                        Self::force_cleanup_worktree_metadata(&git_repo_path, &metadata_name)
+                            .map_err(WorktreeError::Io)?;
+                    }
+                    if let Err(e2) = add() {
                         debug!("Retry of git worktree add failed: {}", e2);
                         return Err(WorktreeError::GitCli(e2.to_string()));
                     }
@@ -352,6 +428,40 @@ impl WorktreeManager {
             .map(PathBuf::from)
     }
 
+    /// Name of the git metadata entry (`.git/worktrees/<name>`) of the worktree at
+    /// `worktree_path`. Git names entries after the worktree's directory and appends a number
+    /// when that name is taken, which happens when attempts with several repositories check
+    /// out a repository under the same directory name. Falls back to the directory name while
+    /// that entry doesn't belong to another worktree that still exists.
+    fn worktree_metadata_name(git_repo_path: &Path, worktree_path: &Path) -> Option<String> {
+        let dir_name = worktree_path.file_name()?.to_str()?.to_string();
+        let metadata_dir = git_repo_path.join(".git").join("worktrees");
+        let registered_path = |name: &str| -> Option<PathBuf> {
+            let gitdir = std::fs::read_to_string(metadata_dir.join(name).join("gitdir")).ok()?;
+            Path::new(gitdir.trim()).parent().map(Path::to_path_buf)
+        };
+        let canonical = std::fs::canonicalize(worktree_path).ok();
+        let is_this_worktree = |path: &Path| {
+            path == worktree_path
+                || canonical
+                    .as_deref()
+                    .is_some_and(|c| std::fs::canonicalize(path).ok().as_deref() == Some(c))
+        };
+
+        if let Ok(entries) = std::fs::read_dir(&metadata_dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if registered_path(&name).is_some_and(|path| is_this_worktree(&path)) {
+                    return Some(name);
+                }
+            }
+        }
+        match registered_path(&dir_name) {
+            Some(path) if path.exists() && !is_this_worktree(&path) => None,
+            _ => Some(dir_name),
+        }
+    }
+
     /// Force cleanup worktree metadata directory
     fn force_cleanup_worktree_metadata(
         git_repo_path: &Path,
@@ -375,6 +485,7 @@ impl WorktreeManager {
 
     /// Clean up a worktree path and its git metadata (non-blocking)
     /// If git_repo_path is None, attempts to infer it from the worktree itself
+    #[tracing::instrument]
     pub async fn cleanup_worktree(
         worktree_path: &Path,
         git_repo_path: Option<&Path>,
@@ -482,4 +593,73 @@ impl WorktreeManager {
     pub fn get_worktree_base_dir() -> std::path::PathBuf {
         utils::path::get_vibe_kanban_temp_dir().join("worktrees")
     }
+
+    /// Bytes used by the files under `path`. Symlinks are counted as links, not followed.
+    pub fn directory_size(path: &Path) -> u64 {
+        let mut total = 0;
+        let mut pending = vec![path.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(metadata) = entry.path().symlink_metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    pending.push(entry.path());
+                } else {
+                    total += metadata.len();
+                }
+            }
+        }
+        total
+    }
+
+    /// Size of every directory in the worktree base dir, largest first
+    pub async fn disk_usage() -> Result<Vec<(PathBuf, u64)>, WorktreeError> {
+        let base_dir = Self::get_worktree_base_dir();
+        tokio::task::spawn_blocking(move || {
+            if !base_dir.exists() {
+                return Ok(Vec::new());
+            }
+            let mut usage = Vec::new();
+            for entry in std::fs::read_dir(&base_dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    let size = Self::directory_size(&path);
+                    usage.push((path, size));
+                }
+            }
+            usage.sort_by(|a, b| b.1.cmp(&a.1));
+            Ok(usage)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
+    /// Free space on the filesystem holding the worktree base dir
+    pub fn available_space() -> Option<u64> {
+        #[cfg(unix)]
+        {
+            use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+            let base_dir = Self::get_worktree_base_dir();
+            let existing = base_dir.ancestors().find(|dir| dir.exists())?;
+            let path = CString::new(existing.as_os_str().as_bytes()).ok()?;
+            let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+            // SAFETY: `path` is a valid NUL-terminated string and `stat` is a valid out pointer
+            if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+                return None;
+            }
+            // Field widths differ between platforms
+            #[allow(clippy::unnecessary_cast)]
+            let available = stat.f_bavail as u64 * stat.f_frsize as u64;
+            Some(available)
+        }
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
 }
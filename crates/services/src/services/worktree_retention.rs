@@ -0,0 +1,128 @@
+//! Retention policy deciding which task attempt worktrees periodic cleanup removes.
+//!
+//! Worktrees are never removed while pinned or while an execution runs in them. Beyond that
+//! each project can keep its most recent attempts, keep attempts whose task is in review, and
+//! cap the total size of its worktrees, which removes the oldest unprotected ones first even
+//! before they expire.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::{
+    project::{DEFAULT_WORKTREE_RETENTION_HOURS, Project},
+    task::TaskStatus,
+    task_attempt::WorktreeCleanupCandidate,
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeRetention {
+    /// Inactivity after which a worktree expires
+    pub retention: Duration,
+    /// Number of most recently active worktrees that are always kept
+    pub keep_recent: usize,
+    pub keep_in_review: bool,
+    pub max_size_bytes: Option<u64>,
+}
+
+impl Default for WorktreeRetention {
+    fn default() -> Self {
+        Self {
+            retention: Duration::hours(DEFAULT_WORKTREE_RETENTION_HOURS),
+            keep_recent: 0,
+            keep_in_review: false,
+            max_size_bytes: None,
+        }
+    }
+}
+
+impl WorktreeRetention {
+    pub fn from_project(project: &Project) -> Self {
+        Self {
+            retention: Duration::hours(project.worktree_retention_hours.max(1)),
+            keep_recent: project
+                .worktree_keep_recent
+                .map_or(0, |n| n.max(0) as usize),
+            keep_in_review: project.worktree_keep_in_review,
+            max_size_bytes: project
+                .worktree_max_size_mb
+                .map(|mb| mb.max(0) as u64 * 1024 * 1024),
+        }
+    }
+}
+
+/// Check project retention settings, returning a user-facing reason when invalid.
+pub fn validate_worktree_retention(
+    retention_hours: Option<i64>,
+    keep_recent: Option<i64>,
+    max_size_mb: Option<i64>,
+) -> Result<(), String> {
+    if retention_hours.is_some_and(|hours| hours < 1) {
+        return Err("Worktree retention must be at least 1 hour".to_string());
+    }
+    if keep_recent.is_some_and(|n| n < 0) {
+        return Err("The number of recent worktrees to keep cannot be negative".to_string());
+    }
+    if max_size_mb.is_some_and(|mb| mb < 1) {
+        return Err("The worktree size limit must be at least 1 MB".to_string());
+    }
+    Ok(())
+}
+
+/// Pick the worktrees to remove, in removal order. Projects missing from `policies` use the
+/// default policy. `sizes` (bytes per attempt) is only consulted for projects with a size
+/// limit.
+pub fn select_worktrees_to_remove(
+    candidates: &[WorktreeCleanupCandidate],
+    policies: &HashMap<Uuid, WorktreeRetention>,
+    sizes: &HashMap<Uuid, u64>,
+    now: DateTime<Utc>,
+) -> Vec<Uuid> {
+    let mut by_project: HashMap<Uuid, Vec<&WorktreeCleanupCandidate>> = HashMap::new();
+    for candidate in candidates {
+        by_project
+            .entry(candidate.project_id)
+            .or_default()
+            .push(candidate);
+    }
+
+    let default_policy = WorktreeRetention::default();
+    let mut to_remove = Vec::new();
+    for (project_id, mut worktrees) in by_project {
+        let policy = policies.get(&project_id).unwrap_or(&default_policy);
+        // Most recently active first
+        worktrees.sort_by(|a, b| b.last_activity_at.cmp(&a.last_activity_at));
+
+        let mut kept = Vec::new();
+        for (rank, worktree) in worktrees.into_iter().enumerate() {
+            let protected = worktree.pinned
+                || worktree.running
+                || rank < policy.keep_recent
+                || (policy.keep_in_review && worktree.task_status == TaskStatus::InReview);
+            if !protected && now - worktree.last_activity_at > policy.retention {
+                to_remove.push(worktree.attempt_id);
+            } else {
+                kept.push((worktree, protected));
+            }
+        }
+
+        let Some(max_size) = policy.max_size_bytes else {
+            continue;
+        };
+        let size_of = |worktree: &WorktreeCleanupCandidate| {
+            sizes.get(&worktree.attempt_id).copied().unwrap_or(0)
+        };
+        let mut total: u64 = kept.iter().map(|(worktree, _)| size_of(worktree)).sum();
+        // Oldest unprotected worktrees go first until the project fits
+        for (worktree, protected) in kept.iter().rev() {
+            if total <= max_size {
+                break;
+            }
+            if !protected {
+                total -= size_of(worktree);
+                to_remove.push(worktree.attempt_id);
+            }
+        }
+    }
+    to_remove
+}
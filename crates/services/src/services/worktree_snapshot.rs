@@ -0,0 +1,356 @@
+//! Snapshots of a worktree's local state, taken before retention cleanup removes it.
+//!
+//! A snapshot is a gzip-compressed tar of every file that recreating the worktree from its
+//! branch would not bring back: modified tracked files, untracked files and ignored files
+//! (copied `copy_files`, dependencies, build output). A JSON manifest next to the archive
+//! records the commit the snapshot was taken on and the tracked files that had been deleted.
+//! [`restore_snapshot`] replays both onto a freshly recreated worktree.
+//!
+//! Snapshots are kept per attempt and repository, as `<dir>/<attempt_id>/<repo_id>.tar.gz`.
+//! A snapshot that can't be applied because the branch moved on is set aside next to it
+//! under a timestamped name, so later snapshots of the same worktree never overwrite it.
+
+use std::{
+    io::Write,
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+use utils::{path::get_vibe_kanban_temp_dir, shell::resolve_executable_path};
+use uuid::Uuid;
+
+use super::git_cli::{GitCli, GitCliError};
+
+#[derive(Debug, Error)]
+pub enum WorktreeSnapshotError {
+    #[error(transparent)]
+    GitCli(#[from] GitCliError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid snapshot manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    #[error("tar failed: {0}")]
+    Tar(String),
+    #[error(
+        "snapshot {} was taken on {snapshot_head} but the branch is now at {head}; it was kept for manual recovery",
+        archive.display()
+    )]
+    HeadMoved {
+        archive: PathBuf,
+        snapshot_head: String,
+        head: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotManifest {
+    /// Commit checked out when the snapshot was taken
+    head: String,
+    /// Tracked files that had been deleted in the worktree
+    deleted: Vec<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// Default directory holding worktree snapshots
+pub fn snapshot_dir() -> PathBuf {
+    get_vibe_kanban_temp_dir().join("worktree-snapshots")
+}
+
+/// Where the snapshot of an attempt's worktree of one repository is kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotLocation {
+    dir: PathBuf,
+    attempt_id: Uuid,
+    /// The project's id for its own repository, the project repository's id otherwise
+    repo_id: Uuid,
+}
+
+impl SnapshotLocation {
+    pub fn new(dir: impl Into<PathBuf>, attempt_id: Uuid, repo_id: Uuid) -> Self {
+        Self {
+            dir: dir.into(),
+            attempt_id,
+            repo_id,
+        }
+    }
+
+    /// Location in [`snapshot_dir`]
+    pub fn in_default_dir(attempt_id: Uuid, repo_id: Uuid) -> Self {
+        Self::new(snapshot_dir(), attempt_id, repo_id)
+    }
+
+    fn attempt_dir(&self) -> PathBuf {
+        attempt_snapshot_dir(&self.dir, self.attempt_id)
+    }
+
+    pub fn archive(&self) -> PathBuf {
+        self.attempt_dir().join(format!("{}.tar.gz", self.repo_id))
+    }
+
+    fn manifest(&self) -> PathBuf {
+        self.attempt_dir().join(format!("{}.json", self.repo_id))
+    }
+
+    /// Names a pending snapshot is moved to when it is set aside
+    fn kept(&self, created_at: DateTime<Utc>) -> (PathBuf, PathBuf) {
+        let stem = format!(
+            "{}.{}",
+            self.repo_id,
+            created_at.format("%Y%m%dT%H%M%S%.3fZ")
+        );
+        (
+            self.attempt_dir().join(format!("{stem}.tar.gz")),
+            self.attempt_dir().join(format!("{stem}.json")),
+        )
+    }
+}
+
+fn attempt_snapshot_dir(dir: &Path, attempt_id: Uuid) -> PathBuf {
+    dir.join(attempt_id.to_string())
+}
+
+/// Whether a snapshot is waiting to be restored at `location`
+pub fn has_snapshot(location: &SnapshotLocation) -> bool {
+    location.archive().exists() && location.manifest().exists()
+}
+
+/// Move the pending snapshot at `location` out of the way, keeping it for manual recovery.
+/// Returns where its archive went.
+fn set_aside(location: &SnapshotLocation) -> Result<PathBuf, WorktreeSnapshotError> {
+    let manifest: SnapshotManifest = serde_json::from_slice(&std::fs::read(location.manifest())?)?;
+    let (archive, manifest_path) = location.kept(manifest.created_at);
+    std::fs::rename(location.archive(), &archive)?;
+    std::fs::rename(location.manifest(), manifest_path)?;
+    Ok(archive)
+}
+
+fn null_separated(output: &str) -> impl Iterator<Item = String> + '_ {
+    output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+}
+
+fn run_tar(args: &[&std::ffi::OsStr], stdin: Option<&[u8]>) -> Result<(), WorktreeSnapshotError> {
+    let tar = resolve_executable_path("tar")
+        .ok_or_else(|| WorktreeSnapshotError::Tar("tar not found on PATH".to_string()))?;
+    let mut child = Command::new(tar)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(input) = stdin
+        && let Some(mut pipe) = child.stdin.take()
+    {
+        pipe.write_all(input)?;
+    }
+    let out = child.wait_with_output()?;
+    if !out.status.success() {
+        return Err(WorktreeSnapshotError::Tar(
+            String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Archive the local state of `worktree_path` at `location`. Returns the archive path, or
+/// `None` when the worktree matches its branch and there is nothing worth keeping. A snapshot
+/// still pending at `location` is set aside rather than overwritten.
+pub fn create_snapshot(
+    location: &SnapshotLocation,
+    worktree_path: &Path,
+) -> Result<Option<PathBuf>, WorktreeSnapshotError> {
+    let git = GitCli::new();
+    let head = git
+        .git(worktree_path, ["rev-parse", "HEAD"])?
+        .trim()
+        .to_string();
+    let changed = git.git(
+        worktree_path,
+        [
+            "diff",
+            "--name-only",
+            "-z",
+            "--no-renames",
+            "--diff-filter=d",
+            "HEAD",
+        ],
+    )?;
+    let deleted = git.git(
+        worktree_path,
+        [
+            "diff",
+            "--name-only",
+            "-z",
+            "--no-renames",
+            "--diff-filter=D",
+            "HEAD",
+        ],
+    )?;
+    let untracked = git.git(
+        worktree_path,
+        ["ls-files", "--others", "--exclude-standard", "-z"],
+    )?;
+    let ignored = git.git(
+        worktree_path,
+        [
+            "ls-files",
+            "--others",
+            "--ignored",
+            "--exclude-standard",
+            "--directory",
+            "-z",
+        ],
+    )?;
+
+    let files: Vec<String> = null_separated(&changed)
+        .chain(null_separated(&untracked))
+        .chain(null_separated(&ignored))
+        .collect();
+    let deleted: Vec<String> = null_separated(&deleted).collect();
+    if files.is_empty() && deleted.is_empty() {
+        return Ok(None);
+    }
+
+    if has_snapshot(location) {
+        let kept = set_aside(location)?;
+        info!("Kept unrestored snapshot as {}", kept.display());
+    }
+    let (archive, manifest) = (location.archive(), location.manifest());
+    std::fs::create_dir_all(location.attempt_dir())?;
+    let partial = archive.with_extension("partial");
+    let mut file_list = files.join("\0").into_bytes();
+    file_list.push(0);
+    run_tar(
+        &[
+            "-czf".as_ref(),
+            partial.as_os_str(),
+            "-C".as_ref(),
+            worktree_path.as_os_str(),
+            "--null".as_ref(),
+            "-T".as_ref(),
+            "-".as_ref(),
+        ],
+        Some(&file_list),
+    )?;
+    std::fs::rename(&partial, &archive)?;
+    std::fs::write(
+        &manifest,
+        serde_json::to_vec_pretty(&SnapshotManifest {
+            head,
+            deleted,
+            created_at: Utc::now(),
+        })?,
+    )?;
+    info!(
+        "Snapshot of worktree {} saved to {}",
+        worktree_path.display(),
+        archive.display()
+    );
+    Ok(Some(archive))
+}
+
+/// Restore the snapshot at `location` into a freshly recreated worktree and remove it.
+/// Returns whether a snapshot was applied. A snapshot taken on a different commit than the
+/// one checked out is not applied over newer history; it is set aside for manual recovery
+/// and [`WorktreeSnapshotError::HeadMoved`] is returned.
+pub fn restore_snapshot(
+    location: &SnapshotLocation,
+    worktree_path: &Path,
+) -> Result<bool, WorktreeSnapshotError> {
+    if !has_snapshot(location) {
+        return Ok(false);
+    }
+    let archive = location.archive();
+    let manifest: SnapshotManifest = serde_json::from_slice(&std::fs::read(location.manifest())?)?;
+
+    let head = GitCli::new()
+        .git(worktree_path, ["rev-parse", "HEAD"])?
+        .trim()
+        .to_string();
+    if head != manifest.head {
+        return Err(WorktreeSnapshotError::HeadMoved {
+            archive: set_aside(location)?,
+            snapshot_head: manifest.head,
+            head,
+        });
+    }
+
+    run_tar(
+        &[
+            "-xzf".as_ref(),
+            archive.as_os_str(),
+            "-C".as_ref(),
+            worktree_path.as_os_str(),
+        ],
+        None,
+    )?;
+    for path in &manifest.deleted {
+        let relative = Path::new(path);
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            continue;
+        }
+        let path = worktree_path.join(relative);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    for path in [archive, location.manifest()] {
+        remove_if_exists(&path)?;
+    }
+    info!(
+        "Restored snapshot taken {} into {}",
+        manifest.created_at,
+        worktree_path.display()
+    );
+    Ok(true)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), WorktreeSnapshotError> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Delete every snapshot of the repository at `location`, including set-aside ones
+pub fn discard_snapshot(location: &SnapshotLocation) -> Result<(), WorktreeSnapshotError> {
+    let entries = match std::fs::read_dir(location.attempt_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    let prefix = format!("{}.", location.repo_id);
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            remove_if_exists(&entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// Delete every snapshot of an attempt in `dir`
+pub fn discard_attempt_snapshots(
+    dir: &Path,
+    attempt_id: Uuid,
+) -> Result<(), WorktreeSnapshotError> {
+    match std::fs::remove_dir_all(attempt_snapshot_dir(dir, attempt_id)) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,10 @@
+use std::{fs, path::Path};
+
+/// Write `content` to `rel` under `base`, creating missing parent directories
+pub fn write_file(base: &Path, rel: &str, content: &str) {
+    let path = base.join(rel);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(path, content).unwrap();
+}
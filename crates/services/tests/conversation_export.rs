@@ -0,0 +1,199 @@
+use chrono::Utc;
+use db::models::execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus};
+use executors::logs::{
+    ActionType, CommandExitStatus, CommandRunResult, FileChange, NormalizedEntry,
+    NormalizedEntryType, ToolStatus, utils::patch::ConversationPatch,
+};
+use std::{sync::Arc, time::Duration};
+
+use services::services::conversation_export::{
+    ConversationExport, EXPORT_SCHEMA_VERSION, ExportFormat, ExportedProcess, drained_patches,
+    entries_from_patches,
+};
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type,
+        content: content.to_string(),
+        metadata: None,
+    }
+}
+
+fn tool(action_type: ActionType, status: ToolStatus) -> NormalizedEntry {
+    entry(
+        NormalizedEntryType::ToolUse {
+            tool_name: "Bash".to_string(),
+            action_type,
+            status,
+        },
+        "",
+    )
+}
+
+fn export(entries: Vec<NormalizedEntry>) -> ConversationExport {
+    ConversationExport {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        exported_at: Utc::now(),
+        task_id: Uuid::new_v4(),
+        task_title: "Fix <login> redirect".to_string(),
+        task_attempt_id: Uuid::new_v4(),
+        branch: "vk/fix-login".to_string(),
+        processes: vec![ExportedProcess {
+            id: Uuid::new_v4(),
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+            status: ExecutionProcessStatus::Completed,
+            executor: Some("CLAUDE_CODE".to_string()),
+            prompt: Some("Fix the login redirect".to_string()),
+            started_at: Utc::now(),
+            completed_at: Some(Utc::now()),
+            entries,
+        }],
+    }
+}
+
+#[test]
+fn patches_replay_into_final_entries() {
+    let command = ActionType::CommandRun {
+        command: "npm test".to_string(),
+        result: None,
+    };
+    let patches = [
+        ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::AssistantMessage, "Looking"),
+        ),
+        ConversationPatch::add_normalized_entry(1, tool(command.clone(), ToolStatus::Created)),
+        ConversationPatch::add_stderr(2, "noise".to_string()),
+        ConversationPatch::replace(
+            0,
+            entry(
+                NormalizedEntryType::AssistantMessage,
+                "Looking at the tests",
+            ),
+        ),
+        ConversationPatch::replace(1, tool(command, ToolStatus::Success)),
+        // Out of range patches are skipped rather than failing the export
+        ConversationPatch::remove(7),
+    ];
+
+    let entries = entries_from_patches(&patches);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].content, "Looking at the tests");
+    assert!(matches!(
+        entries[1].entry_type,
+        NormalizedEntryType::ToolUse {
+            status: ToolStatus::Success,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn markdown_includes_messages_tools_and_approvals() {
+    let export = export(vec![
+        entry(NormalizedEntryType::Thinking, "Redirect happens twice"),
+        tool(
+            ActionType::FileEdit {
+                path: "src/login.ts".to_string(),
+                changes: vec![FileChange::Edit {
+                    unified_diff: "@@ -1 +1 @@\n-a\n+b".to_string(),
+                    has_line_numbers: true,
+                }],
+            },
+            ToolStatus::Success,
+        ),
+        tool(
+            ActionType::CommandRun {
+                command: "rm -rf build".to_string(),
+                result: Some(CommandRunResult {
+                    exit_status: Some(CommandExitStatus::ExitCode { code: 1 }),
+                    output: Some("```\nboom".to_string()),
+                }),
+            },
+            ToolStatus::Denied {
+                reason: Some("not here".to_string()),
+            },
+        ),
+        entry(NormalizedEntryType::AssistantMessage, "Fixed it."),
+    ]);
+
+    let markdown = export.to_markdown();
+    assert!(markdown.starts_with("# Fix <login> redirect\n"));
+    assert!(markdown.contains("## 1. Coding agent (CLAUDE_CODE)"));
+    assert!(markdown.contains("### Prompt\n\nFix the login redirect"));
+    assert!(markdown.contains("<summary>Thinking</summary>\n\nRedirect happens twice"));
+    assert!(markdown.contains("Edited `src/login.ts`\n\n```diff\n@@ -1 +1 @@\n-a\n+b\n```"));
+    assert!(markdown.contains("#### Tool: Bash (denied: not here)"));
+    // Output containing a fence gets a longer one
+    assert!(markdown.contains("````\n```\nboom\n````"));
+    assert!(markdown.contains("_exit code 1_"));
+    assert!(markdown.contains("### Assistant\n\nFixed it."));
+}
+
+#[test]
+fn html_is_standalone_and_escaped() {
+    let export = export(vec![entry(
+        NormalizedEntryType::UserMessage,
+        "<script>alert(1)</script>",
+    )]);
+
+    let html = export.to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<title>Fix &lt;login&gt; redirect</title>"));
+    assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert!(!html.contains("<script>"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn json_is_schema_versioned() {
+    let export = export(vec![entry(NormalizedEntryType::UserMessage, "hi")]);
+    let json: serde_json::Value =
+        serde_json::from_str(&export.render(ExportFormat::Json).unwrap()).unwrap();
+    assert_eq!(json["schema_version"], EXPORT_SCHEMA_VERSION);
+    assert_eq!(json["processes"][0]["run_reason"], "codingagent");
+    assert_eq!(
+        json["processes"][0]["entries"][0]["entry_type"]["type"],
+        "user_message"
+    );
+    assert!(
+        export
+            .file_name(ExportFormat::Html)
+            .starts_with("conversation-")
+    );
+}
+
+#[tokio::test]
+async fn drained_patches_wait_for_the_normalizer() {
+    let store = Arc::new(MsgStore::new());
+    store.push_patch(ConversationPatch::add_normalized_entry(
+        0,
+        entry(NormalizedEntryType::UserMessage, "Fix the login"),
+    ));
+    let normalizer = store.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        normalizer.push_patch(ConversationPatch::add_normalized_entry(
+            1,
+            entry(NormalizedEntryType::AssistantMessage, "Done"),
+        ));
+    });
+
+    let patches = drained_patches(Uuid::new_v4(), store).await.unwrap();
+    let entries = entries_from_patches(&patches);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].content, "Done");
+}
+
+#[tokio::test(start_paused = true)]
+async fn drained_patches_give_up_on_a_stuck_normalizer() {
+    let store = Arc::new(MsgStore::new());
+    let stuck = store.clone();
+
+    assert!(drained_patches(Uuid::new_v4(), store).await.is_none());
+    drop(stuck);
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use std::fs;
+
+use services::services::copy_files::{
+    CopyMode, CopyTemplateValues, copy_project_files, parse_copy_files, validate_copy_files,
+};
+use tempfile::TempDir;
+
+use crate::common::write_file;
+
+fn values() -> CopyTemplateValues {
+    CopyTemplateValues {
+        branch: "vk/1234-fix-login".to_string(),
+        base_branch: "main".to_string(),
+        port: Some(4321),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn entries_are_parsed_with_options_and_exclusions() {
+    let spec = parse_copy_files(
+        ".env template, config/{dev,test}.json\n!config/secret.json\ndata dir/ depth=2\n.cache symlink",
+    )
+    .unwrap();
+    let patterns: Vec<&str> = spec.includes.iter().map(|e| e.pattern.as_str()).collect();
+    assert_eq!(
+        patterns,
+        [".env", "config/{dev,test}.json", "data dir/", ".cache"]
+    );
+    assert!(spec.includes[0].template);
+    assert_eq!(spec.includes[2].max_depth, Some(2));
+    assert_eq!(spec.includes[3].mode, CopyMode::Symlink);
+    assert_eq!(spec.excludes, ["config/secret.json"]);
+    assert!(spec.has_templates());
+
+    // The original comma-separated format still works
+    assert_eq!(
+        parse_copy_files(".env, .env.local").unwrap().includes.len(),
+        2
+    );
+
+    assert!(validate_copy_files(None).is_ok());
+    assert!(validate_copy_files(Some("../outside")).is_err());
+    assert!(validate_copy_files(Some("/etc/hosts")).is_err());
+    assert!(validate_copy_files(Some("data depth=deep")).is_err());
+    assert!(validate_copy_files(Some(".env symlink template")).is_err());
+}
+
+#[test]
+fn globs_exclusions_and_depth_limits_select_files() {
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    let worktree = td.path().join("worktree");
+    write_file(&repo, "config/app.local.json", "{}");
+    write_file(&repo, "config/nested/db.local.json", "{}");
+    write_file(&repo, "config/nested/secret.local.json", "{}");
+    write_file(&repo, "config/app.json", "{}");
+    write_file(&repo, "fixtures/a.txt", "a");
+    write_file(&repo, "fixtures/one/b.txt", "b");
+    write_file(&repo, "fixtures/one/two/c.txt", "c");
+
+    let spec = parse_copy_files(
+        "config/**/*.local.json\n!**/secret.local.json\nfixtures depth=2\nmissing/*.env",
+    )
+    .unwrap();
+    let report = copy_project_files(&repo, &worktree, &spec, &values());
+
+    assert!(worktree.join("config/app.local.json").exists());
+    assert!(worktree.join("config/nested/db.local.json").exists());
+    assert!(!worktree.join("config/nested/secret.local.json").exists());
+    assert!(!worktree.join("config/app.json").exists());
+    assert!(worktree.join("fixtures/a.txt").exists());
+    assert!(worktree.join("fixtures/one/b.txt").exists());
+    assert!(!worktree.join("fixtures/one/two/c.txt").exists());
+
+    assert_eq!(report.copied, 4);
+    assert_eq!(report.unmatched, ["missing/*.env"]);
+    assert!(report.errors.is_empty());
+    assert!(
+        report
+            .log_lines()
+            .iter()
+            .any(|line| line.contains("'missing/*.env' did not match"))
+    );
+}
+
+#[test]
+fn templates_are_rendered_and_symlinks_point_at_the_original() {
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    let worktree = td.path().join("worktree");
+    write_file(
+        &repo,
+        ".env",
+        "PORT={{port}}\nBRANCH={{ branch }}\nBASE={{base_branch}}\nKEEP={{unknown}}\nJSON={\"a\": 1}\n",
+    );
+    write_file(&repo, ".cache/blob", "shared");
+
+    let spec = parse_copy_files(".env template\n.cache symlink").unwrap();
+    let report = copy_project_files(&repo, &worktree, &spec, &values());
+
+    assert_eq!(
+        fs::read_to_string(worktree.join(".env")).unwrap(),
+        "PORT=4321\nBRANCH=vk/1234-fix-login\nBASE=main\nKEEP={{unknown}}\nJSON={\"a\": 1}\n"
+    );
+    assert_eq!(report.rendered, 1);
+    assert_eq!(report.linked, 1);
+    let link = worktree.join(".cache");
+    assert!(
+        fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+    assert_eq!(fs::read_to_string(link.join("blob")).unwrap(), "shared");
+}
+
+#[test]
+fn entries_naming_existing_paths_are_copied_as_written() {
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    let worktree = td.path().join("worktree");
+    // Brackets are a character class in globs, but this entry predates globs
+    write_file(&repo, "pages/[id].local.ts", "export {}");
+    write_file(&repo, "pages/i.local.ts", "glob match");
+    write_file(&repo, "certs/dev/key.pem", "key");
+
+    let spec = parse_copy_files("pages/[id].local.ts, certs/dev").unwrap();
+    let report = copy_project_files(&repo, &worktree, &spec, &values());
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
+    assert!(report.unmatched.is_empty());
+    assert_eq!(
+        fs::read_to_string(worktree.join("pages/[id].local.ts")).unwrap(),
+        "export {}"
+    );
+    assert!(!worktree.join("pages/i.local.ts").exists());
+    assert_eq!(
+        fs::read_to_string(worktree.join("certs/dev/key.pem")).unwrap(),
+        "key"
+    );
+
+    // Without such a path the entry is a glob
+    fs::remove_file(repo.join("pages/[id].local.ts")).unwrap();
+    let globbed = td.path().join("globbed");
+    copy_project_files(&repo, &globbed, &spec, &values());
+    assert!(globbed.join("pages/i.local.ts").exists());
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use std::fs;
+
+use services::services::dependency_cache::{
+    DependencyCache, DependencyCacheEntry, parse_dependency_caches, validate_dependency_caches,
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use crate::common::write_file;
+
+fn node_modules() -> DependencyCacheEntry {
+    DependencyCacheEntry {
+        dir: "web/node_modules".to_string(),
+        lockfiles: vec!["package-lock.json".to_string()],
+    }
+}
+
+#[test]
+fn cache_configuration_is_parsed_with_default_lockfiles() {
+    let entries = parse_dependency_caches(
+        "# shared between attempts\nnode_modules\n\ntarget/\nbuild/deps: deps.lock, extra.lock\n",
+    )
+    .unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].dir, "node_modules");
+    assert!(entries[0].lockfiles.contains(&"pnpm-lock.yaml".to_string()));
+    assert_eq!(entries[1].dir, "target");
+    assert_eq!(entries[1].lockfiles, vec!["Cargo.lock"]);
+    assert_eq!(entries[2].lockfiles, vec!["deps.lock", "extra.lock"]);
+
+    assert!(validate_dependency_caches(None).is_ok());
+    assert!(validate_dependency_caches(Some("build")).is_err());
+    assert!(validate_dependency_caches(Some("../node_modules")).is_err());
+    assert!(validate_dependency_caches(Some("/abs/node_modules")).is_err());
+    assert!(validate_dependency_caches(Some("deps: ../deps.lock")).is_err());
+}
+
+#[test]
+fn warm_base_populates_worktrees_with_matching_lockfiles() {
+    let td = TempDir::new().unwrap();
+    let cache = DependencyCache::new(td.path().join("cache"));
+    let project_id = Uuid::new_v4();
+    let entry = node_modules();
+
+    let warm = td.path().join("warm");
+    write_file(&warm, "web/package-lock.json", "{\"v\": 1}");
+    write_file(
+        &warm,
+        "web/node_modules/left-pad/index.js",
+        "module.exports = 1;",
+    );
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        "../left-pad/index.js",
+        warm.join("web/node_modules/left-pad/alias.js"),
+    )
+    .unwrap();
+    assert!(cache.warm(project_id, &warm, &entry).unwrap());
+    // Already warm for these lockfiles
+    assert!(!cache.warm(project_id, &warm, &entry).unwrap());
+
+    let fresh = td.path().join("fresh");
+    write_file(&fresh, "web/package-lock.json", "{\"v\": 1}");
+    assert!(
+        cache
+            .populate(project_id, &fresh, &entry)
+            .unwrap()
+            .is_some()
+    );
+    assert_eq!(
+        fs::read_to_string(fresh.join("web/node_modules/left-pad/index.js")).unwrap(),
+        "module.exports = 1;"
+    );
+    #[cfg(unix)]
+    assert!(
+        fs::symlink_metadata(fresh.join("web/node_modules/left-pad/alias.js"))
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+    // Existing directories are left alone
+    assert!(
+        cache
+            .populate(project_id, &fresh, &entry)
+            .unwrap()
+            .is_none()
+    );
+
+    // Files rewritten in place in a worktree don't leak into the warm base
+    fs::write(
+        fresh.join("web/node_modules/left-pad/index.js"),
+        "module.exports = 2;",
+    )
+    .unwrap();
+    let second = td.path().join("second");
+    write_file(&second, "web/package-lock.json", "{\"v\": 1}");
+    cache.populate(project_id, &second, &entry).unwrap();
+    assert_eq!(
+        fs::read_to_string(second.join("web/node_modules/left-pad/index.js")).unwrap(),
+        "module.exports = 1;"
+    );
+
+    // Other projects don't share warm bases
+    let other = td.path().join("other");
+    write_file(&other, "web/package-lock.json", "{\"v\": 1}");
+    assert!(
+        cache
+            .populate(Uuid::new_v4(), &other, &entry)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn changed_lockfiles_invalidate_the_warm_base() {
+    let td = TempDir::new().unwrap();
+    let cache = DependencyCache::new(td.path().join("cache"));
+    let project_id = Uuid::new_v4();
+    let entry = node_modules();
+
+    let old = td.path().join("old");
+    write_file(&old, "web/package-lock.json", "{\"v\": 1}");
+    write_file(&old, "web/node_modules/dep/index.js", "old");
+    assert!(cache.warm(project_id, &old, &entry).unwrap());
+
+    let bumped = td.path().join("bumped");
+    write_file(&bumped, "web/package-lock.json", "{\"v\": 2}");
+    assert!(
+        cache
+            .populate(project_id, &bumped, &entry)
+            .unwrap()
+            .is_none()
+    );
+
+    write_file(&bumped, "web/node_modules/dep/index.js", "new");
+    assert!(cache.warm(project_id, &bumped, &entry).unwrap());
+    // The base for the old lockfile was replaced
+    let stale = td.path().join("stale");
+    write_file(&stale, "web/package-lock.json", "{\"v\": 1}");
+    assert!(
+        cache
+            .populate(project_id, &stale, &entry)
+            .unwrap()
+            .is_none()
+    );
+
+    let current = td.path().join("current");
+    write_file(&current, "web/package-lock.json", "{\"v\": 2}");
+    assert!(
+        cache
+            .populate(project_id, &current, &entry)
+            .unwrap()
+            .is_some()
+    );
+    assert_eq!(
+        fs::read_to_string(current.join("web/node_modules/dep/index.js")).unwrap(),
+        "new"
+    );
+
+    cache.clear_project(project_id).unwrap();
+    let cleared = td.path().join("cleared");
+    write_file(&cleared, "web/package-lock.json", "{\"v\": 2}");
+    assert!(
+        cache
+            .populate(project_id, &cleared, &entry)
+            .unwrap()
+            .is_none()
+    );
+}
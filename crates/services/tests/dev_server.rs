@@ -0,0 +1,99 @@
+use std::net::TcpListener;
+
+use services::services::dev_server::{
+    DEV_SERVER_PORT_ENV, DevServers, PORT_ENV, detect_dev_server_url, dev_server_env,
+};
+use uuid::Uuid;
+
+#[test]
+fn urls_are_detected_from_dev_server_output() {
+    // Vite colours the port separately
+    assert_eq!(
+        detect_dev_server_url("  ➜  Local:   http://localhost:\x1b[1m5173\x1b[22m/").as_deref(),
+        Some("http://localhost:5173/")
+    );
+    assert_eq!(
+        detect_dev_server_url("Listening on http://[::1]:41234.").as_deref(),
+        Some("http://[::1]:41234/")
+    );
+    assert_eq!(
+        detect_dev_server_url("ready - started server on localhost:3000").as_deref(),
+        Some("http://localhost:3000/")
+    );
+    assert_eq!(
+        detect_dev_server_url("Learn more at https://nextjs.org/docs"),
+        None
+    );
+    assert_eq!(
+        detect_dev_server_url("Docs at https://vite.dev, app at http://127.0.0.1:5173/app")
+            .as_deref(),
+        Some("http://127.0.0.1:5173/app")
+    );
+    assert_eq!(detect_dev_server_url("compiled 42 modules"), None);
+}
+
+#[test]
+fn only_loopback_urls_are_detected() {
+    for line in [
+        "Listening on http://0.0.0.0:41234",
+        "Listening on 0.0.0.0:41234",
+        "  ➜  Network: http://192.168.1.20:5173/",
+        "Listening on http://10.0.0.1:8080/admin",
+        "Listening on http://[::]:41234",
+        "Listening on http://[fe80::1]:41234",
+        "Metadata at http://169.254.169.254/latest",
+        "Listening on http://localhost.evil.example:3000",
+        "Listening on http://localhost@example.com:3000",
+    ] {
+        assert_eq!(detect_dev_server_url(line), None, "{line}");
+    }
+}
+
+#[test]
+fn attempts_keep_distinct_ports() {
+    let servers = DevServers::default();
+    let first = Uuid::new_v4();
+    let second = Uuid::new_v4();
+
+    let port = servers.port_for(first).unwrap();
+    assert_eq!(servers.port_for(first), Some(port));
+    assert_ne!(servers.port_for(second), Some(port));
+
+    // Ports in use elsewhere are skipped
+    let _listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+    servers.release(first);
+    assert_ne!(servers.port_for(first), Some(port));
+
+    let env = dev_server_env(port);
+    assert_eq!(env[PORT_ENV], port.to_string());
+    assert_eq!(env[DEV_SERVER_PORT_ENV], port.to_string());
+}
+
+#[test]
+fn urls_belong_to_the_process_that_printed_them() {
+    let servers = DevServers::default();
+    let attempt = Uuid::new_v4();
+    let (old_process, new_process) = (Uuid::new_v4(), Uuid::new_v4());
+    let port = servers.port_for(attempt).unwrap();
+
+    let info = servers.get(attempt).unwrap();
+    assert_eq!(info.url, None);
+    assert_eq!(info.upstream(), format!("http://localhost:{port}"));
+    assert_eq!(info.preview_path, format!("/preview/{attempt}/"));
+
+    servers.record_url(attempt, old_process, "http://localhost:5173/".to_string());
+    servers.record_url(
+        attempt,
+        new_process,
+        "http://localhost:5174/app".to_string(),
+    );
+    // The old server stopping doesn't clear the new server's URL
+    servers.clear_url(attempt, old_process);
+    let info = servers.get(attempt).unwrap();
+    assert_eq!(info.url.as_deref(), Some("http://localhost:5174/app"));
+    assert_eq!(info.upstream(), "http://localhost:5174");
+
+    servers.clear_url(attempt, new_process);
+    assert_eq!(servers.get(attempt).unwrap().url, None);
+    assert!(servers.get(Uuid::new_v4()).is_none());
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use db::models::execution_process::DevServerHealth;
+use services::services::{
+    dev_server::{DevServerInfo, DevServers, preview_path},
+    dev_server_health::{
+        HealthCheck, HealthTracker, ProbeTarget, STABLE_UPTIME, STARTUP_GRACE, parse_health_check,
+        restart_backoff, validate_dev_server_health,
+    },
+    oci_runtime::OciRuntime,
+};
+use uuid::Uuid;
+
+#[test]
+fn health_checks_are_parsed() {
+    assert_eq!(
+        parse_health_check("http").unwrap(),
+        Some(HealthCheck::HttpPath("/".to_string()))
+    );
+    assert_eq!(
+        parse_health_check(" http:/api/health ").unwrap(),
+        Some(HealthCheck::HttpPath("/api/health".to_string()))
+    );
+    assert_eq!(
+        parse_health_check("https://localhost:8443/ready").unwrap(),
+        Some(HealthCheck::HttpUrl(
+            "https://localhost:8443/ready".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_health_check("tcp").unwrap(),
+        Some(HealthCheck::Tcp(None))
+    );
+    assert_eq!(
+        parse_health_check("tcp:5432").unwrap(),
+        Some(HealthCheck::Tcp(Some(5432)))
+    );
+    assert_eq!(
+        parse_health_check("command: curl -sf localhost:$PORT").unwrap(),
+        Some(HealthCheck::Command("curl -sf localhost:$PORT".to_string()))
+    );
+    assert_eq!(parse_health_check("  ").unwrap(), None);
+
+    assert!(parse_health_check("http:health").is_err());
+    assert!(parse_health_check("tcp:http").is_err());
+    assert!(parse_health_check("command:").is_err());
+    assert!(parse_health_check("ping").is_err());
+
+    assert!(validate_dev_server_health(None, Some(0)).is_ok());
+    assert!(validate_dev_server_health(Some("tcp:99999"), None).is_err());
+    assert!(validate_dev_server_health(Some("http"), Some(-1)).is_err());
+}
+
+#[test]
+fn failures_are_ignored_while_the_server_starts() {
+    let mut tracker = HealthTracker::default();
+    let early = Duration::from_secs(5);
+    for _ in 0..5 {
+        assert_eq!(tracker.record(false, early), None);
+    }
+    assert_eq!(tracker.state(), DevServerHealth::Starting);
+
+    assert_eq!(tracker.record(true, early), Some(DevServerHealth::Healthy));
+    assert_eq!(tracker.record(true, early), None);
+}
+
+#[test]
+fn consecutive_failures_mark_the_server_unhealthy() {
+    let mut tracker = HealthTracker::default();
+    let late = STARTUP_GRACE + Duration::from_secs(1);
+    assert_eq!(tracker.record(true, late), Some(DevServerHealth::Healthy));
+
+    assert_eq!(tracker.record(false, late), None);
+    assert_eq!(tracker.record(false, late), None);
+    // A pass in between starts the count again
+    assert_eq!(tracker.record(true, late), None);
+    assert_eq!(tracker.record(false, late), None);
+    assert_eq!(tracker.record(false, late), None);
+    assert_eq!(
+        tracker.record(false, late),
+        Some(DevServerHealth::Unhealthy)
+    );
+    assert_eq!(tracker.record(false, late), None);
+
+    assert_eq!(tracker.record(true, late), Some(DevServerHealth::Healthy));
+
+    // Servers that never come up turn unhealthy once the grace period is over
+    let mut tracker = HealthTracker::default();
+    assert_eq!(tracker.record(false, late), None);
+    assert_eq!(tracker.record(false, late), None);
+    assert_eq!(
+        tracker.record(false, late),
+        Some(DevServerHealth::Unhealthy)
+    );
+}
+
+#[test]
+fn restarts_back_off_and_are_counted_per_attempt() {
+    assert_eq!(restart_backoff(1), Duration::from_secs(1));
+    assert_eq!(restart_backoff(2), Duration::from_secs(2));
+    assert_eq!(restart_backoff(4), Duration::from_secs(8));
+    assert_eq!(restart_backoff(20), Duration::from_secs(60));
+
+    let servers = DevServers::default();
+    let (attempt, other) = (Uuid::new_v4(), Uuid::new_v4());
+    let brief = Duration::from_secs(3);
+    assert_eq!(servers.record_restart(attempt, brief), 1);
+    assert_eq!(servers.record_restart(attempt, brief), 2);
+    assert_eq!(servers.record_restart(other, brief), 1);
+
+    servers.reset_restarts(attempt);
+    assert_eq!(servers.record_restart(attempt, brief), 1);
+    servers.release(other);
+    assert_eq!(servers.record_restart(other, brief), 1);
+
+    // A server that stayed up for a while before failing counts afresh
+    assert_eq!(servers.record_restart(attempt, brief), 2);
+    assert_eq!(servers.record_restart(attempt, STABLE_UPTIME), 1);
+    assert_eq!(servers.record_restart(attempt, brief), 2);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn command_checks_of_container_projects_run_in_the_container() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let td = tempfile::TempDir::new().unwrap();
+    let args_file = td.path().join("args");
+    let runtime_path = td.path().join("fake-docker");
+    std::fs::write(
+        &runtime_path,
+        format!(
+            "#!/bin/sh\nfor arg in \"$@\"; do echo \"$arg\"; done > '{}'\n",
+            args_file.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&runtime_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let runtime = OciRuntime::new(runtime_path.to_string_lossy());
+
+    let attempt_id = Uuid::new_v4();
+    let dev_server = DevServerInfo {
+        attempt_id,
+        port: 41234,
+        url: None,
+        preview_path: preview_path(attempt_id),
+    };
+    let execution_id = Uuid::new_v4();
+    let check = HealthCheck::Command("curl -sf localhost:$PORT".to_string());
+    check
+        .probe(
+            &dev_server,
+            td.path(),
+            ProbeTarget::Container {
+                runtime: &runtime,
+                execution_id,
+            },
+        )
+        .await
+        .unwrap();
+
+    let args = std::fs::read_to_string(&args_file).unwrap();
+    let args: Vec<&str> = args.lines().collect();
+    assert_eq!(args[..3], ["exec", "-w", &*td.path().to_string_lossy()]);
+    assert!(args.contains(&"PORT=41234"));
+    assert_eq!(
+        args[args.len() - 4..],
+        [
+            &*OciRuntime::container_name(execution_id),
+            "sh",
+            "-c",
+            "curl -sf localhost:$PORT"
+        ]
+    );
+}
@@ -4,9 +4,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use db::models::merge::MergeStrategy;
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
 use services::services::{
-    git::GitService,
+    git::{ConflictOp, GitService, GitServiceError, parse_conflict_hunks},
     git_cli::{GitCli, GitCliError},
 };
 use tempfile::TempDir;
@@ -496,6 +497,7 @@ fn merge_does_not_overwrite_main_repo_untracked_files() {
         "feature",
         "main",
         "squash merge",
+        MergeStrategy::Squash,
     );
     assert!(
         res.is_err(),
@@ -539,6 +541,7 @@ fn merge_does_not_touch_tracked_uncommitted_changes_in_base_worktree() {
         "feature",
         "main",
         "squash merge",
+        MergeStrategy::Squash,
     );
     assert!(
         res.is_ok(),
@@ -568,7 +571,14 @@ fn merge_refuses_with_staged_changes_on_base() {
     // main has staged change
     write_file(&repo_path, "staged.txt", "staged\n");
     s.add_path(&repo_path, "staged.txt").unwrap();
-    let res = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "squash");
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "squash",
+        MergeStrategy::Squash,
+    );
     assert!(res.is_err(), "should refuse merge due to staged changes");
     // staged file remains
     let content = std::fs::read_to_string(repo_path.join("staged.txt")).unwrap();
@@ -589,7 +599,14 @@ fn merge_preserves_unstaged_changes_on_base() {
     commit_all(&wt_repo, "feature merged");
 
     let _sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
         .unwrap();
     // local edit preserved
     let loc = std::fs::read_to_string(repo_path.join("common.txt")).unwrap();
@@ -614,7 +631,14 @@ fn update_ref_does_not_destroy_feature_worktree_dirty_state() {
     write_file(&worktree_path, "dirty.txt", "unstaged\n");
     // merge from feature into main (CLI path updates task ref via update-ref)
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
         .unwrap();
     // uncommitted change in feature worktree preserved
     let dirty = std::fs::read_to_string(worktree_path.join("dirty.txt")).unwrap();
@@ -642,7 +666,14 @@ fn libgit2_merge_updates_base_ref_in_both_repos() {
 
     // Perform merge (squash) while main repo is NOT on base branch (libgit2 path)
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
         .expect("merge should succeed via libgit2 path");
 
     // Base branch ref advanced in both main and worktree repositories
@@ -664,7 +695,14 @@ fn libgit2_merge_updates_task_ref_and_feature_head_preserves_dirty() {
 
     // Perform merge (squash) from feature into main; this path uses libgit2
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
         .expect("merge should succeed via libgit2 path");
 
     // Dirty file preserved in worktree
@@ -799,6 +837,7 @@ fn merge_when_base_ahead_and_feature_ahead_fails() {
         "feature",
         "main",
         "squash merge",
+        MergeStrategy::Squash,
     );
 
     assert!(
@@ -831,6 +870,7 @@ fn merge_conflict_does_not_move_base_ref() {
         "feature",
         "main",
         "squash merge",
+        MergeStrategy::Squash,
     );
 
     assert!(res.is_err(), "conflicting merge should fail");
@@ -874,6 +914,7 @@ fn merge_delete_vs_modify_conflict_behaves_safely() {
         "feature",
         "main",
         "squash merge",
+        MergeStrategy::Squash,
     );
 
     // Should now fail due to base branch being ahead, not due to merge conflicts
@@ -940,7 +981,14 @@ fn merge_refreshes_main_worktree_when_on_base() {
 
     // Merge into main (squash) and ensure main worktree is updated since it is on base
     let merge_sha = s
-        .merge_changes(&repo_path, &wt, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &wt,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
         .unwrap();
     // Since main is on base branch and we use safe CLI merge, both working tree
     // and ref should reflect the merged content.
@@ -1109,7 +1157,14 @@ fn merge_binary_conflict_does_not_move_ref() {
     let _ = s.commit(&repo_path, "main bin").unwrap();
 
     let before = s.get_branch_oid(&repo_path, "main").unwrap();
-    let res = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "merge bin");
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "merge bin",
+        MergeStrategy::Squash,
+    );
     assert!(res.is_err(), "binary conflict should fail");
     let after = s.get_branch_oid(&repo_path, "main").unwrap();
     assert_eq!(before, after, "main ref unchanged on conflict");
@@ -1147,6 +1202,7 @@ fn merge_rename_vs_modify_conflict_does_not_move_ref() {
         "feature",
         "main",
         "merge rename",
+        MergeStrategy::Squash,
     );
     match res {
         Err(_) => {
@@ -1201,6 +1257,7 @@ fn merge_leaves_no_staged_changes_on_target_branch() {
             "feature",
             "main",
             "merge feature",
+            MergeStrategy::Squash,
         )
         .expect("merge should succeed");
 
@@ -1272,6 +1329,7 @@ fn worktree_to_worktree_merge_leaves_no_staged_changes() {
         "feature-a",
         "feature-b",
         "merge feature-a into feature-b",
+        MergeStrategy::Squash,
     );
 
     // Verify no staged changes were introduced
@@ -1329,6 +1387,7 @@ fn merge_into_orphaned_branch_uses_libgit2_fallback() {
             "feature",
             "orphaned-feature",
             "merge into orphaned branch",
+            MergeStrategy::Squash,
         )
         .expect("libgit2 merge into orphaned branch should succeed");
 
@@ -1401,6 +1460,7 @@ fn merge_base_ahead_of_task_should_error() {
         "feature",
         "main",
         "attempt merge when base ahead",
+        MergeStrategy::Squash,
     );
 
     // TDD: This test will initially fail because merge currently succeeds
@@ -1410,3 +1470,305 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+fn start_conflicted_rebase(repo_path: &Path, worktree_path: &Path) -> GitService {
+    let svc = GitService::new();
+    let _ = svc
+        .rebase_branch(
+            repo_path,
+            worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            None,
+        )
+        .expect_err("rebase should stop on conflicts");
+    svc
+}
+
+#[test]
+fn conflict_hunks_reported_for_paused_rebase() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let svc = start_conflicted_rebase(&repo_path, &worktree_path);
+
+    assert!(matches!(
+        svc.detect_conflict_op(&worktree_path).unwrap(),
+        Some(ConflictOp::Rebase)
+    ));
+    let hunks = svc.get_conflict_hunks(&worktree_path).unwrap();
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[0].path, "conflict.txt");
+    assert!(hunks[0].ours.contains("new-base version"));
+    assert!(hunks[0].theirs.contains("feature version"));
+}
+
+#[test]
+fn continue_conflicts_refuses_while_markers_remain() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let svc = start_conflicted_rebase(&repo_path, &worktree_path);
+
+    let res = svc.continue_conflicts(&worktree_path);
+    assert!(res.is_err(), "markers should block continuing");
+    assert!(svc.is_rebase_in_progress(&worktree_path).unwrap());
+}
+
+#[test]
+fn continue_conflicts_completes_rebase_after_resolution() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let svc = start_conflicted_rebase(&repo_path, &worktree_path);
+
+    write_file(&worktree_path, "conflict.txt", "resolved version\n");
+    let op = svc.continue_conflicts(&worktree_path).unwrap();
+    assert!(matches!(op, Some(ConflictOp::Rebase)));
+    assert!(!svc.is_rebase_in_progress(&worktree_path).unwrap());
+    assert!(svc.detect_conflict_op(&worktree_path).unwrap().is_none());
+
+    let content = fs::read_to_string(worktree_path.join("conflict.txt")).unwrap();
+    assert_eq!(content, "resolved version\n");
+}
+
+#[test]
+fn parse_conflict_hunks_handles_diff3_markers() {
+    let content =
+        "a\n<<<<<<< HEAD\nours\n||||||| base\norig\n=======\ntheirs\n>>>>>>> feature\nb\n";
+    let hunks = parse_conflict_hunks("file.txt", content);
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks[0].start_line, 2);
+    assert_eq!(hunks[0].end_line, 8);
+    assert_eq!(hunks[0].ours, "ours\n");
+    assert_eq!(hunks[0].base.as_deref(), Some("orig\n"));
+    assert_eq!(hunks[0].theirs, "theirs\n");
+}
+
+#[test]
+fn rebase_fast_forward_merge_preserves_task_commits() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+    let feature_before = s.get_branch_oid(&repo_path, "feature").unwrap();
+
+    // libgit2 path: main is not checked out in the main repo
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "unused",
+            MergeStrategy::RebaseFastForward,
+        )
+        .expect("fast-forward should succeed");
+
+    assert_eq!(sha, feature_before);
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "main").unwrap(),
+        feature_before
+    );
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "feature").unwrap(),
+        feature_before
+    );
+}
+
+#[test]
+fn rebase_fast_forward_merge_via_cli_when_base_checked_out() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+    s.checkout_branch(&repo_path, "main").unwrap();
+    let feature_before = s.get_branch_oid(&repo_path, "feature").unwrap();
+
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "unused",
+            MergeStrategy::RebaseFastForward,
+        )
+        .expect("fast-forward should succeed");
+
+    assert_eq!(sha, feature_before);
+    let feat = std::fs::read_to_string(repo_path.join("feat.txt")).unwrap();
+    assert_eq!(feat, "feat change\n");
+}
+
+#[test]
+fn rebase_fast_forward_merge_rebases_onto_moved_base() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let repo = Repository::open(&repo_path).unwrap();
+    checkout_branch(&repo, "main");
+    write_file(&repo_path, "base_ahead.txt", "base ahead\n");
+    commit_all(&repo, "base ahead commit");
+    let s = GitService::new();
+    let main_before = s.get_branch_oid(&repo_path, "main").unwrap();
+
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "unused",
+            MergeStrategy::RebaseFastForward,
+        )
+        .expect("rebase then fast-forward should succeed");
+
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), sha);
+    assert_eq!(s.get_branch_oid(&repo_path, "feature").unwrap(), sha);
+    assert!(
+        repo.graph_descendant_of(
+            git2::Oid::from_str(&sha).unwrap(),
+            git2::Oid::from_str(&main_before).unwrap()
+        )
+        .unwrap()
+    );
+    // The task worktree now carries the base commit it was rebased onto
+    assert!(worktree_path.join("base_ahead.txt").exists());
+    let feat = std::fs::read_to_string(repo_path.join("feat.txt")).unwrap();
+    assert_eq!(feat, "feat change\n");
+}
+
+#[test]
+fn rebase_fast_forward_merge_aborts_conflicting_rebase() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_direct_conflict_repo(&td);
+    let s = GitService::new();
+    let main_before = s.get_branch_oid(&repo_path, "main").unwrap();
+    let feature_before = s.get_branch_oid(&repo_path, "feature").unwrap();
+
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "unused",
+        MergeStrategy::RebaseFastForward,
+    );
+
+    assert!(matches!(res, Err(GitServiceError::MergeConflicts(_))));
+    assert!(!s.is_rebase_in_progress(&worktree_path).unwrap());
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), main_before);
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "feature").unwrap(),
+        feature_before
+    );
+}
+
+#[test]
+fn merge_commit_strategy_records_both_parents() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+    let main_before = s.get_branch_oid(&repo_path, "main").unwrap();
+    let feature_before = s.get_branch_oid(&repo_path, "feature").unwrap();
+
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "Merge feature",
+            MergeStrategy::MergeCommit,
+        )
+        .expect("merge commit should succeed via libgit2 path");
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&sha).unwrap())
+        .unwrap();
+    let parents: Vec<String> = commit.parent_ids().map(|id| id.to_string()).collect();
+    assert_eq!(parents, vec![main_before, feature_before]);
+    assert_eq!(commit.message(), Some("Merge feature"));
+    // Task branch follows the merge so later merges are not blocked
+    assert_eq!(s.get_branch_oid(&repo_path, "feature").unwrap(), sha);
+}
+
+#[test]
+fn merge_commit_strategy_via_cli_when_base_checked_out() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+    s.checkout_branch(&repo_path, "main").unwrap();
+
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "Merge feature",
+            MergeStrategy::MergeCommit,
+        )
+        .expect("merge commit should succeed via CLI path");
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&sha).unwrap())
+        .unwrap();
+    assert_eq!(commit.parent_count(), 2);
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), sha);
+    let feat = std::fs::read_to_string(repo_path.join("feat.txt")).unwrap();
+    assert_eq!(feat, "feat change\n");
+}
+
+#[test]
+fn restack_child_onto_rebased_parent_replays_only_child_commits() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo");
+    let parent_wt = td.path().join("wt-parent");
+    let child_wt = td.path().join("wt-child");
+    let s = GitService::new();
+    s.initialize_repo_with_main_branch(&repo_path).unwrap();
+    let repo = Repository::open(&repo_path).unwrap();
+    configure_user(&repo);
+    checkout_branch(&repo, "main");
+    write_file(&repo_path, "base.txt", "base\n");
+    commit_all(&repo, "base");
+
+    // parent attempt branches from main, child attempt is stacked on parent
+    create_branch_from_head(&repo, "parent");
+    s.add_worktree(&repo_path, &parent_wt, "parent", false)
+        .unwrap();
+    write_file(&parent_wt, "parent.txt", "parent\n");
+    commit_all(&Repository::open(&parent_wt).unwrap(), "parent work");
+
+    let parent_repo = Repository::open(&parent_wt).unwrap();
+    create_branch_from_head(&parent_repo, "child");
+    s.add_worktree(&repo_path, &child_wt, "child", false)
+        .unwrap();
+    write_file(&child_wt, "child.txt", "child\n");
+    commit_all(&Repository::open(&child_wt).unwrap(), "child work");
+
+    // main moves on and the parent is rebased onto it
+    write_file(&repo_path, "main.txt", "main\n");
+    commit_all(&repo, "main work");
+    let old_parent_head = s.get_branch_oid(&repo_path, "parent").unwrap();
+    s.rebase_branch(&repo_path, &parent_wt, "main", "main", "parent", None)
+        .unwrap();
+    let new_parent_head = s.get_branch_oid(&repo_path, "parent").unwrap();
+    assert_ne!(old_parent_head, new_parent_head);
+
+    // restack: replay the child's own commits onto the rewritten parent
+    s.rebase_branch(
+        &repo_path,
+        &child_wt,
+        "parent",
+        &old_parent_head,
+        "child",
+        None,
+    )
+    .unwrap();
+
+    let (ahead, behind) = s.get_branch_status(&repo_path, "child", "parent").unwrap();
+    assert_eq!((ahead, behind), (1, 0));
+    assert!(child_wt.join("main.txt").exists());
+    assert!(child_wt.join("parent.txt").exists());
+    assert!(child_wt.join("child.txt").exists());
+}
@@ -4,7 +4,13 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use db::models::{merge::MergeStrategy, project::CommitMessageValidation};
 use services::services::{
+    commit_identity::{CommitIdentity, CommitSigningKey},
+    commit_message::{
+        extract_commit_message, is_conventional_commit, pull_request_commit_message,
+        validate_commit_message, validate_commit_message_settings,
+    },
     git::{DiffTarget, GitService},
     github_service::{GitHubRepoInfo, GitHubServiceError},
 };
@@ -630,7 +636,14 @@ fn squash_merge_libgit2_sets_author_without_user() {
 
     // Merge feature -> main (libgit2 squash)
     let merge_sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
         .unwrap();
 
     // The squash commit author should not be the feature commit's author, and must be present.
@@ -644,3 +657,220 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+#[test]
+fn commit_messages_on_branch_are_listed_oldest_first_and_validated() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    write_file(&repo_path, "a.txt", "a\n");
+    let _ = s
+        .commit(&repo_path, "feat(api): add a\n\nLonger body")
+        .unwrap();
+    write_file(&repo_path, "b.txt", "b\n");
+    let _ = s.commit(&repo_path, "added b").unwrap();
+
+    let messages = s
+        .get_commit_messages(&repo_path, "main", "feature")
+        .unwrap();
+    assert_eq!(messages, vec!["feat(api): add a\n\nLonger body", "added b"]);
+
+    let validation = CommitMessageValidation::ConventionalCommit;
+    assert!(validate_commit_message(validation, None, &messages[0]).is_ok());
+    assert!(validate_commit_message(validation, None, &messages[1]).is_err());
+
+    let validation = CommitMessageValidation::Regex;
+    assert!(validate_commit_message(validation, Some("^added "), &messages[1]).is_ok());
+    assert!(validate_commit_message(validation, Some("^added "), &messages[0]).is_err());
+}
+
+#[test]
+fn regex_validation_requires_a_pattern() {
+    let validation = CommitMessageValidation::Regex;
+    assert!(validate_commit_message_settings(validation, None).is_err());
+    assert!(validate_commit_message_settings(validation, Some("  ")).is_err());
+    assert!(validate_commit_message_settings(validation, Some("(")).is_err());
+    assert!(validate_commit_message_settings(validation, Some("^feat")).is_ok());
+    assert!(validate_commit_message_settings(CommitMessageValidation::None, None).is_ok());
+    assert!(validate_commit_message(validation, Some(""), "anything").is_err());
+}
+
+#[test]
+fn pull_request_title_and_body_are_validated_as_one_commit() {
+    let validation = CommitMessageValidation::ConventionalCommit;
+    let message = pull_request_commit_message("feat: add export", Some("Adds CSV export.\n"));
+    assert_eq!(message, "feat: add export\n\nAdds CSV export.");
+    assert!(validate_commit_message(validation, None, &message).is_ok());
+
+    let message = pull_request_commit_message("Add export", None);
+    assert!(validate_commit_message(validation, None, &message).is_err());
+}
+
+#[test]
+fn agent_commit_message_is_extracted_from_last_marker() {
+    let summary = "I refactored the parser.\n\nCOMMIT_MESSAGE: <type>: <description>\n\n\
+                   **COMMIT_MESSAGE:** ignored\n`COMMIT_MESSAGE: refactor(parser): split lexer`";
+    assert_eq!(
+        extract_commit_message(summary).as_deref(),
+        Some("refactor(parser): split lexer")
+    );
+    assert_eq!(extract_commit_message("No marker here"), None);
+
+    assert!(is_conventional_commit("fix!: drop legacy flag"));
+    assert!(is_conventional_commit("docs(readme): typo\n\nbody"));
+    assert!(!is_conventional_commit("docs(readme): typo\nbody"));
+    assert!(!is_conventional_commit("Fix the thing"));
+}
+
+fn project_identity() -> CommitIdentity {
+    CommitIdentity {
+        name: Some("Project Agent".to_string()),
+        email: Some("agent@example.com".to_string()),
+        co_authors: vec!["Jane Doe <jane@example.com>".to_string()],
+        signing_key: None,
+    }
+}
+
+fn ssh_signing_key(dir: &Path) -> Option<PathBuf> {
+    let key = dir.join("signing_key");
+    let status = std::process::Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(&key)
+        .status()
+        .ok()?;
+    status.success().then_some(key)
+}
+
+#[test]
+fn commit_uses_project_identity_and_co_author_trailers() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new().with_commit_identity(project_identity());
+
+    write_file(&repo_path, "a.txt", "a\n");
+    assert!(s.commit(&repo_path, "feat: add a").unwrap());
+
+    let head = s.get_head_info(&repo_path).unwrap().oid;
+    let (name, email) = s.get_commit_author(&repo_path, &head).unwrap();
+    assert_eq!(name.as_deref(), Some("Project Agent"));
+    assert_eq!(email.as_deref(), Some("agent@example.com"));
+
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&head).unwrap())
+        .unwrap();
+    assert_eq!(
+        commit.message().unwrap().trim_end(),
+        "feat: add a\n\nCo-authored-by: Jane Doe <jane@example.com>"
+    );
+
+    // The repository's own identity is left untouched
+    let cfg = repo.config().unwrap();
+    assert_eq!(cfg.get_string("user.name").unwrap(), "Test User");
+}
+
+#[test]
+fn squash_merge_libgit2_uses_project_identity() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let worktree_path = td.path().join("wt_feature");
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    write_file(&worktree_path, "f.txt", "feat\n");
+    let _ = s.commit(&worktree_path, "feat").unwrap();
+
+    // Keep main unchecked so merge_changes takes the libgit2 path
+    s.create_branch(&repo_path, "dev").unwrap();
+    s.checkout_branch(&repo_path, "dev").unwrap();
+
+    let merge_sha = s
+        .with_commit_identity(project_identity())
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "feat: squash",
+            MergeStrategy::Squash,
+        )
+        .unwrap();
+
+    let (name, email) = s.get_commit_author(&repo_path, &merge_sha).unwrap();
+    assert_eq!(name.as_deref(), Some("Project Agent"));
+    assert_eq!(email.as_deref(), Some("agent@example.com"));
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&merge_sha).unwrap())
+        .unwrap();
+    assert!(
+        commit
+            .message()
+            .unwrap()
+            .ends_with("Co-authored-by: Jane Doe <jane@example.com>")
+    );
+}
+
+#[test]
+fn ssh_signing_applies_to_cli_and_libgit2_commits() {
+    let td = TempDir::new().unwrap();
+    let Some(key) = ssh_signing_key(td.path()) else {
+        // ssh-keygen not available in this environment
+        return;
+    };
+    let repo_path = init_repo_main(&td);
+    let worktree_path = td.path().join("wt_feature");
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    s.create_branch(&repo_path, "dev").unwrap();
+    s.checkout_branch(&repo_path, "dev").unwrap();
+
+    let signed = s.with_commit_identity(CommitIdentity {
+        signing_key: Some(CommitSigningKey::Ssh(key.to_string_lossy().to_string())),
+        ..project_identity()
+    });
+
+    // CLI commit in the task worktree
+    write_file(&worktree_path, "f.txt", "feat\n");
+    assert!(signed.commit(&worktree_path, "feat").unwrap());
+    let feature_head = s.get_head_info(&worktree_path).unwrap().oid;
+
+    // libgit2 squash merge into the unchecked-out base branch
+    let merge_sha = signed
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeStrategy::Squash,
+        )
+        .unwrap();
+
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    for sha in [feature_head, merge_sha] {
+        let (signature, _) = repo
+            .extract_signature(&git2::Oid::from_str(&sha).unwrap(), None)
+            .unwrap();
+        assert!(
+            std::str::from_utf8(&signature)
+                .unwrap()
+                .contains("BEGIN SSH SIGNATURE")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode, Uri},
+    routing::post,
+};
+use services::services::{
+    config::{
+        NotificationChannelConfig, NotificationChannelKind, NotificationConfig, NotificationEvent,
+        SmtpSecurity,
+    },
+    notification_channels::{Notification, NotificationChannelError, build_channel},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+type Received = (Uri, HeaderMap, serde_json::Value);
+
+fn notification() -> Notification {
+    Notification {
+        event: NotificationEvent::AttemptFailed,
+        title: "Task Complete: Fix <login>".to_string(),
+        message: "❌ 'Fix <login>' execution failed".to_string(),
+        url: Some("http://127.0.0.1:3000/projects/p/tasks/t/attempts/a".to_string()),
+    }
+}
+
+/// Local receiver that records each JSON request and answers with `status`
+async fn spawn_receiver(status: StatusCode) -> (String, mpsc::UnboundedReceiver<Received>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let handler = move |State(tx): State<mpsc::UnboundedSender<Received>>,
+                        uri: Uri,
+                        headers: HeaderMap,
+                        body: Bytes| async move {
+        tx.send((uri, headers, serde_json::from_slice(&body).unwrap()))
+            .unwrap();
+        status
+    };
+    let app = Router::new()
+        .route("/", post(handler))
+        .route("/{*path}", post(handler))
+        .with_state(tx);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("http://{addr}"), rx)
+}
+
+#[test]
+fn channels_filter_by_event() {
+    let mut config = NotificationChannelConfig {
+        name: "team".to_string(),
+        enabled: true,
+        events: vec![NotificationEvent::PrMerged],
+        channel: NotificationChannelKind::Slack {
+            webhook_url: "http://localhost/hook".to_string(),
+        },
+    };
+    assert!(config.accepts(NotificationEvent::PrMerged));
+    assert!(!config.accepts(NotificationEvent::AttemptFailed));
+
+    config.events.clear();
+    assert!(config.accepts(NotificationEvent::AttemptFailed));
+
+    config.enabled = false;
+    assert!(!config.accepts(NotificationEvent::AttemptFailed));
+}
+
+#[test]
+fn configs_without_channels_still_load() {
+    let config: NotificationConfig = serde_json::from_str(
+        r#"{"sound_enabled": true, "push_enabled": false, "sound_file": "COW_MOOING"}"#,
+    )
+    .unwrap();
+    assert!(config.channels.is_empty());
+    assert_eq!(config.base_url, None);
+
+    let channel: NotificationChannelConfig = serde_json::from_str(
+        r#"{"name": "me", "enabled": true, "channel": {"type": "ntfy", "server_url": "https://ntfy.sh", "topic": "vk", "token": null}}"#,
+    )
+    .unwrap();
+    assert!(channel.events.is_empty());
+}
+
+#[tokio::test]
+async fn chat_webhooks_receive_the_message() {
+    let (url, mut received) = spawn_receiver(StatusCode::OK).await;
+    let client = reqwest::Client::new();
+
+    let slack = build_channel(
+        &NotificationChannelKind::Slack {
+            webhook_url: format!("{url}/slack"),
+        },
+        &client,
+    );
+    slack.send(&notification()).await.unwrap();
+    let (uri, _, body) = received.recv().await.unwrap();
+    assert_eq!(uri.path(), "/slack");
+    let text = body["text"].as_str().unwrap();
+    assert!(text.starts_with("*Task Complete: Fix &lt;login&gt;*\n"));
+    assert!(
+        text.ends_with("<http://127.0.0.1:3000/projects/p/tasks/t/attempts/a|Open in Vibe Kanban>")
+    );
+
+    let discord = build_channel(
+        &NotificationChannelKind::Discord {
+            webhook_url: format!("{url}/discord"),
+        },
+        &client,
+    );
+    discord.send(&notification()).await.unwrap();
+    let (_, _, body) = received.recv().await.unwrap();
+    let content = body["content"].as_str().unwrap();
+    assert!(content.starts_with("**Task Complete: Fix <login>**\n"));
+    assert!(content.contains("/attempts/a"));
+}
+
+#[tokio::test]
+async fn push_services_receive_title_message_and_link() {
+    let (url, mut received) = spawn_receiver(StatusCode::OK).await;
+    let client = reqwest::Client::new();
+
+    let ntfy = build_channel(
+        &NotificationChannelKind::Ntfy {
+            server_url: format!("{url}/"),
+            topic: "vibe".to_string(),
+            token: Some("tk_secret".to_string()),
+        },
+        &client,
+    );
+    ntfy.send(&notification()).await.unwrap();
+    let (_, headers, body) = received.recv().await.unwrap();
+    assert_eq!(headers["authorization"], "Bearer tk_secret");
+    assert_eq!(body["topic"], "vibe");
+    assert_eq!(body["title"], "Task Complete: Fix <login>");
+    assert_eq!(body["click"], notification().url.unwrap());
+
+    let gotify = build_channel(
+        &NotificationChannelKind::Gotify {
+            server_url: url.clone(),
+            token: "app-token".to_string(),
+        },
+        &client,
+    );
+    gotify.send(&notification()).await.unwrap();
+    let (uri, headers, body) = received.recv().await.unwrap();
+    assert_eq!(uri.path(), "/message");
+    assert_eq!(headers["x-gotify-key"], "app-token");
+    assert_eq!(body["message"], notification().message);
+    assert_eq!(
+        body["extras"]["client::notification"]["click"]["url"],
+        notification().url.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn rejected_requests_are_errors() {
+    let (url, _received) = spawn_receiver(StatusCode::FORBIDDEN).await;
+    let channel = build_channel(
+        &NotificationChannelKind::Discord { webhook_url: url },
+        &reqwest::Client::new(),
+    );
+    match channel.send(&notification()).await {
+        Err(NotificationChannelError::Status { service, status }) => {
+            assert_eq!(service, "Discord");
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+        other => panic!("expected a status error, got {other:?}"),
+    }
+}
+
+/// Plain SMTP server that accepts one message and returns the client's lines
+async fn spawn_smtp_server(reject_recipients: bool) -> (u16, tokio::task::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut lines = Vec::new();
+        let mut in_data = false;
+
+        writer.write_all(b"220 test ESMTP\r\n").await.unwrap();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap() == 0 {
+                break;
+            }
+            let line = line.trim_end_matches("\r\n").to_string();
+            lines.push(line.clone());
+
+            let reply: &[u8] = if in_data {
+                if line != "." {
+                    continue;
+                }
+                in_data = false;
+                b"250 queued\r\n"
+            } else if line.starts_with("EHLO") {
+                b"250-test\r\n250 AUTH PLAIN LOGIN\r\n"
+            } else if line.starts_with("AUTH") {
+                b"235 authenticated\r\n"
+            } else if line.starts_with("RCPT") && reject_recipients {
+                b"550 no such user\r\n"
+            } else if line == "DATA" {
+                in_data = true;
+                b"354 go ahead\r\n"
+            } else if line == "QUIT" {
+                writer.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 ok\r\n"
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+        lines
+    });
+    (port, handle)
+}
+
+fn email_channel(port: u16) -> NotificationChannelKind {
+    NotificationChannelKind::Email {
+        smtp_host: "127.0.0.1".to_string(),
+        smtp_port: port,
+        security: SmtpSecurity::None,
+        username: Some("user".to_string()),
+        password: Some("pass".to_string()),
+        from: "Vibe Kanban <vk@example.com>".to_string(),
+        to: vec!["dev@example.com".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn email_is_sent_over_smtp() {
+    let (port, server) = spawn_smtp_server(false).await;
+    let mut notification = notification();
+    notification.message = "Plain ASCII\n.hidden line".to_string();
+
+    build_channel(&email_channel(port), &reqwest::Client::new())
+        .send(&notification)
+        .await
+        .unwrap();
+    let lines = server.await.unwrap();
+
+    assert!(lines[0].starts_with("EHLO "));
+    // base64 of "\0user\0pass"
+    assert_eq!(lines[1], "AUTH PLAIN AHVzZXIAcGFzcw==");
+    assert_eq!(lines[2], "MAIL FROM:<vk@example.com>");
+    assert_eq!(lines[3], "RCPT TO:<dev@example.com>");
+    assert_eq!(lines[4], "DATA");
+    assert!(lines.contains(&"Subject: Task Complete: Fix <login>".to_string()));
+    assert!(lines.contains(&"To: dev@example.com".to_string()));
+    assert!(lines.contains(&"Plain ASCII".to_string()));
+    // Leading dots are doubled so the body does not end the message early
+    assert!(lines.contains(&"..hidden line".to_string()));
+    assert_eq!(lines[lines.len() - 2], ".");
+    assert_eq!(lines[lines.len() - 1], "QUIT");
+}
+
+#[tokio::test]
+async fn rejected_recipients_are_smtp_errors() {
+    let (port, _server) = spawn_smtp_server(true).await;
+    let result = build_channel(&email_channel(port), &reqwest::Client::new())
+        .send(&notification())
+        .await;
+    match result {
+        Err(NotificationChannelError::Smtp(error)) => {
+            assert!(error.is_permanent());
+            assert!(error.to_string().contains("no such user"));
+        }
+        other => panic!("expected an SMTP error, got {other:?}"),
+    }
+}
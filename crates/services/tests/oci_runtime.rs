@@ -0,0 +1,195 @@
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::PermissionsExt, path::Path, process::Command};
+
+use services::services::oci_runtime::{
+    ContainerMount, ExecutionContainer, OciRuntime, validate_execution_container,
+};
+use tempfile::TempDir;
+use utils::shell::{get_execution_shell_command, with_execution_shell};
+use uuid::Uuid;
+
+/// Stand-in for docker/podman that prints the arguments it was started with, one per line
+fn fake_runtime(dir: &Path) -> String {
+    let path = dir.join("fake-docker");
+    fs::write(
+        &path,
+        "#!/bin/sh\nfor arg in \"$@\"; do echo \"$arg\"; done\n",
+    )
+    .unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn execution_shell_runs_command_in_container_with_mounts_and_limits() {
+    let td = TempDir::new().unwrap();
+    let worktree = td.path().join("it's a worktree");
+    let git_dir = td.path().join("repo/.git");
+    let credentials = td.path().join("credentials");
+    let agent_config = td.path().join("agent");
+    fs::create_dir_all(&worktree).unwrap();
+    fs::create_dir_all(&git_dir).unwrap();
+    fs::create_dir_all(&credentials).unwrap();
+    fs::create_dir_all(&agent_config).unwrap();
+
+    let runtime = OciRuntime::new(fake_runtime(td.path())).with_mounts(vec![
+        ContainerMount {
+            path: credentials.clone(),
+            read_only: true,
+        },
+        ContainerMount {
+            path: agent_config.clone(),
+            read_only: false,
+        },
+        ContainerMount {
+            path: td.path().join("missing"),
+            read_only: true,
+        },
+    ]);
+    let container = ExecutionContainer {
+        image: "node:22".to_string(),
+        cpus: Some("1.5".to_string()),
+        memory: Some("2g".to_string()),
+    };
+    let execution_id = Uuid::new_v4();
+    let shell = runtime
+        .write_execution_shell(
+            &container,
+            execution_id,
+            &[worktree.as_path(), git_dir.as_path()],
+            &[41234],
+            &td.path().join("wrappers"),
+        )
+        .unwrap();
+
+    // Executors see the wrapper as their shell only inside the override
+    let (shell_cmd, shell_arg) =
+        with_execution_shell(shell.clone(), async { get_execution_shell_command() }).await;
+    assert_eq!(shell_cmd, shell.to_string_lossy());
+    assert_eq!(shell_arg, "-c");
+    assert_ne!(get_execution_shell_command().0, shell.to_string_lossy());
+
+    let out = Command::new(&shell)
+        .arg(shell_arg)
+        .arg("echo 'hello world'")
+        .current_dir(&worktree)
+        .env("VK_EXECUTOR_SET", "1")
+        .env("CARGO_MANIFEST_DIR", "overridden by the executor")
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let args: Vec<String> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+
+    let value_after = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    assert_eq!(args[0], "run");
+    assert!(args.contains(&"--rm".to_string()));
+    assert!(args.contains(&"-i".to_string()));
+    assert_eq!(
+        value_after("--name"),
+        Some(OciRuntime::container_name(execution_id))
+    );
+    assert_eq!(value_after("--cpus").as_deref(), Some("1.5"));
+    assert_eq!(value_after("--memory").as_deref(), Some("2g"));
+    let worktree = worktree.canonicalize().unwrap();
+    assert_eq!(
+        value_after("-w").map(|w| Path::new(&w).canonicalize().unwrap()),
+        Some(worktree.clone())
+    );
+    let mounts: Vec<&String> = args
+        .iter()
+        .zip(args.iter().skip(1))
+        .filter(|(flag, _)| *flag == "-v")
+        .map(|(_, mount)| mount)
+        .collect();
+    assert_eq!(mounts.len(), 4);
+    assert!(mounts[0].ends_with("it's a worktree"));
+    assert!(mounts[1].ends_with("repo/.git"));
+    let credentials = credentials.to_string_lossy();
+    assert_eq!(*mounts[2], format!("{credentials}:{credentials}:ro"));
+    let agent_config = agent_config.to_string_lossy();
+    assert_eq!(*mounts[3], format!("{agent_config}:{agent_config}:rw"));
+    assert!(value_after("--user").is_some_and(|user| user.contains(':')));
+
+    // The host home isn't mounted; the container gets a throwaway one at the same path
+    let home = std::env::var("HOME").unwrap();
+    assert!(
+        !mounts
+            .iter()
+            .any(|mount| mount.starts_with(&format!("{home}:")))
+    );
+    assert!(value_after("--tmpfs").is_some_and(|tmpfs| tmpfs.starts_with(&format!("{home}:"))));
+
+    // Own network, with the dev server port published on loopback only
+    assert!(!args.contains(&"--network".to_string()));
+    assert_eq!(value_after("-p").as_deref(), Some("127.0.0.1:41234:41234"));
+
+    // Variables the executor set or overrode are forwarded by name, the server's own are not
+    let forwarded: Vec<&String> = args
+        .iter()
+        .zip(args.iter().skip(1))
+        .filter(|(flag, _)| *flag == "-e")
+        .map(|(_, name)| name)
+        .collect();
+    assert!(forwarded.contains(&&"VK_EXECUTOR_SET".to_string()));
+    assert!(forwarded.contains(&&"CARGO_MANIFEST_DIR".to_string()));
+    assert!(forwarded.contains(&&format!("HOME={home}")));
+    assert!(!forwarded.contains(&&"HOME".to_string()));
+    assert!(!forwarded.contains(&&"PATH".to_string()));
+    assert!(
+        !forwarded
+            .iter()
+            .any(|name| name.contains('=') && !name.starts_with("HOME="))
+    );
+    assert_eq!(
+        &args[args.len() - 4..],
+        ["node:22", "sh", "-c", "echo 'hello world'"]
+    );
+}
+
+#[test]
+fn container_mounts_are_parsed_read_only_by_default() {
+    let home = Path::new("/home/dev");
+    assert_eq!(
+        ContainerMount::parse("~/.gitconfig", Some(home)),
+        Some(ContainerMount {
+            path: home.join(".gitconfig"),
+            read_only: true,
+        })
+    );
+    assert_eq!(
+        ContainerMount::parse(" ~/.claude:rw ", Some(home)),
+        Some(ContainerMount {
+            path: home.join(".claude"),
+            read_only: false,
+        })
+    );
+    assert_eq!(
+        ContainerMount::parse("/opt/cache:ro", None),
+        Some(ContainerMount {
+            path: "/opt/cache".into(),
+            read_only: true,
+        })
+    );
+    assert_eq!(ContainerMount::parse("~/.ssh", None), None);
+    assert_eq!(ContainerMount::parse("relative/path", Some(home)), None);
+}
+
+#[test]
+fn execution_container_settings_are_validated() {
+    assert!(validate_execution_container(Some("node:22"), Some("2"), Some("4g")).is_ok());
+    assert!(validate_execution_container(None, None, None).is_ok());
+    assert!(validate_execution_container(Some("node 22"), None, None).is_err());
+    assert!(validate_execution_container(Some("node:22"), Some("0"), None).is_err());
+    assert!(validate_execution_container(Some("node:22"), Some("two"), None).is_err());
+    assert!(validate_execution_container(Some("node:22"), None, Some("4 gigs")).is_err());
+    assert!(validate_execution_container(Some("node:22"), None, Some("512M")).is_ok());
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use services::services::{
+    git::GitService,
+    git_cli::GitCli,
+    project_scope::{ProjectScope, normalize_working_dir, validate_project_scope},
+    worktree_manager::WorktreeManager,
+};
+use tempfile::TempDir;
+
+use crate::common::write_file;
+
+#[test]
+fn scope_covers_working_dir_and_sparse_patterns() {
+    let scope = ProjectScope::new(
+        Some("./packages/web/"),
+        Some("# shared code\n/packages/shared/\n!/packages/shared/legacy/\n/package.json\n"),
+    );
+    assert_eq!(scope.working_dir(), Some("packages/web"));
+    assert!(scope.contains("packages/web/src/app.tsx"));
+    assert!(scope.contains("packages/shared/src/util.ts"));
+    assert!(scope.contains("package.json"));
+    assert!(!scope.contains("packages/shared/legacy/old.ts"));
+    assert!(!scope.contains("packages/web-old/index.ts"));
+    assert!(!scope.contains("services/api/main.go"));
+
+    assert_eq!(
+        scope.sparse_checkout_patterns(),
+        vec![
+            "/packages/shared/",
+            "!/packages/shared/legacy/",
+            "/package.json",
+            "/packages/web/",
+        ]
+    );
+    assert_eq!(
+        scope.relative_to_working_dir("packages/web/src/app.tsx"),
+        "src/app.tsx"
+    );
+    assert_eq!(
+        scope.relative_to_working_dir("package.json"),
+        "../../package.json"
+    );
+    assert_eq!(scope.relative_to_working_dir("packages/web"), ".");
+    assert_eq!(
+        scope.relative_to_working_dir("packages/web-old/index.ts"),
+        "../../packages/web-old/index.ts"
+    );
+    assert_eq!(
+        scope.agent_dir(Path::new("/worktrees/task")),
+        Path::new("/worktrees/task/packages/web")
+    );
+
+    // A working directory alone scopes paths without a sparse checkout
+    let working_dir_only = ProjectScope::new(Some("packages/web"), None);
+    assert!(working_dir_only.sparse_checkout_patterns().is_empty());
+    assert!(!working_dir_only.contains("package.json"));
+
+    let unscoped = ProjectScope::default();
+    assert!(!unscoped.is_scoped());
+    assert!(unscoped.contains("anything/at/all.rs"));
+}
+
+#[test]
+fn scope_settings_are_validated() {
+    assert_eq!(normalize_working_dir(" / "), None);
+    assert_eq!(normalize_working_dir("."), None);
+    assert_eq!(normalize_working_dir("/apps/api/"), Some("apps/api".into()));
+
+    assert!(validate_project_scope(Some("apps/api"), Some("/apps/\n/libs/")).is_ok());
+    assert!(validate_project_scope(Some("../elsewhere"), None).is_err());
+    assert!(validate_project_scope(Some("apps/../.."), None).is_err());
+    assert!(validate_project_scope(None, Some("--no-cone")).is_err());
+    assert!(validate_project_scope(None, Some("/apps/[")).is_err());
+}
+
+#[tokio::test]
+async fn sparse_worktrees_only_check_out_matching_paths() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+    git.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&repo_path, "package.json", "{}\n");
+    write_file(&repo_path, "packages/web/index.ts", "web\n");
+    write_file(&repo_path, "packages/api/index.ts", "api\n");
+    git.commit(&repo_path, "monorepo").unwrap();
+
+    let scope = ProjectScope::new(Some("packages/web"), Some("/package.json"));
+    let worktree_path = td.path().join("worktrees").join("vk-task");
+    WorktreeManager::create_worktree(
+        &repo_path,
+        "task",
+        &worktree_path,
+        "main",
+        true,
+        &scope.sparse_checkout_patterns(),
+    )
+    .await
+    .unwrap();
+
+    assert!(worktree_path.join("package.json").exists());
+    assert!(worktree_path.join("packages/web/index.ts").exists());
+    assert!(!worktree_path.join("packages/api").exists());
+    assert!(git.is_worktree_clean(&worktree_path).unwrap());
+    // The main checkout keeps every path
+    assert!(repo_path.join("packages/api/index.ts").exists());
+}
+
+#[tokio::test]
+async fn existing_worktrees_follow_changed_sparse_patterns() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+    git.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&repo_path, "packages/web/index.ts", "web\n");
+    write_file(&repo_path, "packages/api/index.ts", "api\n");
+    git.commit(&repo_path, "monorepo").unwrap();
+
+    let worktree_path = td.path().join("worktrees").join("vk-task");
+    let web_only = ProjectScope::new(None, Some("/packages/web/")).sparse_checkout_patterns();
+    WorktreeManager::create_worktree(&repo_path, "task", &worktree_path, "main", true, &web_only)
+        .await
+        .unwrap();
+    assert!(!worktree_path.join("packages/api").exists());
+
+    let api_only = ProjectScope::new(None, Some("/packages/api/")).sparse_checkout_patterns();
+    WorktreeManager::ensure_worktree_exists(&repo_path, "task", &worktree_path, &api_only, None)
+        .await
+        .unwrap();
+    assert!(worktree_path.join("packages/api/index.ts").exists());
+    assert!(!worktree_path.join("packages/web").exists());
+
+    // Clearing the patterns checks out everything again
+    WorktreeManager::ensure_worktree_exists(&repo_path, "task", &worktree_path, &[], None)
+        .await
+        .unwrap();
+    assert!(worktree_path.join("packages/web/index.ts").exists());
+    assert!(worktree_path.join("packages/api/index.ts").exists());
+    assert!(git.is_worktree_clean(&worktree_path).unwrap());
+}
+
+#[test]
+fn changes_outside_the_scope_are_not_committed() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+    git.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&repo_path, "packages/web/index.ts", "web\n");
+    write_file(&repo_path, "packages/api/index.ts", "api\n");
+    git.commit(&repo_path, "monorepo").unwrap();
+
+    let scope = ProjectScope::new(Some("packages/web"), None);
+    write_file(&repo_path, "packages/web/index.ts", "web 2\n");
+    write_file(&repo_path, "packages/api/index.ts", "api 2\n");
+    write_file(&repo_path, "packages/api/new [1].ts", "new\n");
+    assert!(git.commit_in_scope(&repo_path, "scoped", &scope).unwrap());
+
+    let cli = GitCli::new();
+    let committed = cli
+        .git(&repo_path, ["show", "--name-only", "--format=", "HEAD"])
+        .unwrap();
+    assert_eq!(committed.trim(), "packages/web/index.ts");
+    // The rest stays in the worktree, unstaged
+    assert_eq!(
+        fs::read_to_string(repo_path.join("packages/api/index.ts")).unwrap(),
+        "api 2\n"
+    );
+    assert!(!cli.has_staged_changes(&repo_path).unwrap());
+
+    // Nothing in scope left to commit
+    assert!(!git.commit_in_scope(&repo_path, "scoped", &scope).unwrap());
+}
@@ -0,0 +1,130 @@
+use chrono::Utc;
+use db::models::review_comment::{ReviewComment, ReviewCommentSide};
+use services::services::{
+    git::GitService,
+    review_comments::{Anchor, build_review_prompt, reanchor},
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn comment(
+    file_path: &str,
+    line_start: i64,
+    line_end: i64,
+    code: &str,
+    body: &str,
+) -> ReviewComment {
+    ReviewComment {
+        id: Uuid::new_v4(),
+        task_attempt_id: Uuid::new_v4(),
+        file_path: file_path.to_string(),
+        side: ReviewCommentSide::New,
+        line_start,
+        line_end,
+        commit_sha: None,
+        code: Some(code.to_string()),
+        body: body.to_string(),
+        outdated: false,
+        sent_execution_process_id: None,
+        resolved_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn comments_follow_their_lines() {
+    let code = "let total = sum(items);\nreturn total;";
+    let original = "fn total() {\n    let total = sum(items);\n    return total;\n}\n";
+    // Indentation before the commented code is part of the line
+    assert_eq!(
+        reanchor(
+            original,
+            2,
+            3,
+            Some("    let total = sum(items);\n    return total;")
+        ),
+        Anchor::Unchanged
+    );
+
+    let shifted = "// totals\n\nlet total = sum(items);\nreturn total;\n";
+    assert_eq!(
+        reanchor(shifted, 1, 2, Some(code)),
+        Anchor::Moved {
+            line_start: 3,
+            line_end: 4
+        }
+    );
+
+    // The occurrence closest to the original position wins
+    let repeated = "return total;\nx\nx\nx\nx\nreturn total;\nx\n";
+    assert_eq!(
+        reanchor(repeated, 5, 5, Some("return total;")),
+        Anchor::Moved {
+            line_start: 6,
+            line_end: 6
+        }
+    );
+
+    assert_eq!(
+        reanchor("let total = 0;\n", 1, 2, Some(code)),
+        Anchor::Outdated
+    );
+
+    // Without code, the lines only need to exist
+    assert_eq!(reanchor("a\nb\n", 2, 2, None), Anchor::Unchanged);
+    assert_eq!(reanchor("a\n", 2, 2, None), Anchor::Outdated);
+}
+
+#[test]
+fn review_prompt_lists_every_comment() {
+    let mut removed = comment("src/old.rs", 4, 4, "legacy();", "Why was this removed?");
+    removed.side = ReviewCommentSide::Old;
+    let comments = vec![
+        comment(
+            "src/lib.rs",
+            10,
+            12,
+            "fn a() {\n    b()\n}",
+            "Rename this.\nAnd add a doc comment.",
+        ),
+        removed,
+    ];
+
+    let prompt = build_review_prompt(&comments);
+    assert!(prompt.starts_with("## Review Comments (2)\n"));
+    assert!(prompt.contains("**src/lib.rs** (Lines 10-12)\n```\nfn a() {\n    b()\n}\n```\n"));
+    assert!(prompt.contains("> Rename this.\n> And add a doc comment."));
+    assert!(prompt.contains("**src/old.rs** (Line 4, removed)"));
+    assert!(prompt.contains("> Why was this removed?"));
+}
+
+#[test]
+fn reads_file_content_at_commit() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+    git.checkout_branch(&repo_path, "main").unwrap();
+    std::fs::create_dir_all(repo_path.join("src")).unwrap();
+    std::fs::write(repo_path.join("src/lib.rs"), "pub fn a() {}\n").unwrap();
+    git.commit(&repo_path, "add lib").unwrap();
+
+    let base = git.get_base_commit(&repo_path, "main", "main").unwrap();
+    std::fs::write(repo_path.join("src/lib.rs"), "pub fn b() {}\n").unwrap();
+    git.commit(&repo_path, "change lib").unwrap();
+
+    assert_eq!(
+        git.get_file_content_at_commit(&repo_path, &base, "src/lib.rs")
+            .unwrap()
+            .as_deref(),
+        Some("pub fn a() {}\n")
+    );
+    assert_eq!(
+        git.get_file_content_at_commit(&repo_path, &base, "src/missing.rs")
+            .unwrap(),
+        None
+    );
+}
@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use chrono::Utc;
+use db::models::webhook::{Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookEventType};
+use services::services::webhooks::{
+    DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER, WebhookEvent, WebhookPayload, retry_delay,
+    send_delivery, sign_payload,
+};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn webhook(url: String, secret: Option<&str>, events: Vec<WebhookEventType>) -> Webhook {
+    Webhook {
+        id: Uuid::new_v4(),
+        name: "receiver".to_string(),
+        url,
+        secret: secret.map(str::to_string),
+        has_secret: secret.is_some(),
+        events: sqlx::types::Json(events),
+        project_id: None,
+        enabled: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn ping_delivery(webhook: &Webhook) -> WebhookDelivery {
+    let id = Uuid::new_v4();
+    let event = WebhookEvent::Ping {
+        webhook_id: webhook.id,
+    };
+    let payload = serde_json::to_string(&WebhookPayload {
+        id,
+        timestamp: Utc::now(),
+        project_id: None,
+        event: &event,
+    })
+    .unwrap();
+    WebhookDelivery {
+        id,
+        webhook_id: webhook.id,
+        event_type: event.event_type(),
+        payload,
+        status: WebhookDeliveryStatus::Pending,
+        attempts: 0,
+        response_status: None,
+        error: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+type Received = (HeaderMap, Bytes);
+
+/// Local receiver that records each request and answers with `status`
+async fn spawn_receiver(status: StatusCode) -> (String, mpsc::UnboundedReceiver<Received>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let app = Router::new()
+        .route(
+            "/hook",
+            post(
+                move |State(tx): State<mpsc::UnboundedSender<Received>>,
+                      headers: HeaderMap,
+                      body: Bytes| async move {
+                    tx.send((headers, body)).unwrap();
+                    status
+                },
+            ),
+        )
+        .with_state(tx);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("http://{addr}/hook"), rx)
+}
+
+#[test]
+fn payloads_are_signed_with_hmac_sha256() {
+    // RFC 4231 test cases 1, 2 and 6
+    assert_eq!(
+        sign_payload(&[0x0b; 20], b"Hi There"),
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+    );
+    assert_eq!(
+        sign_payload(b"Jefe", b"what do ya want for nothing?"),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    assert_eq!(
+        sign_payload(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        ),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+}
+
+#[test]
+fn payloads_carry_the_event_type_and_data() {
+    let hook = webhook("http://localhost/hook".to_string(), None, vec![]);
+    let delivery = ping_delivery(&hook);
+    let payload: serde_json::Value = serde_json::from_str(&delivery.payload).unwrap();
+
+    assert_eq!(payload["id"], delivery.id.to_string());
+    assert_eq!(payload["event"], "ping");
+    assert_eq!(payload["data"]["webhook_id"], hook.id.to_string());
+    assert!(payload["project_id"].is_null());
+    assert!(payload["timestamp"].is_string());
+}
+
+#[test]
+fn webhooks_filter_by_project_and_event() {
+    let project_id = Uuid::new_v4();
+    let mut hook = webhook(
+        "http://localhost/hook".to_string(),
+        None,
+        vec![WebhookEventType::PrMerged],
+    );
+    assert!(hook.accepts(project_id, WebhookEventType::PrMerged));
+    assert!(!hook.accepts(project_id, WebhookEventType::TaskCreated));
+
+    hook.events = sqlx::types::Json(vec![]);
+    assert!(hook.accepts(project_id, WebhookEventType::TaskCreated));
+
+    hook.project_id = Some(Uuid::new_v4());
+    assert!(!hook.accepts(project_id, WebhookEventType::TaskCreated));
+
+    hook.project_id = Some(project_id);
+    hook.enabled = false;
+    assert!(!hook.accepts(project_id, WebhookEventType::TaskCreated));
+}
+
+#[test]
+fn retries_back_off() {
+    assert_eq!(retry_delay(1), Duration::from_secs(10));
+    assert_eq!(retry_delay(2), Duration::from_secs(30));
+    assert_eq!(retry_delay(3), Duration::from_secs(90));
+    // Capped for long-failing deliveries
+    assert_eq!(retry_delay(6), retry_delay(100));
+    assert_eq!(retry_delay(0), retry_delay(1));
+}
+
+#[tokio::test]
+async fn deliveries_reach_a_local_receiver() {
+    let (url, mut received) = spawn_receiver(StatusCode::OK).await;
+    let hook = webhook(url, Some("s3cret"), vec![]);
+    let delivery = ping_delivery(&hook);
+    let client = reqwest::Client::new();
+
+    let (status, error) = send_delivery(&client, &hook, &delivery).await;
+    assert_eq!(status, Some(200));
+    assert_eq!(error, None);
+
+    let (headers, body) = received.recv().await.unwrap();
+    assert_eq!(body, delivery.payload.as_bytes());
+    assert_eq!(headers[EVENT_HEADER], "ping");
+    assert_eq!(headers[DELIVERY_HEADER], delivery.id.to_string().as_str());
+    let expected = format!("sha256={}", sign_payload(b"s3cret", &body));
+    assert_eq!(headers[SIGNATURE_HEADER], expected.as_str());
+
+    // Without a secret, nothing is signed
+    let unsigned = webhook(hook.url.clone(), None, vec![]);
+    send_delivery(&client, &unsigned, &ping_delivery(&unsigned)).await;
+    let (headers, _) = received.recv().await.unwrap();
+    assert!(headers.get(SIGNATURE_HEADER).is_none());
+}
+
+#[tokio::test]
+async fn failed_deliveries_report_why() {
+    let (url, _received) = spawn_receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+    let hook = webhook(url, None, vec![]);
+    let client = reqwest::Client::new();
+
+    let (status, error) = send_delivery(&client, &hook, &ping_delivery(&hook)).await;
+    assert_eq!(status, Some(500));
+    assert!(error.unwrap().contains("500"));
+
+    // Nothing listening
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    let unreachable = webhook(format!("http://{addr}/hook"), None, vec![]);
+    let (status, error) = send_delivery(&client, &unreachable, &ping_delivery(&unreachable)).await;
+    assert_eq!(status, None);
+    assert!(error.is_some());
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use services::services::workspace::{
+    AttemptWorkspace, WorkspaceRepo, prefix_diff_paths, primary_repo_dir_name,
+    validate_repository_name,
+};
+use utils::diff::{Diff, DiffChangeKind};
+use uuid::Uuid;
+
+fn repo(root: &Path, name: &str) -> WorkspaceRepo {
+    WorkspaceRepo {
+        attempt_repository: None,
+        repo_id: Uuid::new_v4(),
+        name: name.to_string(),
+        repo_path: PathBuf::from("/repos").join(name),
+        worktree_path: root.join(name),
+        target_branch: "main".to_string(),
+    }
+}
+
+#[test]
+fn workspace_paths_resolve_to_their_repository() {
+    let root = PathBuf::from("/worktrees/1234-task");
+    let workspace = AttemptWorkspace {
+        root: root.clone(),
+        repos: vec![repo(&root, "backend"), repo(&root, "frontend")],
+    };
+    assert!(workspace.is_multi_repo());
+    assert_eq!(workspace.primary().name, "backend");
+    assert_eq!(workspace.extra_repos().len(), 1);
+
+    let (found, path) = workspace.resolve_path("frontend/src/app.tsx");
+    assert_eq!((found.name.as_str(), path), ("frontend", "src/app.tsx"));
+    let (found, path) = workspace.resolve_path("backend/Cargo.toml");
+    assert_eq!((found.name.as_str(), path), ("backend", "Cargo.toml"));
+    // A prefix of a repository name is not that repository
+    let (found, path) = workspace.resolve_path("frontend-old/a.txt");
+    assert_eq!(
+        (found.name.as_str(), path),
+        ("backend", "frontend-old/a.txt")
+    );
+
+    // Single-repository paths are already relative to the worktree
+    let single = AttemptWorkspace {
+        root: root.clone(),
+        repos: vec![WorkspaceRepo {
+            worktree_path: root.clone(),
+            ..repo(&root, "1234-task")
+        }],
+    };
+    let (found, path) = single.resolve_path("frontend/src/app.tsx");
+    assert_eq!(
+        (found.worktree_path.as_path(), path),
+        (root.as_path(), "frontend/src/app.tsx")
+    );
+}
+
+#[test]
+fn repository_names_must_be_usable_directories() {
+    let primary = Path::new("/home/me/My App");
+    assert_eq!(primary_repo_dir_name(primary), "My-App");
+    assert_eq!(primary_repo_dir_name(Path::new("/")), "repo");
+
+    assert!(validate_repository_name("frontend", primary, &["docs"]).is_ok());
+    assert!(validate_repository_name("", primary, &[]).is_err());
+    assert!(validate_repository_name("front end", primary, &[]).is_err());
+    assert!(validate_repository_name("..", primary, &[]).is_err());
+    assert!(validate_repository_name("a/b", primary, &[]).is_err());
+    assert!(validate_repository_name("My-App", primary, &[]).is_err());
+    assert!(validate_repository_name("docs", primary, &["docs"]).is_err());
+}
+
+#[test]
+fn diff_paths_are_prefixed_with_the_repository() {
+    let mut diff = Diff {
+        change: DiffChangeKind::Renamed,
+        old_path: Some("src/old.rs".to_string()),
+        new_path: Some("src/new.rs".to_string()),
+        old_content: None,
+        new_content: None,
+        content_omitted: false,
+        additions: None,
+        deletions: None,
+    };
+    prefix_diff_paths(&mut diff, "backend");
+    assert_eq!(diff.old_path.as_deref(), Some("backend/src/old.rs"));
+    assert_eq!(diff.new_path.as_deref(), Some("backend/src/new.rs"));
+
+    diff.old_path = None;
+    prefix_diff_paths(&mut diff, "api");
+    assert_eq!(diff.old_path, None);
+    assert_eq!(diff.new_path.as_deref(), Some("api/backend/src/new.rs"));
+}
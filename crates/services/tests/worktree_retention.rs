@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::{task::TaskStatus, task_attempt::WorktreeCleanupCandidate};
+use services::services::worktree_retention::{
+    WorktreeRetention, select_worktrees_to_remove, validate_worktree_retention,
+};
+use uuid::Uuid;
+
+fn candidate(project_id: Uuid, hours_idle: i64, now: DateTime<Utc>) -> WorktreeCleanupCandidate {
+    let attempt_id = Uuid::new_v4();
+    WorktreeCleanupCandidate {
+        attempt_id,
+        project_id,
+        container_ref: format!("/tmp/worktrees/{attempt_id}"),
+        git_repo_path: "/tmp/repo".to_string(),
+        task_status: TaskStatus::Done,
+        pinned: false,
+        running: false,
+        last_activity_at: now - Duration::hours(hours_idle),
+        snapshot: false,
+    }
+}
+
+#[test]
+fn expired_worktrees_are_removed_unless_protected() {
+    let now = Utc::now();
+    let project = Uuid::new_v4();
+    let fresh = candidate(project, 1, now);
+    let expired = candidate(project, 100, now);
+    let pinned = WorktreeCleanupCandidate {
+        pinned: true,
+        ..candidate(project, 200, now)
+    };
+    let running = WorktreeCleanupCandidate {
+        running: true,
+        ..candidate(project, 300, now)
+    };
+    let in_review = WorktreeCleanupCandidate {
+        task_status: TaskStatus::InReview,
+        ..candidate(project, 400, now)
+    };
+    let candidates = vec![
+        fresh.clone(),
+        expired.clone(),
+        pinned,
+        running,
+        in_review.clone(),
+    ];
+
+    // Default policy: 72 hours, review status not protected
+    let removed = select_worktrees_to_remove(&candidates, &HashMap::new(), &HashMap::new(), now);
+    assert_eq!(removed.len(), 2);
+    assert!(removed.contains(&expired.attempt_id));
+    assert!(removed.contains(&in_review.attempt_id));
+
+    let policies = HashMap::from([(
+        project,
+        WorktreeRetention {
+            keep_in_review: true,
+            ..WorktreeRetention::default()
+        },
+    )]);
+    let removed = select_worktrees_to_remove(&candidates, &policies, &HashMap::new(), now);
+    assert_eq!(removed, vec![expired.attempt_id]);
+}
+
+#[test]
+fn most_recent_worktrees_are_kept() {
+    let now = Utc::now();
+    let project = Uuid::new_v4();
+    let newest = candidate(project, 100, now);
+    let middle = candidate(project, 200, now);
+    let oldest = candidate(project, 300, now);
+    let policies = HashMap::from([(
+        project,
+        WorktreeRetention {
+            keep_recent: 2,
+            ..WorktreeRetention::default()
+        },
+    )]);
+
+    let removed = select_worktrees_to_remove(
+        &[oldest.clone(), newest, middle],
+        &policies,
+        &HashMap::new(),
+        now,
+    );
+    assert_eq!(removed, vec![oldest.attempt_id]);
+}
+
+#[test]
+fn size_limit_removes_oldest_unprotected_worktrees_first() {
+    let now = Utc::now();
+    let project = Uuid::new_v4();
+    let other_project = Uuid::new_v4();
+    let newest = candidate(project, 1, now);
+    let middle = candidate(project, 2, now);
+    let oldest_pinned = WorktreeCleanupCandidate {
+        pinned: true,
+        ..candidate(project, 3, now)
+    };
+    let unrelated = candidate(other_project, 4, now);
+    let mb = 1024 * 1024;
+    let sizes = HashMap::from([
+        (newest.attempt_id, 40 * mb),
+        (middle.attempt_id, 40 * mb),
+        (oldest_pinned.attempt_id, 40 * mb),
+        (unrelated.attempt_id, 500 * mb),
+    ]);
+    let policies = HashMap::from([(
+        project,
+        WorktreeRetention {
+            max_size_bytes: Some(100 * mb),
+            ..WorktreeRetention::default()
+        },
+    )]);
+
+    // 120 MB over a 100 MB cap: the pinned worktree stays, so the next oldest goes
+    let removed = select_worktrees_to_remove(
+        &[newest.clone(), middle.clone(), oldest_pinned, unrelated],
+        &policies,
+        &sizes,
+        now,
+    );
+    assert_eq!(removed, vec![middle.attempt_id]);
+}
+
+#[test]
+fn retention_settings_are_validated() {
+    assert!(validate_worktree_retention(Some(24), Some(3), Some(2048)).is_ok());
+    assert!(validate_worktree_retention(None, None, None).is_ok());
+    assert!(validate_worktree_retention(Some(0), None, None).is_err());
+    assert!(validate_worktree_retention(None, Some(-1), None).is_err());
+    assert!(validate_worktree_retention(None, None, Some(0)).is_err());
+}
@@ -0,0 +1,214 @@
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use services::services::{
+    git::GitService,
+    worktree_manager::WorktreeManager,
+    worktree_snapshot::{
+        SnapshotLocation, WorktreeSnapshotError, create_snapshot, discard_snapshot, has_snapshot,
+        restore_snapshot,
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use crate::common::write_file;
+
+/// Repository with a committed `.gitignore`
+fn init_repo(root: &TempDir) -> PathBuf {
+    let repo_path = root.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+    git.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&repo_path, ".gitignore", "build/\n");
+    write_file(&repo_path, "tracked.txt", "original\n");
+    write_file(&repo_path, "gone.txt", "delete me\n");
+    git.commit(&repo_path, "initial files").unwrap();
+    repo_path
+}
+
+/// Worktree on branch `branch`, laid out like an attempt workspace: `<workspace>/repo`
+async fn attempt_worktree(
+    root: &TempDir,
+    repo_path: &Path,
+    workspace: &str,
+    branch: &str,
+) -> PathBuf {
+    let worktree_path = root.path().join(workspace).join("repo");
+    WorktreeManager::create_worktree(repo_path, branch, &worktree_path, "main", true, &[])
+        .await
+        .unwrap();
+    worktree_path
+}
+
+/// Remove the worktree like retention cleanup does and recreate it
+async fn recreate(
+    repo_path: &Path,
+    branch: &str,
+    worktree_path: &Path,
+    location: &SnapshotLocation,
+) {
+    WorktreeManager::cleanup_worktree(worktree_path, Some(repo_path))
+        .await
+        .unwrap();
+    assert!(!worktree_path.exists());
+    WorktreeManager::ensure_worktree_exists(repo_path, branch, worktree_path, &[], Some(location))
+        .await
+        .unwrap();
+}
+
+/// Archives set aside in the attempt's snapshot directory
+fn kept_archives(snapshots: &Path, attempt_id: Uuid) -> Vec<PathBuf> {
+    let mut kept: Vec<PathBuf> = fs::read_dir(snapshots.join(attempt_id.to_string()))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(".tar.gz"))
+        .collect();
+    kept.sort();
+    kept
+}
+
+#[tokio::test]
+async fn snapshot_survives_worktree_removal_and_recreation() {
+    let td = TempDir::new().unwrap();
+    let snapshots = td.path().join("snapshots");
+    let repo_path = init_repo(&td);
+    let worktree_path = attempt_worktree(&td, &repo_path, "ws", "task").await;
+    let location = SnapshotLocation::new(&snapshots, Uuid::new_v4(), Uuid::new_v4());
+
+    write_file(&worktree_path, "tracked.txt", "work in progress\n");
+    fs::remove_file(worktree_path.join("gone.txt")).unwrap();
+    write_file(&worktree_path, "notes/new.txt", "untracked\n");
+    write_file(&worktree_path, "build/out.bin", "artefact\n");
+
+    let archive = create_snapshot(&location, &worktree_path).unwrap();
+    assert_eq!(archive, Some(location.archive()));
+    assert!(has_snapshot(&location));
+
+    recreate(&repo_path, "task", &worktree_path, &location).await;
+    let read = |rel: &str| fs::read_to_string(worktree_path.join(rel)).unwrap();
+    assert_eq!(read("tracked.txt"), "work in progress\n");
+    assert_eq!(read("notes/new.txt"), "untracked\n");
+    assert_eq!(read("build/out.bin"), "artefact\n");
+    assert!(!worktree_path.join("gone.txt").exists());
+    // Applied snapshots are removed
+    assert!(!has_snapshot(&location));
+    assert!(!restore_snapshot(&location, &worktree_path).unwrap());
+}
+
+#[tokio::test]
+async fn attempts_of_the_same_project_keep_separate_snapshots() {
+    let td = TempDir::new().unwrap();
+    let snapshots = td.path().join("snapshots");
+    let repo_path = init_repo(&td);
+    // Both worktrees share the directory name of the repository
+    let first_path = attempt_worktree(&td, &repo_path, "ws-first", "first").await;
+    let second_path = attempt_worktree(&td, &repo_path, "ws-second", "second").await;
+    let repo_id = Uuid::new_v4();
+    let first = SnapshotLocation::new(&snapshots, Uuid::new_v4(), repo_id);
+    let second = SnapshotLocation::new(&snapshots, Uuid::new_v4(), repo_id);
+
+    write_file(&first_path, "tracked.txt", "first attempt\n");
+    write_file(&second_path, "tracked.txt", "second attempt\n");
+    create_snapshot(&first, &first_path).unwrap();
+    create_snapshot(&second, &second_path).unwrap();
+    assert_ne!(first.archive(), second.archive());
+
+    recreate(&repo_path, "first", &first_path, &first).await;
+    assert_eq!(
+        fs::read_to_string(first_path.join("tracked.txt")).unwrap(),
+        "first attempt\n"
+    );
+    assert!(has_snapshot(&second));
+
+    discard_snapshot(&second).unwrap();
+    assert!(!has_snapshot(&second));
+    recreate(&repo_path, "second", &second_path, &second).await;
+    assert_eq!(
+        fs::read_to_string(second_path.join("tracked.txt")).unwrap(),
+        "original\n"
+    );
+}
+
+#[tokio::test]
+async fn clean_worktree_needs_no_snapshot() {
+    let td = TempDir::new().unwrap();
+    let snapshots = td.path().join("snapshots");
+    let repo_path = init_repo(&td);
+    let worktree_path = attempt_worktree(&td, &repo_path, "ws", "task").await;
+    let location = SnapshotLocation::new(&snapshots, Uuid::new_v4(), Uuid::new_v4());
+
+    assert!(
+        create_snapshot(&location, &worktree_path)
+            .unwrap()
+            .is_none()
+    );
+    assert!(!has_snapshot(&location));
+}
+
+#[tokio::test]
+async fn snapshot_from_older_commit_is_kept_and_reported() {
+    let td = TempDir::new().unwrap();
+    let snapshots = td.path().join("snapshots");
+    let repo_path = init_repo(&td);
+    let worktree_path = attempt_worktree(&td, &repo_path, "ws", "task").await;
+    let attempt_id = Uuid::new_v4();
+    let location = SnapshotLocation::new(&snapshots, attempt_id, Uuid::new_v4());
+
+    write_file(&worktree_path, "scratch.txt", "stale\n");
+    create_snapshot(&location, &worktree_path).unwrap();
+    write_file(&worktree_path, "tracked.txt", "committed later\n");
+    fs::remove_file(worktree_path.join("scratch.txt")).unwrap();
+    GitService::new()
+        .commit(&worktree_path, "newer work")
+        .unwrap();
+
+    let head = GitService::new().get_head_info(&worktree_path).unwrap().oid;
+    let kept = match restore_snapshot(&location, &worktree_path) {
+        Err(WorktreeSnapshotError::HeadMoved {
+            archive,
+            head: current,
+            snapshot_head,
+        }) => {
+            assert_eq!(current, head);
+            assert_ne!(snapshot_head, head);
+            archive
+        }
+        other => panic!("expected the moved head to be reported, got {other:?}"),
+    };
+    assert!(kept.exists());
+    assert!(!has_snapshot(&location));
+    assert!(!worktree_path.join("scratch.txt").exists());
+
+    // Later snapshots of the same worktree leave the kept one alone
+    write_file(&worktree_path, "scratch.txt", "newer\n");
+    create_snapshot(&location, &worktree_path).unwrap();
+    assert!(has_snapshot(&location));
+    let mut expected = vec![kept, location.archive()];
+    expected.sort();
+    assert_eq!(kept_archives(&snapshots, attempt_id), expected);
+}
+
+#[tokio::test]
+async fn pending_snapshot_is_set_aside_instead_of_overwritten() {
+    let td = TempDir::new().unwrap();
+    let snapshots = td.path().join("snapshots");
+    let repo_path = init_repo(&td);
+    let worktree_path = attempt_worktree(&td, &repo_path, "ws", "task").await;
+    let attempt_id = Uuid::new_v4();
+    let location = SnapshotLocation::new(&snapshots, attempt_id, Uuid::new_v4());
+
+    write_file(&worktree_path, "scratch.txt", "first\n");
+    create_snapshot(&location, &worktree_path).unwrap();
+    write_file(&worktree_path, "scratch.txt", "second\n");
+    create_snapshot(&location, &worktree_path).unwrap();
+
+    assert!(has_snapshot(&location));
+    assert_eq!(kept_archives(&snapshots, attempt_id).len(), 2);
+}
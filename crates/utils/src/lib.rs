@@ -9,6 +9,7 @@ pub mod diff;
 pub mod git;
 pub mod log_msg;
 pub mod msg_store;
+pub mod otel;
 pub mod path;
 pub mod port_file;
 pub mod response;
@@ -1,11 +1,14 @@
 use std::{
     collections::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use axum::response::sse::Event;
 use futures::{StreamExt, TryStreamExt, future};
-use tokio::{sync::broadcast, task::JoinHandle};
+use tokio::{
+    sync::{broadcast, oneshot},
+    task::JoinHandle,
+};
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
@@ -27,6 +30,7 @@ struct Inner {
 pub struct MsgStore {
     inner: RwLock<Inner>,
     sender: broadcast::Sender<LogMsg>,
+    dropped: Mutex<Option<oneshot::Sender<Vec<LogMsg>>>>,
 }
 
 impl Default for MsgStore {
@@ -44,6 +48,7 @@ impl MsgStore {
                 total_bytes: 0,
             }),
             sender,
+            dropped: Mutex::new(None),
         }
     }
 
@@ -97,6 +102,15 @@ impl MsgStore {
             .collect()
     }
 
+    /// The full history, sent once the last reference to the store is dropped, i.e. once
+    /// every task writing to it (such as a log normalizer) has finished. Replaces any earlier
+    /// receiver.
+    pub fn history_when_dropped(&self) -> oneshot::Receiver<Vec<LogMsg>> {
+        let (tx, rx) = oneshot::channel();
+        *self.dropped.lock().unwrap() = Some(tx);
+        rx
+    }
+
     /// History then live, as `LogMsg`.
     pub fn history_plus_stream(
         &self,
@@ -175,3 +189,12 @@ impl MsgStore {
         })
     }
 }
+
+impl Drop for MsgStore {
+    fn drop(&mut self) {
+        if let Some(tx) = self.dropped.get_mut().unwrap().take() {
+            let inner = self.inner.get_mut().unwrap();
+            let _ = tx.send(inner.history.drain(..).map(|s| s.msg).collect());
+        }
+    }
+}
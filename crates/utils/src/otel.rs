@@ -0,0 +1,59 @@
+//! Optional export of tracing spans to an OpenTelemetry collector over OTLP/HTTP.
+//!
+//! Export is off unless `OTEL_EXPORTER_OTLP_ENDPOINT` or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`
+//! is set; the other standard `OTEL_EXPORTER_OTLP_*` variables (headers, timeout) apply as usual.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+use tracing_subscriber::{Layer, registry::LookupSpan};
+
+const SERVICE_NAME: &str = "vibe-kanban";
+
+const ENDPOINT_VARS: [&str; 2] = [
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+];
+
+/// Tracer provider for the collector configured in the environment, if any
+pub fn tracer_provider_from_env() -> Option<SdkTracerProvider> {
+    if !ENDPOINT_VARS
+        .iter()
+        .any(|var| std::env::var(var).is_ok_and(|value| !value.trim().is_empty()))
+    {
+        return None;
+    }
+    match SpanExporter::builder().with_http().build() {
+        Ok(exporter) => Some(build_tracer_provider(exporter)),
+        Err(e) => {
+            eprintln!("Failed to set up OTLP span export: {e}");
+            None
+        }
+    }
+}
+
+/// Tracer provider sending spans to `traces_endpoint`, the full URL of the collector's
+/// traces path (usually ending in `/v1/traces`)
+pub fn tracer_provider(traces_endpoint: &str) -> Result<SdkTracerProvider, ExporterBuildError> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(traces_endpoint)
+        .build()?;
+    Ok(build_tracer_provider(exporter))
+}
+
+fn build_tracer_provider(exporter: SpanExporter) -> SdkTracerProvider {
+    SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+        .build()
+}
+
+/// Layer turning tracing spans into OpenTelemetry spans exported by `provider`
+pub fn otlp_layer<S>(provider: &SdkTracerProvider) -> impl Layer<S> + use<S>
+where
+    S: tracing::Subscriber,
+    S: for<'a> LookupSpan<'a>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME))
+}
@@ -1,5 +1,11 @@
 //! Cross-platform shell command utilities
 
+use std::{future::Future, path::PathBuf};
+
+tokio::task_local! {
+    static EXECUTION_SHELL: PathBuf;
+}
+
 /// Returns the appropriate shell command and argument for the current platform.
 ///
 /// Returns (shell_program, shell_arg) where:
@@ -20,6 +26,27 @@ pub fn get_shell_command() -> (&'static str, &'static str) {
     }
 }
 
+/// Returns the shell used to run executor and script commands.
+///
+/// This is [`get_shell_command`] unless the caller is running inside
+/// [`with_execution_shell`], in which case commands are handed to the override instead
+/// (e.g. a wrapper that runs them inside an OCI container). The override is invoked as
+/// `<shell> -c <command>`.
+pub fn get_execution_shell_command() -> (String, &'static str) {
+    EXECUTION_SHELL
+        .try_with(|shell| (shell.to_string_lossy().to_string(), "-c"))
+        .unwrap_or_else(|_| {
+            let (shell, arg) = get_shell_command();
+            (shell.to_string(), arg)
+        })
+}
+
+/// Run `fut` with every [`get_execution_shell_command`] call inside it resolving to `shell`.
+/// The override does not carry over into tasks spawned from `fut`.
+pub async fn with_execution_shell<F: Future>(shell: PathBuf, fut: F) -> F::Output {
+    EXECUTION_SHELL.scope(shell, fut).await
+}
+
 /// Resolves the full path of an executable using the system's PATH environment variable.
 /// Note: On Windows, resolving the executable path can be necessary before passing
 /// it to `std::process::Command::new`, as the latter has been deficient in finding executables.
@@ -5,6 +5,7 @@ import i18n from '@/i18n';
 import { Projects } from '@/pages/projects';
 import { ProjectTasks } from '@/pages/project-tasks';
 import { FullAttemptLogsPage } from '@/pages/full-attempt-logs';
+import { TranscriptSearch } from '@/pages/transcript-search';
 import { NormalLayout } from '@/components/layout/NormalLayout';
 import { usePostHog } from 'posthog-js/react';
 
@@ -15,6 +16,7 @@ import {
   ProjectSettings,
   RelationshipTypesSettings,
   TaskTemplatesSettings,
+  WebhooksSettings,
   SettingsLayout,
 } from '@/pages/settings/';
 import {
@@ -182,7 +184,9 @@ function AppContent() {
                   <Route path="mcp" element={<McpSettings />} />
                   <Route path="relationship-types" element={<RelationshipTypesSettings />} />
                   <Route path="task-templates" element={<TaskTemplatesSettings />} />
+                  <Route path="webhooks" element={<WebhooksSettings />} />
                 </Route>
+                <Route path="/transcripts" element={<TranscriptSearch />} />
                 <Route
                   path="/mcp-servers"
                   element={<Navigate to="/settings/mcp" replace />}
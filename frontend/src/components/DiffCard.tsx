@@ -50,19 +50,24 @@ function labelAndIcon(diff: Diff) {
   return { label: undefined as string | undefined, Icon: PencilLine };
 }
 
-function readPlainLine(
+function readPlainLines(
   diffFile: DiffFile | null,
-  lineNumber: number,
+  lineStart: number,
+  lineEnd: number,
   side: SplitSide
 ) {
   if (!diffFile) return undefined;
   try {
-    const rawLine =
-      side === SplitSide.old
-        ? diffFile.getOldPlainLine(lineNumber)
-        : diffFile.getNewPlainLine(lineNumber);
-    if (rawLine?.value === undefined) return undefined;
-    return stripLineEnding(rawLine.value);
+    const lines: string[] = [];
+    for (let lineNumber = lineStart; lineNumber <= lineEnd; lineNumber++) {
+      const rawLine =
+        side === SplitSide.old
+          ? diffFile.getOldPlainLine(lineNumber)
+          : diffFile.getNewPlainLine(lineNumber);
+      if (rawLine?.value === undefined) return undefined;
+      lines.push(stripLineEnding(rawLine.value));
+    }
+    return lines.join('\n');
   } catch (error) {
     console.error('Failed to read line content for review comment', error);
     return undefined;
@@ -147,6 +152,12 @@ export default function DiffCard({
     [comments, filePath]
   );
 
+  // Comments whose lines are gone are listed below the diff instead
+  const outdatedComments = useMemo(
+    () => commentsForFile.filter((c) => c.outdated),
+    [commentsForFile]
+  );
+
   // Transform comments to git-diff-view extendData format
   const extendData = useMemo(() => {
     // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -154,6 +165,7 @@ export default function DiffCard({
     const newFileData: Record<string, { data: any }> = {};
 
     commentsForFile.forEach((comment) => {
+      if (comment.outdated) return;
       const lineKey = String(comment.lineNumber);
       if (comment.side === SplitSide.old) {
         oldFileData[lineKey] = { data: comment };
@@ -170,11 +182,12 @@ export default function DiffCard({
 
   const handleAddWidgetClick = (lineNumber: number, side: SplitSide) => {
     const widgetKey = `${filePath}-${side}-${lineNumber}`;
-    const codeLine = readPlainLine(diffFile, lineNumber, side);
+    const codeLine = readPlainLines(diffFile, lineNumber, lineNumber, side);
     const draft: ReviewDraft = {
       filePath,
       side,
       lineNumber,
+      lineStart: lineNumber,
       text: '',
       ...(codeLine !== undefined ? { codeLine } : {}),
     };
@@ -190,6 +203,9 @@ export default function DiffCard({
       <CommentWidgetLine
         draft={draft}
         widgetKey={widgetKey}
+        readCode={(lineStart) =>
+          readPlainLines(diffFile, lineStart, draft.lineNumber, draft.side)
+        }
         onSave={props.onClose}
         onCancel={props.onClose}
         projectId={projectId}
@@ -305,6 +321,23 @@ export default function DiffCard({
           />
         </div>
       )}
+      {expanded && outdatedComments.length > 0 && (
+        <div>
+          <div
+            className="px-4 pt-2 text-xs font-mono"
+            style={{ color: 'hsl(var(--muted-foreground) / 0.9)' }}
+          >
+            Outdated comments
+          </div>
+          {outdatedComments.map((comment) => (
+            <ReviewCommentRenderer
+              key={comment.id}
+              comment={comment}
+              projectId={projectId}
+            />
+          ))}
+        </div>
+      )}
       {expanded && !diffFile && (
         <div
           className="px-4 pb-4 text-xs font-mono"
@@ -7,6 +7,7 @@ import {
   useState,
 } from 'react';
 import type { ReactNode } from 'react';
+import { useSearchParams } from 'react-router-dom';
 import type { ApprovalStatus, ToolStatus } from 'shared/types';
 import { Button } from '@/components/ui/button';
 import {
@@ -190,6 +191,17 @@ const PendingApprovalEntry = ({
   const denyReasonRef = useRef<HTMLTextAreaElement | null>(null);
   const { projectId } = useProject();
 
+  // Notifications link here with ?approval=<id>
+  const [searchParams] = useSearchParams();
+  const isLinked = searchParams.get('approval') === pendingStatus.approval_id;
+  const containerRef = useRef<HTMLDivElement | null>(null);
+
+  useEffect(() => {
+    if (isLinked) {
+      containerRef.current?.scrollIntoView({ block: 'center' });
+    }
+  }, [isLinked]);
+
   const { enableScope, disableScope, activeScopes } = useHotkeysContext();
   const tabNav = useContext(TabNavContext);
   const isLogsTabActive = tabNav ? tabNav.activeTab === 'logs' : true;
@@ -321,8 +333,10 @@ const PendingApprovalEntry = ({
   }, [isEnteringReason]);
 
   return (
-    <div className="relative mt-3">
-      <div className="overflow-hidden border">
+    <div className="relative mt-3" ref={containerRef}>
+      <div
+        className={`overflow-hidden border${isLinked ? ' border-primary' : ''}`}
+      >
         {children}
 
         <div className="border-t bg-background px-2 py-1.5 text-xs sm:text-sm">
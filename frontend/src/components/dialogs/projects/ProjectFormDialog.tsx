@@ -61,6 +61,31 @@ export const ProjectFormDialog = NiceModal.create<ProjectFormDialogProps>(
         dev_script: null,
         cleanup_script: null,
         copy_files: null,
+        merge_strategy: null,
+        merge_commit_template: null,
+        commit_message_strategy: null,
+        commit_message_template: null,
+        commit_message_validation: null,
+        commit_message_pattern: null,
+        commit_author_name: null,
+        commit_author_email: null,
+        commit_co_authors: null,
+        commit_signing_format: null,
+        commit_signing_key: null,
+        container_image: null,
+        container_cpus: null,
+        container_memory: null,
+        worktree_retention_hours: null,
+        worktree_keep_recent: null,
+        worktree_keep_in_review: null,
+        worktree_max_size_mb: null,
+        worktree_snapshot_enabled: null,
+        dependency_cache: null,
+        dev_server_health_check: null,
+        dev_server_restart_policy: null,
+        dev_server_max_restarts: null,
+        working_dir: null,
+        sparse_checkout: null,
       };
 
       createProject.mutate(createData);
@@ -91,6 +116,31 @@ export const ProjectFormDialog = NiceModal.create<ProjectFormDialogProps>(
         dev_script: null,
         cleanup_script: null,
         copy_files: null,
+        merge_strategy: null,
+        merge_commit_template: null,
+        commit_message_strategy: null,
+        commit_message_template: null,
+        commit_message_validation: null,
+        commit_message_pattern: null,
+        commit_author_name: null,
+        commit_author_email: null,
+        commit_co_authors: null,
+        commit_signing_format: null,
+        commit_signing_key: null,
+        container_image: null,
+        container_cpus: null,
+        container_memory: null,
+        worktree_retention_hours: null,
+        worktree_keep_recent: null,
+        worktree_keep_in_review: null,
+        worktree_max_size_mb: null,
+        worktree_snapshot_enabled: null,
+        dependency_cache: null,
+        dev_server_health_check: null,
+        dev_server_restart_policy: null,
+        dev_server_max_restarts: null,
+        working_dir: null,
+        sparse_checkout: null,
       };
 
       createProject.mutate(createData);
@@ -9,6 +9,7 @@ import {
 } from '@/components/ui/dialog';
 import { Loader } from '@/components/ui/loader';
 import GitOperations from '@/components/tasks/Toolbar/GitOperations';
+import { AttemptRepositories } from '@/components/tasks/Toolbar/AttemptRepositories';
 import { useTaskAttempt } from '@/hooks/useTaskAttempt';
 import { useBranchStatus, useAttemptExecution } from '@/hooks';
 import { useProject } from '@/contexts/project-context';
@@ -98,6 +99,12 @@ function GitActionsDialogContent({
         selectedBranch={branchStatus?.target_branch_name ?? null}
         layout="vertical"
       />
+      <AttemptRepositories
+        attempt={attempt}
+        task={task}
+        isAttemptRunning={isAttemptRunning}
+        setError={setGitError}
+      />
     </div>
   );
 }
@@ -8,6 +8,8 @@ import { useHotkeysContext } from 'react-hotkeys-hook';
 interface CommentWidgetLineProps {
   draft: ReviewDraft;
   widgetKey: string;
+  /** Content of the lines from `lineStart` to the draft's line */
+  readCode: (lineStart: number) => string | undefined;
   onSave: () => void;
   onCancel: () => void;
   projectId?: string;
@@ -16,12 +18,14 @@ interface CommentWidgetLineProps {
 export function CommentWidgetLine({
   draft,
   widgetKey,
+  readCode,
   onSave,
   onCancel,
   projectId,
 }: CommentWidgetLineProps) {
   const { setDraft, addComment } = useReview();
   const [value, setValue] = useState(draft.text);
+  const [lineStart, setLineStart] = useState(draft.lineStart);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
   const { enableScope, disableScope } = useHotkeysContext();
 
@@ -43,17 +47,29 @@ export function CommentWidgetLine({
 
   const handleSave = useCallback(() => {
     if (value.trim()) {
+      const codeLine =
+        lineStart === draft.lineStart ? draft.codeLine : readCode(lineStart);
       addComment({
         filePath: draft.filePath,
         side: draft.side,
         lineNumber: draft.lineNumber,
+        lineStart,
         text: value.trim(),
-        codeLine: draft.codeLine,
+        codeLine,
       });
     }
     setDraft(widgetKey, null);
     onSave();
-  }, [value, draft, setDraft, widgetKey, onSave, addComment]);
+  }, [
+    value,
+    draft,
+    lineStart,
+    readCode,
+    setDraft,
+    widgetKey,
+    onSave,
+    addComment,
+  ]);
 
   const handleSubmitShortcut = useCallback(
     (e?: KeyboardEvent) => {
@@ -81,6 +97,23 @@ export function CommentWidgetLine({
 
   return (
     <div className="p-4 border-y bg-primary">
+      <label className="mb-2 flex items-center gap-2 text-xs text-secondary-foreground">
+        From line
+        <input
+          type="number"
+          min={1}
+          max={draft.lineNumber}
+          value={lineStart}
+          onChange={(e) => {
+            const line = Number(e.target.value);
+            if (Number.isInteger(line)) {
+              setLineStart(Math.min(Math.max(line, 1), draft.lineNumber));
+            }
+          }}
+          className="w-16 bg-primary border rounded px-1 font-mono"
+        />
+        to {draft.lineNumber}
+      </label>
       <FileSearchTextarea
         ref={textareaRef}
         value={value}
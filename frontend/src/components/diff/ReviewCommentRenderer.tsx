@@ -1,5 +1,5 @@
 import { useState, useRef, useEffect } from 'react';
-import { Trash2, Pencil } from 'lucide-react';
+import { Trash2, Pencil, Check } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { FileSearchTextarea } from '@/components/ui/file-search-textarea';
 import { useReview, type ReviewComment } from '@/contexts/ReviewProvider';
@@ -13,7 +13,7 @@ export function ReviewCommentRenderer({
   comment,
   projectId,
 }: ReviewCommentRendererProps) {
-  const { deleteComment, updateComment } = useReview();
+  const { deleteComment, updateComment, resolveComment } = useReview();
   const [isEditing, setIsEditing] = useState(false);
   const [editText, setEditText] = useState(comment.text);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
@@ -77,8 +77,22 @@ export function ReviewCommentRenderer({
     <div className="border-y bg-background p-4 flex gap-2 items-center">
       <div className="flex-1 text-sm whitespace-pre-wrap text-foreground">
         {comment.text}
+        {comment.sent && (
+          <div className="mt-1 text-xs text-muted-foreground">
+            Sent to the agent, resolved when its run completes
+          </div>
+        )}
       </div>
       <div className="flex gap-1">
+        <Button
+          variant="ghost"
+          size="xs"
+          onClick={() => resolveComment(comment.id)}
+          title="Resolve comment"
+          className="h-auto"
+        >
+          <Check className="h-3 w-3" />
+        </Button>
         <Button
           variant="ghost"
           size="xs"
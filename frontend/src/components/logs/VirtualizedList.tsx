@@ -7,6 +7,7 @@ import {
   VirtuosoMessageListProps,
 } from '@virtuoso.dev/message-list';
 import { useEffect, useMemo, useRef, useState } from 'react';
+import { useSearchParams } from 'react-router-dom';
 
 import DisplayConversationEntry from '../NormalizedConversation/DisplayConversationEntry';
 import { useEntries } from '@/contexts/EntriesContext';
@@ -27,6 +28,8 @@ interface VirtualizedListProps {
 interface MessageListContext {
   attempt: TaskAttempt;
   task?: TaskWithAttemptStatus;
+  /** Patch key of the entry linked to with `?entry=`, e.g. from transcript search */
+  focusedKey: string | null;
 }
 
 const INITIAL_TOP_ITEM = { index: 'LAST' as const, align: 'end' as const };
@@ -56,7 +59,7 @@ const ItemContent: VirtuosoMessageListProps<
     return <p>{data.content}</p>;
   }
   if (data.type === 'NORMALIZED_ENTRY' && attempt) {
-    return (
+    const entry = (
       <DisplayConversationEntry
         expansionKey={data.patchKey}
         entry={data.content}
@@ -65,6 +68,10 @@ const ItemContent: VirtuosoMessageListProps<
         task={task}
       />
     );
+    if (data.patchKey === context?.focusedKey) {
+      return <div className="rounded-md ring-2 ring-primary">{entry}</div>;
+    }
+    return entry;
   }
 
   return null;
@@ -80,11 +87,15 @@ const VirtualizedList = ({ attempt, task }: VirtualizedListProps) => {
     useState<DataWithScrollModifier<PatchTypeWithKey> | null>(null);
   const [loading, setLoading] = useState(true);
   const { setEntries, reset } = useEntries();
+  const [searchParams] = useSearchParams();
+  const focusedKey = searchParams.get('entry');
+  const scrolledToFocusedKey = useRef<string | null>(null);
 
   useEffect(() => {
     setLoading(true);
     setChannelData(null);
     reset();
+    scrolledToFocusedKey.current = null;
   }, [attempt.id, reset]);
 
   const onEntriesUpdated = (
@@ -98,6 +109,19 @@ const VirtualizedList = ({ attempt, task }: VirtualizedListProps) => {
       scrollModifier = AutoScrollToBottom;
     }
 
+    // Jump to the linked entry once it has loaded
+    if (focusedKey && scrolledToFocusedKey.current !== focusedKey) {
+      const index = newEntries.findIndex((e) => e.patchKey === focusedKey);
+      if (index !== -1) {
+        scrollModifier = {
+          type: 'item-location',
+          location: { index, align: 'center' },
+          purgeItemSizes: true,
+        };
+        scrolledToFocusedKey.current = focusedKey;
+      }
+    }
+
     setChannelData({ data: newEntries, scrollModifier });
     setEntries(newEntries);
 
@@ -110,8 +134,8 @@ const VirtualizedList = ({ attempt, task }: VirtualizedListProps) => {
 
   const messageListRef = useRef<VirtuosoMessageListMethods | null>(null);
   const messageListContext = useMemo(
-    () => ({ attempt, task }),
-    [attempt, task]
+    () => ({ attempt, task, focusedKey }),
+    [attempt, task, focusedKey]
   );
 
   return (
@@ -15,6 +15,7 @@ import {
   TooltipTrigger,
 } from '@/components/ui/tooltip';
 import type { TaskAttempt } from 'shared/types';
+import { useReview } from '@/contexts/ReviewProvider';
 import GitOperations, {
   type GitOperationsInputs,
 } from '@/components/tasks/Toolbar/GitOperations.tsx';
@@ -33,6 +34,14 @@ export function DiffsPanel({ selectedAttempt, gitOps }: DiffsPanelProps) {
   const { fileCount, added, deleted } = useDiffSummary(
     selectedAttempt?.id ?? null
   );
+  const { refreshComments } = useReview();
+
+  // Re-anchor review comments once the diff settles after a change
+  useEffect(() => {
+    if (diffs.length === 0) return;
+    const timer = setTimeout(refreshComments, 1000);
+    return () => clearTimeout(timer);
+  }, [diffs, refreshComments]);
 
   useEffect(() => {
     setLoading(true);
@@ -1,4 +1,4 @@
-import { useState, useEffect, useRef } from 'react';
+import { useState, useEffect, useMemo, useRef } from 'react';
 import { useParams } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
 import { Loader2, X } from 'lucide-react';
@@ -6,7 +6,10 @@ import { Button } from '@/components/ui/button';
 import { useDevserverPreview } from '@/hooks/useDevserverPreview';
 import { useDevServer } from '@/hooks/useDevServer';
 import { useLogStream } from '@/hooks/useLogStream';
-import { useDevserverUrlFromLogs } from '@/hooks/useDevserverUrl';
+import {
+  detectDevserverUrl,
+  useDevserverUrlFromLogs,
+} from '@/hooks/useDevserverUrl';
 import { ClickToComponentListener } from '@/utils/previewBridge';
 import { useClickedElements } from '@/contexts/ClickedElementsProvider';
 import { Alert } from '@/components/ui/alert';
@@ -40,10 +43,17 @@ export function PreviewPanel() {
     isStopping: isStoppingDevServer,
     runningDevServer,
     latestDevServerProcess,
+    devServerInfo,
   } = useDevServer(attemptId);
 
   const logStream = useLogStream(latestDevServerProcess?.id ?? '');
-  const lastKnownUrl = useDevserverUrlFromLogs(logStream.logs);
+  const urlFromLogs = useDevserverUrlFromLogs(logStream.logs);
+  // The server reads the whole log, so prefer its URL over what the stream still holds
+  const urlFromServer = useMemo(
+    () => (devServerInfo?.url ? detectDevserverUrl(devServerInfo.url) : null),
+    [devServerInfo?.url]
+  );
+  const lastKnownUrl = urlFromServer ?? urlFromLogs;
 
   const previewState = useDevserverPreview(attemptId, {
     projectHasDevScript,
@@ -171,6 +181,8 @@ export function PreviewPanel() {
             <PreviewToolbar
               mode={mode}
               url={previewState.url}
+              previewPath={devServerInfo?.preview_path}
+              health={runningDevServer?.health_status}
               onRefresh={handleRefresh}
               onCopyUrl={handleCopyUrl}
               onStop={stopDevServer}
@@ -10,6 +10,7 @@ import { PlusIcon } from 'lucide-react';
 import NiceModal from '@ebay/nice-modal-react';
 import MarkdownRenderer from '@/components/ui/markdown-renderer';
 import { TaskRelationshipsSection } from '../tasks/TaskRelationshipsSection';
+import { TaskActivityTimeline } from '../tasks/TaskActivityTimeline';
 
 interface TaskPanelProps {
   task: TaskWithAttemptStatus | null;
@@ -95,6 +96,22 @@ const TaskPanel = ({ task }: TaskPanelProps) => {
                 />
               </div>
             )}
+
+            <div className="mt-4 pt-4 border-t">
+              <TaskActivityTimeline
+                taskId={task.id}
+                onNavigateToTask={(taskId) => {
+                  if (projectId) {
+                    navigate(paths.task(projectId, taskId));
+                  }
+                }}
+                onNavigateToAttempt={(attemptId) => {
+                  if (projectId) {
+                    navigate(paths.attempt(projectId, task.id, attemptId));
+                  }
+                }}
+              />
+            </div>
           </div>
 
           <div className="mt-6 flex-shrink-0">
@@ -19,7 +19,7 @@ export function CopyFilesField({
       <MultiFileSearchTextarea
         value={value}
         onChange={onChange}
-        placeholder="Start typing a file path... (.env, config.local.json, .local/settings.yml)"
+        placeholder="Start typing a file path or glob... (.env template, config/**/*.local.json, !config/secrets.json)"
         rows={3}
         disabled={disabled}
         className="w-full px-3 py-2 text-sm border border-input bg-background text-foreground disabled:opacity-50 rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring"
@@ -34,7 +34,7 @@ export function CopyFilesField({
     <textarea
       value={value}
       onChange={(e) => onChange(e.target.value)}
-      placeholder=".env,config.local.json,.local/settings.yml"
+      placeholder={'.env template\nconfig/**/*.local.json\n!config/secrets.local.json'}
       rows={3}
       disabled={disabled}
       className="w-full px-3 py-2 text-sm border border-input bg-background text-foreground rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring"
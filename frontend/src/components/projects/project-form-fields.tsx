@@ -442,7 +442,9 @@ export function ProjectFormFields({
             <p className="text-sm text-muted-foreground">
               This script can be run from task attempts to start a development
               server. Use it to quickly start your project's dev server for
-              testing changes.
+              testing changes. Each attempt gets its own port in{' '}
+              <code>$PORT</code> and <code>$VK_DEV_SERVER_PORT</code>, so
+              attempts can run their dev servers side by side.
             </p>
           </div>
 
@@ -472,11 +474,14 @@ export function ProjectFormFields({
               projectId={projectId}
             />
             <p className="text-sm text-muted-foreground">
-              Comma-separated list of files to copy from the original project
-              directory to the worktree. These files will be copied after the
-              worktree is created but before the setup script runs. Useful for
-              environment-specific files like .env, configuration files, and
-              local settings. Make sure these are gitignored or they could get
+              Files to copy from the original project directory to the
+              worktree, one glob per line or comma-separated. Prefix a pattern
+              with ! to exclude matches, and follow it with symlink to link
+              instead of copying, template to fill in {'{{branch}}'} and{' '}
+              {'{{port}}'}, or depth=N to limit how deep directories are
+              copied. These files will be copied after the worktree is created
+              but before the setup script runs; unmatched patterns are reported
+              in the setup log. Make sure these are gitignored or they could get
               committed!
             </p>
           </div>
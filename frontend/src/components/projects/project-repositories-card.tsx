@@ -0,0 +1,193 @@
+import { useState } from 'react';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { useTranslation } from 'react-i18next';
+import { Folder, Loader2, Trash2 } from 'lucide-react';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { showFolderPicker } from '@/lib/modals';
+import { projectsApi } from '@/lib/api';
+
+interface ProjectRepositoriesCardProps {
+  projectId: string;
+}
+
+// Last path segment, used to suggest a repository name
+function basename(path: string): string {
+  return path.split(/[\\/]/).filter(Boolean).pop() ?? '';
+}
+
+export function ProjectRepositoriesCard({
+  projectId,
+}: ProjectRepositoriesCardProps) {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+  const queryKey = ['projectRepositories', projectId];
+  const [name, setName] = useState('');
+  const [path, setPath] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  const { data: repositories = [], isLoading } = useQuery({
+    queryKey,
+    queryFn: () => projectsApi.getRepositories(projectId),
+  });
+
+  const addRepository = useMutation({
+    mutationFn: () =>
+      projectsApi.addRepository(projectId, {
+        name: name.trim(),
+        git_repo_path: path.trim(),
+      }),
+    onSuccess: () => {
+      setName('');
+      setPath('');
+      setError(null);
+      queryClient.invalidateQueries({ queryKey });
+    },
+    onError: (err: unknown) => {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.repositories.addError')
+      );
+    },
+  });
+
+  const deleteRepository = useMutation({
+    mutationFn: (repositoryId: string) =>
+      projectsApi.deleteRepository(projectId, repositoryId),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey }),
+    onError: (err: unknown) => {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.repositories.deleteError')
+      );
+    },
+  });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>{t('settings.projects.repositories.title')}</CardTitle>
+        <CardDescription>
+          {t('settings.projects.repositories.description')}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        {isLoading ? (
+          <Loader2 className="h-4 w-4 animate-spin" />
+        ) : repositories.length === 0 ? (
+          <p className="text-sm text-muted-foreground">
+            {t('settings.projects.repositories.empty')}
+          </p>
+        ) : (
+          <ul className="divide-y rounded-md border">
+            {repositories.map((repository) => (
+              <li
+                key={repository.id}
+                className="flex items-center justify-between gap-2 px-3 py-2"
+              >
+                <div className="min-w-0">
+                  <div className="font-medium text-sm">{repository.name}</div>
+                  <div className="truncate font-mono text-xs text-muted-foreground">
+                    {repository.git_repo_path}
+                  </div>
+                </div>
+                <Button
+                  type="button"
+                  variant="ghost"
+                  size="sm"
+                  disabled={deleteRepository.isPending}
+                  onClick={() => {
+                    if (
+                      window.confirm(
+                        t('settings.projects.repositories.confirmDelete', {
+                          name: repository.name,
+                        })
+                      )
+                    ) {
+                      deleteRepository.mutate(repository.id);
+                    }
+                  }}
+                >
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              </li>
+            ))}
+          </ul>
+        )}
+
+        <div className="space-y-2">
+          <Label htmlFor="project-repository-path">
+            {t('settings.projects.repositories.add')}
+          </Label>
+          <div className="flex space-x-2">
+            <Input
+              id="project-repository-name"
+              type="text"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              placeholder={t('settings.projects.repositories.namePlaceholder')}
+              className="w-40"
+            />
+            <Input
+              id="project-repository-path"
+              type="text"
+              value={path}
+              onChange={(e) => setPath(e.target.value)}
+              placeholder={t('settings.projects.repositories.pathPlaceholder')}
+              className="flex-1"
+            />
+            <Button
+              type="button"
+              variant="outline"
+              onClick={async () => {
+                const selectedPath = await showFolderPicker({
+                  title: 'Select Git Repository',
+                  description: 'Choose an existing git repository',
+                  value: path,
+                });
+                if (selectedPath) {
+                  setPath(selectedPath);
+                  if (!name.trim()) setName(basename(selectedPath));
+                }
+              }}
+            >
+              <Folder className="h-4 w-4" />
+            </Button>
+            <Button
+              type="button"
+              onClick={() => addRepository.mutate()}
+              disabled={
+                !name.trim() || !path.trim() || addRepository.isPending
+              }
+            >
+              {addRepository.isPending && (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              )}
+              {t('settings.projects.repositories.addButton')}
+            </Button>
+          </div>
+          <p className="text-sm text-muted-foreground">
+            {t('settings.projects.repositories.helper')}
+          </p>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
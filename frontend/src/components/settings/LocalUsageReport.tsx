@@ -0,0 +1,131 @@
+import { useTranslation } from 'react-i18next';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { Loader2, Trash2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { analyticsApi } from '@/lib/api';
+
+const reportKey = ['analyticsReport'] as const;
+
+const percent = (value: number | null) =>
+  value === null ? '—' : `${Math.round(value * 100)}%`;
+
+const minutes = (seconds: number | null) =>
+  seconds === null ? '—' : `${(seconds / 60).toFixed(1)}m`;
+
+/** Usage over the last 30 days, from analytics events stored on this machine */
+export default function LocalUsageReport() {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+
+  const { data: report, isLoading } = useQuery({
+    queryKey: reportKey,
+    queryFn: () => analyticsApi.getReport(),
+  });
+
+  const { mutate: clearEvents, isPending: isClearing } = useMutation({
+    mutationFn: analyticsApi.clearEvents,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: reportKey }),
+  });
+
+  if (isLoading) {
+    return <Loader2 className="h-4 w-4 animate-spin" />;
+  }
+
+  if (!report || report.events === 0) {
+    return (
+      <p className="text-sm text-muted-foreground">
+        {t('settings.general.privacy.localAnalytics.empty')}
+      </p>
+    );
+  }
+
+  return (
+    <div className="space-y-4">
+      <table className="w-full text-sm">
+        <thead className="text-left text-muted-foreground">
+          <tr>
+            <th className="py-1 pr-4">
+              {t('settings.general.privacy.localAnalytics.executor')}
+            </th>
+            <th className="py-1 pr-4">
+              {t('settings.general.privacy.localAnalytics.attempts')}
+            </th>
+            <th className="py-1 pr-4">
+              {t('settings.general.privacy.localAnalytics.runs')}
+            </th>
+            <th className="py-1 pr-4">
+              {t('settings.general.privacy.localAnalytics.failureRate')}
+            </th>
+            <th className="py-1 pr-4">
+              {t('settings.general.privacy.localAnalytics.averageRuntime')}
+            </th>
+            <th className="py-1">
+              {t('settings.general.privacy.localAnalytics.approvalsPerRun')}
+            </th>
+          </tr>
+        </thead>
+        <tbody>
+          {report.executors.map((usage) => (
+            <tr
+              key={`${usage.executor}:${usage.variant}`}
+              className="border-t"
+            >
+              <td className="py-1 pr-4">
+                {usage.executor ??
+                  t('settings.general.privacy.localAnalytics.unknownExecutor')}
+                {usage.variant && (
+                  <span className="text-muted-foreground">
+                    {' '}
+                    / {usage.variant}
+                  </span>
+                )}
+              </td>
+              <td className="py-1 pr-4">{usage.attempts}</td>
+              <td className="py-1 pr-4">{usage.runs}</td>
+              <td className="py-1 pr-4">{percent(usage.failure_rate)}</td>
+              <td className="py-1 pr-4">
+                {minutes(usage.average_runtime_seconds)}
+              </td>
+              <td className="py-1">
+                {usage.approvals_per_run?.toFixed(1) ?? '—'}
+              </td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+
+      {report.merges_per_week.length > 0 && (
+        <div className="space-y-1">
+          <p className="text-sm font-medium">
+            {t('settings.general.privacy.localAnalytics.mergesPerWeek')}
+          </p>
+          <ul className="text-sm text-muted-foreground">
+            {report.merges_per_week.map((week) => (
+              <li key={week.week_start}>
+                {t('settings.general.privacy.localAnalytics.week', {
+                  week: week.week_start,
+                  merges: week.merges,
+                  prMerges: week.pr_merges,
+                })}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
+      <Button
+        variant="outline"
+        size="sm"
+        onClick={() => clearEvents()}
+        disabled={isClearing}
+      >
+        {isClearing ? (
+          <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+        ) : (
+          <Trash2 className="mr-2 h-4 w-4" />
+        )}
+        {t('settings.general.privacy.localAnalytics.clear')}
+      </Button>
+    </div>
+  );
+}
@@ -0,0 +1,380 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Loader2, Plus, Send, Trash2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { configApi } from '@/lib/api';
+import type {
+  NotificationChannelConfig,
+  NotificationChannelKind,
+  NotificationEvent,
+  SmtpSecurity,
+} from 'shared/types';
+
+type ChannelType = NotificationChannelKind['type'];
+
+const CHANNEL_TYPES: ChannelType[] = [
+  'slack',
+  'discord',
+  'ntfy',
+  'gotify',
+  'email',
+];
+
+const EVENTS: NotificationEvent[] = [
+  'attempt_completed',
+  'attempt_failed',
+  'approval_pending',
+  'pr_merged',
+];
+
+const SMTP_SECURITY: SmtpSecurity[] = ['starttls', 'tls', 'none'];
+
+function defaultChannel(type: ChannelType): NotificationChannelKind {
+  switch (type) {
+    case 'slack':
+    case 'discord':
+      return { type, webhook_url: '' };
+    case 'ntfy':
+      return { type, server_url: 'https://ntfy.sh', topic: '', token: null };
+    case 'gotify':
+      return { type, server_url: '', token: '' };
+    case 'email':
+      return {
+        type,
+        smtp_host: '',
+        smtp_port: 587,
+        security: 'starttls',
+        username: null,
+        password: null,
+        from: '',
+        to: [],
+      };
+  }
+}
+
+type Props = {
+  channels: NotificationChannelConfig[];
+  onChange: (channels: NotificationChannelConfig[]) => void;
+};
+
+type TestResult = { index: number; error: string | null };
+
+export function NotificationChannelsEditor({ channels, onChange }: Props) {
+  const { t } = useTranslation('settings');
+  const [testing, setTesting] = useState<number | null>(null);
+  const [testResult, setTestResult] = useState<TestResult | null>(null);
+
+  const updateChannel = (
+    index: number,
+    patch: Partial<NotificationChannelConfig>
+  ) => {
+    onChange(
+      channels.map((channel, i) =>
+        i === index ? { ...channel, ...patch } : channel
+      )
+    );
+  };
+
+  const updateKind = (index: number, patch: Record<string, unknown>) => {
+    updateChannel(index, {
+      channel: { ...channels[index].channel, ...patch } as NotificationChannelKind,
+    });
+  };
+
+  const toggleEvent = (
+    index: number,
+    event: NotificationEvent,
+    checked: boolean
+  ) => {
+    const events = channels[index].events.filter((e) => e !== event);
+    updateChannel(index, { events: checked ? [...events, event] : events });
+  };
+
+  const handleTest = async (index: number) => {
+    setTesting(index);
+    setTestResult(null);
+    try {
+      await configApi.testNotificationChannel(channels[index]);
+      setTestResult({ index, error: null });
+    } catch (err) {
+      setTestResult({
+        index,
+        error: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setTesting(null);
+    }
+  };
+
+  const textField = (
+    index: number,
+    key: string,
+    value: string | null,
+    options: { type?: string; nullable?: boolean } = {}
+  ) => (
+    <div className="space-y-1">
+      <Label htmlFor={`channel-${index}-${key}`}>
+        {t(`settings.general.notifications.channels.fields.${key}`)}
+      </Label>
+      <Input
+        id={`channel-${index}-${key}`}
+        type={options.type ?? 'text'}
+        value={value ?? ''}
+        onChange={(e) =>
+          updateKind(index, {
+            [key]: options.nullable && !e.target.value ? null : e.target.value,
+          })
+        }
+      />
+    </div>
+  );
+
+  return (
+    <div className="space-y-4">
+      <div className="space-y-0.5">
+        <Label>{t('settings.general.notifications.channels.label')}</Label>
+        <p className="text-sm text-muted-foreground">
+          {t('settings.general.notifications.channels.helper')}
+        </p>
+      </div>
+
+      {channels.map((config, index) => {
+        const kind = config.channel;
+        return (
+          <div key={index} className="space-y-3 rounded-md border p-4">
+            <div className="flex items-center gap-2">
+              <Input
+                value={config.name}
+                placeholder={t(
+                  'settings.general.notifications.channels.namePlaceholder'
+                )}
+                onChange={(e) => updateChannel(index, { name: e.target.value })}
+              />
+              <Select
+                value={kind.type}
+                onValueChange={(value: ChannelType) =>
+                  updateChannel(index, { channel: defaultChannel(value) })
+                }
+              >
+                <SelectTrigger className="w-40">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {CHANNEL_TYPES.map((type) => (
+                    <SelectItem key={type} value={type}>
+                      {t(`settings.general.notifications.channels.types.${type}`)}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={() => handleTest(index)}
+                disabled={testing !== null}
+                title={t('settings.general.notifications.channels.test')}
+              >
+                {testing === index ? (
+                  <Loader2 className="h-4 w-4 animate-spin" />
+                ) : (
+                  <Send className="h-4 w-4" />
+                )}
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() =>
+                  onChange(channels.filter((_, i) => i !== index))
+                }
+                title={t('settings.general.notifications.channels.remove')}
+              >
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+
+            {testResult?.index === index && (
+              <p
+                className={
+                  testResult.error
+                    ? 'text-sm text-destructive'
+                    : 'text-sm text-muted-foreground'
+                }
+              >
+                {testResult.error ??
+                  t('settings.general.notifications.channels.testSent')}
+              </p>
+            )}
+
+            <div className="grid gap-3 sm:grid-cols-2">
+              {(kind.type === 'slack' || kind.type === 'discord') &&
+                textField(index, 'webhook_url', kind.webhook_url)}
+              {kind.type === 'ntfy' && (
+                <>
+                  {textField(index, 'server_url', kind.server_url)}
+                  {textField(index, 'topic', kind.topic)}
+                  {textField(index, 'token', kind.token, {
+                    type: 'password',
+                    nullable: true,
+                  })}
+                </>
+              )}
+              {kind.type === 'gotify' && (
+                <>
+                  {textField(index, 'server_url', kind.server_url)}
+                  {textField(index, 'token', kind.token, { type: 'password' })}
+                </>
+              )}
+              {kind.type === 'email' && (
+                <>
+                  {textField(index, 'smtp_host', kind.smtp_host)}
+                  <div className="space-y-1">
+                    <Label htmlFor={`channel-${index}-smtp_port`}>
+                      {t(
+                        'settings.general.notifications.channels.fields.smtp_port'
+                      )}
+                    </Label>
+                    <Input
+                      id={`channel-${index}-smtp_port`}
+                      type="number"
+                      value={kind.smtp_port}
+                      onChange={(e) =>
+                        updateKind(index, {
+                          smtp_port: Number(e.target.value) || 0,
+                        })
+                      }
+                    />
+                  </div>
+                  <div className="space-y-1">
+                    <Label>
+                      {t(
+                        'settings.general.notifications.channels.fields.security'
+                      )}
+                    </Label>
+                    <Select
+                      value={kind.security}
+                      onValueChange={(value: SmtpSecurity) =>
+                        updateKind(index, { security: value })
+                      }
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        {SMTP_SECURITY.map((security) => (
+                          <SelectItem key={security} value={security}>
+                            {t(
+                              `settings.general.notifications.channels.security.${security}`
+                            )}
+                          </SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  {textField(index, 'username', kind.username, {
+                    nullable: true,
+                  })}
+                  {textField(index, 'password', kind.password, {
+                    type: 'password',
+                    nullable: true,
+                  })}
+                  {textField(index, 'from', kind.from)}
+                  <div className="space-y-1">
+                    <Label htmlFor={`channel-${index}-to`}>
+                      {t('settings.general.notifications.channels.fields.to')}
+                    </Label>
+                    <Input
+                      id={`channel-${index}-to`}
+                      value={kind.to.join(', ')}
+                      onChange={(e) =>
+                        updateKind(index, {
+                          to: e.target.value
+                            .split(',')
+                            .map((address) => address.trim())
+                            .filter(Boolean),
+                        })
+                      }
+                    />
+                  </div>
+                </>
+              )}
+            </div>
+
+            <div className="space-y-2">
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.notifications.channels.eventsHelper')}
+              </p>
+              <div className="flex flex-wrap gap-4">
+                {EVENTS.map((event) => (
+                  <div key={event} className="flex items-center space-x-2">
+                    <Checkbox
+                      id={`channel-${index}-${event}`}
+                      checked={config.events.includes(event)}
+                      onCheckedChange={(checked: boolean) =>
+                        toggleEvent(index, event, checked)
+                      }
+                    />
+                    <Label
+                      htmlFor={`channel-${index}-${event}`}
+                      className="cursor-pointer"
+                    >
+                      {t(
+                        `settings.general.notifications.channels.events.${event}`
+                      )}
+                    </Label>
+                  </div>
+                ))}
+              </div>
+            </div>
+
+            <div className="flex items-center space-x-2">
+              <Checkbox
+                id={`channel-${index}-enabled`}
+                checked={config.enabled}
+                onCheckedChange={(checked: boolean) =>
+                  updateChannel(index, { enabled: checked })
+                }
+              />
+              <Label
+                htmlFor={`channel-${index}-enabled`}
+                className="cursor-pointer"
+              >
+                {t('settings.general.notifications.channels.enabled')}
+              </Label>
+            </div>
+          </div>
+        );
+      })}
+
+      <Button
+        variant="outline"
+        size="sm"
+        onClick={() =>
+          onChange([
+            ...channels,
+            {
+              name: '',
+              enabled: true,
+              events: [],
+              channel: defaultChannel('slack'),
+            },
+          ])
+        }
+      >
+        <Plus className="h-4 w-4 mr-2" />
+        {t('settings.general.notifications.channels.add')}
+      </Button>
+    </div>
+  );
+}
+
+export default NotificationChannelsEditor;
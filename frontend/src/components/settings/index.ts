@@ -1 +1,3 @@
 export { default as ExecutorProfileSelector } from './ExecutorProfileSelector';
+export { default as NotificationChannelsEditor } from './NotificationChannelsEditor';
+export { default as LocalUsageReport } from './LocalUsageReport';
@@ -0,0 +1,218 @@
+import { useTranslation } from 'react-i18next';
+import {
+  Check,
+  CircleDot,
+  GitCommit,
+  GitMerge,
+  GitPullRequest,
+  GitPullRequestClosed,
+  Link2,
+  Loader2,
+  MessageSquare,
+  Play,
+  ShieldQuestion,
+  Square,
+  Unlink,
+  type LucideIcon,
+} from 'lucide-react';
+import { useTaskActivity } from '@/hooks/useTaskActivity';
+import { statusLabels } from '@/utils/status-labels';
+import type { TaskActivityEvent } from 'shared/types';
+import { cn } from '@/lib/utils';
+
+interface TaskActivityTimelineProps {
+  taskId: string;
+  onNavigateToTask?: (taskId: string) => void;
+  onNavigateToAttempt?: (attemptId: string) => void;
+}
+
+const EVENT_ICONS: Record<TaskActivityEvent['type'], LucideIcon> = {
+  status_changed: CircleDot,
+  attempt_started: Play,
+  process_started: Play,
+  follow_up_sent: MessageSquare,
+  process_finished: Square,
+  approval_requested: ShieldQuestion,
+  approval_resolved: Check,
+  committed: GitCommit,
+  merged: GitMerge,
+  pr_opened: GitPullRequest,
+  pr_merged: GitMerge,
+  pr_closed: GitPullRequestClosed,
+  relationship_changed: Link2,
+};
+
+const shortSha = (sha: string) => sha.slice(0, 7);
+
+export function TaskActivityTimeline({
+  taskId,
+  onNavigateToTask,
+  onNavigateToAttempt,
+}: TaskActivityTimelineProps) {
+  const { t } = useTranslation('tasks');
+  const { activity, isLoading, error } = useTaskActivity(taskId);
+
+  const describe = (event: TaskActivityEvent): string => {
+    switch (event.type) {
+      case 'status_changed':
+        return event.from
+          ? t('taskActivity.statusChanged', {
+              from: statusLabels[event.from],
+              to: statusLabels[event.to],
+            })
+          : t('taskActivity.created', { status: statusLabels[event.to] });
+      case 'attempt_started':
+        return t('taskActivity.attemptStarted', {
+          executor: event.executor,
+          branch: event.branch,
+        });
+      case 'process_started':
+        return t(`taskActivity.processStarted.${event.run_reason}`);
+      case 'follow_up_sent':
+        return t('taskActivity.followUpSent');
+      case 'process_finished':
+        return t('taskActivity.processFinished', {
+          process: t(`taskActivity.runReasons.${event.run_reason}`),
+          status: event.status,
+        });
+      case 'approval_requested':
+        return t('taskActivity.approvalRequested', { tool: event.tool_name });
+      case 'approval_resolved':
+        return t(`taskActivity.approvalResolved.${event.status}`, {
+          tool: event.tool_name,
+        });
+      case 'committed':
+        return t('taskActivity.committed', {
+          commit: shortSha(event.commit),
+        });
+      case 'merged':
+        return t('taskActivity.merged', {
+          branch: event.target_branch,
+          commit: shortSha(event.merge_commit),
+        });
+      case 'pr_opened':
+        return t('taskActivity.prOpened', {
+          number: event.number,
+          branch: event.target_branch,
+        });
+      case 'pr_merged':
+        return t('taskActivity.prMerged', { number: event.number });
+      case 'pr_closed':
+        return t('taskActivity.prClosed', { number: event.number });
+      case 'relationship_changed':
+        return t(`taskActivity.relationship.${event.change}`, {
+          label: event.label ?? t('taskActivity.relationship.unknownType'),
+          task: event.other_task_title,
+        });
+    }
+  };
+
+  /** Free text shown under the event, e.g. a prompt or the agent's summary */
+  const details = (event: TaskActivityEvent): string | null => {
+    switch (event.type) {
+      case 'process_started':
+      case 'follow_up_sent':
+        return event.prompt;
+      case 'process_finished':
+        return event.summary;
+      case 'approval_requested':
+        return event.tool_input_summary;
+      case 'approval_resolved':
+        return event.denial_reason;
+      default:
+        return null;
+    }
+  };
+
+  const handleClick = (event: TaskActivityEvent) => {
+    if (event.type === 'relationship_changed') {
+      onNavigateToTask?.(event.other_task_id);
+    } else if ('task_attempt_id' in event) {
+      onNavigateToAttempt?.(event.task_attempt_id);
+    }
+  };
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center py-4">
+        <Loader2 className="h-4 w-4 animate-spin" />
+      </div>
+    );
+  }
+
+  if (error) {
+    return <div className="text-sm text-destructive p-2">{error}</div>;
+  }
+
+  return (
+    <div className="space-y-2">
+      <h3 className="text-sm font-semibold">{t('taskActivity.title')}</h3>
+      {activity.length === 0 ? (
+        <div className="text-sm text-muted-foreground">
+          {t('taskActivity.empty')}
+        </div>
+      ) : (
+        <ol className="border-l ml-2 space-y-3">
+          {[...activity].reverse().map((entry) => {
+            const { event } = entry;
+            const Icon =
+              event.type === 'relationship_changed' &&
+              event.change === 'removed'
+                ? Unlink
+                : EVENT_ICONS[event.type];
+            const detail = details(event);
+            const clickable =
+              event.type === 'relationship_changed'
+                ? !!onNavigateToTask
+                : 'task_attempt_id' in event && !!onNavigateToAttempt;
+            return (
+              <li
+                key={entry.key}
+                className={cn(
+                  'relative ml-4 text-sm',
+                  clickable && 'cursor-pointer hover:text-foreground'
+                )}
+                onClick={clickable ? () => handleClick(event) : undefined}
+              >
+                <span className="absolute -left-6 top-0.5 flex h-4 w-4 items-center justify-center rounded-full bg-background">
+                  <Icon className="h-3 w-3 text-muted-foreground" />
+                </span>
+                <div className="flex items-baseline justify-between gap-2">
+                  <span>{describe(event)}</span>
+                  <time
+                    className="text-xs text-muted-foreground shrink-0"
+                    dateTime={String(entry.at)}
+                    title={new Date(entry.at).toLocaleString()}
+                  >
+                    {new Date(entry.at).toLocaleString(undefined, {
+                      dateStyle: 'short',
+                      timeStyle: 'short',
+                    })}
+                  </time>
+                </div>
+                {detail && (
+                  <div className="text-xs text-muted-foreground mt-0.5 line-clamp-2 whitespace-pre-wrap break-words">
+                    {detail}
+                  </div>
+                )}
+                {(event.type === 'pr_opened' ||
+                  event.type === 'pr_merged' ||
+                  event.type === 'pr_closed') && (
+                  <a
+                    href={event.url}
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    className="text-xs text-primary hover:underline"
+                    onClick={(e) => e.stopPropagation()}
+                  >
+                    {event.url}
+                  </a>
+                )}
+              </li>
+            );
+          })}
+        </ol>
+      )}
+    </div>
+  );
+}
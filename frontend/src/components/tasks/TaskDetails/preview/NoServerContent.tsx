@@ -94,6 +94,31 @@ export function NoServerContent({
           dev_script: script,
           cleanup_script: project.cleanup_script ?? null,
           copy_files: project.copy_files ?? null,
+          merge_strategy: project.merge_strategy,
+          merge_commit_template: project.merge_commit_template ?? null,
+          commit_message_strategy: project.commit_message_strategy,
+          commit_message_template: project.commit_message_template ?? null,
+          commit_message_validation: project.commit_message_validation,
+          commit_message_pattern: project.commit_message_pattern ?? null,
+          commit_author_name: project.commit_author_name ?? null,
+          commit_author_email: project.commit_author_email ?? null,
+          commit_co_authors: project.commit_co_authors ?? null,
+          commit_signing_format: project.commit_signing_format,
+          commit_signing_key: project.commit_signing_key ?? null,
+          container_image: project.container_image ?? null,
+          container_cpus: project.container_cpus ?? null,
+          container_memory: project.container_memory ?? null,
+          worktree_retention_hours: project.worktree_retention_hours,
+          worktree_keep_recent: project.worktree_keep_recent ?? null,
+          worktree_keep_in_review: project.worktree_keep_in_review,
+          worktree_max_size_mb: project.worktree_max_size_mb ?? null,
+          worktree_snapshot_enabled: project.worktree_snapshot_enabled,
+          dependency_cache: project.dependency_cache ?? null,
+          dev_server_health_check: project.dev_server_health_check ?? null,
+          dev_server_restart_policy: project.dev_server_restart_policy ?? null,
+          dev_server_max_restarts: project.dev_server_max_restarts ?? null,
+          working_dir: project.working_dir ?? null,
+          sparse_checkout: project.sparse_checkout ?? null,
         },
       },
       {
@@ -1,5 +1,13 @@
-import { ExternalLink, RefreshCw, Copy, Loader2, Pause } from 'lucide-react';
+import {
+  ExternalLink,
+  Globe,
+  RefreshCw,
+  Copy,
+  Loader2,
+  Pause,
+} from 'lucide-react';
 import { useTranslation } from 'react-i18next';
+import { Badge } from '@/components/ui/badge';
 import { Button } from '@/components/ui/button';
 import {
   Tooltip,
@@ -8,10 +16,19 @@ import {
   TooltipTrigger,
 } from '@/components/ui/tooltip';
 import { NewCardHeader } from '@/components/ui/new-card';
+import type { DevServerHealth } from 'shared/types';
+
+const HEALTH_VARIANTS = {
+  starting: 'outline',
+  healthy: 'secondary',
+  unhealthy: 'destructive',
+} as const;
 
 interface PreviewToolbarProps {
   mode: 'noServer' | 'error' | 'ready';
   url?: string;
+  previewPath?: string;
+  health?: DevServerHealth | null;
   onRefresh: () => void;
   onCopyUrl: () => void;
   onStop: () => void;
@@ -21,6 +38,8 @@ interface PreviewToolbarProps {
 export function PreviewToolbar({
   mode,
   url,
+  previewPath,
+  health,
   onRefresh,
   onCopyUrl,
   onStop,
@@ -91,6 +110,32 @@ export function PreviewToolbar({
           </Tooltip>
         </TooltipProvider>
 
+        {previewPath && (
+          <TooltipProvider>
+            <Tooltip>
+              <TooltipTrigger asChild>
+                <Button
+                  variant="icon"
+                  aria-label={t('preview.toolbar.openPreviewProxy')}
+                  asChild
+                >
+                  <a
+                    href={previewPath}
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    className="flex items-center"
+                  >
+                    <Globe className="h-4 w-4" />
+                  </a>
+                </Button>
+              </TooltipTrigger>
+              <TooltipContent side="bottom">
+                {t('preview.toolbar.openPreviewProxy')}
+              </TooltipContent>
+            </Tooltip>
+          </TooltipProvider>
+        )}
+
         <div className="h-4 w-px bg-border" />
 
         <TooltipProvider>
@@ -126,6 +171,11 @@ export function PreviewToolbar({
         >
           {url || <Loader2 className="h-4 w-4 animate-spin" />}
         </span>
+        {health && (
+          <Badge variant={HEALTH_VARIANTS[health]} className="ml-2 shrink-0">
+            {t(`preview.toolbar.health.${health}`)}
+          </Badge>
+        )}
       </div>
     </NewCardHeader>
   );
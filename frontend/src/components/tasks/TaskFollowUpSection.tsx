@@ -63,7 +63,12 @@ export function TaskFollowUpSection({
   const { branch: attemptBranch, refetch: refetchAttemptBranch } =
     useAttemptBranch(selectedAttemptId);
   const { profiles } = useUserSystem();
-  const { comments, generateReviewMarkdown, clearComments } = useReview();
+  const {
+    openComments,
+    generateReviewMarkdown,
+    clearComments,
+    refreshComments,
+  } = useReview();
   const {
     generateMarkdown: generateClickedMarkdown,
     clearElements: clearClickedElements,
@@ -208,12 +213,12 @@ export function TaskFollowUpSection({
       attemptId: selectedAttemptId,
       message: followUpMessage,
       conflictMarkdown: conflictResolutionInstructions,
-      reviewMarkdown,
+      sendReview: openComments.length > 0,
       clickedMarkdown,
       selectedVariant,
       images,
       newlyUploadedImageIds,
-      clearComments,
+      refreshComments,
       clearClickedElements,
       jumpToLogsTab,
       onAfterSendCleanup: clearImagesAndUploads,
@@ -549,7 +554,7 @@ export function TaskFollowUpSection({
                   </Button>
                 ) : (
                   <div className="flex items-center gap-2">
-                    {comments.length > 0 && (
+                    {openComments.length > 0 && (
                       <Button
                         onClick={clearComments}
                         size="sm"
@@ -577,7 +582,9 @@ export function TaskFollowUpSection({
                           <Send className="h-4 w-4 mr-2" />
                           {conflictResolutionInstructions
                             ? t('followUp.resolveConflicts')
-                            : t('followUp.send')}
+                            : openComments.length > 0
+                              ? t('followUp.sendReview')
+                              : t('followUp.send')}
                         </>
                       )}
                     </Button>
@@ -0,0 +1,179 @@
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { useTranslation } from 'react-i18next';
+import { ExternalLink, GitBranch as GitBranchIcon } from 'lucide-react';
+import { useState } from 'react';
+import { Button } from '@/components/ui/button';
+import { attemptsApi } from '@/lib/api';
+import type {
+  TaskAttempt,
+  TaskAttemptRepositoryStatus,
+  TaskWithAttemptStatus,
+} from 'shared/types';
+
+interface AttemptRepositoriesProps {
+  attempt: TaskAttempt;
+  task: TaskWithAttemptStatus;
+  isAttemptRunning: boolean;
+  setError: (error: string | null) => void;
+}
+
+type RepositoryAction = 'merge' | 'push' | 'pr';
+
+/**
+ * Merge, push and PR controls for the attempt's worktrees of the project's other
+ * repositories. Renders nothing for attempts of single-repository projects.
+ */
+export function AttemptRepositories({
+  attempt,
+  task,
+  isAttemptRunning,
+  setError,
+}: AttemptRepositoriesProps) {
+  const { t } = useTranslation('tasks');
+  const queryClient = useQueryClient();
+  const queryKey = ['attemptRepositories', attempt.id];
+  const [pending, setPending] = useState<{
+    repositoryId: string;
+    action: RepositoryAction;
+  } | null>(null);
+
+  const { data: repositories = [] } = useQuery({
+    queryKey,
+    queryFn: () => attemptsApi.getRepositories(attempt.id),
+    enabled: !!attempt.container_ref,
+    refetchInterval: 5000,
+  });
+
+  if (repositories.length === 0) return null;
+
+  const run = async (
+    status: TaskAttemptRepositoryStatus,
+    action: RepositoryAction
+  ) => {
+    const repositoryId = status.repository.project_repository_id;
+    setPending({ repositoryId, action });
+    setError(null);
+    try {
+      if (action === 'merge') {
+        await attemptsApi.mergeRepository(attempt.id, repositoryId);
+      } else if (action === 'push') {
+        await attemptsApi.pushRepository(attempt.id, repositoryId);
+      } else {
+        const result = await attemptsApi.createRepositoryPR(
+          attempt.id,
+          repositoryId,
+          { title: task.title, body: task.description, target_branch: null }
+        );
+        if (!result.success) {
+          setError(result.message ?? t('git.repositories.errors.pr'));
+        }
+      }
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t(`git.repositories.errors.${action}`)
+      );
+    } finally {
+      setPending(null);
+      queryClient.invalidateQueries({ queryKey });
+    }
+  };
+
+  const isPending = (status: TaskAttemptRepositoryStatus) =>
+    pending?.repositoryId === status.repository.project_repository_id;
+
+  return (
+    <div className="space-y-2">
+      <div className="text-sm font-medium">{t('git.repositories.title')}</div>
+      <ul className="divide-y rounded-md border">
+        {repositories.map((status) => {
+          const { repository } = status;
+          const merged = repository.merge_commit !== null;
+          return (
+            <li
+              key={repository.id}
+              className="flex flex-wrap items-center justify-between gap-2 px-3 py-2 text-sm"
+            >
+              <div className="flex min-w-0 items-center gap-2">
+                <GitBranchIcon className="h-3.5 w-3.5 shrink-0" />
+                <span className="font-medium">{repository.name}</span>
+                <span className="truncate text-muted-foreground">
+                  {t('git.repositories.target', {
+                    branch: repository.target_branch,
+                  })}
+                </span>
+                {status.commits_ahead !== null && (
+                  <span className="text-muted-foreground">
+                    {t('git.repositories.aheadBehind', {
+                      ahead: status.commits_ahead,
+                      behind: status.commits_behind ?? 0,
+                    })}
+                  </span>
+                )}
+                {status.has_uncommitted_changes && (
+                  <span className="text-amber-600">
+                    {t('git.repositories.uncommitted')}
+                  </span>
+                )}
+              </div>
+              <div className="flex items-center gap-2">
+                {repository.pr_url && (
+                  <a
+                    href={repository.pr_url}
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    className="inline-flex items-center gap-1 text-primary hover:underline"
+                  >
+                    {t('git.pr.number', {
+                      number: Number(repository.pr_number),
+                    })}
+                    <ExternalLink className="h-3.5 w-3.5" />
+                  </a>
+                )}
+                {merged ? (
+                  <span className="text-muted-foreground">
+                    {t('git.states.merged')}
+                  </span>
+                ) : (
+                  <>
+                    <Button
+                      size="xs"
+                      variant="outline"
+                      disabled={
+                        isAttemptRunning ||
+                        isPending(status) ||
+                        !status.commits_ahead
+                      }
+                      onClick={() => run(status, 'merge')}
+                    >
+                      {pending?.action === 'merge' && isPending(status)
+                        ? t('git.states.merging')
+                        : t('git.states.merge')}
+                    </Button>
+                    <Button
+                      size="xs"
+                      variant="outline"
+                      disabled={isAttemptRunning || isPending(status)}
+                      onClick={() =>
+                        run(status, repository.pr_url ? 'push' : 'pr')
+                      }
+                    >
+                      {repository.pr_url
+                        ? pending?.action === 'push' && isPending(status)
+                          ? t('git.states.pushing')
+                          : t('git.states.push')
+                        : pending?.action === 'pr' && isPending(status)
+                          ? t('git.states.creating')
+                          : t('git.states.createPr')}
+                    </Button>
+                  </>
+                )}
+              </div>
+            </li>
+          );
+        })}
+      </ul>
+    </div>
+  );
+}
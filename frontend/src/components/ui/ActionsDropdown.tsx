@@ -6,14 +6,25 @@ import {
   DropdownMenuItem,
   DropdownMenuLabel,
   DropdownMenuSeparator,
+  DropdownMenuSub,
+  DropdownMenuSubContent,
+  DropdownMenuSubTrigger,
   DropdownMenuTrigger,
 } from '@/components/ui/dropdown-menu';
 import { MoreHorizontal } from 'lucide-react';
-import type { TaskWithAttemptStatus, TaskAttempt } from 'shared/types';
+import type {
+  ExportFormat,
+  TaskWithAttemptStatus,
+  TaskAttempt,
+} from 'shared/types';
 import { useOpenInEditor } from '@/hooks/useOpenInEditor';
+import { usePinWorktree } from '@/hooks/usePinWorktree';
 import NiceModal from '@ebay/nice-modal-react';
 import { useProject } from '@/contexts/project-context';
 import { openTaskForm } from '@/lib/openTaskForm';
+import { attemptsApi } from '@/lib/api';
+
+const EXPORT_FORMATS: ExportFormat[] = ['markdown', 'html', 'json'];
 
 interface ActionsDropdownProps {
   task?: TaskWithAttemptStatus | null;
@@ -24,6 +35,7 @@ export function ActionsDropdown({ task, attempt }: ActionsDropdownProps) {
   const { t } = useTranslation('tasks');
   const { projectId } = useProject();
   const openInEditor = useOpenInEditor(attempt?.id);
+  const pinWorktree = usePinWorktree(attempt?.id);
 
   const hasAttemptActions = Boolean(attempt);
   const hasTaskActions = Boolean(task);
@@ -81,6 +93,21 @@ export function ActionsDropdown({ task, attempt }: ActionsDropdownProps) {
     });
   };
 
+  const handleTogglePin = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    if (!attempt?.id) return;
+    pinWorktree.mutate(!attempt.worktree_pinned);
+  };
+
+  const handleExport = (e: React.MouseEvent, format: ExportFormat) => {
+    e.stopPropagation();
+    if (!attempt?.id) return;
+    // The server answers with an attachment, so this downloads in place
+    const link = document.createElement('a');
+    link.href = attemptsApi.exportUrl(attempt.id, format);
+    link.click();
+  };
+
   const handleGitActions = (e: React.MouseEvent) => {
     e.stopPropagation();
     if (!attempt?.id || !task) return;
@@ -119,6 +146,21 @@ export function ActionsDropdown({ task, attempt }: ActionsDropdownProps) {
               >
                 {t('actionsMenu.viewProcesses')}
               </DropdownMenuItem>
+              <DropdownMenuSub>
+                <DropdownMenuSubTrigger disabled={!attempt?.id}>
+                  {t('actionsMenu.exportConversation')}
+                </DropdownMenuSubTrigger>
+                <DropdownMenuSubContent>
+                  {EXPORT_FORMATS.map((format) => (
+                    <DropdownMenuItem
+                      key={format}
+                      onClick={(e) => handleExport(e, format)}
+                    >
+                      {t(`actionsMenu.exportFormats.${format}`)}
+                    </DropdownMenuItem>
+                  ))}
+                </DropdownMenuSubContent>
+              </DropdownMenuSub>
               <DropdownMenuItem onClick={handleCreateNewAttempt}>
                 {t('actionsMenu.createNewAttempt')}
               </DropdownMenuItem>
@@ -134,6 +176,18 @@ export function ActionsDropdown({ task, attempt }: ActionsDropdownProps) {
               >
                 {t('actionsMenu.gitActions')}
               </DropdownMenuItem>
+              <DropdownMenuItem
+                disabled={
+                  !attempt?.id ||
+                  attempt.worktree_deleted ||
+                  pinWorktree.isPending
+                }
+                onClick={handleTogglePin}
+              >
+                {attempt?.worktree_pinned
+                  ? t('actionsMenu.unpinWorktree')
+                  : t('actionsMenu.pinWorktree')}
+              </DropdownMenuItem>
               <DropdownMenuSeparator />
             </>
           )}
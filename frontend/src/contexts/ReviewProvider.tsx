@@ -1,30 +1,54 @@
 import { SplitSide } from '@git-diff-view/react';
-import { createContext, useContext, useState, ReactNode } from 'react';
+import {
+  createContext,
+  useCallback,
+  useContext,
+  useMemo,
+  useState,
+  ReactNode,
+} from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { attemptsApi } from '@/lib/api';
+import type { ReviewComment as ReviewCommentRow } from 'shared/types';
 
 export interface ReviewComment {
   id: string;
   filePath: string;
+  /** Last commented line, where the comment is shown */
   lineNumber: number;
+  lineStart: number;
   side: SplitSide;
   text: string;
   codeLine?: string;
+  /** The commented lines are no longer in the file */
+  outdated: boolean;
+  /** Sent to the agent and waiting for its run to finish */
+  sent: boolean;
 }
 
 export interface ReviewDraft {
   filePath: string;
   side: SplitSide;
+  /** Last selected line, where the draft is shown */
   lineNumber: number;
+  lineStart: number;
   text: string;
+  /** Content of the selected lines */
   codeLine?: string;
 }
 
 interface ReviewContextType {
+  /** Unresolved comments, including those already sent to the agent */
   comments: ReviewComment[];
+  /** Comments the next review will send */
+  openComments: ReviewComment[];
   drafts: Record<string, ReviewDraft>;
-  addComment: (comment: Omit<ReviewComment, 'id'>) => void;
+  addComment: (comment: ReviewDraft) => void;
   updateComment: (id: string, text: string) => void;
   deleteComment: (id: string) => void;
+  resolveComment: (id: string) => void;
   clearComments: () => void;
+  refreshComments: () => void;
   setDraft: (key: string, draft: ReviewDraft | null) => void;
   generateReviewMarkdown: () => string;
 }
@@ -39,33 +63,134 @@ export function useReview() {
   return context;
 }
 
-export function ReviewProvider({ children }: { children: ReactNode }) {
-  const [comments, setComments] = useState<ReviewComment[]>([]);
+function fromRow(row: ReviewCommentRow): ReviewComment {
+  return {
+    id: row.id,
+    filePath: row.file_path,
+    lineNumber: row.line_end,
+    lineStart: row.line_start,
+    side: row.side === 'old' ? SplitSide.old : SplitSide.new,
+    text: row.body,
+    ...(row.code !== null ? { codeLine: row.code } : {}),
+    outdated: row.outdated,
+    sent: row.sent_execution_process_id !== null,
+  };
+}
+
+export function ReviewProvider({
+  attemptId,
+  children,
+}: {
+  attemptId?: string;
+  children: ReactNode;
+}) {
+  const queryClient = useQueryClient();
+  const queryKey = useMemo(() => ['reviewComments', attemptId], [attemptId]);
+  const promptQueryKey = useMemo(
+    () => ['reviewPrompt', attemptId],
+    [attemptId]
+  );
+  const { data: rows } = useQuery({
+    queryKey,
+    queryFn: () => attemptsApi.getReviewComments(attemptId!),
+    enabled: !!attemptId,
+  });
+  // Rendered by the server, exactly as a review sends it
+  const { data: reviewPrompt } = useQuery({
+    queryKey: promptQueryKey,
+    queryFn: () => attemptsApi.getReviewPrompt(attemptId!),
+    enabled: !!attemptId,
+  });
   const [drafts, setDrafts] = useState<Record<string, ReviewDraft>>({});
 
-  const addComment = (comment: Omit<ReviewComment, 'id'>) => {
-    const newComment: ReviewComment = {
-      ...comment,
-      id: crypto.randomUUID(),
-    };
-    setComments((prev) => [...prev, newComment]);
+  const comments = useMemo(
+    () => (rows ?? []).filter((row) => row.resolved_at === null).map(fromRow),
+    [rows]
+  );
+  const openComments = useMemo(
+    () => comments.filter((comment) => !comment.sent),
+    [comments]
+  );
+
+  const invalidateComments = useCallback(() => {
+    queryClient.invalidateQueries({ queryKey });
+    queryClient.invalidateQueries({ queryKey: promptQueryKey });
+  }, [queryClient, queryKey, promptQueryKey]);
+
+  // Move comments to where their lines are now and settle those sent to finished runs
+  const refreshComments = useCallback(() => {
+    if (!attemptId) return;
+    attemptsApi
+      .reanchorReviewComments(attemptId)
+      .then((updated) => queryClient.setQueryData(queryKey, updated))
+      .catch((error) =>
+        console.error('Failed to re-anchor review comments', error)
+      )
+      .finally(() =>
+        queryClient.invalidateQueries({ queryKey: promptQueryKey })
+      );
+  }, [attemptId, queryClient, queryKey, promptQueryKey]);
+
+  const run = useCallback(
+    (request: () => Promise<unknown>) => {
+      request()
+        .catch((error) =>
+          console.error('Failed to update review comments', error)
+        )
+        .finally(invalidateComments);
+    },
+    [invalidateComments]
+  );
+
+  const addComment = (comment: ReviewDraft) => {
+    if (!attemptId) return;
+    run(() =>
+      attemptsApi.createReviewComment(attemptId, {
+        file_path: comment.filePath,
+        side: comment.side === SplitSide.old ? 'old' : 'new',
+        line_start: comment.lineStart,
+        line_end: comment.lineNumber,
+        code: comment.codeLine ?? null,
+        body: comment.text,
+      })
+    );
   };
 
   const updateComment = (id: string, text: string) => {
-    setComments((prev) =>
-      prev.map((comment) =>
-        comment.id === id ? { ...comment, text } : comment
-      )
+    if (!attemptId) return;
+    run(() =>
+      attemptsApi.updateReviewComment(attemptId, id, {
+        body: text,
+        resolved: null,
+      })
     );
   };
 
   const deleteComment = (id: string) => {
-    setComments((prev) => prev.filter((comment) => comment.id !== id));
+    if (!attemptId) return;
+    run(() => attemptsApi.deleteReviewComment(attemptId, id));
+  };
+
+  const resolveComment = (id: string) => {
+    if (!attemptId) return;
+    run(() =>
+      attemptsApi.updateReviewComment(attemptId, id, {
+        body: null,
+        resolved: true,
+      })
+    );
   };
 
   const clearComments = () => {
-    setComments([]);
     setDrafts({});
+    if (!attemptId) return;
+    run(() =>
+      Promise.all(
+        openComments.map((comment) =>
+          attemptsApi.deleteReviewComment(attemptId, comment.id)
+        )
+      )
+    );
   };
 
   const setDraft = (key: string, draft: ReviewDraft | null) => {
@@ -79,46 +204,22 @@ export function ReviewProvider({ children }: { children: ReactNode }) {
     });
   };
 
-  const generateReviewMarkdown = () => {
-    if (comments.length === 0) return '';
-
-    const commentsNum = comments.length;
-
-    const header = `## Review Comments (${commentsNum})\n\n`;
-    const formatCodeLine = (line?: string) => {
-      if (!line) return '';
-      if (line.includes('`')) {
-        return `\`\`\`\n${line}\n\`\`\``;
-      }
-      return `\`${line}\``;
-    };
-
-    const commentsMd = comments
-      .map((comment) => {
-        const codeLine = formatCodeLine(comment.codeLine);
-        // Format file paths in comment body with backticks
-        const bodyWithFormattedPaths = comment.text
-          .trim()
-          .replace(/([/\\]?[\w.-]+(?:[/\\][\w.-]+)+)/g, '`$1`');
-        if (codeLine) {
-          return `**${comment.filePath}** (Line ${comment.lineNumber})\n${codeLine}\n\n> ${bodyWithFormattedPaths}\n`;
-        }
-        return `**${comment.filePath}** (Line ${comment.lineNumber})\n\n> ${bodyWithFormattedPaths}\n`;
-      })
-      .join('\n');
-
-    return header + commentsMd;
-  };
+  // Preview of what the server bundles into the follow-up prompt
+  const generateReviewMarkdown = () =>
+    openComments.length === 0 ? '' : (reviewPrompt ?? '');
 
   return (
     <ReviewContext.Provider
       value={{
         comments,
+        openComments,
         drafts,
         addComment,
         updateComment,
         deleteComment,
+        resolveComment,
         clearComments,
+        refreshComments,
         setDraft,
         generateReviewMarkdown,
       }}
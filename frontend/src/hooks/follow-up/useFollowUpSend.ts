@@ -6,12 +6,12 @@ type Args = {
   attemptId?: string;
   message: string;
   conflictMarkdown: string | null;
-  reviewMarkdown: string;
+  sendReview: boolean;
   clickedMarkdown?: string;
   selectedVariant: string | null;
   images: ImageResponse[];
   newlyUploadedImageIds: string[];
-  clearComments: () => void;
+  refreshComments: () => void;
   clearClickedElements?: () => void;
   jumpToLogsTab: () => void;
   onAfterSendCleanup: () => void;
@@ -22,12 +22,12 @@ export function useFollowUpSend({
   attemptId,
   message,
   conflictMarkdown,
-  reviewMarkdown,
+  sendReview,
   clickedMarkdown,
   selectedVariant,
   images,
   newlyUploadedImageIds,
-  clearComments,
+  refreshComments,
   clearClickedElements,
   jumpToLogsTab,
   onAfterSendCleanup,
@@ -39,15 +39,15 @@ export function useFollowUpSend({
   const onSendFollowUp = useCallback(async () => {
     if (!attemptId) return;
     const extraMessage = message.trim();
+    // Open review comments are bundled into the prompt by the server
     const finalPrompt = [
       conflictMarkdown,
       clickedMarkdown?.trim(),
-      reviewMarkdown?.trim(),
       extraMessage,
     ]
       .filter(Boolean)
       .join('\n\n');
-    if (!finalPrompt) return;
+    if (!finalPrompt && !sendReview) return;
     try {
       setIsSendingFollowUp(true);
       setFollowUpError(null);
@@ -64,9 +64,10 @@ export function useFollowUpSend({
         retry_process_id: null,
         force_when_dirty: null,
         perform_git_reset: null,
+        send_review: sendReview,
       } as any);
       setMessage('');
-      clearComments();
+      refreshComments();
       clearClickedElements?.();
       onAfterSendCleanup();
       jumpToLogsTab();
@@ -82,12 +83,12 @@ export function useFollowUpSend({
     attemptId,
     message,
     conflictMarkdown,
-    reviewMarkdown,
+    sendReview,
     clickedMarkdown,
     newlyUploadedImageIds,
     images,
     selectedVariant,
-    clearComments,
+    refreshComments,
     clearClickedElements,
     jumpToLogsTab,
     onAfterSendCleanup,
@@ -1,5 +1,5 @@
 import { useMemo } from 'react';
-import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
 import { attemptsApi, executionProcessesApi } from '@/lib/api';
 import { useAttemptExecution } from '@/hooks/useAttemptExecution';
 import type { ExecutionProcess } from 'shared/types';
@@ -36,6 +36,14 @@ export function useDevServer(
       )[0];
   }, [attemptData.processes]);
 
+  // Managed port and detected URL; polled until the running server prints its URL
+  const { data: devServerInfo } = useQuery({
+    queryKey: ['devServer', attemptId, runningDevServer?.id],
+    queryFn: () => attemptsApi.getDevServer(attemptId!),
+    enabled: !!attemptId && !!runningDevServer,
+    refetchInterval: (query) => (query.state.data?.url ? false : 2000),
+  });
+
   // Start mutation
   const startMutation = useMutation({
     mutationKey: ['startDevServer', attemptId],
@@ -88,5 +96,6 @@ export function useDevServer(
     isStopping: stopMutation.isPending,
     runningDevServer,
     latestDevServerProcess,
+    devServerInfo: runningDevServer ? devServerInfo : undefined,
   };
 }
@@ -0,0 +1,27 @@
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { attemptsApi } from '@/lib/api';
+import type { TaskAttempt } from 'shared/types';
+
+export function usePinWorktree(attemptId: string | undefined) {
+  const queryClient = useQueryClient();
+
+  return useMutation<TaskAttempt, unknown, boolean>({
+    mutationFn: async (pinned) => {
+      if (!attemptId) {
+        throw new Error('Attempt id is not set');
+      }
+      return attemptsApi.pinWorktree(attemptId, pinned);
+    },
+    onSuccess: (attempt) => {
+      queryClient.invalidateQueries({
+        queryKey: ['taskAttempt', attempt.id],
+      });
+      queryClient.invalidateQueries({
+        queryKey: ['taskAttempts', attempt.task_id],
+      });
+    },
+    onError: (err) => {
+      console.error('Failed to update worktree pin:', err);
+    },
+  });
+}
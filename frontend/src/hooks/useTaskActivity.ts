@@ -0,0 +1,41 @@
+import { useCallback } from 'react';
+import { useJsonPatchWsStream } from './useJsonPatchWsStream';
+import type { TaskActivity } from 'shared/types';
+
+type TaskActivityState = {
+  activity: Record<string, TaskActivity>;
+};
+
+/**
+ * Stream a task's activity timeline via WebSocket (JSON Patch), oldest first.
+ * Server sends initial snapshot: replace /activity with an object keyed by entry key.
+ * Live updates arrive at /activity/<key> via add/remove operations.
+ */
+export const useTaskActivity = (taskId: string | undefined) => {
+  const endpoint = taskId
+    ? `/api/tasks/${taskId}/activity/stream/ws`
+    : undefined;
+
+  const initialData = useCallback(
+    (): TaskActivityState => ({ activity: {} }),
+    []
+  );
+
+  const { data, isConnected, error } =
+    useJsonPatchWsStream<TaskActivityState>(endpoint, !!taskId, initialData);
+
+  // Entries at the same instant, like a process finishing and committing, sort by key
+  const activity = Object.values(data?.activity ?? {}).sort(
+    (a, b) =>
+      new Date(a.at as unknown as string).getTime() -
+        new Date(b.at as unknown as string).getTime() ||
+      a.key.localeCompare(b.key)
+  );
+
+  return {
+    activity,
+    isLoading: !!taskId && !data && !error,
+    isConnected,
+    error,
+  };
+};
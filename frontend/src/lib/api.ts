@@ -1,6 +1,8 @@
 // Import all necessary types from shared types
 
 import {
+  AnalyticsReport,
+  AnalyticsReportQuery,
   ApprovalStatus,
   ApiResponse,
   BranchStatus,
@@ -17,8 +19,10 @@ import {
   DevicePollStatus,
   DirectoryListResponse,
   DirectoryEntry,
+  DevServerInfo,
   EditorType,
   ExecutionProcess,
+  ExportFormat,
   GitBranch,
   Project,
   CreateProject,
@@ -26,6 +30,8 @@ import {
   SearchResult,
   Task,
   TaskAttempt,
+  TaskMetrics,
+  TaskMetricsQuery,
   TaskRelationships,
   Tag,
   TagSearchParams,
@@ -41,6 +47,7 @@ import {
   CreateTaskTemplateGroup,
   UpdateTaskTemplateGroup,
   UserSystemInfo,
+  NotificationChannelConfig,
   GitHubServiceError,
   UpdateRetryFollowUpDraftRequest,
   McpServerQuery,
@@ -51,9 +58,25 @@ import {
   UpdateFollowUpDraftRequest,
   GitOperationError,
   ApprovalResponse,
+  MergeTaskAttemptRequest,
   RebaseTaskAttemptRequest,
+  ResolveConflictsRequest,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
+  WorktreeDiskUsageReport,
+  ProjectRepository,
+  CreateProjectRepository,
+  TaskAttemptRepositoryStatus,
+  ReviewComment,
+  CreateReviewComment,
+  UpdateReviewComment,
+  Webhook,
+  CreateWebhook,
+  CreatedWebhook,
+  UpdateWebhook,
+  WebhookDelivery,
+  TranscriptSearchQuery,
+  TranscriptSearchResult,
 } from 'shared/types';
 
 // Re-export types for convenience
@@ -270,6 +293,43 @@ export const projectsApi = {
     );
     return handleApiResponse<SearchResult[]>(response);
   },
+
+  getMetrics: async (
+    id: string,
+    query: Partial<TaskMetricsQuery> = {}
+  ): Promise<TaskMetrics> => {
+    const params = new URLSearchParams();
+    if (query.from) params.set('from', query.from.toISOString());
+    if (query.to) params.set('to', query.to.toISOString());
+    const response = await makeRequest(`/api/projects/${id}/metrics?${params}`);
+    return handleApiResponse<TaskMetrics>(response);
+  },
+
+  getRepositories: async (id: string): Promise<ProjectRepository[]> => {
+    const response = await makeRequest(`/api/projects/${id}/repositories`);
+    return handleApiResponse<ProjectRepository[]>(response);
+  },
+
+  addRepository: async (
+    id: string,
+    data: CreateProjectRepository
+  ): Promise<ProjectRepository> => {
+    const response = await makeRequest(`/api/projects/${id}/repositories`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectRepository>(response);
+  },
+
+  deleteRepository: async (id: string, repositoryId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/repositories/${repositoryId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
 };
 
 // Task Management APIs
@@ -327,6 +387,17 @@ export const attemptsApi = {
     return handleApiResponse<TaskRelationships>(response);
   },
 
+  /** Download URL of the attempt's coding agent conversations */
+  exportUrl: (attemptId: string, format: ExportFormat): string =>
+    `/api/task-attempts/${attemptId}/export?format=${format}`,
+
+  getStackedAttempts: async (attemptId: string): Promise<TaskAttempt[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/stacked-attempts`
+    );
+    return handleApiResponse<TaskAttempt[]>(response);
+  },
+
   getAll: async (taskId: string): Promise<TaskAttempt[]> => {
     const response = await makeRequest(`/api/task-attempts?task_id=${taskId}`);
     return handleApiResponse<TaskAttempt[]>(response);
@@ -486,11 +557,15 @@ export const attemptsApi = {
     return handleApiResponse<BranchStatus>(response);
   },
 
-  merge: async (attemptId: string): Promise<void> => {
+  merge: async (
+    attemptId: string,
+    data: MergeTaskAttemptRequest = { strategy: null, commit_message: null }
+  ): Promise<void> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/merge`,
       {
         method: 'POST',
+        body: JSON.stringify(data),
       }
     );
     return handleApiResponse<void>(response);
@@ -541,6 +616,20 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  resolveConflicts: async (
+    attemptId: string,
+    data: ResolveConflictsRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
   createPR: async (
     attemptId: string,
     data: CreateGitHubPrRequest
@@ -561,6 +650,154 @@ export const attemptsApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  getDevServer: async (attemptId: string): Promise<DevServerInfo | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/dev-server`
+    );
+    return handleApiResponse<DevServerInfo | null>(response);
+  },
+
+  pinWorktree: async (
+    attemptId: string,
+    pinned: boolean
+  ): Promise<TaskAttempt> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pin-worktree`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ pinned }),
+      }
+    );
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  getRepositories: async (
+    attemptId: string
+  ): Promise<TaskAttemptRepositoryStatus[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/repositories`
+    );
+    return handleApiResponse<TaskAttemptRepositoryStatus[]>(response);
+  },
+
+  mergeRepository: async (
+    attemptId: string,
+    repositoryId: string,
+    data: MergeTaskAttemptRequest = { strategy: null, commit_message: null }
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/repositories/${repositoryId}/merge`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  pushRepository: async (
+    attemptId: string,
+    repositoryId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/repositories/${repositoryId}/push`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  createRepositoryPR: async (
+    attemptId: string,
+    repositoryId: string,
+    data: CreateGitHubPrRequest
+  ): Promise<Result<string, GitHubServiceError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/repositories/${repositoryId}/pr`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<string, GitHubServiceError>(response);
+  },
+
+  getReviewComments: async (attemptId: string): Promise<ReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`
+    );
+    return handleApiResponse<ReviewComment[]>(response);
+  },
+
+  reanchorReviewComments: async (
+    attemptId: string
+  ): Promise<ReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/reanchor`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<ReviewComment[]>(response);
+  },
+
+  getReviewPrompt: async (attemptId: string): Promise<string> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/prompt`
+    );
+    return handleApiResponse<string>(response);
+  },
+
+  createReviewComment: async (
+    attemptId: string,
+    data: CreateReviewComment
+  ): Promise<ReviewComment> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ReviewComment>(response);
+  },
+
+  updateReviewComment: async (
+    attemptId: string,
+    commentId: string,
+    data: UpdateReviewComment
+  ): Promise<ReviewComment> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/${commentId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ReviewComment>(response);
+  },
+
+  deleteReviewComment: async (
+    attemptId: string,
+    commentId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/${commentId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+export const containersApi = {
+  getDiskUsage: async (): Promise<WorktreeDiskUsageReport> => {
+    const response = await makeRequest('/api/containers/disk-usage');
+    return handleApiResponse<WorktreeDiskUsageReport>(response);
+  },
 };
 
 // Extra helpers
@@ -603,6 +840,10 @@ export const executionProcessesApi = {
     return handleApiResponse<ExecutionProcess[]>(response);
   },
 
+  /** Download URL of the process's conversation */
+  exportUrl: (processId: string, format: ExportFormat): string =>
+    `/api/execution-processes/${processId}/export?format=${format}`,
+
   getDetails: async (processId: string): Promise<ExecutionProcess> => {
     const response = await makeRequest(`/api/execution-processes/${processId}`);
     return handleApiResponse<ExecutionProcess>(response);
@@ -651,6 +892,15 @@ export const configApi = {
     });
     return handleApiResponse<Config>(response);
   },
+  testNotificationChannel: async (
+    channel: NotificationChannelConfig
+  ): Promise<void> => {
+    const response = await makeRequest('/api/notifications/test', {
+      method: 'POST',
+      body: JSON.stringify(channel),
+    });
+    return handleApiResponse<void>(response);
+  },
 };
 
 // GitHub Device Auth APIs
@@ -720,6 +970,67 @@ export const tagsApi = {
   },
 };
 
+export const transcriptsApi = {
+  search: async (
+    query: Partial<TranscriptSearchQuery> & { q: string }
+  ): Promise<TranscriptSearchResult[]> => {
+    const params = new URLSearchParams();
+    Object.entries(query).forEach(([key, value]) => {
+      if (value === null || value === undefined || value === '') return;
+      params.set(
+        key,
+        value instanceof Date ? value.toISOString() : String(value)
+      );
+    });
+    const response = await makeRequest(`/api/transcripts/search?${params}`);
+    return handleApiResponse<TranscriptSearchResult[]>(response);
+  },
+};
+
+export const webhooksApi = {
+  list: async (): Promise<Webhook[]> => {
+    const response = await makeRequest('/api/webhooks');
+    return handleApiResponse<Webhook[]>(response);
+  },
+
+  create: async (data: CreateWebhook): Promise<CreatedWebhook> => {
+    const response = await makeRequest('/api/webhooks', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CreatedWebhook>(response);
+  },
+
+  update: async (webhookId: string, data: UpdateWebhook): Promise<Webhook> => {
+    const response = await makeRequest(`/api/webhooks/${webhookId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Webhook>(response);
+  },
+
+  delete: async (webhookId: string): Promise<void> => {
+    const response = await makeRequest(`/api/webhooks/${webhookId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getDeliveries: async (webhookId: string): Promise<WebhookDelivery[]> => {
+    const response = await makeRequest(
+      `/api/webhooks/${webhookId}/deliveries`
+    );
+    return handleApiResponse<WebhookDelivery[]>(response);
+  },
+
+  test: async (webhookId: string): Promise<WebhookDelivery> => {
+    const response = await makeRequest(`/api/webhooks/${webhookId}/test`, {
+      method: 'POST',
+    });
+    return handleApiResponse<WebhookDelivery>(response);
+  },
+};
+
 // Task Template APIs (all templates are global)
 export const taskTemplatesApi = {
   list: async (params?: { group_id?: string; search?: string }): Promise<TaskTemplate[]> => {
@@ -1100,3 +1411,22 @@ export const taskRelationshipsApi = {
     );
   },
 };
+
+export const analyticsApi = {
+  getReport: async (
+    query: Partial<AnalyticsReportQuery> = {}
+  ): Promise<AnalyticsReport> => {
+    const params = new URLSearchParams();
+    if (query.from) params.set('from', query.from.toISOString());
+    if (query.to) params.set('to', query.to.toISOString());
+    const response = await makeRequest(`/api/analytics/report?${params}`);
+    return handleApiResponse<AnalyticsReport>(response);
+  },
+
+  clearEvents: async (): Promise<number> => {
+    const response = await makeRequest('/api/analytics/events', {
+      method: 'DELETE',
+    });
+    return handleApiResponse<number>(response);
+  },
+};
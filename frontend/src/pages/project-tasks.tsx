@@ -768,7 +768,7 @@ export function ProjectTasks() {
 
   const attemptArea = attempt ? (
     <ClickedElementsProvider attempt={attempt}>
-      <ReviewProvider key={attempt.id}>
+      <ReviewProvider key={attempt.id} attemptId={attempt.id}>
         <ExecutionProcessesProvider key={attempt.id} attemptId={attempt.id}>
           <TasksLayout
             kanban={kanbanContent}
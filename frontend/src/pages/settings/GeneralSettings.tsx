@@ -31,6 +31,7 @@ import {
   BaseCodingAgent,
   EditorType,
   ExecutorProfileId,
+  NotificationChannelConfig,
   SoundFile,
   ThemeMode,
   UiLanguage,
@@ -41,6 +42,10 @@ import { toPrettyCase } from '@/utils/string';
 import { useTheme } from '@/components/theme-provider';
 import { useUserSystem } from '@/components/config-provider';
 import { TagManager } from '@/components/TagManager';
+import {
+  LocalUsageReport,
+  NotificationChannelsEditor,
+} from '@/components/settings';
 import NiceModal from '@ebay/nice-modal-react';
 
 export function GeneralSettings() {
@@ -125,6 +130,24 @@ export function GeneralSettings() {
     [config]
   );
 
+  // Replaced rather than merged, since merge() combines arrays index by index
+  const updateNotificationChannels = useCallback(
+    (channels: NotificationChannelConfig[]) => {
+      setDraft((prev: typeof config) => {
+        if (!prev) return prev;
+        const next = {
+          ...prev,
+          notifications: { ...prev.notifications, channels },
+        };
+        if (!isEqual(next, config)) {
+          setDirty(true);
+        }
+        return next;
+      });
+    },
+    [config]
+  );
+
   // Optional: warn on tab close/navigation with unsaved changes
   useEffect(() => {
     const handler = (e: BeforeUnloadEvent) => {
@@ -726,6 +749,31 @@ export function GeneralSettings() {
               </p>
             </div>
           </div>
+          <NotificationChannelsEditor
+            channels={draft?.notifications.channels ?? []}
+            onChange={updateNotificationChannels}
+          />
+          <div className="space-y-2">
+            <Label htmlFor="notification-base-url">
+              {t('settings.general.notifications.baseUrl.label')}
+            </Label>
+            <Input
+              id="notification-base-url"
+              placeholder="http://127.0.0.1:3000"
+              value={draft?.notifications.base_url ?? ''}
+              onChange={(e) =>
+                updateDraft({
+                  notifications: {
+                    ...draft!.notifications,
+                    base_url: e.target.value || null,
+                  },
+                })
+              }
+            />
+            <p className="text-sm text-muted-foreground">
+              {t('settings.general.notifications.baseUrl.helper')}
+            </p>
+          </div>
         </CardContent>
       </Card>
 
@@ -754,6 +802,27 @@ export function GeneralSettings() {
               </p>
             </div>
           </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="local-analytics-enabled"
+              checked={draft?.local_analytics_enabled ?? false}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({ local_analytics_enabled: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label
+                htmlFor="local-analytics-enabled"
+                className="cursor-pointer"
+              >
+                {t('settings.general.privacy.localAnalytics.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.privacy.localAnalytics.helper')}
+              </p>
+            </div>
+          </div>
+          {config?.local_analytics_enabled && <LocalUsageReport />}
         </CardContent>
       </Card>
 
@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useMemo, useState } from 'react';
 import { useSearchParams } from 'react-router-dom';
+import { useQuery } from '@tanstack/react-query';
 import { useTranslation } from 'react-i18next';
 import { isEqual } from 'lodash';
 import {
@@ -19,15 +20,30 @@ import {
 } from '@/components/ui/select';
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
+import { Checkbox } from '@/components/ui/checkbox';
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Loader2, Folder } from 'lucide-react';
 import { useProjects } from '@/hooks/useProjects';
 import { useProjectMutations } from '@/hooks/useProjectMutations';
 import { useScriptPlaceholders } from '@/hooks/useScriptPlaceholders';
 import { CopyFilesField } from '@/components/projects/copy-files-field';
+import { ProjectRepositoriesCard } from '@/components/projects/project-repositories-card';
 import { AutoExpandingTextarea } from '@/components/ui/auto-expanding-textarea';
 import { showFolderPicker } from '@/lib/modals';
-import type { Project, UpdateProject } from 'shared/types';
+import { containersApi } from '@/lib/api';
+import type {
+  CommitMessageStrategy,
+  CommitMessageValidation,
+  CommitSigningFormat,
+  DevServerRestartPolicy,
+  MergeStrategy,
+  Project,
+  UpdateProject,
+} from 'shared/types';
+
+// Rendered literally in the copy_files help text
+const COPY_FILES_PLACEHOLDERS =
+  '{{branch}}, {{base_branch}}, {{port}}, {{attempt_id}}, {{task_id}}, {{worktree}}';
 
 interface ProjectFormState {
   name: string;
@@ -36,6 +52,31 @@ interface ProjectFormState {
   dev_script: string;
   cleanup_script: string;
   copy_files: string;
+  merge_strategy: MergeStrategy;
+  merge_commit_template: string;
+  commit_message_strategy: CommitMessageStrategy;
+  commit_message_template: string;
+  commit_message_validation: CommitMessageValidation;
+  commit_message_pattern: string;
+  commit_author_name: string;
+  commit_author_email: string;
+  commit_co_authors: string;
+  commit_signing_format: CommitSigningFormat;
+  commit_signing_key: string;
+  container_image: string;
+  container_cpus: string;
+  container_memory: string;
+  worktree_retention_hours: string;
+  worktree_keep_recent: string;
+  worktree_keep_in_review: boolean;
+  worktree_max_size_mb: string;
+  worktree_snapshot_enabled: boolean;
+  dependency_cache: string;
+  dev_server_health_check: string;
+  dev_server_restart_policy: DevServerRestartPolicy;
+  dev_server_max_restarts: string;
+  working_dir: string;
+  sparse_checkout: string;
 }
 
 function projectToFormState(project: Project): ProjectFormState {
@@ -46,9 +87,50 @@ function projectToFormState(project: Project): ProjectFormState {
     dev_script: project.dev_script ?? '',
     cleanup_script: project.cleanup_script ?? '',
     copy_files: project.copy_files ?? '',
+    merge_strategy: project.merge_strategy,
+    merge_commit_template: project.merge_commit_template ?? '',
+    commit_message_strategy: project.commit_message_strategy,
+    commit_message_template: project.commit_message_template ?? '',
+    commit_message_validation: project.commit_message_validation,
+    commit_message_pattern: project.commit_message_pattern ?? '',
+    commit_author_name: project.commit_author_name ?? '',
+    commit_author_email: project.commit_author_email ?? '',
+    commit_co_authors: project.commit_co_authors ?? '',
+    commit_signing_format: project.commit_signing_format,
+    commit_signing_key: project.commit_signing_key ?? '',
+    container_image: project.container_image ?? '',
+    container_cpus: project.container_cpus ?? '',
+    container_memory: project.container_memory ?? '',
+    worktree_retention_hours: String(project.worktree_retention_hours),
+    worktree_keep_recent: project.worktree_keep_recent?.toString() ?? '',
+    worktree_keep_in_review: project.worktree_keep_in_review,
+    worktree_max_size_mb: project.worktree_max_size_mb?.toString() ?? '',
+    worktree_snapshot_enabled: project.worktree_snapshot_enabled,
+    dependency_cache: project.dependency_cache ?? '',
+    dev_server_health_check: project.dev_server_health_check ?? '',
+    dev_server_restart_policy: project.dev_server_restart_policy,
+    dev_server_max_restarts: String(project.dev_server_max_restarts),
+    working_dir: project.working_dir ?? '',
+    sparse_checkout: project.sparse_checkout ?? '',
   };
 }
 
+function parseOptionalInt(value: string): number | null {
+  const parsed = Number.parseInt(value.trim(), 10);
+  return Number.isNaN(parsed) ? null : parsed;
+}
+
+function formatBytes(bytes: number): string {
+  const units = ['B', 'KB', 'MB', 'GB', 'TB'];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit += 1;
+  }
+  return `${value.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`;
+}
+
 export function ProjectSettings() {
   const [searchParams, setSearchParams] = useSearchParams();
   const projectIdParam = searchParams.get('projectId') ?? '';
@@ -76,6 +158,19 @@ export function ProjectSettings() {
   // Get OS-appropriate script placeholders
   const placeholders = useScriptPlaceholders();
 
+  const { data: diskUsage } = useQuery({
+    queryKey: ['worktreeDiskUsage'],
+    queryFn: () => containersApi.getDiskUsage(),
+    staleTime: 60_000,
+  });
+  const projectWorktreeBytes = useMemo(
+    () =>
+      diskUsage?.worktrees
+        .filter((worktree) => worktree.project_id === selectedProjectId)
+        .reduce((total, worktree) => total + worktree.size_bytes, 0) ?? 0,
+    [diskUsage, selectedProjectId]
+  );
+
   // Check for unsaved changes
   const hasUnsavedChanges = useMemo(() => {
     if (!draft || !selectedProject) return false;
@@ -214,6 +309,35 @@ export function ProjectSettings() {
         dev_script: draft.dev_script.trim() || null,
         cleanup_script: draft.cleanup_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
+        merge_strategy: draft.merge_strategy,
+        merge_commit_template: draft.merge_commit_template.trim() || null,
+        commit_message_strategy: draft.commit_message_strategy,
+        commit_message_template: draft.commit_message_template.trim() || null,
+        commit_message_validation: draft.commit_message_validation,
+        commit_message_pattern: draft.commit_message_pattern.trim() || null,
+        commit_author_name: draft.commit_author_name.trim() || null,
+        commit_author_email: draft.commit_author_email.trim() || null,
+        commit_co_authors: draft.commit_co_authors.trim() || null,
+        commit_signing_format: draft.commit_signing_format,
+        commit_signing_key: draft.commit_signing_key.trim() || null,
+        container_image: draft.container_image.trim() || null,
+        container_cpus: draft.container_cpus.trim() || null,
+        container_memory: draft.container_memory.trim() || null,
+        worktree_retention_hours: parseOptionalInt(
+          draft.worktree_retention_hours
+        ),
+        worktree_keep_recent: parseOptionalInt(draft.worktree_keep_recent),
+        worktree_keep_in_review: draft.worktree_keep_in_review,
+        worktree_max_size_mb: parseOptionalInt(draft.worktree_max_size_mb),
+        worktree_snapshot_enabled: draft.worktree_snapshot_enabled,
+        dependency_cache: draft.dependency_cache.trim() || null,
+        dev_server_health_check: draft.dev_server_health_check.trim() || null,
+        dev_server_restart_policy: draft.dev_server_restart_policy,
+        dev_server_max_restarts: parseOptionalInt(
+          draft.dev_server_max_restarts
+        ),
+        working_dir: draft.working_dir.trim() || null,
+        sparse_checkout: draft.sparse_checkout.trim() || null,
       };
 
       updateProject.mutate({
@@ -389,6 +513,8 @@ export function ProjectSettings() {
             </CardContent>
           </Card>
 
+          <ProjectRepositoriesCard projectId={selectedProject.id} />
+
           <Card>
             <CardHeader>
               <CardTitle>{t('settings.projects.scripts.title')}</CardTitle>
@@ -433,6 +559,71 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="dev-server-health-check">
+                  {t('settings.projects.scripts.devHealth.label')}
+                </Label>
+                <Input
+                  id="dev-server-health-check"
+                  value={draft.dev_server_health_check}
+                  onChange={(e) =>
+                    updateDraft({ dev_server_health_check: e.target.value })
+                  }
+                  placeholder="http:/health"
+                  className="font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.devHealth.helper')}
+                </p>
+              </div>
+
+              <div className="grid grid-cols-1 gap-4 sm:grid-cols-2">
+                <div className="space-y-2">
+                  <Label htmlFor="dev-server-restart-policy">
+                    {t('settings.projects.scripts.devRestart.label')}
+                  </Label>
+                  <Select
+                    value={draft.dev_server_restart_policy}
+                    onValueChange={(value) =>
+                      updateDraft({
+                        dev_server_restart_policy:
+                          value as DevServerRestartPolicy,
+                      })
+                    }
+                  >
+                    <SelectTrigger id="dev-server-restart-policy">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="never">
+                        {t('settings.projects.scripts.devRestart.never')}
+                      </SelectItem>
+                      <SelectItem value="on_failure">
+                        {t('settings.projects.scripts.devRestart.onFailure')}
+                      </SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="dev-server-max-restarts">
+                    {t('settings.projects.scripts.devRestart.maxRestarts')}
+                  </Label>
+                  <Input
+                    id="dev-server-max-restarts"
+                    type="number"
+                    min={0}
+                    value={draft.dev_server_max_restarts}
+                    onChange={(e) =>
+                      updateDraft({ dev_server_max_restarts: e.target.value })
+                    }
+                    disabled={draft.dev_server_restart_policy === 'never'}
+                  />
+                </div>
+              </div>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.projects.scripts.devRestart.helper')}
+              </p>
+
               <div className="space-y-2">
                 <Label htmlFor="cleanup-script">
                   {t('settings.projects.scripts.cleanup.label')}
@@ -460,12 +651,562 @@ export function ProjectSettings() {
                   projectId={selectedProject.id}
                 />
                 <p className="text-sm text-muted-foreground">
-                  {t('settings.projects.scripts.copyFiles.helper')}
+                  {t('settings.projects.scripts.copyFiles.helper', {
+                    placeholders: COPY_FILES_PLACEHOLDERS,
+                  })}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="dependency-cache">
+                  {t('settings.projects.scripts.dependencyCache.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="dependency-cache"
+                  value={draft.dependency_cache}
+                  onChange={(e) =>
+                    updateDraft({ dependency_cache: e.target.value })
+                  }
+                  placeholder={t(
+                    'settings.projects.scripts.dependencyCache.placeholder'
+                  )}
+                  maxRows={8}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.dependencyCache.helper')}
+                </p>
+              </div>
+            </CardContent>
+          </Card>
+
+          <Card>
+            <CardHeader>
+              <CardTitle>{t('settings.projects.scope.title')}</CardTitle>
+              <CardDescription>
+                {t('settings.projects.scope.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="working-dir">
+                  {t('settings.projects.scope.workingDir.label')}
+                </Label>
+                <Input
+                  id="working-dir"
+                  type="text"
+                  value={draft.working_dir}
+                  onChange={(e) => updateDraft({ working_dir: e.target.value })}
+                  placeholder={t(
+                    'settings.projects.scope.workingDir.placeholder'
+                  )}
+                  className="font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scope.workingDir.helper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="sparse-checkout">
+                  {t('settings.projects.scope.sparseCheckout.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="sparse-checkout"
+                  value={draft.sparse_checkout}
+                  onChange={(e) =>
+                    updateDraft({ sparse_checkout: e.target.value })
+                  }
+                  placeholder={t(
+                    'settings.projects.scope.sparseCheckout.placeholder'
+                  )}
+                  maxRows={8}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scope.sparseCheckout.helper')}
                 </p>
               </div>
             </CardContent>
           </Card>
 
+          <Card>
+            <CardHeader>
+              <CardTitle>{t('settings.projects.merging.title')}</CardTitle>
+              <CardDescription>
+                {t('settings.projects.merging.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="merge-strategy">
+                  {t('settings.projects.merging.strategy.label')}
+                </Label>
+                <Select
+                  value={draft.merge_strategy}
+                  onValueChange={(value) =>
+                    updateDraft({ merge_strategy: value as MergeStrategy })
+                  }
+                >
+                  <SelectTrigger id="merge-strategy">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="squash">
+                      {t('settings.projects.merging.strategy.squash')}
+                    </SelectItem>
+                    <SelectItem value="rebase_fast_forward">
+                      {t('settings.projects.merging.strategy.rebaseFastForward')}
+                    </SelectItem>
+                    <SelectItem value="merge_commit">
+                      {t('settings.projects.merging.strategy.mergeCommit')}
+                    </SelectItem>
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.merging.strategy.helper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="merge-commit-template">
+                  {t('settings.projects.merging.template.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="merge-commit-template"
+                  value={draft.merge_commit_template}
+                  onChange={(e) =>
+                    updateDraft({ merge_commit_template: e.target.value })
+                  }
+                  placeholder={'{task_title} (vibe-kanban {task_id_short})'}
+                  maxRows={8}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.merging.template.helper')}
+                </p>
+              </div>
+            </CardContent>
+          </Card>
+
+          <Card>
+            <CardHeader>
+              <CardTitle>{t('settings.projects.commitMessages.title')}</CardTitle>
+              <CardDescription>
+                {t('settings.projects.commitMessages.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="commit-message-strategy">
+                  {t('settings.projects.commitMessages.strategy.label')}
+                </Label>
+                <Select
+                  value={draft.commit_message_strategy}
+                  onValueChange={(value) =>
+                    updateDraft({
+                      commit_message_strategy: value as CommitMessageStrategy,
+                    })
+                  }
+                >
+                  <SelectTrigger id="commit-message-strategy">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="agent_summary">
+                      {t('settings.projects.commitMessages.strategy.agentSummary')}
+                    </SelectItem>
+                    <SelectItem value="conventional_commit">
+                      {t(
+                        'settings.projects.commitMessages.strategy.conventionalCommit'
+                      )}
+                    </SelectItem>
+                    <SelectItem value="template">
+                      {t('settings.projects.commitMessages.strategy.template')}
+                    </SelectItem>
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.commitMessages.strategy.helper')}
+                </p>
+              </div>
+
+              {draft.commit_message_strategy === 'template' && (
+                <div className="space-y-2">
+                  <Label htmlFor="commit-message-template">
+                    {t('settings.projects.commitMessages.template.label')}
+                  </Label>
+                  <AutoExpandingTextarea
+                    id="commit-message-template"
+                    value={draft.commit_message_template}
+                    onChange={(e) =>
+                      updateDraft({ commit_message_template: e.target.value })
+                    }
+                    placeholder={'{task_title} ({task_id_short})\n\n{summary}'}
+                    maxRows={8}
+                    className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.commitMessages.template.helper')}
+                  </p>
+                </div>
+              )}
+
+              <div className="space-y-2">
+                <Label htmlFor="commit-message-validation">
+                  {t('settings.projects.commitMessages.validation.label')}
+                </Label>
+                <Select
+                  value={draft.commit_message_validation}
+                  onValueChange={(value) =>
+                    updateDraft({
+                      commit_message_validation:
+                        value as CommitMessageValidation,
+                    })
+                  }
+                >
+                  <SelectTrigger id="commit-message-validation">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">
+                      {t('settings.projects.commitMessages.validation.none')}
+                    </SelectItem>
+                    <SelectItem value="conventional_commit">
+                      {t(
+                        'settings.projects.commitMessages.validation.conventionalCommit'
+                      )}
+                    </SelectItem>
+                    <SelectItem value="regex">
+                      {t('settings.projects.commitMessages.validation.regex')}
+                    </SelectItem>
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.commitMessages.validation.helper')}
+                </p>
+              </div>
+
+              {draft.commit_message_validation === 'regex' && (
+                <div className="space-y-2">
+                  <Label htmlFor="commit-message-pattern">
+                    {t('settings.projects.commitMessages.pattern.label')}
+                  </Label>
+                  <Input
+                    id="commit-message-pattern"
+                    value={draft.commit_message_pattern}
+                    onChange={(e) =>
+                      updateDraft({ commit_message_pattern: e.target.value })
+                    }
+                    placeholder={'^[A-Z]+-\\d+: '}
+                    className="font-mono"
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.commitMessages.pattern.helper')}
+                  </p>
+                </div>
+              )}
+            </CardContent>
+          </Card>
+
+          <Card>
+            <CardHeader>
+              <CardTitle>{t('settings.projects.commitIdentity.title')}</CardTitle>
+              <CardDescription>
+                {t('settings.projects.commitIdentity.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="grid gap-4 sm:grid-cols-2">
+                <div className="space-y-2">
+                  <Label htmlFor="commit-author-name">
+                    {t('settings.projects.commitIdentity.authorName.label')}
+                  </Label>
+                  <Input
+                    id="commit-author-name"
+                    value={draft.commit_author_name}
+                    onChange={(e) =>
+                      updateDraft({ commit_author_name: e.target.value })
+                    }
+                    placeholder={t(
+                      'settings.projects.commitIdentity.authorName.placeholder'
+                    )}
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="commit-author-email">
+                    {t('settings.projects.commitIdentity.authorEmail.label')}
+                  </Label>
+                  <Input
+                    id="commit-author-email"
+                    type="email"
+                    value={draft.commit_author_email}
+                    onChange={(e) =>
+                      updateDraft({ commit_author_email: e.target.value })
+                    }
+                    placeholder="agent@example.com"
+                  />
+                </div>
+              </div>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.projects.commitIdentity.authorHelper')}
+              </p>
+
+              <div className="space-y-2">
+                <Label htmlFor="commit-co-authors">
+                  {t('settings.projects.commitIdentity.coAuthors.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="commit-co-authors"
+                  value={draft.commit_co_authors}
+                  onChange={(e) =>
+                    updateDraft({ commit_co_authors: e.target.value })
+                  }
+                  placeholder="Jane Doe <jane@example.com>"
+                  maxRows={6}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.commitIdentity.coAuthors.helper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="commit-signing-format">
+                  {t('settings.projects.commitIdentity.signing.label')}
+                </Label>
+                <Select
+                  value={draft.commit_signing_format}
+                  onValueChange={(value) =>
+                    updateDraft({
+                      commit_signing_format: value as CommitSigningFormat,
+                    })
+                  }
+                >
+                  <SelectTrigger id="commit-signing-format">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">
+                      {t('settings.projects.commitIdentity.signing.none')}
+                    </SelectItem>
+                    <SelectItem value="gpg">
+                      {t('settings.projects.commitIdentity.signing.gpg')}
+                    </SelectItem>
+                    <SelectItem value="ssh">
+                      {t('settings.projects.commitIdentity.signing.ssh')}
+                    </SelectItem>
+                  </SelectContent>
+                </Select>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.commitIdentity.signing.helper')}
+                </p>
+              </div>
+
+              {draft.commit_signing_format !== 'none' && (
+                <div className="space-y-2">
+                  <Label htmlFor="commit-signing-key">
+                    {t('settings.projects.commitIdentity.signingKey.label')}
+                  </Label>
+                  <Input
+                    id="commit-signing-key"
+                    value={draft.commit_signing_key}
+                    onChange={(e) =>
+                      updateDraft({ commit_signing_key: e.target.value })
+                    }
+                    placeholder={
+                      draft.commit_signing_format === 'ssh'
+                        ? '~/.ssh/id_ed25519'
+                        : '3AA5C34371567BD2'
+                    }
+                    className="font-mono"
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.commitIdentity.signingKey.helper')}
+                  </p>
+                </div>
+              )}
+            </CardContent>
+          </Card>
+
+          <Card>
+            <CardHeader>
+              <CardTitle>{t('settings.projects.container.title')}</CardTitle>
+              <CardDescription>
+                {t('settings.projects.container.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="container-image">
+                  {t('settings.projects.container.image.label')}
+                </Label>
+                <Input
+                  id="container-image"
+                  value={draft.container_image}
+                  onChange={(e) =>
+                    updateDraft({ container_image: e.target.value })
+                  }
+                  placeholder="ghcr.io/acme/agent-sandbox:latest"
+                  className="font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.container.image.helper')}
+                </p>
+              </div>
+
+              {draft.container_image.trim() && (
+                <>
+                  <div className="grid gap-4 sm:grid-cols-2">
+                    <div className="space-y-2">
+                      <Label htmlFor="container-cpus">
+                        {t('settings.projects.container.cpus.label')}
+                      </Label>
+                      <Input
+                        id="container-cpus"
+                        value={draft.container_cpus}
+                        onChange={(e) =>
+                          updateDraft({ container_cpus: e.target.value })
+                        }
+                        placeholder="2"
+                      />
+                    </div>
+                    <div className="space-y-2">
+                      <Label htmlFor="container-memory">
+                        {t('settings.projects.container.memory.label')}
+                      </Label>
+                      <Input
+                        id="container-memory"
+                        value={draft.container_memory}
+                        onChange={(e) =>
+                          updateDraft({ container_memory: e.target.value })
+                        }
+                        placeholder="4g"
+                      />
+                    </div>
+                  </div>
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.container.limitsHelper')}
+                  </p>
+                </>
+              )}
+            </CardContent>
+          </Card>
+
+          <Card>
+            <CardHeader>
+              <CardTitle>
+                {t('settings.projects.worktreeRetention.title')}
+              </CardTitle>
+              <CardDescription>
+                {t('settings.projects.worktreeRetention.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              <div className="grid gap-4 sm:grid-cols-3">
+                <div className="space-y-2">
+                  <Label htmlFor="worktree-retention-hours">
+                    {t('settings.projects.worktreeRetention.hours.label')}
+                  </Label>
+                  <Input
+                    id="worktree-retention-hours"
+                    type="number"
+                    min={1}
+                    value={draft.worktree_retention_hours}
+                    onChange={(e) =>
+                      updateDraft({ worktree_retention_hours: e.target.value })
+                    }
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="worktree-keep-recent">
+                    {t('settings.projects.worktreeRetention.keepRecent.label')}
+                  </Label>
+                  <Input
+                    id="worktree-keep-recent"
+                    type="number"
+                    min={0}
+                    value={draft.worktree_keep_recent}
+                    onChange={(e) =>
+                      updateDraft({ worktree_keep_recent: e.target.value })
+                    }
+                    placeholder="0"
+                  />
+                </div>
+                <div className="space-y-2">
+                  <Label htmlFor="worktree-max-size">
+                    {t('settings.projects.worktreeRetention.maxSize.label')}
+                  </Label>
+                  <Input
+                    id="worktree-max-size"
+                    type="number"
+                    min={1}
+                    value={draft.worktree_max_size_mb}
+                    onChange={(e) =>
+                      updateDraft({ worktree_max_size_mb: e.target.value })
+                    }
+                    placeholder={t(
+                      'settings.projects.worktreeRetention.maxSize.placeholder'
+                    )}
+                  />
+                </div>
+              </div>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.projects.worktreeRetention.helper')}
+              </p>
+
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="worktree-keep-in-review"
+                  checked={draft.worktree_keep_in_review}
+                  onCheckedChange={(checked: boolean) =>
+                    updateDraft({ worktree_keep_in_review: checked })
+                  }
+                />
+                <Label
+                  htmlFor="worktree-keep-in-review"
+                  className="cursor-pointer"
+                >
+                  {t('settings.projects.worktreeRetention.keepInReview.label')}
+                </Label>
+              </div>
+
+              <div className="space-y-1">
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="worktree-snapshot-enabled"
+                    checked={draft.worktree_snapshot_enabled}
+                    onCheckedChange={(checked: boolean) =>
+                      updateDraft({ worktree_snapshot_enabled: checked })
+                    }
+                  />
+                  <Label
+                    htmlFor="worktree-snapshot-enabled"
+                    className="cursor-pointer"
+                  >
+                    {t('settings.projects.worktreeRetention.snapshot.label')}
+                  </Label>
+                </div>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.worktreeRetention.snapshot.helper')}
+                </p>
+              </div>
+
+              {diskUsage && (
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.worktreeRetention.usage', {
+                    project: formatBytes(projectWorktreeBytes),
+                    total: formatBytes(diskUsage.total_bytes),
+                    available:
+                      diskUsage.available_bytes !== null
+                        ? formatBytes(diskUsage.available_bytes)
+                        : '?',
+                  })}
+                </p>
+              )}
+            </CardContent>
+          </Card>
+
           {/* Sticky Save Button */}
           <div className="sticky bottom-0 z-10 bg-background/80 backdrop-blur-sm border-t py-4">
             <div className="flex items-center justify-between">
@@ -1,6 +1,15 @@
 import { NavLink, Outlet, useNavigate, useLocation } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { Settings, Cpu, Server, X, FolderOpen, Link2, FileText } from 'lucide-react';
+import {
+  Settings,
+  Cpu,
+  Server,
+  X,
+  FolderOpen,
+  Link2,
+  FileText,
+  Webhook,
+} from 'lucide-react';
 import { cn } from '@/lib/utils';
 import { Button } from '@/components/ui/button';
 import { useEffect } from 'react';
@@ -33,6 +42,10 @@ const settingsNavigation = [
     path: 'task-templates',
     icon: FileText,
   },
+  {
+    path: 'webhooks',
+    icon: Webhook,
+  },
 ];
 
 export function SettingsLayout() {
@@ -0,0 +1,466 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Badge } from '@/components/ui/badge';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Loader2, Plus, Send, Trash2 } from 'lucide-react';
+import { webhooksApi } from '@/lib/api';
+import { useProjects } from '@/hooks/useProjects';
+import type {
+  Webhook,
+  WebhookDeliveryStatus,
+  WebhookEventType,
+} from 'shared/types';
+
+const SUBSCRIBABLE_EVENTS: WebhookEventType[] = [
+  'task_created',
+  'task_status_changed',
+  'attempt_started',
+  'process_completed',
+  'process_failed',
+  'approval_requested',
+  'pr_merged',
+];
+
+const ALL_PROJECTS = 'all';
+
+const deliveryStatusVariant: Record<
+  WebhookDeliveryStatus,
+  'default' | 'secondary' | 'destructive'
+> = {
+  succeeded: 'default',
+  pending: 'secondary',
+  failed: 'destructive',
+};
+
+function WebhookDeliveries({ webhookId }: { webhookId: string }) {
+  const { t } = useTranslation('settings');
+  const { data: deliveries, isLoading } = useQuery({
+    queryKey: ['webhookDeliveries', webhookId],
+    queryFn: () => webhooksApi.getDeliveries(webhookId),
+    refetchInterval: 5000,
+  });
+
+  if (isLoading) {
+    return <Loader2 className="h-4 w-4 animate-spin" />;
+  }
+  if (!deliveries || deliveries.length === 0) {
+    return (
+      <p className="text-sm text-muted-foreground">
+        {t('settings.webhooks.deliveries.empty')}
+      </p>
+    );
+  }
+
+  return (
+    <div className="border rounded-lg overflow-hidden">
+      <table className="w-full text-sm">
+        <thead className="border-b bg-muted/50">
+          <tr>
+            <th className="text-left p-2 font-medium">
+              {t('settings.webhooks.deliveries.event')}
+            </th>
+            <th className="text-left p-2 font-medium">
+              {t('settings.webhooks.deliveries.status')}
+            </th>
+            <th className="text-left p-2 font-medium">
+              {t('settings.webhooks.deliveries.attempts')}
+            </th>
+            <th className="text-left p-2 font-medium">
+              {t('settings.webhooks.deliveries.response')}
+            </th>
+            <th className="text-left p-2 font-medium">
+              {t('settings.webhooks.deliveries.time')}
+            </th>
+          </tr>
+        </thead>
+        <tbody>
+          {deliveries.map((delivery) => (
+            <tr key={delivery.id} className="border-b last:border-0">
+              <td className="p-2 font-mono">{delivery.event_type}</td>
+              <td className="p-2">
+                <Badge variant={deliveryStatusVariant[delivery.status]}>
+                  {t(`settings.webhooks.deliveries.statuses.${delivery.status}`)}
+                </Badge>
+              </td>
+              <td className="p-2">{delivery.attempts}</td>
+              <td className="p-2">
+                {delivery.response_status ?? '—'}
+                {delivery.error && (
+                  <div className="text-xs text-muted-foreground break-all">
+                    {delivery.error}
+                  </div>
+                )}
+              </td>
+              <td className="p-2 whitespace-nowrap">
+                {new Date(delivery.created_at).toLocaleString()}
+              </td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+export function WebhooksSettings() {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+  const { data: projects } = useProjects();
+  const { data: webhooks, isLoading } = useQuery({
+    queryKey: ['webhooks'],
+    queryFn: () => webhooksApi.list(),
+  });
+
+  const [name, setName] = useState('');
+  const [url, setUrl] = useState('');
+  const [secret, setSecret] = useState('');
+  const [projectId, setProjectId] = useState(ALL_PROJECTS);
+  const [events, setEvents] = useState<WebhookEventType[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [createdSecret, setCreatedSecret] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+  const [openLog, setOpenLog] = useState<string | null>(null);
+  const [testing, setTesting] = useState<string | null>(null);
+
+  const refresh = () => {
+    queryClient.invalidateQueries({ queryKey: ['webhooks'] });
+  };
+
+  const toggleEvent = (event: WebhookEventType) => {
+    setEvents((prev) =>
+      prev.includes(event) ? prev.filter((e) => e !== event) : [...prev, event]
+    );
+  };
+
+  const handleCreate = async () => {
+    setError(null);
+    setCreatedSecret(null);
+    setSaving(true);
+    try {
+      const created = await webhooksApi.create({
+        name: name.trim(),
+        url: url.trim(),
+        secret: secret.trim() || null,
+        events,
+        project_id: projectId === ALL_PROJECTS ? null : projectId,
+      });
+      setName('');
+      setUrl('');
+      setSecret('');
+      setEvents([]);
+      setProjectId(ALL_PROJECTS);
+      setCreatedSecret(created.secret);
+      refresh();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleToggleEnabled = async (webhook: Webhook) => {
+    try {
+      await webhooksApi.update(webhook.id, {
+        name: null,
+        url: null,
+        secret: null,
+        events: null,
+        enabled: !webhook.enabled,
+      });
+      refresh();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleDelete = async (webhook: Webhook) => {
+    if (
+      !confirm(t('settings.webhooks.deleteConfirm', { name: webhook.name }))
+    ) {
+      return;
+    }
+    try {
+      await webhooksApi.delete(webhook.id);
+      refresh();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleTest = async (webhook: Webhook) => {
+    setTesting(webhook.id);
+    try {
+      await webhooksApi.test(webhook.id);
+      setOpenLog(webhook.id);
+      queryClient.invalidateQueries({
+        queryKey: ['webhookDeliveries', webhook.id],
+      });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setTesting(null);
+    }
+  };
+
+  const projectName = (id: string | null) =>
+    id === null
+      ? t('settings.webhooks.form.allProjects')
+      : (projects?.find((project) => project.id === id)?.name ?? id);
+
+  return (
+    <div className="space-y-6">
+      <div>
+        <h2 className="text-2xl font-bold">{t('settings.webhooks.title')}</h2>
+        <p className="text-sm text-muted-foreground mt-1">
+          {t('settings.webhooks.description')}
+        </p>
+      </div>
+
+      {error && (
+        <Alert variant="destructive">
+          <AlertDescription>{error}</AlertDescription>
+        </Alert>
+      )}
+
+      {createdSecret && (
+        <Alert>
+          <AlertDescription>
+            {t('settings.webhooks.secretShownOnce')}{' '}
+            <code className="break-all">{createdSecret}</code>
+          </AlertDescription>
+        </Alert>
+      )}
+
+      <Card>
+        <CardHeader>
+          <CardTitle>{t('settings.webhooks.form.title')}</CardTitle>
+          <CardDescription>
+            {t('settings.webhooks.form.description')}
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="grid gap-4 md:grid-cols-2">
+            <div className="space-y-2">
+              <Label htmlFor="webhook-name">
+                {t('settings.webhooks.form.name')}
+              </Label>
+              <Input
+                id="webhook-name"
+                value={name}
+                onChange={(e) => setName(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="webhook-url">
+                {t('settings.webhooks.form.url')}
+              </Label>
+              <Input
+                id="webhook-url"
+                placeholder="https://example.com/hooks/vibe-kanban"
+                value={url}
+                onChange={(e) => setUrl(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="webhook-secret">
+                {t('settings.webhooks.form.secret')}
+              </Label>
+              <Input
+                id="webhook-secret"
+                placeholder={t('settings.webhooks.form.secretPlaceholder')}
+                value={secret}
+                onChange={(e) => setSecret(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="webhook-project">
+                {t('settings.webhooks.form.project')}
+              </Label>
+              <Select value={projectId} onValueChange={setProjectId}>
+                <SelectTrigger id="webhook-project">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value={ALL_PROJECTS}>
+                    {t('settings.webhooks.form.allProjects')}
+                  </SelectItem>
+                  {projects?.map((project) => (
+                    <SelectItem key={project.id} value={project.id}>
+                      {project.name}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          <div className="space-y-2">
+            <Label>{t('settings.webhooks.form.events')}</Label>
+            <p className="text-sm text-muted-foreground">
+              {t('settings.webhooks.form.eventsHelper')}
+            </p>
+            <div className="grid gap-2 md:grid-cols-2">
+              {SUBSCRIBABLE_EVENTS.map((event) => (
+                <div key={event} className="flex items-center gap-2">
+                  <Checkbox
+                    id={`webhook-event-${event}`}
+                    checked={events.includes(event)}
+                    onCheckedChange={() => toggleEvent(event)}
+                  />
+                  <Label
+                    htmlFor={`webhook-event-${event}`}
+                    className="font-normal"
+                  >
+                    {t(`settings.webhooks.events.${event}`)}
+                  </Label>
+                </div>
+              ))}
+            </div>
+          </div>
+
+          <Button
+            onClick={handleCreate}
+            disabled={saving || !name.trim() || !url.trim()}
+          >
+            {saving ? (
+              <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+            ) : (
+              <Plus className="h-4 w-4 mr-2" />
+            )}
+            {t('settings.webhooks.form.add')}
+          </Button>
+        </CardContent>
+      </Card>
+
+      {isLoading ? (
+        <div className="flex items-center justify-center py-8">
+          <Loader2 className="h-8 w-8 animate-spin" />
+        </div>
+      ) : !webhooks || webhooks.length === 0 ? (
+        <div className="text-center py-8 text-muted-foreground">
+          {t('settings.webhooks.empty')}
+        </div>
+      ) : (
+        webhooks.map((webhook) => (
+          <Card key={webhook.id}>
+            <CardHeader>
+              <div className="flex items-start justify-between gap-4">
+                <div className="min-w-0">
+                  <CardTitle className="flex items-center gap-2">
+                    {webhook.name}
+                    {!webhook.enabled && (
+                      <Badge variant="secondary">
+                        {t('settings.webhooks.disabled')}
+                      </Badge>
+                    )}
+                  </CardTitle>
+                  <CardDescription className="font-mono break-all">
+                    {webhook.url}
+                  </CardDescription>
+                </div>
+                <div className="flex items-center gap-2 shrink-0">
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => handleTest(webhook)}
+                    disabled={testing === webhook.id}
+                  >
+                    {testing === webhook.id ? (
+                      <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+                    ) : (
+                      <Send className="h-4 w-4 mr-2" />
+                    )}
+                    {t('settings.webhooks.test')}
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    onClick={() => handleDelete(webhook)}
+                  >
+                    <Trash2 className="h-4 w-4" />
+                  </Button>
+                </div>
+              </div>
+            </CardHeader>
+            <CardContent className="space-y-3 text-sm">
+              <div className="flex items-center gap-2">
+                <Checkbox
+                  id={`webhook-enabled-${webhook.id}`}
+                  checked={webhook.enabled}
+                  onCheckedChange={() => handleToggleEnabled(webhook)}
+                />
+                <Label htmlFor={`webhook-enabled-${webhook.id}`}>
+                  {t('settings.webhooks.enabled')}
+                </Label>
+              </div>
+              <div>
+                <span className="text-muted-foreground">
+                  {t('settings.webhooks.form.project')}:
+                </span>{' '}
+                {projectName(webhook.project_id)}
+              </div>
+              <div className="flex flex-wrap items-center gap-1">
+                <span className="text-muted-foreground mr-1">
+                  {t('settings.webhooks.form.events')}:
+                </span>
+                {webhook.events.length === 0 ? (
+                  <Badge variant="outline">
+                    {t('settings.webhooks.allEvents')}
+                  </Badge>
+                ) : (
+                  webhook.events.map((event) => (
+                    <Badge key={event} variant="outline">
+                      {t(`settings.webhooks.events.${event}`)}
+                    </Badge>
+                  ))
+                )}
+              </div>
+              <div>
+                <span className="text-muted-foreground">
+                  {t('settings.webhooks.form.secret')}:
+                </span>{' '}
+                {webhook.has_secret
+                  ? t('settings.webhooks.signed')
+                  : t('settings.webhooks.unsigned')}
+              </div>
+              <Button
+                variant="link"
+                size="sm"
+                className="px-0"
+                onClick={() =>
+                  setOpenLog(openLog === webhook.id ? null : webhook.id)
+                }
+              >
+                {openLog === webhook.id
+                  ? t('settings.webhooks.deliveries.hide')
+                  : t('settings.webhooks.deliveries.show')}
+              </Button>
+              {openLog === webhook.id && (
+                <WebhookDeliveries webhookId={webhook.id} />
+              )}
+            </CardContent>
+          </Card>
+        ))
+      )}
+    </div>
+  );
+}
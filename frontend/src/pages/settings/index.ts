@@ -5,3 +5,4 @@ export { AgentSettings } from './AgentSettings';
 export { McpSettings } from './McpSettings';
 export { RelationshipTypesSettings } from './RelationshipTypesSettings';
 export { TaskTemplatesSettings } from './TaskTemplatesSettings';
+export { WebhooksSettings } from './WebhooksSettings';
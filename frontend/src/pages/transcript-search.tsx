@@ -0,0 +1,298 @@
+import { useState } from 'react';
+import { Link } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { useQuery } from '@tanstack/react-query';
+import { AlertCircle, Loader2, Search } from 'lucide-react';
+
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import { Card, CardContent } from '@/components/ui/card';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { useProjects } from '@/hooks/useProjects';
+import { transcriptsApi } from '@/lib/api';
+import {
+  BaseCodingAgent,
+  type TranscriptEntryKind,
+  type TranscriptSearchQuery,
+} from 'shared/types';
+
+const ALL = 'all';
+
+const KINDS: TranscriptEntryKind[] = [
+  'assistant_message',
+  'command',
+  'file_path',
+];
+
+type Filters = {
+  projectId: string;
+  executor: string;
+  kind: string;
+  from: string;
+  to: string;
+};
+
+const EMPTY_FILTERS: Filters = {
+  projectId: ALL,
+  executor: ALL,
+  kind: ALL,
+  from: '',
+  to: '',
+};
+
+/** Snippets wrap matched terms in \u0002 and \u0003 */
+function Snippet({ text }: { text: string }) {
+  const [first, ...rest] = text.split('\u0002');
+  return (
+    <span className="whitespace-pre-wrap break-words">
+      {first}
+      {rest.map((part, i) => {
+        const [match, after = ''] = part.split('\u0003');
+        return (
+          <span key={i}>
+            <mark className="bg-yellow-200 dark:bg-yellow-800 rounded-sm">
+              {match}
+            </mark>
+            {after}
+          </span>
+        );
+      })}
+    </span>
+  );
+}
+
+function toQuery(q: string, filters: Filters): TranscriptSearchQuery {
+  const optional = (value: string) => (value === ALL ? null : value);
+  // Date inputs are local days; `to` is exclusive so it covers the whole day
+  const day = (value: string, offset = 0) => {
+    if (!value) return null;
+    const date = new Date(`${value}T00:00:00`);
+    date.setDate(date.getDate() + offset);
+    return date;
+  };
+  return {
+    q,
+    project_id: optional(filters.projectId),
+    task_id: null,
+    executor: optional(filters.executor),
+    kind: optional(filters.kind) as TranscriptEntryKind | null,
+    from: day(filters.from),
+    to: day(filters.to, 1),
+    limit: null,
+  };
+}
+
+export function TranscriptSearch() {
+  const { t } = useTranslation('tasks');
+  const { data: projects = [] } = useProjects();
+  const [input, setInput] = useState('');
+  const [filters, setFilters] = useState<Filters>(EMPTY_FILTERS);
+  const [submitted, setSubmitted] = useState<TranscriptSearchQuery | null>(
+    null
+  );
+
+  const {
+    data: results = [],
+    isFetching,
+    error,
+  } = useQuery({
+    queryKey: ['transcripts', submitted],
+    queryFn: () => transcriptsApi.search(submitted!),
+    enabled: Boolean(submitted?.q.trim()),
+  });
+
+  const updateFilter = (key: keyof Filters, value: string) => {
+    const next = { ...filters, [key]: value };
+    setFilters(next);
+    if (submitted) setSubmitted(toQuery(submitted.q, next));
+  };
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    setSubmitted(toQuery(input.trim(), filters));
+  };
+
+  return (
+    <div className="space-y-6 p-8 pb-16 md:pb-8 h-full overflow-auto">
+      <div>
+        <h1 className="text-3xl font-bold tracking-tight">
+          {t('transcriptSearch.title')}
+        </h1>
+        <p className="text-muted-foreground">
+          {t('transcriptSearch.subtitle')}
+        </p>
+      </div>
+
+      <form onSubmit={handleSubmit} className="flex gap-2">
+        <Input
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          placeholder={t('transcriptSearch.placeholder')}
+          autoFocus
+        />
+        <Button type="submit" disabled={!input.trim()}>
+          <Search className="mr-2 h-4 w-4" />
+          {t('transcriptSearch.search')}
+        </Button>
+      </form>
+
+      <div className="grid gap-3 sm:grid-cols-2 lg:grid-cols-5">
+        <div className="space-y-1">
+          <Label>{t('transcriptSearch.filters.project')}</Label>
+          <Select
+            value={filters.projectId}
+            onValueChange={(value) => updateFilter('projectId', value)}
+          >
+            <SelectTrigger>
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value={ALL}>
+                {t('transcriptSearch.filters.all')}
+              </SelectItem>
+              {projects.map((project) => (
+                <SelectItem key={project.id} value={project.id}>
+                  {project.name}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-1">
+          <Label>{t('transcriptSearch.filters.executor')}</Label>
+          <Select
+            value={filters.executor}
+            onValueChange={(value) => updateFilter('executor', value)}
+          >
+            <SelectTrigger>
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value={ALL}>
+                {t('transcriptSearch.filters.all')}
+              </SelectItem>
+              {Object.values(BaseCodingAgent).map((executor) => (
+                <SelectItem key={executor} value={executor}>
+                  {executor}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-1">
+          <Label>{t('transcriptSearch.filters.kind')}</Label>
+          <Select
+            value={filters.kind}
+            onValueChange={(value) => updateFilter('kind', value)}
+          >
+            <SelectTrigger>
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value={ALL}>
+                {t('transcriptSearch.filters.all')}
+              </SelectItem>
+              {KINDS.map((kind) => (
+                <SelectItem key={kind} value={kind}>
+                  {t(`transcriptSearch.kinds.${kind}`)}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="transcript-search-from">
+            {t('transcriptSearch.filters.from')}
+          </Label>
+          <Input
+            id="transcript-search-from"
+            type="date"
+            value={filters.from}
+            onChange={(e) => updateFilter('from', e.target.value)}
+          />
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="transcript-search-to">
+            {t('transcriptSearch.filters.to')}
+          </Label>
+          <Input
+            id="transcript-search-to"
+            type="date"
+            value={filters.to}
+            onChange={(e) => updateFilter('to', e.target.value)}
+          />
+        </div>
+      </div>
+
+      {error && (
+        <Alert variant="destructive">
+          <AlertCircle className="h-4 w-4" />
+          <AlertDescription>
+            {error instanceof Error ? error.message : String(error)}
+          </AlertDescription>
+        </Alert>
+      )}
+
+      {isFetching ? (
+        <div className="flex items-center justify-center py-12">
+          <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          {t('transcriptSearch.searching')}
+        </div>
+      ) : (
+        submitted?.q &&
+        !error && (
+          <div className="space-y-3">
+            {results.length === 0 ? (
+              <p className="text-muted-foreground">
+                {t('transcriptSearch.noResults')}
+              </p>
+            ) : (
+              results.map((result) => (
+                <Link
+                  key={`${result.execution_process_id}:${result.entry_index}:${result.kind}`}
+                  to={`/projects/${result.project_id}/tasks/${result.task_id}/attempts/${result.task_attempt_id}?entry=${result.execution_process_id}:${result.entry_index}`}
+                  className="block"
+                >
+                  <Card className="hover:bg-accent/50 transition-colors">
+                    <CardContent className="space-y-2 p-4">
+                      <div className="flex flex-wrap items-center gap-2 text-sm">
+                        <span className="font-medium">
+                          {result.task_title}
+                        </span>
+                        <Badge variant="secondary">
+                          {t(`transcriptSearch.kinds.${result.kind}`)}
+                        </Badge>
+                        <span className="text-muted-foreground">
+                          {result.executor} &middot;{' '}
+                          {new Date(result.started_at).toLocaleString()}
+                        </span>
+                      </div>
+                      <p
+                        className={
+                          result.kind === 'assistant_message'
+                            ? 'text-sm'
+                            : 'text-sm font-mono'
+                        }
+                      >
+                        <Snippet text={result.snippet} />
+                      </p>
+                    </CardContent>
+                  </Card>
+                </Link>
+              ))
+            )}
+          </div>
+        )
+      )}
+    </div>
+  );
+}
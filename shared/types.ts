@@ -23,16 +23,242 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, 
+/**
+ * Default strategy for direct merges of this project's attempts
+ */
+merge_strategy: MergeStrategy, 
+/**
+ * Commit message template for direct merges; `None` uses the built-in format
+ */
+merge_commit_template: string | null, commit_message_strategy: CommitMessageStrategy, 
+/**
+ * Template used by the `template` commit message strategy
+ */
+commit_message_template: string | null, commit_message_validation: CommitMessageValidation, 
+/**
+ * Regex used by the `regex` commit message validation
+ */
+commit_message_pattern: string | null, 
+/**
+ * Author and committer name for commits; falls back to the git config when unset
+ */
+commit_author_name: string | null, 
+/**
+ * Author and committer email for commits; falls back to the git config when unset
+ */
+commit_author_email: string | null, 
+/**
+ * `Name <email>` entries, one per line, added as `Co-authored-by` trailers
+ */
+commit_co_authors: string | null, commit_signing_format: CommitSigningFormat, 
+/**
+ * GPG key id, or path to the SSH key, used to sign commits
+ */
+commit_signing_key: string | null, 
+/**
+ * OCI image executions run in; `None` runs them directly on the host
+ */
+container_image: string | null, 
+/**
+ * CPU limit for execution containers, e.g. `2` or `1.5`
+ */
+container_cpus: string | null, 
+/**
+ * Memory limit for execution containers, e.g. `4g` or `512m`
+ */
+container_memory: string | null, 
+/**
+ * Hours without activity before an attempt's worktree is removed
+ */
+worktree_retention_hours: number, 
+/**
+ * Always keep the worktrees of this many most recently active attempts
+ */
+worktree_keep_recent: number | null, 
+/**
+ * Keep worktrees while their task is in review
+ */
+worktree_keep_in_review: boolean, 
+/**
+ * Remove the oldest worktrees once the project's worktrees exceed this size
+ */
+worktree_max_size_mb: number | null, 
+/**
+ * Archive uncommitted and untracked files before cleanup and restore them when the
+ * worktree is recreated
+ */
+worktree_snapshot_enabled: boolean, 
+/**
+ * Directories shared across worktrees, one per line as `dir[: lockfile, ...]`
+ */
+dependency_cache: string | null, 
+/**
+ * Dev server health check: `http[:/path]`, an `http(s)://` URL, `tcp[:port]` or
+ * `command: <script>`
+ */
+dev_server_health_check: string | null, dev_server_restart_policy: DevServerRestartPolicy, 
+/**
+ * Automatic restarts in a row before the dev server is left stopped
+ */
+dev_server_max_restarts: number, 
+/**
+ * Directory coding agents run in, relative to the repository root
+ */
+working_dir: string | null, 
+/**
+ * Sparse checkout patterns for worktrees, one per line in gitignore syntax
+ */
+sparse_checkout: string | null, created_at: Date, updated_at: Date, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, merge_strategy: MergeStrategy | null, merge_commit_template: string | null, commit_message_strategy: CommitMessageStrategy | null, commit_message_template: string | null, commit_message_validation: CommitMessageValidation | null, commit_message_pattern: string | null, commit_author_name: string | null, commit_author_email: string | null, commit_co_authors: string | null, commit_signing_format: CommitSigningFormat | null, commit_signing_key: string | null, container_image: string | null, container_cpus: string | null, container_memory: string | null, worktree_retention_hours: number | null, worktree_keep_recent: number | null, worktree_keep_in_review: boolean | null, worktree_max_size_mb: number | null, worktree_snapshot_enabled: boolean | null, dependency_cache: string | null, dev_server_health_check: string | null, dev_server_restart_policy: DevServerRestartPolicy | null, dev_server_max_restarts: number | null, working_dir: string | null, sparse_checkout: string | null, };
 
-export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, merge_strategy: MergeStrategy | null, merge_commit_template: string | null, commit_message_strategy: CommitMessageStrategy | null, commit_message_template: string | null, commit_message_validation: CommitMessageValidation | null, commit_message_pattern: string | null, commit_author_name: string | null, commit_author_email: string | null, commit_co_authors: string | null, commit_signing_format: CommitSigningFormat | null, commit_signing_key: string | null, container_image: string | null, container_cpus: string | null, container_memory: string | null, worktree_retention_hours: number | null, worktree_keep_recent: number | null, worktree_keep_in_review: boolean | null, worktree_max_size_mb: number | null, worktree_snapshot_enabled: boolean | null, dependency_cache: string | null, dev_server_health_check: string | null, dev_server_restart_policy: DevServerRestartPolicy | null, dev_server_max_restarts: number | null, working_dir: string | null, sparse_checkout: string | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type CommitMessageStrategy = "agent_summary" | "conventional_commit" | "template";
+
+export type CommitMessageValidation = "none" | "conventional_commit" | "regex";
+
+export type CommitSigningFormat = "none" | "gpg" | "ssh";
+
+export type DevServerRestartPolicy = "never" | "on_failure";
+
+export type ProjectRepository = { id: string, project_id: string, 
+/**
+ * Directory name of the repository's worktree inside an attempt's workspace
+ */
+name: string, git_repo_path: string, created_at: Date, updated_at: Date, };
+
+export type CreateProjectRepository = { name: string, git_repo_path: string, };
+
+export type ReviewComment = { id: string, task_attempt_id: string, file_path: string, side: ReviewCommentSide, 
+/**
+ * First commented line, 1-based
+ */
+line_start: number, 
+/**
+ * Last commented line, inclusive
+ */
+line_end: number, 
+/**
+ * Worktree HEAD when the comment was written
+ */
+commit_sha: string | null, 
+/**
+ * Content of the commented lines, used to re-anchor the comment as the diff changes
+ */
+code: string | null, body: string, 
+/**
+ * The commented lines no longer exist in the file
+ */
+outdated: boolean, 
+/**
+ * Execution process the comment was sent to for the agent to address
+ */
+sent_execution_process_id: string | null, resolved_at: Date | null, created_at: Date, updated_at: Date, };
+
+export type ReviewCommentSide = "old" | "new";
+
+export type CreateReviewComment = { file_path: string, side: ReviewCommentSide, line_start: number, line_end: number | null, code: string | null, body: string, };
+
+export type UpdateReviewComment = { body: string | null, 
+/**
+ * Resolve or reopen the comment
+ */
+resolved: boolean | null, };
+
+export type TranscriptEntryKind = "assistant_message" | "command" | "file_path";
+
+export type TranscriptSearchQuery = { 
+/**
+ * Words to find; each matches as a prefix and all of them must match
+ */
+q: string, project_id: string | null, task_id: string | null, 
+/**
+ * Base coding agent of the attempt, e.g. `CLAUDE_CODE`
+ */
+executor: string | null, kind: TranscriptEntryKind | null, 
+/**
+ * Only processes started at or after this time
+ */
+from: Date | null, 
+/**
+ * Only processes started before this time
+ */
+to: Date | null, limit: number | null, };
+
+export type TranscriptSearchResult = { execution_process_id: string, 
+/**
+ * Index of the entry in the process's normalized log
+ */
+entry_index: number, kind: TranscriptEntryKind, 
+/**
+ * Excerpt of the entry with matched terms wrapped in `\u{2}` and `\u{3}`
+ */
+snippet: string, task_attempt_id: string, task_id: string, task_title: string, project_id: string, executor: string, started_at: Date, };
+
+export type Webhook = { id: string, name: string, url: string, 
+/**
+ * Whether payloads are signed
+ */
+has_secret: boolean, 
+/**
+ * Subscribed events, empty for all
+ */
+events: WebhookEventType[], 
+/**
+ * Only send events from this project, or from every project when unset
+ */
+project_id: string | null, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type WebhookEventType = "task_created" | "task_status_changed" | "attempt_started" | "process_completed" | "process_failed" | "approval_requested" | "pr_merged" | "ping";
+
+export type WebhookDeliveryStatus = "pending" | "succeeded" | "failed";
+
+export type CreateWebhook = { name: string, url: string, 
+/**
+ * Generated when not given; empty to send unsigned payloads
+ */
+secret: string | null, events: Array<WebhookEventType>, project_id: string | null, };
+
+export type CreatedWebhook = { secret: string | null, id: string, name: string, url: string, 
+/**
+ * Whether payloads are signed
+ */
+has_secret: boolean, 
+/**
+ * Subscribed events, empty for all
+ */
+events: WebhookEventType[], 
+/**
+ * Only send events from this project, or from every project when unset
+ */
+project_id: string | null, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type UpdateWebhook = { name: string | null, url: string | null, 
+/**
+ * Empty to stop signing payloads
+ */
+secret: string | null, events: Array<WebhookEventType> | null, enabled: boolean | null, };
+
+export type WebhookDelivery = { id: string, webhook_id: string, event_type: WebhookEventType, 
+/**
+ * Request body, exactly as signed
+ */
+payload: string, status: WebhookDeliveryStatus, attempts: number, 
+/**
+ * HTTP status of the last attempt
+ */
+response_status: number | null, 
+/**
+ * Why the last attempt failed
+ */
+error: string | null, created_at: Date, updated_at: Date, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
@@ -69,6 +295,143 @@ export type CreateTask = { project_id: string, title: string, description: strin
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
 
+export type RelationshipChange = "added" | "removed";
+
+export type ToolApprovalStatus = "pending" | "approved" | "denied" | "timed_out";
+
+export type TaskActivityEvent = { "type": "status_changed", from: TaskStatus | null, to: TaskStatus, } | { "type": "attempt_started", task_attempt_id: string, executor: string, branch: string, target_branch: string, } | { "type": "process_started", task_attempt_id: string, execution_process_id: string, run_reason: ExecutionProcessRunReason, prompt: string | null, } | { "type": "follow_up_sent", task_attempt_id: string, execution_process_id: string, prompt: string, } | { "type": "process_finished", task_attempt_id: string, execution_process_id: string, run_reason: ExecutionProcessRunReason, status: ExecutionProcessStatus, exit_code: number | null, 
+/**
+ * The coding agent's final message
+ */
+summary: string | null, } | { "type": "approval_requested", task_attempt_id: string, execution_process_id: string, approval_id: string, tool_name: string, tool_input_summary: string, } | { "type": "approval_resolved", task_attempt_id: string, execution_process_id: string, approval_id: string, tool_name: string, status: ToolApprovalStatus, denial_reason: string | null, } | { "type": "committed", task_attempt_id: string, execution_process_id: string, commit: string, } | { "type": "merged", task_attempt_id: string, merge_commit: string, target_branch: string, } | { "type": "pr_opened", task_attempt_id: string, number: number, url: string, target_branch: string, } | { "type": "pr_merged", task_attempt_id: string, number: number, url: string, merge_commit_sha: string | null, } | { "type": "pr_closed", task_attempt_id: string, number: number, url: string, } | { "type": "relationship_changed", change: RelationshipChange, 
+/**
+ * Whether this task is the relationship's source
+ */
+outgoing: boolean, other_task_id: string, other_task_title: string, label: string | null, };
+
+export type TaskActivity = { 
+/**
+ * Identifies the entry in the task's timeline, so an update to it can replace it. Entries
+ * of an attempt start with `attempt:<id>:`, and those of a process within it continue with
+ * `process:<id>:`.
+ */
+key: string, at: Date, event: TaskActivityEvent, };
+
+export type TaskMetricsQuery = { 
+/**
+ * Start of the window; defaults to 30 days before `to`
+ */
+from: Date | null, 
+/**
+ * End of the window; defaults to now
+ */
+to: Date | null, };
+
+/**
+ * Summary of a set of durations, in seconds
+ */
+export type DurationStats = { count: number, mean_seconds: number | null, median_seconds: number | null, p85_seconds: number | null, };
+
+export type AgentRunStats = { 
+/**
+ * Finished runs
+ */
+runs: number, succeeded: number, failed: number, 
+/**
+ * Runs stopped by the user; they don't count towards the success rate
+ */
+killed: number, 
+/**
+ * Share of succeeded runs among succeeded and failed ones
+ */
+success_rate: number | null, };
+
+export type FlowMetrics = { 
+/**
+ * Tasks that were done at the end of the window and became done within it
+ */
+tasks_completed: number, 
+/**
+ * Completed tasks per week of the window
+ */
+throughput_per_week: number, 
+/**
+ * From creation to done
+ */
+lead_time: DurationStats, 
+/**
+ * From first moving to in progress to done
+ */
+cycle_time: DurationStats, 
+/**
+ * Total time spent in progress by completed tasks that were ever in progress
+ */
+time_in_progress: DurationStats, 
+/**
+ * Total time spent in review by completed tasks that were ever in review
+ */
+time_in_review: DurationStats, 
+/**
+ * Coding agent runs started within the window
+ */
+agent_runs: AgentRunStats, };
+
+export type ExecutorProfileMetrics = { executor_profile_id: ExecutorProfileId, metrics: FlowMetrics, };
+
+export type TaskMetrics = { from: Date, to: Date, overall: FlowMetrics, 
+/**
+ * Completed tasks are attributed to the profile of their last coding agent run; tasks
+ * finished without one only count towards `overall`
+ */
+by_executor_profile: Array<ExecutorProfileMetrics>, };
+
+export type WeeklyMerges = { 
+/**
+ * Monday of the week, as `YYYY-MM-DD`
+ */
+week_start: string, 
+/**
+ * Attempts merged directly into their target branch
+ */
+merges: number, 
+/**
+ * Pull requests of attempts merged on GitHub
+ */
+pr_merges: number, };
+
+export type AnalyticsReportQuery = { 
+/**
+ * Start of the window; defaults to 30 days before `to`
+ */
+from: Date | null, 
+/**
+ * End of the window; defaults to now
+ */
+to: Date | null, };
+
+/**
+ * Coding agent usage of one executor profile
+ */
+export type ExecutorUsage = { 
+/**
+ * `None` for runs recorded before the executor was part of the event
+ */
+executor: string | null, variant: string | null, attempts: number, 
+/**
+ * Finished coding agent runs, including follow-ups
+ */
+runs: number, failed_runs: number, failure_rate: number | null, average_runtime_seconds: number | null, 
+/**
+ * Tool approvals answered per finished run
+ */
+approvals_per_run: number | null, };
+
+export type AnalyticsReport = { from: Date, to: Date, 
+/**
+ * Events recorded within the window
+ */
+events: number, executors: Array<ExecutorUsage>, merges_per_week: Array<WeeklyMerges>, };
+
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
@@ -89,7 +452,11 @@ export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, }
 
 export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
 
-export type CreateFollowUpAttempt = { prompt: string, variant: string | null, image_ids: Array<string> | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+export type CreateFollowUpAttempt = { prompt: string, variant: string | null, image_ids: Array<string> | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, 
+/**
+ * Bundle the attempt's open review comments into the prompt
+ */
+send_review: boolean | null, };
 
 export type DraftResponse = { task_attempt_id: string, draft_type: DraftType, retry_process_id: string | null, prompt: string, queued: boolean, variant: string | null, image_ids: Array<string> | null, version: bigint, };
 
@@ -101,6 +468,56 @@ export type ChangeTargetBranchRequest = { new_target_branch: string, };
 
 export type ChangeTargetBranchResponse = { new_target_branch: string, status: [number, number], };
 
+export type PinWorktreeRequest = { pinned: boolean, };
+
+export type TaskAttemptRepository = { id: string, task_attempt_id: string, project_repository_id: string, name: string, git_repo_path: string, 
+/**
+ * Branch the attempt's branch was created from in this repository
+ */
+target_branch: string, 
+/**
+ * Commit on the target branch once the repository's changes were merged
+ */
+merge_commit: string | null, pr_number: number | null, pr_url: string | null, created_at: Date, updated_at: Date, };
+
+export type TaskAttemptRepositoryStatus = { repository: TaskAttemptRepository, 
+/**
+ * Path of the repository's worktree
+ */
+worktree_path: string, commits_ahead: number | null, commits_behind: number | null, has_uncommitted_changes: boolean | null, };
+
+export type DevServerInfo = { attempt_id: string, 
+/**
+ * Port passed to the dev script
+ */
+port: number, 
+/**
+ * URL the running dev server printed, once it has printed one
+ */
+url: string | null, 
+/**
+ * Path of the preview proxy for this attempt
+ */
+preview_path: string, };
+
+export type ExportFormat = "markdown" | "html" | "json";
+
+export type WorktreeDiskUsage = { path: string, size_bytes: number, 
+/**
+ * Attempt owning the worktree; `None` for directories no attempt refers to
+ */
+attempt_id: string | null, project_id: string | null, pinned: boolean, };
+
+export type WorktreeDiskUsageReport = { base_dir: string, total_bytes: number, 
+/**
+ * Free space on the filesystem holding the worktrees, when known
+ */
+available_bytes: number | null, 
+/**
+ * Largest first
+ */
+worktrees: Array<WorktreeDiskUsage>, };
+
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, base_branch: string, };
 
 export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, };
@@ -109,9 +526,41 @@ export type ImageResponse = { id: string, file_path: string, original_name: stri
 
 export enum GitHubServiceError { TOKEN_INVALID = "TOKEN_INVALID", INSUFFICIENT_PERMISSIONS = "INSUFFICIENT_PERMISSIONS", REPO_NOT_FOUND_OR_NO_ACCESS = "REPO_NOT_FOUND_OR_NO_ACCESS" }
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, 
+/**
+ * Also keep analytics events in the local database, regardless of `analytics_enabled`
+ */
+local_analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, };
 
-export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
+export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, 
+/**
+ * Chat, push and email channels that also receive notifications
+ */
+channels: Array<NotificationChannelConfig>, 
+/**
+ * Address of this instance used for links in notifications, defaults to the local server
+ */
+base_url: string | null, };
+
+export type NotificationChannelConfig = { name: string, enabled: boolean, 
+/**
+ * Events sent to this channel, empty for all
+ */
+events: Array<NotificationEvent>, channel: NotificationChannelKind, };
+
+export type NotificationChannelKind = { "type": "slack", webhook_url: string, } | { "type": "discord", webhook_url: string, } | { "type": "ntfy", server_url: string, topic: string, 
+/**
+ * Access token for protected topics
+ */
+token: string | null, } | { "type": "gotify", server_url: string, 
+/**
+ * Application token
+ */
+token: string, } | { "type": "email", smtp_host: string, smtp_port: number, security: SmtpSecurity, username: string | null, password: string | null, from: string, to: Array<string>, };
+
+export type NotificationEvent = "attempt_completed" | "attempt_failed" | "approval_pending" | "pr_merged";
+
+export type SmtpSecurity = "none" | "starttls" | "tls";
 
 export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
 
@@ -219,10 +668,30 @@ export type CreateTaskAttemptBody = { task_id: string,
 /**
  * Executor profile specification
  */
-executor_profile_id: ExecutorProfileId, base_branch: string, };
+executor_profile_id: ExecutorProfileId, base_branch: string, 
+/**
+ * Stack the new attempt on this attempt's branch instead of `base_branch`
+ */
+parent_attempt_id: string | null, };
 
 export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, };
 
+export type MergeTaskAttemptRequest = { 
+/**
+ * Strategy for this merge; defaults to the project's merge strategy
+ */
+strategy: MergeStrategy | null, 
+/**
+ * Commit message for this merge; defaults to the project's commit message template
+ */
+commit_message: string | null, };
+
+export type ResolveConflictsRequest = { 
+/**
+ * Optional variant override for the resolving agent
+ */
+variant: string | null, };
+
 export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
 
 export type ReplaceProcessRequest = { 
@@ -265,7 +734,7 @@ conflicted_files: Array<string>, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, worktree_pinned: boolean, setup_completed_at: string | null, parent_attempt_id: string | null, created_at: string, updated_at: string, };
 
 export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, 
 /**
@@ -281,20 +750,28 @@ after_head_commit: string | null, status: ExecutionProcessStatus, exit_code: big
  * history view (due to restore/trimming). Hidden from logs/timeline;
  * still listed in the Processes tab.
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+dropped: boolean, 
+/**
+ * Health of a dev server with a health check; `None` for other processes
+ */
+health_status: DevServerHealth | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
 export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
 
+export type DevServerHealth = "starting" | "healthy" | "unhealthy";
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
-export type DirectMerge = { id: string, task_attempt_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
+export type DirectMerge = { id: string, task_attempt_id: string, merge_commit: string, target_branch_name: string, merge_strategy: MergeStrategy, created_at: string, };
 
 export type PrMerge = { id: string, task_attempt_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
 
 export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
+export type MergeStrategy = "squash" | "rebase_fast_forward" | "merge_commit";
+
 export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
 
 export type Draft = { id: string, task_attempt_id: string, draft_type: DraftType, retry_process_id: string | null, prompt: string, queued: boolean, sending: boolean, variant: string | null, image_ids: Array<string> | null, created_at: string, updated_at: string, version: bigint, };